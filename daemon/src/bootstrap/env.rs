@@ -2,11 +2,19 @@ use std::path::PathBuf;
 
 use crate::bootstrap::paths::AppPaths;
 use crate::error::AppResult;
+use crate::history::migrations::run_pending_migrations;
 
 pub fn bootstrap_env(paths: &AppPaths) -> AppResult<PathBuf> {
     std::fs::create_dir_all(&paths.state_dir)?;
 
     std::env::set_var("FRANKEN_WHISPER_STATE_DIR", &paths.state_dir);
+
+    // Runs before the controller or franken_whisper ever open
+    // `paths.history_db`, so every read/write downstream sees an
+    // already-current schema. A `history.db_path` config override (read
+    // later, once `AppConfig` is loaded) bypasses this and is not migrated.
+    run_pending_migrations(&paths.history_db)?;
+
     Ok(paths.state_dir.clone())
 }
 
@@ -28,6 +36,8 @@ mod tests {
             config_file: temp_dir.path().join("config").join("config.toml"),
             history_db: temp_dir.path().join("data").join("history.sqlite3"),
             autostart_file: temp_dir.path().join("autostart").join("entry"),
+            ipc_socket: temp_dir.path().join("cache").join("quedo.sock"),
+            system_config_file: temp_dir.path().join("system-config.toml"),
         };
 
         let before = std::env::var_os("FRANKEN_WHISPER_STATE_DIR");
@@ -38,6 +48,10 @@ mod tests {
             std::env::var("FRANKEN_WHISPER_STATE_DIR").ok().as_deref(),
             Some(state_dir.to_str().expect("utf8"))
         );
+        assert!(
+            paths.history_db.is_file(),
+            "bootstrap_env should have migrated the history db into existence"
+        );
 
         match before {
             Some(value) => std::env::set_var("FRANKEN_WHISPER_STATE_DIR", value),
@@ -14,6 +14,13 @@ pub struct AppPaths {
     pub config_file: PathBuf,
     pub history_db: PathBuf,
     pub autostart_file: PathBuf,
+    /// Unix-domain socket the `controller::ipc` server listens on for
+    /// external clients (status-bar applets, editor plugins, shell scripts).
+    pub ipc_socket: PathBuf,
+    /// Read-only, admin-managed config layer merged underneath
+    /// `config_file`; see `config::load::load_config`. Not created by
+    /// `ensure_dirs` — it's someone else's file to manage.
+    pub system_config_file: PathBuf,
 }
 
 impl AppPaths {
@@ -29,6 +36,7 @@ impl AppPaths {
 
         let config_file = config_dir.join("config.toml");
         let history_db = data_dir.join("history.sqlite3");
+        let ipc_socket = cache_dir.join("quedo.sock");
 
         let base_dirs = BaseDirs::new()
             .ok_or_else(|| AppError::Config("unable to resolve base directories".to_owned()))?;
@@ -45,6 +53,12 @@ impl AppPaths {
                 .join("quedo-daemon.desktop")
         };
 
+        let system_config_file = if cfg!(windows) {
+            PathBuf::from(r"C:\ProgramData\quedo\config.toml")
+        } else {
+            PathBuf::from("/etc/quedo/config.toml")
+        };
+
         Ok(Self {
             config_dir,
             data_dir,
@@ -54,6 +68,8 @@ impl AppPaths {
             config_file,
             history_db,
             autostart_file,
+            ipc_socket,
+            system_config_file,
         })
     }
 
@@ -141,6 +157,8 @@ mod tests {
             config_file: temp_dir.path().join("config").join("config.toml"),
             history_db: temp_dir.path().join("data").join("history.sqlite3"),
             autostart_file,
+            ipc_socket: temp_dir.path().join("cache").join("quedo.sock"),
+            system_config_file: temp_dir.path().join("system-config.toml"),
         };
 
         paths.ensure_dirs().expect("ensure dirs");
@@ -0,0 +1,110 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::config::TranscriptionConfig;
+use crate::error::{AppError, AppResult};
+use crate::evaluation::score_transcript;
+use crate::transcription::engine::EngineAdapter;
+use crate::transcription::run_transcription_job;
+
+/// `threads`/`processors` pairs tried during calibration; kept small and
+/// hand-picked rather than a full cartesian sweep over plausible core
+/// counts, since each point costs one real decode of the reference fixture.
+const CALIBRATION_GRID: &[(u32, u32)] = &[(1, 1), (2, 1), (4, 1), (2, 2), (4, 2)];
+
+/// How much worse (in absolute WER) a faster grid point is allowed to be
+/// than the best WER observed across the whole grid before it's
+/// disqualified; calibration optimizes for latency among the configurations
+/// that are basically as accurate as the best one, not for accuracy alone.
+const WER_TOLERANCE: f64 = 0.05;
+
+/// Golden transcript for the bundled reference fixture audio (see
+/// `reference_fixture_wav`), used as calibration's WER ground truth; shared
+/// with `differential_reference_comparison_matches_whisper_cli`, which this
+/// module generalizes into a runtime subsystem.
+const REFERENCE_TRANSCRIPT: &str = "And so my fellow Americans ask not what your \
+    country can do for you ask what you can do for your country";
+
+/// Candidate locations for the bundled reference fixture audio, checked in
+/// order; mirrors `franken_whisper_release_gate.rs`'s `fixture_candidates`.
+fn reference_fixture_candidates() -> [PathBuf; 2] {
+    [
+        PathBuf::from("/home/jorge/.local/src/whisper.cpp/samples/jfk.wav"),
+        PathBuf::from("/tmp/franken_whisper/test_data/jfk.wav"),
+    ]
+}
+
+/// Locates the bundled reference fixture audio on disk, if installed.
+/// `calibrate` fails cleanly when this returns `None` rather than shipping
+/// the fixture inside the binary, since it's the same multi-second voice
+/// sample `franken_whisper_release_gate.rs` already expects at a fixed path.
+pub fn reference_fixture_wav() -> Option<PathBuf> {
+    reference_fixture_candidates()
+        .into_iter()
+        .find(|path| path.exists())
+}
+
+/// The `threads`/`processors` pair `calibrate` chose, plus the numbers that
+/// justified picking it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalibratedSettings {
+    pub threads: u32,
+    pub processors: u32,
+    pub wer: f64,
+    pub latency: Duration,
+}
+
+/// Transcribes the bundled reference fixture once per `CALIBRATION_GRID`
+/// point, scoring each against `REFERENCE_TRANSCRIPT` with
+/// `evaluation::score_transcript` and timing the decode, then returns the
+/// fastest point whose WER is within `WER_TOLERANCE` of the best WER seen
+/// across the grid. `config` supplies every other `TranscriptionConfig`
+/// field (model, backend, language, ...); only `threads`/`processors` vary
+/// per attempt.
+pub fn calibrate(
+    engine: &dyn EngineAdapter,
+    config: &TranscriptionConfig,
+    db_path: &Path,
+) -> AppResult<CalibratedSettings> {
+    let fixture = reference_fixture_wav().ok_or_else(|| {
+        AppError::Transcription("no reference calibration fixture found on disk".to_owned())
+    })?;
+
+    let mut attempts = Vec::with_capacity(CALIBRATION_GRID.len());
+    for &(threads, processors) in CALIBRATION_GRID {
+        let mut trial_config = config.clone();
+        trial_config.threads = Some(threads);
+        trial_config.processors = Some(processors);
+
+        let started = Instant::now();
+        let result = run_transcription_job(
+            engine,
+            fixture.clone(),
+            db_path.to_path_buf(),
+            &trial_config,
+            false,
+        )?;
+        let latency = started.elapsed();
+
+        let report = score_transcript(REFERENCE_TRANSCRIPT, &result.transcript);
+        attempts.push((threads, processors, report.wer, latency));
+    }
+
+    let best_wer = attempts
+        .iter()
+        .map(|&(_, _, wer, _)| wer)
+        .fold(f64::MAX, f64::min);
+
+    let chosen = attempts
+        .into_iter()
+        .filter(|&(_, _, wer, _)| wer <= best_wer + WER_TOLERANCE)
+        .min_by(|a, b| a.3.cmp(&b.3))
+        .expect("CALIBRATION_GRID is non-empty, so at least one attempt ties its own best wer");
+
+    Ok(CalibratedSettings {
+        threads: chosen.0,
+        processors: chosen.1,
+        wer: chosen.2,
+        latency: chosen.3,
+    })
+}
@@ -0,0 +1,337 @@
+use std::path::Path;
+
+use realfft::RealFftPlanner;
+
+use crate::error::{AppError, AppResult};
+
+/// Duration and peak short-term loudness of a finalized capture WAV, used to
+/// gate out empty or silent recordings before they reach the whisper worker.
+#[derive(Debug, Clone, Copy)]
+pub struct RecordingAnalysis {
+    pub duration_ms: u64,
+    pub peak_rms: f32,
+    /// Fraction of `SPEECH_FRAME_MS` frames classified as speech by
+    /// `classify_speech_frames`; see its doc comment for how.
+    pub speech_fraction: f32,
+}
+
+/// Why `validate_wav` rejected a capture before it reached the transcription
+/// backend. Kept structurally distinct from a plain string so callers (and
+/// their degraded-state reasons) can tell "nothing was recorded" apart from
+/// "the file is there but unreadable" instead of both reading as the same
+/// opaque backend failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WavValidationError {
+    /// The file parses as a WAV but contains zero sample frames.
+    Empty,
+    /// The file couldn't be opened as a WAV, or its header claims more
+    /// sample data than the file actually contains.
+    CorruptHeader(String),
+}
+
+impl std::fmt::Display for WavValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WavValidationError::Empty => write!(f, "capture file has zero audio frames"),
+            WavValidationError::CorruptHeader(detail) => {
+                write!(f, "capture file has an unreadable or corrupt WAV header: {detail}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WavValidationError {}
+
+/// The number of leading sample frames `validate_wav` actually decodes to
+/// catch a header that claims more data than the file body contains, beyond
+/// just opening the header successfully.
+const VALIDATION_PROBE_FRAMES: usize = 64;
+
+/// Parses `path` as a WAV with `hound` and decodes a few leading sample
+/// frames, without invoking the transcription backend, so a zero-length
+/// capture and a corrupt/unreadable one are classified as structurally
+/// distinct failures before either ever reaches `whisper-cli`. Assumes
+/// 16-bit PCM, which is the only format `capture::mic` ever writes.
+pub fn validate_wav(path: &Path) -> Result<(), WavValidationError> {
+    let mut reader = hound::WavReader::open(path)
+        .map_err(|error| WavValidationError::CorruptHeader(error.to_string()))?;
+
+    if reader.len() == 0 {
+        return Err(WavValidationError::Empty);
+    }
+
+    for sample in reader.samples::<i16>().take(VALIDATION_PROBE_FRAMES) {
+        sample.map_err(|error| WavValidationError::CorruptHeader(error.to_string()))?;
+    }
+
+    Ok(())
+}
+
+const RMS_WINDOW_MS: u64 = 20;
+
+/// Frame width used for speech/silence classification via FFT. Longer than
+/// `RMS_WINDOW_MS` because a useful magnitude spectrum needs more samples per
+/// frame than a plain energy window does.
+const SPEECH_FRAME_MS: u64 = 25;
+
+/// The speech formant band a frame's energy is compared against. Telephony's
+/// classic 300-3400 Hz range: narrow enough to exclude most low-frequency
+/// hum and high-frequency hiss, wide enough to cover the bulk of speech
+/// energy regardless of speaker.
+const SPEECH_BAND_HZ: (f64, f64) = (300.0, 3_400.0);
+
+/// How quickly the rolling noise-floor estimate is allowed to rise when a
+/// frame is louder than it; mirrors `capture::denoise`'s minimum-statistics
+/// tracker (fall instantly to a new minimum, rise slowly) so one loud frame
+/// doesn't drag the floor up and blind the classifier to quieter speech.
+const NOISE_FLOOR_RISE: f64 = 0.1;
+
+/// Reads `path` and computes its total duration, peak RMS energy across
+/// `RMS_WINDOW_MS` windows, and the fraction of frames classified as speech
+/// (see `classify_speech_frames`) with `speech_band_margin_db` as the margin
+/// above the rolling noise floor a frame's band energy must clear. Assumes
+/// 16-bit PCM, which is the only format `capture::mic` ever writes.
+pub fn analyze_wav(path: &Path, speech_band_margin_db: f64) -> AppResult<RecordingAnalysis> {
+    let mut reader = hound::WavReader::open(path).map_err(|error| {
+        AppError::Capture(format!(
+            "failed to open {} for silence analysis: {error}",
+            path.display()
+        ))
+    })?;
+
+    let spec = reader.spec();
+    let sample_rate = spec.sample_rate.max(1) as u64;
+    let channels = spec.channels.max(1) as u64;
+    let total_samples = reader.len() as u64;
+    let duration_ms = (total_samples * 1000) / (sample_rate * channels);
+
+    let samples: Vec<i16> = reader.samples::<i16>().collect::<Result<_, _>>().map_err(|error| {
+        AppError::Capture(format!("failed to read sample from {}: {error}", path.display()))
+    })?;
+
+    let window_samples = (((sample_rate * RMS_WINDOW_MS) / 1000).max(1) as usize) * channels as usize;
+    let mut peak_rms: f32 = 0.0;
+    let mut window_sum_sq: f64 = 0.0;
+    let mut window_count: usize = 0;
+
+    for &sample in &samples {
+        let normalized = f64::from(sample) / f64::from(i16::MAX);
+        window_sum_sq += normalized * normalized;
+        window_count += 1;
+
+        if window_count >= window_samples {
+            peak_rms = peak_rms.max((window_sum_sq / window_count as f64).sqrt() as f32);
+            window_sum_sq = 0.0;
+            window_count = 0;
+        }
+    }
+    if window_count > 0 {
+        peak_rms = peak_rms.max((window_sum_sq / window_count as f64).sqrt() as f32);
+    }
+
+    let speech_fraction = classify_speech_frames(
+        &samples,
+        spec.sample_rate.max(1),
+        channels as usize,
+        speech_band_margin_db,
+    );
+
+    Ok(RecordingAnalysis {
+        duration_ms,
+        peak_rms,
+        speech_fraction,
+    })
+}
+
+/// Splits `samples` (interleaved, `channels` per frame) into `SPEECH_FRAME_MS`
+/// windows, downmixed to mono, and classifies each as speech when its energy
+/// in `SPEECH_BAND_HZ` exceeds a rolling noise-floor estimate by
+/// `margin_db`. Returns the fraction of frames classified as speech, or 0.0
+/// if there are no whole frames to classify.
+fn classify_speech_frames(samples: &[i16], sample_rate: u32, channels: usize, margin_db: f64) -> f32 {
+    let channels = channels.max(1);
+    let mono: Vec<f32> = samples
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().map(|&s| f32::from(s) / f32::from(i16::MAX)).sum::<f32>() / frame.len() as f32)
+        .collect();
+
+    let frame_len = ((u64::from(sample_rate) * SPEECH_FRAME_MS) / 1000).max(2) as usize;
+    let frame_len = frame_len & !1;
+    if frame_len < 2 || mono.len() < frame_len {
+        return 0.0;
+    }
+
+    let mut planner = RealFftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(frame_len);
+    let bin_hz = f64::from(sample_rate) / frame_len as f64;
+    let band_bins: Vec<usize> = (0..frame_len / 2 + 1)
+        .filter(|&bin| {
+            let freq = bin as f64 * bin_hz;
+            freq >= SPEECH_BAND_HZ.0 && freq <= SPEECH_BAND_HZ.1
+        })
+        .collect();
+
+    let mut noise_floor_db = f64::NEG_INFINITY;
+    let mut total_frames = 0usize;
+    let mut speech_frames = 0usize;
+
+    let mut start = 0usize;
+    while start + frame_len <= mono.len() {
+        let mut input = fft.make_input_vec();
+        input.copy_from_slice(&mono[start..start + frame_len]);
+        let mut spectrum = fft.make_output_vec();
+        if fft.process(&mut input, &mut spectrum).is_err() {
+            start += frame_len;
+            continue;
+        }
+
+        let band_energy: f64 = band_bins.iter().map(|&bin| f64::from(spectrum[bin].norm_sqr())).sum();
+        let band_energy_db = 10.0 * (band_energy / band_bins.len().max(1) as f64 + 1e-12).log10();
+
+        if noise_floor_db.is_infinite() {
+            noise_floor_db = band_energy_db;
+        } else if band_energy_db < noise_floor_db {
+            noise_floor_db = band_energy_db;
+        } else {
+            noise_floor_db += (band_energy_db - noise_floor_db) * NOISE_FLOOR_RISE;
+        }
+
+        if band_energy_db > noise_floor_db + margin_db {
+            speech_frames += 1;
+        }
+        total_frames += 1;
+        start += frame_len;
+    }
+
+    if total_frames == 0 {
+        0.0
+    } else {
+        speech_frames as f32 / total_frames as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{analyze_wav, validate_wav, WavValidationError};
+
+    fn write_wav(path: &std::path::Path, samples: &[i16], sample_rate: u32) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).expect("create wav");
+        for sample in samples {
+            writer.write_sample(*sample).expect("write sample");
+        }
+        writer.finalize().expect("finalize wav");
+    }
+
+    #[test]
+    fn reports_zero_peak_rms_for_silence() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("silence.wav");
+        write_wav(&path, &[0_i16; 16_000], 16_000);
+
+        let analysis = analyze_wav(&path, 6.0).expect("analyze");
+        assert_eq!(analysis.duration_ms, 1_000);
+        assert_eq!(analysis.peak_rms, 0.0);
+    }
+
+    #[test]
+    fn reports_nonzero_peak_rms_for_full_scale_tone() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("tone.wav");
+        let samples: Vec<i16> = (0..16_000)
+            .map(|i| if i % 2 == 0 { i16::MAX } else { i16::MIN })
+            .collect();
+        write_wav(&path, &samples, 16_000);
+
+        let analysis = analyze_wav(&path, 6.0).expect("analyze");
+        assert_eq!(analysis.duration_ms, 1_000);
+        assert!(analysis.peak_rms > 0.9);
+    }
+
+    #[test]
+    fn reports_short_duration_for_brief_capture() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("brief.wav");
+        write_wav(&path, &[0_i16; 800], 16_000);
+
+        let analysis = analyze_wav(&path, 6.0).expect("analyze");
+        assert_eq!(analysis.duration_ms, 50);
+    }
+
+    #[test]
+    fn reports_zero_speech_fraction_for_silence() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("silence.wav");
+        write_wav(&path, &[0_i16; 16_000], 16_000);
+
+        let analysis = analyze_wav(&path, 6.0).expect("analyze");
+        assert_eq!(analysis.speech_fraction, 0.0);
+    }
+
+    #[test]
+    fn reports_nonzero_speech_fraction_once_a_speech_band_tone_follows_silence() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("tone.wav");
+        // Leading silence lets the rolling noise floor settle low before a
+        // loud 1 kHz tone (squarely in the 300-3400 Hz speech band) arrives,
+        // so the tone's frames clear the noise-floor margin.
+        let silence = std::iter::repeat(0_i16).take(8_000);
+        let tone = (0..8_000).map(|i| {
+            let t = i as f64 / 16_000.0;
+            ((2.0 * std::f64::consts::PI * 1_000.0 * t).sin() * f64::from(i16::MAX) * 0.8) as i16
+        });
+        let samples: Vec<i16> = silence.chain(tone).collect();
+        write_wav(&path, &samples, 16_000);
+
+        let analysis = analyze_wav(&path, 6.0).expect("analyze");
+        assert!(analysis.speech_fraction > 0.0);
+    }
+
+    #[test]
+    fn validate_wav_accepts_a_well_formed_capture() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("valid.wav");
+        write_wav(&path, &[0_i16; 1_600], 16_000);
+
+        validate_wav(&path).expect("valid capture should pass validation");
+    }
+
+    #[test]
+    fn validate_wav_rejects_a_zero_length_capture() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("empty.wav");
+        write_wav(&path, &[], 16_000);
+
+        assert_eq!(validate_wav(&path), Err(WavValidationError::Empty));
+    }
+
+    #[test]
+    fn validate_wav_rejects_random_bytes_as_a_corrupt_header() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("corrupt.wav");
+        std::fs::write(&path, [0xFFu8; 64]).expect("write garbage bytes");
+
+        assert!(matches!(
+            validate_wav(&path),
+            Err(WavValidationError::CorruptHeader(_))
+        ));
+    }
+
+    #[test]
+    fn validate_wav_distinguishes_empty_from_corrupt() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let empty_path = temp.path().join("empty.wav");
+        write_wav(&empty_path, &[], 16_000);
+        let corrupt_path = temp.path().join("corrupt.wav");
+        std::fs::write(&corrupt_path, [0xFFu8; 64]).expect("write garbage bytes");
+
+        let empty_error = validate_wav(&empty_path).expect_err("empty capture must fail");
+        let corrupt_error = validate_wav(&corrupt_path).expect_err("corrupt capture must fail");
+        assert_ne!(empty_error.to_string(), corrupt_error.to_string());
+    }
+}
@@ -0,0 +1,597 @@
+use std::path::Path;
+
+use realfft::num_complex::Complex;
+use realfft::RealFftPlanner;
+
+use crate::error::{AppError, AppResult};
+
+/// Analysis/synthesis frame width: 10 ms, matching the frame size RNNoise
+/// itself uses at 48 kHz (480 samples). `capture::mic` always hands us
+/// 16 kHz mono, so this is 160 samples here rather than 480; resampling up
+/// to 48 kHz just to resample back down afterwards would only cost quality
+/// for no benefit, so the bands below are derived from whatever the
+/// capture's actual sample rate is instead.
+const FRAME_MS: u64 = 10;
+
+/// Frames overlap by half their width so overlap-add resynthesis has no
+/// seams at frame boundaries.
+const OVERLAP_DIVISOR: usize = 2;
+
+/// Number of Bark-like bands the spectrum is grouped into before a gain is
+/// estimated per band, echoing RNNoise's 22 Bark-scale bands. Collapsing
+/// ~80 FFT bins into a handful of bands is what keeps the gain estimate
+/// smooth frame-to-frame instead of chasing every bin independently.
+const BAND_COUNT: usize = 18;
+
+/// How quickly the per-band noise floor estimate is allowed to rise when the
+/// current frame's energy is louder than it (classic minimum-statistics
+/// noise tracking: fall instantly to a new minimum, rise slowly so a burst
+/// of speech doesn't get mistaken for a higher noise floor).
+const NOISE_FLOOR_RISE: f64 = 0.05;
+
+/// How much a band's gain can move per frame. This is the simplified stand-in
+/// for RNNoise's recurrent (GRU) gain estimator: rather than a trained
+/// network predicting the next gain from recent history, the gain just
+/// relaxes towards the instantaneous Wiener estimate, which is enough to
+/// avoid the "musical noise" chirping that comes from applying an
+/// unsmoothed gain every frame.
+const GAIN_SMOOTHING: f64 = 0.3;
+
+/// Floor under the per-band gain so a band is attenuated
+/// rather than muted outright; full suppression turns remaining background
+/// hiss into distracting gaps.
+const MIN_GAIN: f64 = 0.1;
+
+/// What a denoise pass did, reported so callers (and tests) can observe the
+/// effect without re-reading the WAV.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DenoiseReport {
+    pub frames_processed: usize,
+    pub average_gain: f64,
+    /// RMS level, on the same 0.0-1.0 normalized scale as the input samples,
+    /// of the leading window of audio used to estimate the noise profile.
+    pub noise_rms: f64,
+}
+
+/// 512-sample analysis/synthesis frame for `spectral_subtract_wav`, per the
+/// classic spectral-subtraction literature; unlike `denoise_wav`'s
+/// sample-rate-derived `FRAME_MS`, this is a fixed bin count.
+const SUBTRACTION_FRAME_LEN: usize = 512;
+
+/// How long a leading window of the capture is assumed to be noise-only when
+/// estimating the noise magnitude spectrum for `spectral_subtract_wav`.
+const NOISE_PROFILE_MS: u64 = 300;
+
+/// Spectral floor, as a fraction of the estimated noise magnitude, that
+/// `spectral_subtract_wav` never subtracts below; prevents the "musical
+/// noise" chirping that comes from over-subtracting a bin to near-zero.
+const SPECTRAL_FLOOR: f64 = 0.02;
+
+fn time_domain_rms(samples: &[f32]) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let mean_square: f64 = samples.iter().map(|&sample| f64::from(sample) * f64::from(sample)).sum::<f64>()
+        / samples.len() as f64;
+    mean_square.sqrt()
+}
+
+fn hann(i: usize, len: usize) -> f64 {
+    0.5 - 0.5 * (2.0 * std::f64::consts::PI * i as f64 / len as f64).cos()
+}
+
+/// Maps an FFT bin to one of `BAND_COUNT` bands using a square-root warp of
+/// frequency, a cheap approximation of the Bark scale's emphasis on low
+/// frequencies (where speech energy concentrates) over a linear mapping.
+fn band_of(bin: usize, bin_count: usize) -> usize {
+    let fraction = bin as f64 / bin_count.max(1) as f64;
+    ((fraction.sqrt() * BAND_COUNT as f64) as usize).min(BAND_COUNT - 1)
+}
+
+/// Runs a spectral noise-suppression pass over `path` in place: each
+/// overlapping frame is transformed to the frequency domain, grouped into
+/// `BAND_COUNT` Bark-like bands, and each band is attenuated by a smoothed
+/// Wiener-style gain derived from a running per-band noise-floor estimate,
+/// before being transformed back and overlap-added into the output. This is
+/// a practical approximation of RNNoise: the band layout and per-frame gain
+/// computation follow the same shape, but the noise floor and gain
+/// smoothing are simple running estimates rather than a trained recurrent
+/// network, which isn't something a single pass over this tree can stand
+/// up. Assumes 16-bit PCM, the only format `capture::mic` ever writes.
+pub fn denoise_wav(path: &Path) -> AppResult<DenoiseReport> {
+    let mut reader = hound::WavReader::open(path).map_err(|error| {
+        AppError::Capture(format!("failed to open {} for denoising: {error}", path.display()))
+    })?;
+    let spec = reader.spec();
+
+    let samples: Vec<f32> = reader
+        .samples::<i16>()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| {
+            AppError::Capture(format!(
+                "failed to read samples from {} for denoising: {error}",
+                path.display()
+            ))
+        })?
+        .into_iter()
+        .map(|sample| f32::from(sample) / f32::from(i16::MAX))
+        .collect();
+
+    if samples.is_empty() {
+        return Ok(DenoiseReport {
+            frames_processed: 0,
+            average_gain: 1.0,
+            noise_rms: 0.0,
+        });
+    }
+
+    let profile_len = ((u64::from(spec.sample_rate.max(1)) * NOISE_PROFILE_MS) / 1000) as usize;
+    let noise_rms = time_domain_rms(&samples[..samples.len().min(profile_len).max(1)]);
+
+    let raw_frame_len = ((u64::from(spec.sample_rate.max(1)) * FRAME_MS) / 1000).max(2) as usize;
+    let frame_len = (raw_frame_len & !1).max(2);
+    let hop = frame_len / OVERLAP_DIVISOR;
+
+    let mut planner = RealFftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(frame_len);
+    let ifft = planner.plan_fft_inverse(frame_len);
+    let bin_count = frame_len / 2 + 1;
+
+    let window: Vec<f64> = (0..frame_len).map(|i| hann(i, frame_len)).collect();
+
+    let mut noise_floor = vec![0.0_f64; BAND_COUNT];
+    let mut gain = vec![1.0_f64; BAND_COUNT];
+    let mut noise_floor_initialized = false;
+
+    let mut output = vec![0.0_f32; samples.len()];
+    let mut weight = vec![0.0_f32; samples.len()];
+
+    let mut frames_processed = 0usize;
+    let mut gain_sum = 0.0_f64;
+    let mut gain_samples = 0usize;
+
+    let mut start = 0usize;
+    while start < samples.len() {
+        let end = (start + frame_len).min(samples.len());
+
+        let mut windowed = fft.make_input_vec();
+        for (i, slot) in windowed.iter_mut().enumerate() {
+            let sample = samples.get(start + i).copied().unwrap_or(0.0);
+            *slot = sample * window[i] as f32;
+        }
+
+        let mut spectrum = fft.make_output_vec();
+        fft.process(&mut windowed, &mut spectrum).map_err(|error| {
+            AppError::Capture(format!("denoise FFT failed on {}: {error}", path.display()))
+        })?;
+
+        let mut band_energy = vec![0.0_f64; BAND_COUNT];
+        let mut band_bins = vec![0usize; BAND_COUNT];
+        for (bin, value) in spectrum.iter().enumerate() {
+            let band = band_of(bin, bin_count);
+            band_energy[band] += f64::from(value.norm_sqr());
+            band_bins[band] += 1;
+        }
+        for band in 0..BAND_COUNT {
+            if band_bins[band] > 0 {
+                band_energy[band] /= band_bins[band] as f64;
+            }
+        }
+
+        if !noise_floor_initialized {
+            noise_floor.copy_from_slice(&band_energy);
+            noise_floor_initialized = true;
+        }
+
+        for band in 0..BAND_COUNT {
+            let energy = band_energy[band];
+            if energy < noise_floor[band] {
+                noise_floor[band] = energy;
+            } else {
+                noise_floor[band] += (energy - noise_floor[band]) * NOISE_FLOOR_RISE;
+            }
+
+            let raw_gain = if energy > 0.0 {
+                (1.0 - noise_floor[band] / energy).clamp(MIN_GAIN, 1.0)
+            } else {
+                MIN_GAIN
+            };
+            gain[band] += (raw_gain - gain[band]) * GAIN_SMOOTHING;
+            gain_sum += gain[band];
+            gain_samples += 1;
+        }
+
+        for (bin, value) in spectrum.iter_mut().enumerate() {
+            let band = band_of(bin, bin_count);
+            *value *= gain[band] as f32;
+        }
+
+        let mut resynthesized = ifft.make_output_vec();
+        ifft.process(&mut spectrum, &mut resynthesized).map_err(|error| {
+            AppError::Capture(format!("denoise inverse FFT failed on {}: {error}", path.display()))
+        })?;
+
+        for (i, value) in resynthesized.iter().enumerate() {
+            let index = start + i;
+            if index >= output.len() {
+                break;
+            }
+            let normalized = value / frame_len as f32;
+            let windowed_value = normalized * window[i] as f32;
+            output[index] += windowed_value;
+            weight[index] += window[i] as f32;
+        }
+
+        frames_processed += 1;
+        if end == samples.len() {
+            break;
+        }
+        start += hop;
+    }
+
+    let normalized_samples: Vec<i16> = output
+        .iter()
+        .zip(weight.iter())
+        .map(|(&value, &weight)| {
+            let sample = if weight > 1e-6 { value / weight } else { value };
+            (sample * f32::from(i16::MAX)).round().clamp(f32::from(i16::MIN), f32::from(i16::MAX)) as i16
+        })
+        .collect();
+
+    let mut writer = hound::WavWriter::create(path, spec).map_err(|error| {
+        AppError::Capture(format!("failed to rewrite {} with denoised audio: {error}", path.display()))
+    })?;
+    for sample in normalized_samples {
+        writer.write_sample(sample).map_err(|error| {
+            AppError::Capture(format!("failed to write denoised sample to {}: {error}", path.display()))
+        })?;
+    }
+    writer.finalize().map_err(|error| {
+        AppError::Capture(format!("failed to finalize denoised wav {}: {error}", path.display()))
+    })?;
+
+    Ok(DenoiseReport {
+        frames_processed,
+        average_gain: if gain_samples > 0 {
+            gain_sum / gain_samples as f64
+        } else {
+            1.0
+        },
+        noise_rms,
+    })
+}
+
+/// Runs classic STFT spectral subtraction over `path` in place: 512-sample
+/// Hann-windowed frames with 50% overlap are transformed to the frequency
+/// domain, the noise magnitude spectrum is estimated from the leading
+/// `NOISE_PROFILE_MS` of the capture (assumed to be noise-only, the same
+/// leading-silence assumption `capture::vad::trim_silence_and_segment`
+/// relies on), and each frame's magnitude has `alpha` times that noise
+/// estimate subtracted from it, floored at `SPECTRAL_FLOOR` of the noise
+/// estimate so bins are attenuated rather than muted outright. The original
+/// phase is kept and the result is overlap-added back together. Unlike
+/// `denoise_wav`'s continuously-adapting Wiener gain, this is a single
+/// static noise profile, which is truer to the classic algorithm but means a
+/// capture whose background noise changes partway through won't be tracked.
+/// Assumes 16-bit PCM, the only format `capture::mic` ever writes.
+pub fn spectral_subtract_wav(path: &Path, alpha: f64) -> AppResult<DenoiseReport> {
+    let mut reader = hound::WavReader::open(path).map_err(|error| {
+        AppError::Capture(format!("failed to open {} for denoising: {error}", path.display()))
+    })?;
+    let spec = reader.spec();
+
+    let samples: Vec<f32> = reader
+        .samples::<i16>()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| {
+            AppError::Capture(format!(
+                "failed to read samples from {} for denoising: {error}",
+                path.display()
+            ))
+        })?
+        .into_iter()
+        .map(|sample| f32::from(sample) / f32::from(i16::MAX))
+        .collect();
+
+    if samples.is_empty() {
+        return Ok(DenoiseReport {
+            frames_processed: 0,
+            average_gain: 1.0,
+            noise_rms: 0.0,
+        });
+    }
+
+    let profile_len = ((u64::from(spec.sample_rate.max(1)) * NOISE_PROFILE_MS) / 1000) as usize;
+    let noise_rms = time_domain_rms(&samples[..samples.len().min(profile_len).max(1)]);
+
+    let frame_len = SUBTRACTION_FRAME_LEN.min(samples.len().max(2));
+    let hop = (frame_len / 2).max(1);
+    let bin_count = frame_len / 2 + 1;
+
+    let mut planner = RealFftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(frame_len);
+    let ifft = planner.plan_fft_inverse(frame_len);
+
+    let window: Vec<f64> = (0..frame_len).map(|i| hann(i, frame_len)).collect();
+
+    let frame_of = |start: usize| -> AppResult<Vec<Complex<f32>>> {
+        let mut windowed = fft.make_input_vec();
+        for (i, slot) in windowed.iter_mut().enumerate() {
+            let sample = samples.get(start + i).copied().unwrap_or(0.0);
+            *slot = sample * window[i] as f32;
+        }
+        let mut spectrum = fft.make_output_vec();
+        fft.process(&mut windowed, &mut spectrum).map_err(|error| {
+            AppError::Capture(format!("denoise FFT failed on {}: {error}", path.display()))
+        })?;
+        Ok(spectrum)
+    };
+
+    // Average the magnitude spectrum of every frame that falls entirely
+    // within the leading noise-only window (at least one frame, even for a
+    // capture shorter than NOISE_PROFILE_MS).
+    let mut noise_magnitude = vec![0.0_f64; bin_count];
+    let mut noise_frame_count = 0usize;
+    let mut start = 0usize;
+    while start == 0 || (start + frame_len <= profile_len.max(frame_len) && start + frame_len <= samples.len()) {
+        let spectrum = frame_of(start)?;
+        for (bin, value) in spectrum.iter().enumerate() {
+            noise_magnitude[bin] += f64::from(value.norm());
+        }
+        noise_frame_count += 1;
+        if start + frame_len >= samples.len() {
+            break;
+        }
+        start += hop;
+    }
+    for value in noise_magnitude.iter_mut() {
+        *value /= noise_frame_count.max(1) as f64;
+    }
+
+    let mut output = vec![0.0_f32; samples.len()];
+    let mut weight = vec![0.0_f32; samples.len()];
+
+    let mut frames_processed = 0usize;
+    let mut gain_sum = 0.0_f64;
+    let mut gain_samples = 0usize;
+
+    let mut start = 0usize;
+    while start < samples.len() {
+        let mut spectrum = frame_of(start)?;
+
+        for (bin, value) in spectrum.iter_mut().enumerate() {
+            let magnitude = f64::from(value.norm());
+            let phase = f64::from(value.arg());
+            let noise = noise_magnitude[bin];
+            let subtracted = (magnitude - alpha * noise).max(SPECTRAL_FLOOR * noise);
+            *value = Complex::from_polar(subtracted as f32, phase as f32);
+            if magnitude > 0.0 {
+                gain_sum += subtracted / magnitude;
+                gain_samples += 1;
+            }
+        }
+
+        let mut resynthesized = ifft.make_output_vec();
+        ifft.process(&mut spectrum, &mut resynthesized).map_err(|error| {
+            AppError::Capture(format!("denoise inverse FFT failed on {}: {error}", path.display()))
+        })?;
+
+        for (i, value) in resynthesized.iter().enumerate() {
+            let index = start + i;
+            if index >= output.len() {
+                break;
+            }
+            let normalized = value / frame_len as f32;
+            let windowed_value = normalized * window[i] as f32;
+            output[index] += windowed_value;
+            weight[index] += window[i] as f32;
+        }
+
+        frames_processed += 1;
+        if start + frame_len >= samples.len() {
+            break;
+        }
+        start += hop;
+    }
+
+    let normalized_samples: Vec<i16> = output
+        .iter()
+        .zip(weight.iter())
+        .map(|(&value, &weight)| {
+            let sample = if weight > 1e-6 { value / weight } else { value };
+            (sample * f32::from(i16::MAX)).round().clamp(f32::from(i16::MIN), f32::from(i16::MAX)) as i16
+        })
+        .collect();
+
+    let mut writer = hound::WavWriter::create(path, spec).map_err(|error| {
+        AppError::Capture(format!("failed to rewrite {} with denoised audio: {error}", path.display()))
+    })?;
+    for sample in normalized_samples {
+        writer.write_sample(sample).map_err(|error| {
+            AppError::Capture(format!("failed to write denoised sample to {}: {error}", path.display()))
+        })?;
+    }
+    writer.finalize().map_err(|error| {
+        AppError::Capture(format!("failed to finalize denoised wav {}: {error}", path.display()))
+    })?;
+
+    Ok(DenoiseReport {
+        frames_processed,
+        average_gain: if gain_samples > 0 {
+            gain_sum / gain_samples as f64
+        } else {
+            1.0
+        },
+        noise_rms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::denoise_wav;
+
+    fn write_wav(path: &std::path::Path, samples: &[i16], sample_rate: u32) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).expect("create wav");
+        for sample in samples {
+            writer.write_sample(*sample).expect("write sample");
+        }
+        writer.finalize().expect("finalize wav");
+    }
+
+    fn read_wav(path: &std::path::Path) -> Vec<i16> {
+        let mut reader = hound::WavReader::open(path).expect("reopen wav");
+        reader.samples::<i16>().map(|sample| sample.expect("sample")).collect()
+    }
+
+    fn rms(samples: &[i16]) -> f64 {
+        let mean_square: f64 = samples
+            .iter()
+            .map(|&sample| {
+                let normalized = f64::from(sample) / f64::from(i16::MAX);
+                normalized * normalized
+            })
+            .sum::<f64>()
+            / samples.len() as f64;
+        mean_square.sqrt()
+    }
+
+    /// A steady hiss-like signal built from several incommensurate tones,
+    /// standing in for broadband background noise without pulling in a
+    /// `rand` dependency just for this test.
+    fn synthetic_noise(len: usize, sample_rate: u32, amplitude: f64) -> Vec<i16> {
+        let freqs = [733.0, 1_901.0, 3_407.0, 4_999.0];
+        (0..len)
+            .map(|i| {
+                let t = i as f64 / sample_rate as f64;
+                let value: f64 = freqs.iter().map(|f| (2.0 * std::f64::consts::PI * f * t).sin()).sum::<f64>()
+                    / freqs.len() as f64;
+                (value * amplitude * f64::from(i16::MAX)) as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn silence_stays_silent() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("silence.wav");
+        write_wav(&path, &[0_i16; 3_200], 16_000);
+
+        let report = denoise_wav(&path).expect("denoise");
+        assert!(report.frames_processed > 0);
+        assert!(read_wav(&path).iter().all(|&sample| sample.abs() < 50));
+    }
+
+    #[test]
+    fn steady_background_noise_is_attenuated() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("noisy.wav");
+        let samples = synthetic_noise(16_000, 16_000, 0.05);
+        write_wav(&path, &samples, 16_000);
+        let before_rms = rms(&samples);
+
+        let report = denoise_wav(&path).expect("denoise");
+        let after_rms = rms(&read_wav(&path));
+
+        assert!(report.average_gain < 1.0, "steady noise should not get a full-pass gain");
+        assert!(
+            after_rms < before_rms,
+            "denoised rms {after_rms} should be below original {before_rms}"
+        );
+    }
+
+    #[test]
+    fn output_is_same_length_as_input() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("length.wav");
+        let samples = synthetic_noise(12_345, 16_000, 0.1);
+        write_wav(&path, &samples, 16_000);
+
+        denoise_wav(&path).expect("denoise");
+        assert_eq!(read_wav(&path).len(), samples.len());
+    }
+
+    fn tone(len: usize, freq_hz: f64, sample_rate: u32, amplitude: f64) -> Vec<i16> {
+        (0..len)
+            .map(|i| {
+                let t = i as f64 / sample_rate as f64;
+                ((2.0 * std::f64::consts::PI * freq_hz * t).sin() * amplitude * f64::from(i16::MAX)) as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn spectral_subtraction_leaves_silence_silent() {
+        use super::spectral_subtract_wav;
+
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("silence.wav");
+        write_wav(&path, &[0_i16; 3_200], 16_000);
+
+        let report = spectral_subtract_wav(&path, 1.75).expect("denoise");
+        assert!(report.frames_processed > 0);
+        assert!(read_wav(&path).iter().all(|&sample| sample.abs() < 50));
+    }
+
+    #[test]
+    fn spectral_subtraction_attenuates_steady_background_noise() {
+        use super::spectral_subtract_wav;
+
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("noisy.wav");
+        let samples = synthetic_noise(16_000, 16_000, 0.05);
+        write_wav(&path, &samples, 16_000);
+        let before_rms = rms(&samples);
+
+        let report = spectral_subtract_wav(&path, 1.75).expect("denoise");
+        let after_rms = rms(&read_wav(&path));
+
+        assert!(report.average_gain < 1.0, "steady noise should not get a full-pass gain");
+        assert!(
+            after_rms < before_rms,
+            "denoised rms {after_rms} should be below original {before_rms}"
+        );
+    }
+
+    #[test]
+    fn spectral_subtraction_preserves_a_loud_burst_over_the_noise_profile_better_than_the_noise_itself() {
+        use super::spectral_subtract_wav;
+
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("burst.wav");
+
+        let sample_rate = 16_000;
+        let mut samples = synthetic_noise(4_800, sample_rate, 0.05);
+        samples.extend(tone(3_200, 440.0, sample_rate, 0.8));
+        samples.extend(synthetic_noise(4_800, sample_rate, 0.05));
+        write_wav(&path, &samples, sample_rate);
+
+        spectral_subtract_wav(&path, 1.75).expect("denoise");
+        let denoised = read_wav(&path);
+
+        let noise_rms_after = rms(&denoised[..4_800]);
+        let burst_rms_after = rms(&denoised[4_800..4_800 + 3_200]);
+        assert!(
+            burst_rms_after > noise_rms_after,
+            "burst rms {burst_rms_after} should remain louder than the suppressed noise rms {noise_rms_after}"
+        );
+    }
+
+    #[test]
+    fn spectral_subtraction_output_is_same_length_as_input() {
+        use super::spectral_subtract_wav;
+
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("length.wav");
+        let samples = synthetic_noise(12_345, 16_000, 0.1);
+        write_wav(&path, &samples, 16_000);
+
+        spectral_subtract_wav(&path, 1.75).expect("denoise");
+        assert_eq!(read_wav(&path).len(), samples.len());
+    }
+}
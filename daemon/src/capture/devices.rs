@@ -1,65 +1,406 @@
-#[cfg(target_os = "macos")]
 use cpal::traits::{DeviceTrait, HostTrait};
 
 use crate::error::{AppError, AppResult};
 
+/// A single recording input device, enumerated well enough for a UI to
+/// present a device picker and to validate `preferred_device` before
+/// recording starts; see `MicrophoneCapture::list_input_devices`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputDeviceInfo {
+    pub name: String,
+    /// Whether this is the host's default input device.
+    pub is_default: bool,
+    /// Every sample rate (Hz) the device reports support for; empty where
+    /// the enumeration source doesn't expose capabilities (see
+    /// `via_arecord_fallback` below).
+    pub supported_sample_rates: Vec<u32>,
+    /// Every channel count the device reports support for; empty where the
+    /// enumeration source doesn't expose capabilities.
+    pub supported_channels: Vec<u16>,
+    /// Set when this entry came from the `arecord -L` fallback rather than
+    /// cpal's enumeration. ALSA's PCM listing doesn't expose capabilities
+    /// without opening the device, so `supported_sample_rates` and
+    /// `supported_channels` are always empty on a fallback entry, and `name`
+    /// is a raw ALSA PCM identifier (`default`, `sysdefault:CARD=...`)
+    /// rather than a human-readable device name.
+    pub via_arecord_fallback: bool,
+    /// Stable identifier shared by every stream belonging to the same
+    /// physical audio interface, from CoreAudio's `kAudioDevicePropertyRelatedDevices`
+    /// (see `describe_input_devices_via_coreaudio`). `None` wherever the
+    /// enumeration source has no such concept (cpal on Linux/Windows, the
+    /// `arecord -L` fallback), in which case every entry is its own group;
+    /// see `dedupe_by_group`.
+    pub group_id: Option<String>,
+}
+
+/// Enumerates input devices with their supported sample rates and channel
+/// counts via cpal (`Host::input_devices`/`default_input_device`, the same
+/// approach as cpal's `enumerate` example), so the capture layer can pick a
+/// device that actually supports the 16 kHz mono format whisper wants
+/// instead of guessing from a name string. On macOS it first queries
+/// CoreAudio directly (`describe_input_devices_via_coreaudio`) and uses cpal
+/// only if that fails; Linux (ALSA) and Windows (WASAPI) go through cpal
+/// directly. On Linux, if cpal's host fails to initialize, falls back
+/// to parsing `arecord -L` with `via_arecord_fallback` set on every entry.
+/// Backs `MicrophoneCapture::list_input_devices`.
+pub fn describe_input_devices() -> AppResult<Vec<InputDeviceInfo>> {
+    #[cfg(target_os = "macos")]
+    {
+        match describe_input_devices_via_coreaudio() {
+            Ok(devices) => return Ok(devices),
+            Err(error) => {
+                tracing::warn!(
+                    "CoreAudio input enumeration failed, falling back to cpal: {error}"
+                );
+            }
+        }
+    }
+
+    let cpal_result = describe_input_devices_via_cpal();
+
+    #[cfg(target_os = "linux")]
+    if let Err(error) = &cpal_result {
+        tracing::warn!("cpal input enumeration failed, falling back to `arecord -L`: {error}");
+        return describe_input_devices_via_arecord();
+    }
+
+    cpal_result
+}
+
+/// Collapses `devices` down to the count of distinct physical interfaces,
+/// using `group_id` where the enumeration source provides one (currently
+/// only `describe_input_devices_via_coreaudio`) so a multi-channel interface
+/// that shows up as several streams is reported as one device. Entries with
+/// no `group_id` are never collapsed with each other, since there's no
+/// signal they share hardware.
+pub fn dedupe_by_group(devices: &[InputDeviceInfo]) -> usize {
+    let mut seen_groups = std::collections::HashSet::new();
+    let mut count = 0;
+    for device in devices {
+        match &device.group_id {
+            Some(group) => {
+                if seen_groups.insert(group.clone()) {
+                    count += 1;
+                }
+            }
+            None => count += 1,
+        }
+    }
+    count
+}
+
+/// Enumerates input devices directly against CoreAudio's `AudioObject`
+/// property API rather than going through cpal, so that each entry can carry
+/// a `group_id` derived from `kAudioDevicePropertyRelatedDevices`: devices
+/// that share a physical interface (e.g. the inputs of an aggregate or a
+/// multi-channel USB audio box) report the same group, letting callers like
+/// `dedupe_by_group` collapse them into one logical microphone. cpal has no
+/// equivalent concept, which is why `describe_input_devices` only takes this
+/// path on macOS and falls back to `describe_input_devices_via_cpal` on
+/// error.
 #[cfg(target_os = "macos")]
-pub fn list_input_devices() -> AppResult<Vec<String>> {
+fn describe_input_devices_via_coreaudio() -> AppResult<Vec<InputDeviceInfo>> {
+    use coreaudio_sys::{
+        kAudioDevicePropertyAvailableNominalSampleRates, kAudioDevicePropertyDeviceUID,
+        kAudioDevicePropertyRelatedDevices, kAudioDevicePropertyStreamConfiguration,
+        kAudioHardwarePropertyDefaultInputDevice, kAudioHardwarePropertyDevices,
+        kAudioObjectPropertyElementMaster, kAudioObjectPropertyScopeGlobal,
+        kAudioObjectPropertyScopeInput, kAudioObjectSystemObject, AudioBufferList, AudioObjectID,
+        AudioObjectPropertyAddress, AudioValueRange,
+    };
+
+    fn coreaudio_error(context: &str, status: coreaudio_sys::OSStatus) -> AppError {
+        AppError::Capture(format!("{context}: CoreAudio status {status}"))
+    }
+
+    fn get_property_data_size(
+        object_id: AudioObjectID,
+        address: &AudioObjectPropertyAddress,
+    ) -> AppResult<u32> {
+        let mut size: u32 = 0;
+        let status = unsafe {
+            coreaudio_sys::AudioObjectGetPropertyDataSize(
+                object_id,
+                address,
+                0,
+                std::ptr::null(),
+                &mut size,
+            )
+        };
+        if status != 0 {
+            return Err(coreaudio_error("failed to read property size", status));
+        }
+        Ok(size)
+    }
+
+    fn device_ids(object_id: AudioObjectID, address: &AudioObjectPropertyAddress) -> AppResult<Vec<AudioObjectID>> {
+        let size = get_property_data_size(object_id, address)?;
+        let count = size as usize / std::mem::size_of::<AudioObjectID>();
+        let mut ids = vec![0 as AudioObjectID; count];
+        let mut actual_size = size;
+        let status = unsafe {
+            coreaudio_sys::AudioObjectGetPropertyData(
+                object_id,
+                address,
+                0,
+                std::ptr::null(),
+                &mut actual_size,
+                ids.as_mut_ptr() as *mut _,
+            )
+        };
+        if status != 0 {
+            return Err(coreaudio_error("failed to read device id list", status));
+        }
+        Ok(ids)
+    }
+
+    fn device_uid(device_id: AudioObjectID) -> AppResult<String> {
+        let address = AudioObjectPropertyAddress {
+            mSelector: kAudioDevicePropertyDeviceUID,
+            mScope: kAudioObjectPropertyScopeGlobal,
+            mElement: kAudioObjectPropertyElementMaster,
+        };
+        let mut size = std::mem::size_of::<core_foundation::string::CFStringRef>() as u32;
+        let mut uid_ref: core_foundation::string::CFStringRef = std::ptr::null_mut();
+        let status = unsafe {
+            coreaudio_sys::AudioObjectGetPropertyData(
+                device_id,
+                &address,
+                0,
+                std::ptr::null(),
+                &mut size,
+                &mut uid_ref as *mut _ as *mut _,
+            )
+        };
+        if status != 0 || uid_ref.is_null() {
+            return Err(coreaudio_error("failed to read device UID", status));
+        }
+        let uid = unsafe { core_foundation::string::CFString::wrap_under_create_rule(uid_ref) };
+        Ok(uid.to_string())
+    }
+
+    fn input_channel_count(device_id: AudioObjectID) -> AppResult<u16> {
+        let address = AudioObjectPropertyAddress {
+            mSelector: kAudioDevicePropertyStreamConfiguration,
+            mScope: kAudioObjectPropertyScopeInput,
+            mElement: kAudioObjectPropertyElementMaster,
+        };
+        let size = get_property_data_size(device_id, &address)?;
+        if size == 0 {
+            return Ok(0);
+        }
+        let mut buffer = vec![0u8; size as usize];
+        let mut actual_size = size;
+        let status = unsafe {
+            coreaudio_sys::AudioObjectGetPropertyData(
+                device_id,
+                &address,
+                0,
+                std::ptr::null(),
+                &mut actual_size,
+                buffer.as_mut_ptr() as *mut _,
+            )
+        };
+        if status != 0 {
+            return Err(coreaudio_error("failed to read stream configuration", status));
+        }
+        let list = unsafe { &*(buffer.as_ptr() as *const AudioBufferList) };
+        let channels = (0..list.mNumberBuffers)
+            .map(|index| unsafe { list.mBuffers.get_unchecked(index as usize).mNumberChannels })
+            .sum();
+        Ok(channels as u16)
+    }
+
+    fn sample_rates(device_id: AudioObjectID) -> AppResult<Vec<u32>> {
+        let address = AudioObjectPropertyAddress {
+            mSelector: kAudioDevicePropertyAvailableNominalSampleRates,
+            mScope: kAudioObjectPropertyScopeGlobal,
+            mElement: kAudioObjectPropertyElementMaster,
+        };
+        let size = get_property_data_size(device_id, &address)?;
+        let count = size as usize / std::mem::size_of::<AudioValueRange>();
+        let mut ranges = vec![AudioValueRange { mMinimum: 0.0, mMaximum: 0.0 }; count];
+        let mut actual_size = size;
+        let status = unsafe {
+            coreaudio_sys::AudioObjectGetPropertyData(
+                device_id,
+                &address,
+                0,
+                std::ptr::null(),
+                &mut actual_size,
+                ranges.as_mut_ptr() as *mut _,
+            )
+        };
+        if status != 0 {
+            return Err(coreaudio_error("failed to read sample rate ranges", status));
+        }
+        let mut rates: Vec<u32> = ranges
+            .iter()
+            .flat_map(|range| [range.mMinimum as u32, range.mMaximum as u32])
+            .collect();
+        rates.sort_unstable();
+        rates.dedup();
+        Ok(rates)
+    }
+
+    /// The group id is the lexicographically smallest UID among a device's
+    /// related devices (itself included), so every member of the group
+    /// agrees on the same stable key regardless of enumeration order.
+    fn group_id(device_id: AudioObjectID, own_uid: &str) -> AppResult<String> {
+        let address = AudioObjectPropertyAddress {
+            mSelector: kAudioDevicePropertyRelatedDevices,
+            mScope: kAudioObjectPropertyScopeGlobal,
+            mElement: kAudioObjectPropertyElementMaster,
+        };
+        let related = match device_ids(device_id, &address) {
+            Ok(related) => related,
+            Err(_) => return Ok(own_uid.to_owned()),
+        };
+        let mut uids = vec![own_uid.to_owned()];
+        for related_id in related {
+            if related_id != device_id {
+                if let Ok(uid) = device_uid(related_id) {
+                    uids.push(uid);
+                }
+            }
+        }
+        uids.sort_unstable();
+        Ok(uids.into_iter().next().unwrap_or_else(|| own_uid.to_owned()))
+    }
+
+    let devices_address = AudioObjectPropertyAddress {
+        mSelector: kAudioHardwarePropertyDevices,
+        mScope: kAudioObjectPropertyScopeGlobal,
+        mElement: kAudioObjectPropertyElementMaster,
+    };
+    let all_devices = device_ids(kAudioObjectSystemObject, &devices_address)?;
+
+    let default_address = AudioObjectPropertyAddress {
+        mSelector: kAudioHardwarePropertyDefaultInputDevice,
+        mScope: kAudioObjectPropertyScopeGlobal,
+        mElement: kAudioObjectPropertyElementMaster,
+    };
+    let default_device_id = device_ids(kAudioObjectSystemObject, &default_address)?
+        .into_iter()
+        .next()
+        .unwrap_or(0);
+
+    let mut infos = Vec::new();
+    for device_id in all_devices {
+        let channels = input_channel_count(device_id)?;
+        if channels == 0 {
+            // No input streams; this is an output-only or unrelated device.
+            continue;
+        }
+
+        let uid = device_uid(device_id)?;
+        infos.push(InputDeviceInfo {
+            is_default: device_id == default_device_id,
+            name: uid.clone(),
+            supported_sample_rates: sample_rates(device_id).unwrap_or_default(),
+            supported_channels: vec![channels],
+            via_arecord_fallback: false,
+            group_id: Some(group_id(device_id, &uid)?),
+        });
+    }
+
+    Ok(infos)
+}
+
+fn describe_input_devices_via_cpal() -> AppResult<Vec<InputDeviceInfo>> {
     let host = cpal::default_host();
+    let default_name = host.default_input_device().and_then(|device| device.name().ok());
+
     let devices = host.input_devices().map_err(|error| {
         AppError::Capture(format!("failed to enumerate input devices: {error}"))
     })?;
 
-    let mut names = Vec::new();
+    let mut infos = Vec::new();
     for device in devices {
         let name = device
             .name()
             .map_err(|error| AppError::Capture(format!("failed to read device name: {error}")))?;
-        names.push(name);
+        let configs = device.supported_input_configs().map_err(|error| {
+            AppError::Capture(format!(
+                "failed to read supported configs for `{name}`: {error}"
+            ))
+        })?;
+
+        let mut supported_sample_rates = Vec::new();
+        let mut supported_channels = Vec::new();
+        for config in configs {
+            for rate in [config.min_sample_rate().0, config.max_sample_rate().0] {
+                if !supported_sample_rates.contains(&rate) {
+                    supported_sample_rates.push(rate);
+                }
+            }
+            let channels = config.channels();
+            if !supported_channels.contains(&channels) {
+                supported_channels.push(channels);
+            }
+        }
+        supported_sample_rates.sort_unstable();
+        supported_channels.sort_unstable();
+
+        infos.push(InputDeviceInfo {
+            is_default: default_name.as_deref() == Some(name.as_str()),
+            name,
+            supported_sample_rates,
+            supported_channels,
+            via_arecord_fallback: false,
+            group_id: None,
+        });
     }
 
-    Ok(names)
+    Ok(infos)
 }
 
+/// Enumerates input devices by parsing `arecord -L`, which lists one ALSA
+/// PCM name per line (`default`, `sysdefault:CARD=...`, `hw:...`) followed
+/// by an indented description line. Only reached from `describe_input_devices`
+/// when cpal's ALSA host fails to initialize.
 #[cfg(target_os = "linux")]
-pub fn list_input_devices() -> AppResult<Vec<String>> {
-    if which::which("arecord").is_ok() {
-        let output = std::process::Command::new("arecord")
-            .arg("-l")
-            .output()
-            .map_err(|error| {
-                AppError::Capture(format!("failed to execute `arecord -l`: {error}"))
-            })?;
+fn describe_input_devices_via_arecord() -> AppResult<Vec<InputDeviceInfo>> {
+    if which::which("arecord").is_err() {
+        return Err(AppError::BinaryMissing {
+            binary: "arecord".to_owned(),
+        });
+    }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let devices = stdout
-            .lines()
-            .filter(|line| line.contains("card "))
-            .map(|line| line.trim().to_owned())
-            .collect::<Vec<_>>();
+    let output = std::process::Command::new("arecord")
+        .arg("-L")
+        .output()
+        .map_err(|error| AppError::Capture(format!("failed to execute `arecord -L`: {error}")))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
 
-        return Ok(devices);
-    }
+    let infos: Vec<InputDeviceInfo> = stdout
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with(' '))
+        .map(|line| {
+            let name = line.trim().to_owned();
+            InputDeviceInfo {
+                is_default: name == "default",
+                name,
+                supported_sample_rates: Vec::new(),
+                supported_channels: Vec::new(),
+                via_arecord_fallback: true,
+                group_id: None,
+            }
+        })
+        .collect();
 
-    if which::which("ffmpeg").is_ok() {
-        return Ok(vec!["default (ffmpeg/alsa input)".to_owned()]);
+    if infos.is_empty() {
+        return Err(AppError::Capture(
+            "arecord -L reported no PCM devices".to_owned(),
+        ));
     }
 
-    Err(AppError::BinaryMissing {
-        binary: "arecord or ffmpeg".to_owned(),
-    })
-}
-
-#[cfg(not(any(target_os = "macos", target_os = "linux")))]
-pub fn list_input_devices() -> AppResult<Vec<String>> {
-    Err(AppError::Capture(
-        "input device enumeration is only implemented for macOS and Linux in v1".to_owned(),
-    ))
+    Ok(infos)
 }
 
 #[cfg(all(test, target_os = "linux"))]
 mod tests {
-    use super::list_input_devices;
+    use super::{describe_input_devices_via_arecord, InputDeviceInfo};
     use crate::error::AppError;
     use std::fs;
     use std::path::Path;
@@ -96,7 +437,7 @@ mod tests {
     }
 
     #[test]
-    fn list_devices_prefers_arecord() {
+    fn arecord_fallback_parses_pcm_names_and_labels_every_entry() {
         let _guard = crate::test_support::lock_env();
         let temp = tempfile::TempDir::new().expect("tempdir");
         let bin = temp.path().join("bin");
@@ -105,46 +446,40 @@ mod tests {
         write_script(
             &bin.join("arecord"),
             r#"#!/bin/sh
-echo "card 0: Mock [Mock], device 0: USB [USB]"
+echo "default"
+echo "    Default ALSA Output"
+echo "sysdefault:CARD=USB"
+echo "    USB Microphone, USB Audio"
 "#,
         );
-        write_script(
-            &bin.join("ffmpeg"),
-            r#"#!/bin/sh
-echo "ffmpeg version 9.0"
-"#,
-        );
-        let devices = list_input_devices().expect("devices");
-        assert_eq!(devices.len(), 1);
-        assert!(devices[0].contains("card 0"));
-    }
 
-    #[test]
-    fn list_devices_falls_back_to_ffmpeg() {
-        let _guard = crate::test_support::lock_env();
-        let temp = tempfile::TempDir::new().expect("tempdir");
-        let bin = temp.path().join("bin");
-        fs::create_dir_all(&bin).expect("mkdir");
-        let _path = EnvVarGuard::set("PATH", bin.to_str().expect("utf8"));
-        write_script(
-            &bin.join("ffmpeg"),
-            r#"#!/bin/sh
-echo "ffmpeg version 9.0"
-"#,
+        let devices = describe_input_devices_via_arecord().expect("devices");
+        assert_eq!(devices.len(), 2);
+        assert_eq!(
+            devices[0],
+            InputDeviceInfo {
+                name: "default".to_owned(),
+                is_default: true,
+                supported_sample_rates: Vec::new(),
+                supported_channels: Vec::new(),
+                via_arecord_fallback: true,
+                group_id: None,
+            }
         );
-        let devices = list_input_devices().expect("devices");
-        assert_eq!(devices, vec!["default (ffmpeg/alsa input)".to_owned()]);
+        assert_eq!(devices[1].name, "sysdefault:CARD=USB");
+        assert!(!devices[1].is_default);
+        assert!(devices[1].via_arecord_fallback);
     }
 
     #[test]
-    fn list_devices_errors_when_no_recorders() {
+    fn arecord_fallback_errors_when_arecord_missing() {
         let _guard = crate::test_support::lock_env();
         let temp = tempfile::TempDir::new().expect("tempdir");
         let bin = temp.path().join("bin");
         fs::create_dir_all(&bin).expect("mkdir");
         let _path = EnvVarGuard::set("PATH", bin.to_str().expect("utf8"));
 
-        let err = list_input_devices().expect_err("must fail");
+        let err = describe_input_devices_via_arecord().expect_err("must fail");
         assert!(matches!(err, AppError::BinaryMissing { .. }));
     }
 }
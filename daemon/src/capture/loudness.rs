@@ -0,0 +1,423 @@
+use std::f64::consts::PI;
+use std::path::Path;
+
+use crate::error::{AppError, AppResult};
+
+/// Target integrated loudness EBU R128 prescribes for broadcast audio, in
+/// LUFS; kept around as a documented reference point even though
+/// `AudioConfig::target_lufs` now defaults to `SPEECH_TARGET_LUFS` instead.
+pub const EBU_R128_TARGET_LUFS: f64 = -23.0;
+
+/// Default target for dictation/speech capture, a good deal louder than the
+/// broadcast reference level above: speech recognizers and human listeners
+/// both benefit from a hotter level than -23 LUFS gives a typically
+/// close-mic'd, low-dynamic-range dictation clip.
+pub const SPEECH_TARGET_LUFS: f64 = -16.0;
+
+/// How much gain `normalize_wav_loudness` will apply in either direction; a
+/// capture measured far outside this range is left alone rather than
+/// amplified into audible noise floor or crushed towards silence.
+const MAX_GAIN_DB: f64 = 24.0;
+
+/// Block size and hop for the gated loudness measurement below, per ITU-R
+/// BS.1770 / EBU R128: 400ms blocks with a 100ms hop (75% overlap).
+const BLOCK_MS: f64 = 400.0;
+const HOP_MS: f64 = 100.0;
+
+/// BS.1770's absolute gate: blocks quieter than this are silence/noise floor
+/// and never contribute to the integrated measurement.
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+
+/// BS.1770's relative gate: after the absolute gate, blocks more than this
+/// many LU below the (absolute-gated) mean are dropped too, so a short loud
+/// passage isn't diluted by a long quiet one.
+const RELATIVE_GATE_LU: f64 = -10.0;
+
+/// What `normalize_wav_loudness` measured and applied, reported so callers
+/// (and tests) can observe the effect without re-reading the WAV.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoudnessReport {
+    /// Integrated loudness of the capture before normalization, in LUFS.
+    pub input_lufs: f64,
+    /// Integrated loudness after the applied gain, in LUFS (equal to
+    /// `input_lufs + gain_db` except where peak-limiting clamped the gain).
+    pub output_lufs: f64,
+    /// Gain actually applied to the samples, in dB.
+    pub gain_db: f64,
+}
+
+/// A single cascaded biquad stage of the BS.1770 K-weighting filter, in
+/// direct form I. Coefficients are derived in `k_weighting_filters` from the
+/// standard bilinear-transform formulas, parametrized by the capture's
+/// actual sample rate (BS.1770 itself only tabulates coefficients for
+/// 48kHz; deriving them lets this work at the 16kHz mono rate
+/// `capture::mic` records at).
+struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    x1: f64,
+    x2: f64,
+    y1: f64,
+    y2: f64,
+}
+
+impl Biquad {
+    fn new(b0: f64, b1: f64, b2: f64, a1: f64, a2: f64) -> Self {
+        Self { b0, b1, b2, a1, a2, x1: 0.0, x2: 0.0, y1: 0.0, y2: 0.0 }
+    }
+
+    fn process(&mut self, x0: f64) -> f64 {
+        let y0 = self.b0 * x0 + self.b1 * self.x1 + self.b2 * self.x2
+            - self.a1 * self.y1
+            - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x0;
+        self.y2 = self.y1;
+        self.y1 = y0;
+        y0
+    }
+}
+
+/// Builds the two cascaded biquad stages of the BS.1770 K-weighting filter
+/// for `sample_rate`: a high-shelf stage approximating the head's acoustic
+/// effect at high frequencies, followed by a high-pass stage approximating
+/// the combined effect of head diffraction and the ear's reduced
+/// sensitivity to very low frequencies. Coefficients use the standard
+/// libebur128/BS.1770 shelf and high-pass constants (f0, gain, Q), mapped to
+/// `sample_rate` via the RBJ bilinear-transform formulas rather than the
+/// 48kHz-only tabulated values BS.1770 publishes.
+fn k_weighting_filters(sample_rate: f64) -> (Biquad, Biquad) {
+    // Stage 1: high-shelf, +4dB above ~1.5kHz.
+    let f0 = 1681.974_450_955_531_9;
+    let gain_db = 3.999_843_853_973_347;
+    let q = 0.707_175_236_955_419_3;
+    let k = (PI * f0 / sample_rate).tan();
+    let vh = 10f64.powf(gain_db / 20.0);
+    let vb = vh.powf(0.499_666_774_155_6);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad::new(
+        (vh + vb * k / q + k * k) / a0,
+        2.0 * (k * k - vh) / a0,
+        (vh - vb * k / q + k * k) / a0,
+        2.0 * (k * k - 1.0) / a0,
+        (1.0 - k / q + k * k) / a0,
+    );
+
+    // Stage 2: high-pass, ~38Hz.
+    let f0 = 38.135_470_876_024_44;
+    let q = 0.500_327_037_323_877_3;
+    let k = (PI * f0 / sample_rate).tan();
+    let a0 = 1.0 + k / q + k * k;
+    let highpass = Biquad::new(1.0 / a0, -2.0 / a0, 1.0 / a0, 2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0);
+
+    (shelf, highpass)
+}
+
+/// Runs `samples` through both K-weighting stages in cascade, returning the
+/// filtered signal used for block-loudness measurement.
+fn k_weight(samples: &[i16], sample_rate: u32) -> Vec<f64> {
+    let (mut shelf, mut highpass) = k_weighting_filters(f64::from(sample_rate));
+    samples
+        .iter()
+        .map(|&sample| {
+            let x = f64::from(sample) / f64::from(i16::MAX);
+            highpass.process(shelf.process(x))
+        })
+        .collect()
+}
+
+/// Converts a block/gate's mean-square power to LUFS via BS.1770's
+/// loudness-to-power relation.
+fn power_to_lufs(mean_square: f64) -> f64 {
+    -0.691 + 10.0 * mean_square.log10()
+}
+
+/// Measures `weighted`'s gated integrated loudness per BS.1770: mean-square
+/// power is measured in overlapping 400ms blocks (100ms hop), blocks quieter
+/// than `ABSOLUTE_GATE_LUFS` are dropped, then blocks more than
+/// `RELATIVE_GATE_LU` below the mean of what's left are dropped too, and the
+/// integrated loudness is the energy mean of what survives both gates.
+/// Returns `f64::NEG_INFINITY` if every block is silence (nothing survives
+/// the absolute gate).
+fn gated_integrated_loudness(weighted: &[f64], sample_rate: u32) -> f64 {
+    let block_len = ((BLOCK_MS / 1000.0) * f64::from(sample_rate)) as usize;
+    let hop_len = ((HOP_MS / 1000.0) * f64::from(sample_rate)) as usize;
+    if block_len == 0 || hop_len == 0 || weighted.len() < block_len {
+        let mean_square = weighted.iter().map(|v| v * v).sum::<f64>() / weighted.len().max(1) as f64;
+        return if mean_square > 0.0 { power_to_lufs(mean_square) } else { f64::NEG_INFINITY };
+    }
+
+    let block_powers: Vec<f64> = weighted
+        .windows(block_len)
+        .step_by(hop_len)
+        .map(|block| block.iter().map(|v| v * v).sum::<f64>() / block_len as f64)
+        .collect();
+
+    let absolute_gated: Vec<f64> = block_powers
+        .iter()
+        .copied()
+        .filter(|&power| power > 0.0 && power_to_lufs(power) > ABSOLUTE_GATE_LUFS)
+        .collect();
+    if absolute_gated.is_empty() {
+        return f64::NEG_INFINITY;
+    }
+
+    let absolute_gated_mean =
+        absolute_gated.iter().sum::<f64>() / absolute_gated.len() as f64;
+    let relative_threshold_lufs = power_to_lufs(absolute_gated_mean) + RELATIVE_GATE_LU;
+
+    let relative_gated: Vec<f64> = absolute_gated
+        .iter()
+        .copied()
+        .filter(|&power| power_to_lufs(power) > relative_threshold_lufs)
+        .collect();
+    if relative_gated.is_empty() {
+        return power_to_lufs(absolute_gated_mean);
+    }
+
+    let relative_gated_mean = relative_gated.iter().sum::<f64>() / relative_gated.len() as f64;
+    power_to_lufs(relative_gated_mean)
+}
+
+/// Measures `path`'s integrated loudness and rewrites it in place with
+/// whatever gain brings it to `target_lufs`, clamped to `MAX_GAIN_DB` and
+/// limited so no sample clips. Implements the full ITU-R BS.1770 / EBU R128
+/// algorithm: samples are K-weighted (a high-shelf stage then a high-pass
+/// stage, both derived for the capture's actual sample rate), measured in
+/// overlapping 400ms blocks, and gated absolutely (below -70 LUFS) and
+/// relatively (more than 10 LU under the absolute-gated mean) before being
+/// averaged into one integrated value. Assumes 16-bit PCM, the only format
+/// `capture::mic` ever writes.
+pub fn normalize_wav_loudness(path: &Path, target_lufs: f64) -> AppResult<LoudnessReport> {
+    let mut reader = hound::WavReader::open(path).map_err(|error| {
+        AppError::Capture(format!(
+            "failed to open {} for loudness normalization: {error}",
+            path.display()
+        ))
+    })?;
+    let spec = reader.spec();
+
+    let samples: Vec<i16> = reader.samples::<i16>().collect::<Result<_, _>>().map_err(|error| {
+        AppError::Capture(format!(
+            "failed to read samples from {} for loudness normalization: {error}",
+            path.display()
+        ))
+    })?;
+
+    if samples.is_empty() {
+        return Ok(LoudnessReport { input_lufs: f64::NEG_INFINITY, output_lufs: f64::NEG_INFINITY, gain_db: 0.0 });
+    }
+
+    let weighted = k_weight(&samples, spec.sample_rate);
+    let input_lufs = gated_integrated_loudness(&weighted, spec.sample_rate);
+
+    if input_lufs == f64::NEG_INFINITY {
+        // Pure digital silence (or noise floor entirely below the absolute
+        // gate): there is no level to correct.
+        return Ok(LoudnessReport { input_lufs, output_lufs: input_lufs, gain_db: 0.0 });
+    }
+
+    let desired_gain_db = (target_lufs - input_lufs).clamp(-MAX_GAIN_DB, MAX_GAIN_DB);
+    let desired_gain = 10f64.powf(desired_gain_db / 20.0);
+
+    let peak = samples
+        .iter()
+        .map(|&sample| f64::from(sample).abs())
+        .fold(0.0_f64, f64::max);
+    let applied_gain = if peak * desired_gain > f64::from(i16::MAX) {
+        f64::from(i16::MAX) / peak.max(1.0)
+    } else {
+        desired_gain
+    };
+
+    let normalized_samples: Vec<i16> = samples
+        .iter()
+        .map(|&sample| {
+            (f64::from(sample) * applied_gain)
+                .round()
+                .clamp(f64::from(i16::MIN), f64::from(i16::MAX)) as i16
+        })
+        .collect();
+
+    let mut writer = hound::WavWriter::create(path, spec).map_err(|error| {
+        AppError::Capture(format!(
+            "failed to rewrite {} with normalized loudness: {error}",
+            path.display()
+        ))
+    })?;
+    for sample in &normalized_samples {
+        writer.write_sample(*sample).map_err(|error| {
+            AppError::Capture(format!(
+                "failed to write normalized sample to {}: {error}",
+                path.display()
+            ))
+        })?;
+    }
+    writer.finalize().map_err(|error| {
+        AppError::Capture(format!(
+            "failed to finalize normalized wav {}: {error}",
+            path.display()
+        ))
+    })?;
+
+    let gain_db = 20.0 * applied_gain.log10();
+    let output_lufs = input_lufs + gain_db;
+
+    Ok(LoudnessReport { input_lufs, output_lufs, gain_db })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_wav_loudness, EBU_R128_TARGET_LUFS};
+
+    fn write_wav(path: &std::path::Path, samples: &[i16], sample_rate: u32) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).expect("create wav");
+        for sample in samples {
+            writer.write_sample(*sample).expect("write sample");
+        }
+        writer.finalize().expect("finalize wav");
+    }
+
+    fn read_wav(path: &std::path::Path) -> Vec<i16> {
+        let mut reader = hound::WavReader::open(path).expect("reopen wav");
+        reader.samples::<i16>().map(|sample| sample.expect("sample")).collect()
+    }
+
+    fn rms(samples: &[i16]) -> f64 {
+        let mean_square: f64 = samples
+            .iter()
+            .map(|&sample| {
+                let normalized = f64::from(sample) / f64::from(i16::MAX);
+                normalized * normalized
+            })
+            .sum::<f64>()
+            / samples.len() as f64;
+        mean_square.sqrt()
+    }
+
+    fn tone(sample_count: usize, half_period: usize, amplitude: i16) -> Vec<i16> {
+        (0..sample_count)
+            .map(|i| if (i / half_period) % 2 == 0 { amplitude } else { -amplitude })
+            .collect()
+    }
+
+    #[test]
+    fn silence_is_left_untouched() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("silence.wav");
+        write_wav(&path, &[0_i16; 16_000], 16_000);
+
+        let report = normalize_wav_loudness(&path, EBU_R128_TARGET_LUFS).expect("normalize");
+        assert_eq!(report.input_lufs, f64::NEG_INFINITY);
+        assert_eq!(report.gain_db, 0.0);
+        assert!(read_wav(&path).iter().all(|&sample| sample == 0));
+    }
+
+    #[test]
+    fn quiet_capture_is_boosted_towards_target() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("quiet.wav");
+        let quiet_amplitude = (i16::MAX as f64 * 0.02) as i16;
+        let samples = tone(16_000 * 2, 8, quiet_amplitude);
+        write_wav(&path, &samples, 16_000);
+        let before_rms = rms(&samples);
+
+        let report = normalize_wav_loudness(&path, EBU_R128_TARGET_LUFS).expect("normalize");
+        assert!(report.gain_db > 0.0, "a quiet capture must be boosted");
+
+        let after_rms = rms(&read_wav(&path));
+        assert!(
+            after_rms > before_rms,
+            "normalized rms {after_rms} should exceed original {before_rms}"
+        );
+        assert!(report.output_lufs > report.input_lufs);
+    }
+
+    #[test]
+    fn loud_capture_is_limited_to_avoid_clipping() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("loud.wav");
+        let samples = tone(16_000 * 2, 8, i16::MAX);
+        write_wav(&path, &samples, 16_000);
+
+        let report = normalize_wav_loudness(&path, EBU_R128_TARGET_LUFS).expect("normalize");
+        assert!(
+            report.gain_db <= 0.0,
+            "a full-scale capture must not be boosted further"
+        );
+
+        let after = read_wav(&path);
+        assert!(after.iter().all(|&sample| sample <= i16::MAX && sample >= -i16::MAX));
+    }
+
+    #[test]
+    fn gain_is_clamped_so_near_silent_captures_are_not_amplified_into_noise() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("near_silent.wav");
+        let tiny_amplitude: i16 = 2;
+        let samples = tone(16_000 * 2, 8, tiny_amplitude);
+        write_wav(&path, &samples, 16_000);
+
+        let report = normalize_wav_loudness(&path, EBU_R128_TARGET_LUFS).expect("normalize");
+        assert!(report.gain_db <= 24.0 + f64::EPSILON);
+    }
+
+    #[test]
+    fn k_weighting_boosts_measured_loudness_relative_to_unweighted_rms() {
+        // A steady low tone sits below the K-weighting high-pass's corner, so
+        // its K-weighted loudness measures quieter than plain (unweighted)
+        // RMS would suggest.
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("low_tone.wav");
+        let amplitude = (i16::MAX as f64 * 0.2) as i16;
+        let samples = tone(16_000 * 2, 400, amplitude);
+        write_wav(&path, &samples, 16_000);
+        let unweighted_lufs = -0.691 + 10.0 * rms(&samples).powi(2).log10();
+
+        let report = normalize_wav_loudness(&path, EBU_R128_TARGET_LUFS).expect("normalize");
+        assert!(
+            report.input_lufs < unweighted_lufs,
+            "K-weighted loudness {} should measure quieter than unweighted {unweighted_lufs} for a low tone",
+            report.input_lufs
+        );
+    }
+
+    #[test]
+    fn quiet_leading_silence_is_gated_out_of_the_integrated_measurement() {
+        // A long silent lead-in followed by a much louder burst of speech:
+        // the gates should mean the integrated loudness tracks the burst,
+        // not the diluted whole-file average.
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("gated.wav");
+        let mut samples = vec![0_i16; 16_000 * 3];
+        let amplitude = (i16::MAX as f64 * 0.5) as i16;
+        samples.extend(tone(16_000, 8, amplitude));
+        write_wav(&path, &samples, 16_000);
+
+        let whole_file_mean_square: f64 = samples
+            .iter()
+            .map(|&sample| {
+                let normalized = f64::from(sample) / f64::from(i16::MAX);
+                normalized * normalized
+            })
+            .sum::<f64>()
+            / samples.len() as f64;
+        let whole_file_lufs = -0.691 + 10.0 * whole_file_mean_square.log10();
+
+        let report = normalize_wav_loudness(&path, EBU_R128_TARGET_LUFS).expect("normalize");
+        assert!(
+            report.input_lufs > whole_file_lufs,
+            "gated loudness {} should exceed the silence-diluted whole-file loudness {whole_file_lufs}",
+            report.input_lufs
+        );
+    }
+}
@@ -1,12 +1,16 @@
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+use crate::capture::vad::{VadConfig, VadSnapshot, VoiceActivityTracker};
 use crate::error::{AppError, AppResult};
 
 #[derive(Debug, Clone, Copy)]
 pub struct CaptureWatchdogConfig {
     pub arming_timeout: Duration,
     pub stall_timeout: Duration,
+    /// RMS level, in dBFS, below which the live signal is considered
+    /// silent; see `WatchdogSnapshot::silent`.
+    pub silence_threshold_dbfs: f32,
 }
 
 #[derive(Debug, Clone)]
@@ -14,6 +18,44 @@ pub struct WatchdogSnapshot {
     pub armed: bool,
     pub stalled: bool,
     pub first_frame_seen: bool,
+    /// Short-term RMS level of the most recently captured audio, in dBFS
+    /// (0.0 = full scale). Drives a live level meter.
+    pub rms_dbfs: f32,
+    /// Peak sample level of the most recently captured audio, in dBFS.
+    pub peak_dbfs: f32,
+    /// Whether `rms_dbfs` has stayed below `silence_threshold_dbfs` for at
+    /// least `arming_timeout` since capture started, suggesting a muted or
+    /// gain-zero microphone rather than a quiet room.
+    pub silent: bool,
+    /// Samples the streaming tap (see `start_recording_streaming`) could not
+    /// push into its ring buffer because a consumer wasn't draining it fast
+    /// enough. Always `0` for recordings started without the streaming tap.
+    pub dropped_frames: u64,
+}
+
+/// Floor applied to dBFS conversions so a perfectly silent buffer reports a
+/// finite level instead of `-inf`.
+const DBFS_FLOOR: f32 = -120.0;
+
+fn linear_to_dbfs(linear: f32) -> f32 {
+    if linear <= 0.0 {
+        DBFS_FLOOR
+    } else {
+        (20.0 * linear.log10()).max(DBFS_FLOOR)
+    }
+}
+
+/// Computes `(rms_dbfs, peak_dbfs)` for a block of samples normalized to
+/// `-1.0..=1.0`, the format both the macOS resampler and the Linux PCM
+/// decoder produce.
+fn level_dbfs(samples: &[f32]) -> (f32, f32) {
+    if samples.is_empty() {
+        return (DBFS_FLOOR, DBFS_FLOOR);
+    }
+    let sum_sq: f64 = samples.iter().map(|sample| f64::from(*sample) * f64::from(*sample)).sum();
+    let rms = (sum_sq / samples.len() as f64).sqrt() as f32;
+    let peak = samples.iter().fold(0.0_f32, |acc, sample| acc.max(sample.abs()));
+    (linear_to_dbfs(rms), linear_to_dbfs(peak))
 }
 
 #[derive(Debug, Clone)]
@@ -26,13 +68,21 @@ impl MicrophoneCapture {
         Self { preferred_device }
     }
 
+    /// Enumerates available input devices so a UI can present a picker and
+    /// validate `preferred_device` before recording; see
+    /// `crate::capture::devices::describe_input_devices`.
+    pub fn list_input_devices() -> AppResult<Vec<crate::capture::devices::InputDeviceInfo>> {
+        crate::capture::devices::describe_input_devices()
+    }
+
     #[cfg(target_os = "macos")]
     pub fn start_recording(
         &self,
         output_dir: &Path,
         watchdog: CaptureWatchdogConfig,
+        vad: VadConfig,
     ) -> AppResult<ActiveRecording> {
-        start_recording_macos(self.preferred_device.as_deref(), output_dir, watchdog)
+        start_recording_cpal(self.preferred_device.as_deref(), output_dir, watchdog, vad)
     }
 
     #[cfg(target_os = "linux")]
@@ -40,8 +90,9 @@ impl MicrophoneCapture {
         &self,
         output_dir: &Path,
         watchdog: CaptureWatchdogConfig,
+        vad: VadConfig,
     ) -> AppResult<ActiveRecording> {
-        start_recording_linux(self.preferred_device.as_deref(), output_dir, watchdog)
+        start_recording_linux(self.preferred_device.as_deref(), output_dir, watchdog, vad)
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "linux")))]
@@ -49,6 +100,7 @@ impl MicrophoneCapture {
         &self,
         _output_dir: &Path,
         _watchdog: CaptureWatchdogConfig,
+        _vad: VadConfig,
     ) -> AppResult<ActiveRecording> {
         Err(AppError::Capture(
             "microphone capture is only implemented for macOS and Linux in this build".to_owned(),
@@ -56,8 +108,8 @@ impl MicrophoneCapture {
     }
 }
 
-#[cfg(target_os = "macos")]
-mod macos_capture {
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+mod cpal_capture {
     use std::fs::File;
     use std::io::BufWriter;
     use std::sync::atomic::{AtomicBool, Ordering};
@@ -66,14 +118,128 @@ mod macos_capture {
 
     use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
     use cpal::{SampleFormat, Stream};
+    use ringbuf::traits::{Consumer, Producer, Split};
     use uuid::Uuid;
 
     use super::*;
 
+    /// Matches Linux's `LINUX_CAPTURE_SAMPLE_RATE`: both platforms hand
+    /// Whisper 16 kHz mono WAVs so decode time and VAD framing behave
+    /// identically regardless of the host device's native format.
+    const TARGET_SAMPLE_RATE: u32 = 16_000;
+    /// Half-width of the windowed-sinc resampling kernel, i.e. ~16 taps.
+    const RESAMPLE_KERNEL_HALF_WIDTH: i64 = 8;
+    const RESAMPLE_KERNEL_TAPS: usize = (2 * RESAMPLE_KERNEL_HALF_WIDTH + 1) as usize;
+
+    /// Hann-windowed sinc, used as the resampling kernel in
+    /// `MonoResampler::process`.
+    fn sinc_window(x: f64) -> f64 {
+        let sinc = if x.abs() < 1e-9 {
+            1.0
+        } else {
+            (std::f64::consts::PI * x).sin() / (std::f64::consts::PI * x)
+        };
+        let half_width = RESAMPLE_KERNEL_HALF_WIDTH as f64;
+        let hann = 0.5 + 0.5 * (std::f64::consts::PI * x / half_width).cos();
+        sinc * hann
+    }
+
+    /// Downmixes interleaved multi-channel frames to mono and resamples them
+    /// from the device's native rate to `TARGET_SAMPLE_RATE`, via a
+    /// windowed-sinc kernel. cpal delivers variable-length blocks per
+    /// callback, so a small history of source samples is carried across
+    /// `process` calls to keep the kernel fed at block boundaries.
+    struct MonoResampler {
+        src_rate: f64,
+        dst_rate: f64,
+        channels: usize,
+        /// Trailing source samples not yet fully consumed by the kernel,
+        /// kept so output samples near a block boundary can still see the
+        /// taps that fall before it.
+        history: Vec<f32>,
+        /// Absolute source-sample index of `history[0]`.
+        history_start: u64,
+        /// Absolute output-sample index of the next sample to produce.
+        next_output: u64,
+    }
+
+    impl MonoResampler {
+        fn new(src_rate: u32, channels: u16) -> Self {
+            Self {
+                src_rate: f64::from(src_rate),
+                dst_rate: f64::from(TARGET_SAMPLE_RATE),
+                channels: channels.max(1) as usize,
+                history: Vec::new(),
+                history_start: 0,
+                next_output: 0,
+            }
+        }
+
+        /// Downmixes `data` (interleaved, `self.channels` per frame) to mono
+        /// and appends it to the history buffer, then emits every output
+        /// sample whose kernel window is fully covered by samples seen so
+        /// far, returning them in order.
+        fn process(&mut self, data: &[f32]) -> Vec<f32> {
+            let mono: Vec<f32> = data
+                .chunks_exact(self.channels)
+                .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+                .collect();
+
+            self.history.extend_from_slice(&mono);
+            let available_end = self.history_start + self.history.len() as u64;
+
+            let mut output = Vec::new();
+            loop {
+                let t = self.next_output as f64 / self.dst_rate;
+                let p = t * self.src_rate;
+                let base = p.floor() as i64;
+
+                let last_tap = base + RESAMPLE_KERNEL_HALF_WIDTH;
+                if last_tap as u64 >= available_end {
+                    break;
+                }
+
+                let mut sample = 0.0_f64;
+                for k in -RESAMPLE_KERNEL_HALF_WIDTH..=RESAMPLE_KERNEL_HALF_WIDTH {
+                    let tap_index = base + k;
+                    let clamped = tap_index.clamp(
+                        self.history_start as i64,
+                        (available_end.saturating_sub(1)) as i64,
+                    );
+                    let offset = (clamped - self.history_start as i64) as usize;
+                    let weight = sinc_window(p - (tap_index as f64));
+                    sample += f64::from(self.history[offset]) * weight;
+                }
+
+                output.push(sample as f32);
+                self.next_output += 1;
+            }
+
+            // Keep only enough trailing history to feed the next kernel.
+            if self.history.len() > RESAMPLE_KERNEL_TAPS {
+                let drop = self.history.len() - RESAMPLE_KERNEL_TAPS;
+                self.history.drain(0..drop);
+                self.history_start += drop as u64;
+            }
+
+            output
+        }
+    }
+
+    struct LevelState {
+        rms_dbfs: f32,
+        peak_dbfs: f32,
+        below_threshold_since: Option<Instant>,
+    }
+
     struct WatchdogState {
         first_frame_seen: AtomicBool,
         last_frame_at: Mutex<Option<Instant>>,
         started_at: Instant,
+        level: Mutex<LevelState>,
+        /// Count of samples the streaming tap's ring buffer has dropped for
+        /// overrunning; see `SampleConsumer` and `dropped_frames` below.
+        dropped_frames: std::sync::atomic::AtomicU64,
     }
 
     impl WatchdogState {
@@ -82,6 +248,12 @@ mod macos_capture {
                 first_frame_seen: AtomicBool::new(false),
                 last_frame_at: Mutex::new(None),
                 started_at: Instant::now(),
+                level: Mutex::new(LevelState {
+                    rms_dbfs: DBFS_FLOOR,
+                    peak_dbfs: DBFS_FLOOR,
+                    below_threshold_since: None,
+                }),
+                dropped_frames: std::sync::atomic::AtomicU64::new(0),
             }
         }
 
@@ -93,6 +265,28 @@ mod macos_capture {
             }
         }
 
+        /// Called by the streaming tap in `build_input_stream` when the ring
+        /// buffer's producer half couldn't accept all of a block's samples.
+        fn record_dropped(&self, count: u64) {
+            self.dropped_frames.fetch_add(count, Ordering::Relaxed);
+        }
+
+        /// Records the level of the most recently captured (resampled,
+        /// downmixed) block, feeding `WatchdogSnapshot::silent`'s
+        /// persistence check.
+        fn record_level(&self, samples: &[f32], silence_threshold_dbfs: f32) {
+            let (rms_dbfs, peak_dbfs) = level_dbfs(samples);
+            if let Ok(mut level) = self.level.lock() {
+                level.rms_dbfs = rms_dbfs;
+                level.peak_dbfs = peak_dbfs;
+                if rms_dbfs < silence_threshold_dbfs {
+                    level.below_threshold_since.get_or_insert_with(Instant::now);
+                } else {
+                    level.below_threshold_since = None;
+                }
+            }
+        }
+
         fn snapshot(&self, cfg: CaptureWatchdogConfig) -> WatchdogSnapshot {
             let first_seen = self.first_frame_seen.load(Ordering::SeqCst);
 
@@ -114,10 +308,26 @@ mod macos_capture {
                 false
             };
 
+            let (rms_dbfs, peak_dbfs, silent) = match self.level.lock() {
+                Ok(level) => {
+                    let silent = first_seen
+                        && level
+                            .below_threshold_since
+                            .map(|since| since.elapsed() >= cfg.arming_timeout)
+                            .unwrap_or(false);
+                    (level.rms_dbfs, level.peak_dbfs, silent)
+                }
+                Err(_) => (DBFS_FLOOR, DBFS_FLOOR, false),
+            };
+
             WatchdogSnapshot {
                 armed,
                 stalled,
                 first_frame_seen: first_seen,
+                rms_dbfs,
+                peak_dbfs,
+                silent,
+                dropped_frames: self.dropped_frames.load(Ordering::Relaxed),
             }
         }
     }
@@ -128,6 +338,24 @@ mod macos_capture {
         writer: Arc<Mutex<Option<hound::WavWriter<BufWriter<File>>>>>,
         watchdog_cfg: CaptureWatchdogConfig,
         watchdog_state: Arc<WatchdogState>,
+        vad_tracker: Arc<Mutex<VoiceActivityTracker>>,
+    }
+
+    /// Read side of the streaming tap set up by `start_recording_streaming`.
+    /// Wraps a `ringbuf` consumer of post-resample 16 kHz mono i16 samples so
+    /// a transcription worker can pull rolling windows while recording is
+    /// still in progress, without waiting on `ActiveRecording::stop`.
+    pub struct SampleConsumer {
+        consumer: ringbuf::HeapCons<i16>,
+    }
+
+    impl SampleConsumer {
+        /// Copies up to `buf.len()` already-captured samples into `buf`,
+        /// returning how many were copied. Returns `0` if nothing new has
+        /// arrived since the last call.
+        pub fn read(&mut self, buf: &mut [i16]) -> usize {
+            self.consumer.pop_slice(buf)
+        }
     }
 
     impl ActiveRecording {
@@ -135,6 +363,22 @@ mod macos_capture {
             self.watchdog_state.snapshot(self.watchdog_cfg)
         }
 
+        pub fn vad_snapshot(&self) -> VadSnapshot {
+            self.vad_tracker
+                .lock()
+                .map(|tracker| tracker.snapshot())
+                .unwrap_or_default()
+        }
+
+        /// The WAV file currently being written to, for a provisional decode
+        /// of the in-progress recording. The file's RIFF/data length fields
+        /// are not yet finalized while recording is active; callers feeding
+        /// it to the transcription engine accept that in exchange for live
+        /// partial text.
+        pub fn partial_wav_path(&self) -> PathBuf {
+            self.wav_path.clone()
+        }
+
         pub fn stop(mut self) -> AppResult<PathBuf> {
             let stream = self.stream.take();
             drop(stream);
@@ -153,10 +397,50 @@ mod macos_capture {
         }
     }
 
-    pub fn start_recording_macos(
+    /// Number of i16 samples the streaming tap's ring buffer holds before it
+    /// starts dropping newly captured samples that no longer fit, about two seconds at
+    /// `TARGET_SAMPLE_RATE` — comfortably more than a transcription worker
+    /// should ever fall behind by.
+    const STREAM_RING_CAPACITY: usize = TARGET_SAMPLE_RATE as usize * 2;
+
+    /// Captures via cpal, used as the default backend on both macOS (cpal's
+    /// CoreAudio host) and Linux (cpal's ALSA host); see `cpal_capture`'s
+    /// module-level callers for the Linux `WHISPER_CAPTURE_BACKEND=subprocess`
+    /// escape hatch this sits alongside.
+    pub fn start_recording_cpal(
         preferred_device: Option<&str>,
         output_dir: &Path,
         watchdog: CaptureWatchdogConfig,
+        vad: VadConfig,
+    ) -> AppResult<ActiveRecording> {
+        start_recording_cpal_impl(preferred_device, output_dir, watchdog, vad, None)
+    }
+
+    /// Like `start_recording_cpal`, but also taps the post-resample 16 kHz
+    /// mono samples into a lock-free SPSC ring buffer so a transcription
+    /// worker can read rolling windows while recording is still in progress.
+    /// The WAV file is still written and finalized on `stop()` exactly as in
+    /// the non-streaming path; the ring buffer is an additional, best-effort
+    /// sink whose overruns are counted in `WatchdogSnapshot::dropped_frames`
+    /// rather than ever blocking the capture callback.
+    pub fn start_recording_streaming(
+        preferred_device: Option<&str>,
+        output_dir: &Path,
+        watchdog: CaptureWatchdogConfig,
+        vad: VadConfig,
+    ) -> AppResult<(ActiveRecording, SampleConsumer)> {
+        let (producer, consumer) = ringbuf::HeapRb::<i16>::new(STREAM_RING_CAPACITY).split();
+        let recording =
+            start_recording_cpal_impl(preferred_device, output_dir, watchdog, vad, Some(producer))?;
+        Ok((recording, SampleConsumer { consumer }))
+    }
+
+    fn start_recording_cpal_impl(
+        preferred_device: Option<&str>,
+        output_dir: &Path,
+        watchdog: CaptureWatchdogConfig,
+        vad: VadConfig,
+        stream_producer: Option<ringbuf::HeapProd<i16>>,
     ) -> AppResult<ActiveRecording> {
         std::fs::create_dir_all(output_dir)?;
         let wav_path = output_dir.join(format!("capture-{}.wav", Uuid::new_v4()));
@@ -168,8 +452,8 @@ mod macos_capture {
         })?;
 
         let wav_spec = hound::WavSpec {
-            channels: input_config.channels(),
-            sample_rate: input_config.sample_rate().0,
+            channels: 1,
+            sample_rate: TARGET_SAMPLE_RATE,
             bits_per_sample: 16,
             sample_format: hound::SampleFormat::Int,
         };
@@ -179,6 +463,10 @@ mod macos_capture {
         let writer = Arc::new(Mutex::new(Some(writer)));
 
         let watchdog_state = Arc::new(WatchdogState::new());
+        let vad_tracker = Arc::new(Mutex::new(VoiceActivityTracker::new(
+            vad,
+            TARGET_SAMPLE_RATE,
+        )));
 
         let stream = build_stream(
             &device,
@@ -186,6 +474,9 @@ mod macos_capture {
             &input_config.into(),
             writer.clone(),
             watchdog_state.clone(),
+            vad_tracker.clone(),
+            watchdog.silence_threshold_dbfs,
+            stream_producer,
         )?;
 
         stream.play().map_err(|error| {
@@ -198,6 +489,7 @@ mod macos_capture {
             writer,
             watchdog_cfg: watchdog,
             watchdog_state,
+            vad_tracker,
         })
     }
 
@@ -226,6 +518,9 @@ mod macos_capture {
         stream_config: &cpal::StreamConfig,
         writer: Arc<Mutex<Option<hound::WavWriter<BufWriter<File>>>>>,
         watchdog_state: Arc<WatchdogState>,
+        vad_tracker: Arc<Mutex<VoiceActivityTracker>>,
+        silence_threshold_dbfs: f32,
+        stream_producer: Option<ringbuf::HeapProd<i16>>,
     ) -> AppResult<Stream> {
         let error_callback = |error| {
             tracing::error!("cpal stream error: {error}");
@@ -237,6 +532,9 @@ mod macos_capture {
                 stream_config,
                 writer,
                 watchdog_state,
+                vad_tracker,
+                silence_threshold_dbfs,
+                stream_producer,
                 error_callback,
             ),
             SampleFormat::I16 => build_input_stream::<i16>(
@@ -244,6 +542,9 @@ mod macos_capture {
                 stream_config,
                 writer,
                 watchdog_state,
+                vad_tracker,
+                silence_threshold_dbfs,
+                stream_producer,
                 error_callback,
             ),
             SampleFormat::U16 => build_input_stream::<u16>(
@@ -251,6 +552,9 @@ mod macos_capture {
                 stream_config,
                 writer,
                 watchdog_state,
+                vad_tracker,
+                silence_threshold_dbfs,
+                stream_producer,
                 error_callback,
             ),
             _ => Err(AppError::Capture(format!(
@@ -264,18 +568,36 @@ mod macos_capture {
         stream_config: &cpal::StreamConfig,
         writer: Arc<Mutex<Option<hound::WavWriter<BufWriter<File>>>>>,
         watchdog_state: Arc<WatchdogState>,
+        vad_tracker: Arc<Mutex<VoiceActivityTracker>>,
+        silence_threshold_dbfs: f32,
+        mut stream_producer: Option<ringbuf::HeapProd<i16>>,
         mut error_callback: impl FnMut(cpal::StreamError) + Send + 'static,
     ) -> AppResult<Stream>
     where
         T: cpal::SizedSample,
-        i16: cpal::FromSample<T>,
+        f32: cpal::FromSample<T>,
     {
+        let mut resampler = MonoResampler::new(stream_config.sample_rate.0, stream_config.channels);
+        let mut raw_buffer: Vec<f32> = Vec::new();
+        let mut int_buffer: Vec<i16> = Vec::new();
         let callback = move |data: &[T], _info: &cpal::InputCallbackInfo| {
             watchdog_state.mark_frame();
+
+            raw_buffer.clear();
+            raw_buffer.extend(data.iter().map(|sample| sample.to_sample::<f32>()));
+            let resampled = resampler.process(&raw_buffer);
+            watchdog_state.record_level(&resampled, silence_threshold_dbfs);
+
+            int_buffer.clear();
+            int_buffer.extend(
+                resampled
+                    .iter()
+                    .map(|sample| (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16),
+            );
+
             if let Ok(mut guard) = writer.lock() {
                 if let Some(writer) = guard.as_mut() {
-                    for sample in data {
-                        let as_i16: i16 = sample.to_sample::<i16>();
+                    for &as_i16 in &int_buffer {
                         if let Err(error) = writer.write_sample(as_i16) {
                             tracing::error!("failed writing sample to wav: {error}");
                             break;
@@ -283,6 +605,17 @@ mod macos_capture {
                     }
                 }
             }
+
+            if let Some(producer) = stream_producer.as_mut() {
+                let pushed = producer.push_slice(&int_buffer);
+                if pushed < int_buffer.len() {
+                    watchdog_state.record_dropped((int_buffer.len() - pushed) as u64);
+                }
+            }
+
+            if let Ok(mut tracker) = vad_tracker.lock() {
+                tracker.push_samples(&resampled);
+            }
         };
 
         device
@@ -297,14 +630,14 @@ mod macos_capture {
 }
 
 #[cfg(target_os = "macos")]
-pub use macos_capture::ActiveRecording;
+pub use cpal_capture::ActiveRecording;
 
-#[cfg(target_os = "macos")]
-use macos_capture::start_recording_macos;
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use cpal_capture::start_recording_cpal;
 
 #[cfg(target_os = "linux")]
 mod linux_capture {
-    use std::io::Read;
+    use std::io::{Read, Seek};
     use std::process::{Child, Command, Stdio};
     use std::sync::Mutex;
     use std::thread;
@@ -318,6 +651,60 @@ mod linux_capture {
         first_frame_seen: bool,
         last_size: u64,
         last_growth_at: Instant,
+        level_bytes_fed: u64,
+        rms_dbfs: f32,
+        peak_dbfs: f32,
+        below_threshold_since: Option<Instant>,
+    }
+
+    /// Fallback capture path for `WHISPER_CAPTURE_BACKEND=subprocess`, for
+    /// environments where cpal can't open an ALSA device. It runs through an
+    /// `arecord`/`ffmpeg` subprocess with no direct access to raw frames, so
+    /// voice activity is derived by re-reading the growing WAV file's
+    /// newly-written bytes on each poll and feeding them into the same
+    /// `VoiceActivityTracker` the cpal stream callback uses. The fixed 16
+    /// kHz mono format set in `spawn_arecord`/`spawn_ffmpeg` is assumed when
+    /// decoding samples.
+    const LINUX_CAPTURE_SAMPLE_RATE: u32 = 16_000;
+    const WAV_HEADER_BYTES: u64 = 44;
+
+    /// Reads whatever 16-bit PCM samples have been appended to `wav_path`
+    /// since `bytes_fed`, normalizing them to `-1.0..=1.0`. Returns the new
+    /// samples plus the updated `bytes_fed` cursor, or `None` if there's
+    /// nothing new to read. Shared by the VAD feed and the watchdog's level
+    /// meter, which track the growing file independently via their own
+    /// `bytes_fed` cursors.
+    fn read_new_pcm_samples(wav_path: &Path, bytes_fed: u64) -> Option<(Vec<f32>, u64)> {
+        let metadata = std::fs::metadata(wav_path).ok()?;
+        let size = metadata.len();
+        if size <= WAV_HEADER_BYTES || size <= bytes_fed {
+            return None;
+        }
+
+        let mut file = std::fs::File::open(wav_path).ok()?;
+        let read_from = bytes_fed.max(WAV_HEADER_BYTES);
+        file.seek(std::io::SeekFrom::Start(read_from)).ok()?;
+
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).ok()?;
+        // Drop a trailing odd byte: samples are 16-bit and the file may be
+        // mid-write.
+        bytes.truncate(bytes.len() - (bytes.len() % 2));
+        if bytes.is_empty() {
+            return None;
+        }
+
+        let samples: Vec<f32> = bytes
+            .chunks_exact(2)
+            .map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]]) as f32 / f32::from(i16::MAX))
+            .collect();
+        let new_bytes_fed = read_from + (samples.len() as u64 * 2);
+        Some((samples, new_bytes_fed))
+    }
+
+    struct LinuxVadState {
+        tracker: VoiceActivityTracker,
+        bytes_fed: u64,
     }
 
     pub struct ActiveRecording {
@@ -326,6 +713,7 @@ mod linux_capture {
         started_at: Instant,
         watchdog_cfg: CaptureWatchdogConfig,
         watchdog_state: Mutex<LinuxWatchdogState>,
+        vad_state: Mutex<LinuxVadState>,
     }
 
     impl ActiveRecording {
@@ -357,20 +745,75 @@ mod linux_capture {
                         && now.duration_since(state.last_growth_at)
                             > self.watchdog_cfg.stall_timeout;
 
+                    if let Some((samples, new_bytes_fed)) =
+                        read_new_pcm_samples(&self.wav_path, state.level_bytes_fed)
+                    {
+                        let (rms_dbfs, peak_dbfs) = level_dbfs(&samples);
+                        state.rms_dbfs = rms_dbfs;
+                        state.peak_dbfs = peak_dbfs;
+                        state.level_bytes_fed = new_bytes_fed;
+                        if rms_dbfs < self.watchdog_cfg.silence_threshold_dbfs {
+                            state.below_threshold_since.get_or_insert(now);
+                        } else {
+                            state.below_threshold_since = None;
+                        }
+                    }
+                    let silent = state.first_frame_seen
+                        && state
+                            .below_threshold_since
+                            .map(|since| now.duration_since(since) >= self.watchdog_cfg.arming_timeout)
+                            .unwrap_or(false);
+
                     WatchdogSnapshot {
                         armed,
                         stalled,
                         first_frame_seen: state.first_frame_seen,
+                        rms_dbfs: state.rms_dbfs,
+                        peak_dbfs: state.peak_dbfs,
+                        silent,
+                        dropped_frames: 0,
                     }
                 }
                 Err(_) => WatchdogSnapshot {
                     armed: false,
                     stalled: true,
                     first_frame_seen: false,
+                    rms_dbfs: DBFS_FLOOR,
+                    peak_dbfs: DBFS_FLOOR,
+                    silent: false,
+                    dropped_frames: 0,
                 },
             }
         }
 
+        pub fn vad_snapshot(&self) -> VadSnapshot {
+            self.feed_vad_from_file();
+            self.vad_state
+                .lock()
+                .map(|state| state.tracker.snapshot())
+                .unwrap_or_default()
+        }
+
+        /// The WAV file currently being written to by the `arecord`/`ffmpeg`
+        /// subprocess, for a provisional decode of the in-progress recording.
+        pub fn partial_wav_path(&self) -> PathBuf {
+            self.wav_path.clone()
+        }
+
+        /// Reads whatever PCM bytes have been appended to `wav_path` since
+        /// the last poll and feeds them to the voice-activity tracker.
+        fn feed_vad_from_file(&self) {
+            let Ok(mut state) = self.vad_state.lock() else {
+                return;
+            };
+            if let Some((samples, new_bytes_fed)) =
+                read_new_pcm_samples(&self.wav_path, state.bytes_fed)
+            {
+                state.tracker.push_samples(&samples);
+                state.bytes_fed = new_bytes_fed;
+            }
+        }
+
         pub fn stop(mut self) -> AppResult<PathBuf> {
             terminate_recorder_gracefully(&mut self.child)?;
             validate_wav_header(&self.wav_path)?;
@@ -460,10 +903,11 @@ mod linux_capture {
         Ok(())
     }
 
-    pub fn start_recording_linux(
+    pub fn start_recording_linux_subprocess(
         preferred_device: Option<&str>,
         output_dir: &Path,
         watchdog: CaptureWatchdogConfig,
+        vad: VadConfig,
     ) -> AppResult<ActiveRecording> {
         std::fs::create_dir_all(output_dir)?;
         let wav_path = output_dir.join(format!("capture-{}.wav", Uuid::new_v4()));
@@ -487,6 +931,14 @@ mod linux_capture {
                 first_frame_seen: false,
                 last_size: 0,
                 last_growth_at: Instant::now(),
+                level_bytes_fed: 0,
+                rms_dbfs: DBFS_FLOOR,
+                peak_dbfs: DBFS_FLOOR,
+                below_threshold_since: None,
+            }),
+            vad_state: Mutex::new(LinuxVadState {
+                tracker: VoiceActivityTracker::new(vad, LINUX_CAPTURE_SAMPLE_RATE),
+                bytes_fed: 0,
             }),
         })
     }
@@ -538,11 +990,62 @@ mod linux_capture {
     }
 }
 
+/// Linux defaults to the shared `cpal_capture` path (the same ALSA-backed
+/// `build_stream`/`WatchdogState` machinery macOS uses), falling back to the
+/// `arecord`/`ffmpeg` subprocess spawners in `linux_capture` only when
+/// `WHISPER_CAPTURE_BACKEND=subprocess` is set, for environments where
+/// cpal's ALSA device can't be opened.
 #[cfg(target_os = "linux")]
-pub use linux_capture::ActiveRecording;
+pub enum ActiveRecording {
+    Cpal(cpal_capture::ActiveRecording),
+    Subprocess(linux_capture::ActiveRecording),
+}
+
+#[cfg(target_os = "linux")]
+impl ActiveRecording {
+    pub fn watchdog_snapshot(&self) -> WatchdogSnapshot {
+        match self {
+            Self::Cpal(recording) => recording.watchdog_snapshot(),
+            Self::Subprocess(recording) => recording.watchdog_snapshot(),
+        }
+    }
+
+    pub fn vad_snapshot(&self) -> VadSnapshot {
+        match self {
+            Self::Cpal(recording) => recording.vad_snapshot(),
+            Self::Subprocess(recording) => recording.vad_snapshot(),
+        }
+    }
+
+    pub fn partial_wav_path(&self) -> PathBuf {
+        match self {
+            Self::Cpal(recording) => recording.partial_wav_path(),
+            Self::Subprocess(recording) => recording.partial_wav_path(),
+        }
+    }
+
+    pub fn stop(self) -> AppResult<PathBuf> {
+        match self {
+            Self::Cpal(recording) => recording.stop(),
+            Self::Subprocess(recording) => recording.stop(),
+        }
+    }
+}
 
 #[cfg(target_os = "linux")]
-use linux_capture::start_recording_linux;
+fn start_recording_linux(
+    preferred_device: Option<&str>,
+    output_dir: &Path,
+    watchdog: CaptureWatchdogConfig,
+    vad: VadConfig,
+) -> AppResult<ActiveRecording> {
+    if std::env::var("WHISPER_CAPTURE_BACKEND").as_deref() == Ok("subprocess") {
+        return linux_capture::start_recording_linux_subprocess(preferred_device, output_dir, watchdog, vad)
+            .map(ActiveRecording::Subprocess);
+    }
+
+    start_recording_cpal(preferred_device, output_dir, watchdog, vad).map(ActiveRecording::Cpal)
+}
 
 #[cfg(not(any(target_os = "macos", target_os = "linux")))]
 pub struct ActiveRecording;
@@ -554,9 +1057,21 @@ impl ActiveRecording {
             armed: false,
             stalled: true,
             first_frame_seen: false,
+            rms_dbfs: DBFS_FLOOR,
+            peak_dbfs: DBFS_FLOOR,
+            silent: false,
+            dropped_frames: 0,
         }
     }
 
+    pub fn vad_snapshot(&self) -> VadSnapshot {
+        VadSnapshot::default()
+    }
+
+    pub fn partial_wav_path(&self) -> PathBuf {
+        PathBuf::new()
+    }
+
     pub fn stop(self) -> AppResult<PathBuf> {
         Err(AppError::Capture(
             "recording stop unavailable because capture is unsupported on this platform build"
@@ -567,8 +1082,9 @@ impl ActiveRecording {
 
 #[cfg(all(test, target_os = "linux"))]
 mod tests {
-    use super::linux_capture::start_recording_linux;
+    use super::start_recording_linux;
     use super::CaptureWatchdogConfig;
+    use crate::capture::vad::VadConfig;
     use crate::error::AppError;
     use std::fs;
     use std::path::Path;
@@ -605,16 +1121,33 @@ mod tests {
         fs::set_permissions(path, perms).expect("chmod");
     }
 
+    /// These tests mock `arecord`/`ffmpeg` on `PATH` and have no real ALSA
+    /// device to open, so they force the subprocess escape hatch rather than
+    /// exercising the default cpal path.
+    fn subprocess_backend_guard() -> EnvVarGuard {
+        EnvVarGuard::set("WHISPER_CAPTURE_BACKEND", "subprocess")
+    }
+
     fn watchdog(arming_ms: u64, stall_ms: u64) -> CaptureWatchdogConfig {
         CaptureWatchdogConfig {
             arming_timeout: Duration::from_millis(arming_ms),
             stall_timeout: Duration::from_millis(stall_ms),
+            silence_threshold_dbfs: -50.0,
+        }
+    }
+
+    fn vad_cfg() -> VadConfig {
+        VadConfig {
+            energy_threshold: 0.05,
+            high_band_ratio_threshold: 0.05,
+            auto_stop_silence: Duration::from_millis(1_200),
         }
     }
 
     #[test]
     fn linux_uses_arecord_when_present() {
         let _guard = crate::test_support::lock_env();
+        let _backend = subprocess_backend_guard();
         let temp = tempfile::TempDir::new().expect("tempdir");
         let bin = temp.path().join("bin");
         fs::create_dir_all(&bin).expect("mkdir");
@@ -632,7 +1165,7 @@ sleep 30
         write_script(&bin.join("ffmpeg"), recorder_script);
 
         let recording =
-            start_recording_linux(None, temp.path(), watchdog(500, 500)).expect("start");
+            start_recording_linux(None, temp.path(), watchdog(500, 500), vad_cfg()).expect("start");
         std::thread::sleep(Duration::from_millis(80));
         let wav_path = recording.stop().expect("stop");
         assert!(wav_path.exists());
@@ -644,6 +1177,7 @@ sleep 30
     #[test]
     fn linux_falls_back_to_ffmpeg_when_arecord_missing() {
         let _guard = crate::test_support::lock_env();
+        let _backend = subprocess_backend_guard();
         let temp = tempfile::TempDir::new().expect("tempdir");
         let bin = temp.path().join("bin");
         fs::create_dir_all(&bin).expect("mkdir");
@@ -660,7 +1194,7 @@ sleep 30
         write_script(&bin.join("ffmpeg"), ffmpeg_script);
 
         let recording =
-            start_recording_linux(None, temp.path(), watchdog(500, 500)).expect("start");
+            start_recording_linux(None, temp.path(), watchdog(500, 500), vad_cfg()).expect("start");
         std::thread::sleep(Duration::from_millis(80));
         let wav_path = recording.stop().expect("stop");
         assert!(wav_path.exists());
@@ -671,18 +1205,20 @@ sleep 30
     #[test]
     fn linux_errors_when_no_recorder_binary() {
         let _guard = crate::test_support::lock_env();
+        let _backend = subprocess_backend_guard();
         let temp = tempfile::TempDir::new().expect("tempdir");
         let empty_bin = temp.path().join("empty-bin");
         fs::create_dir_all(&empty_bin).expect("mkdir");
         let _path = EnvVarGuard::set("PATH", empty_bin.to_str().expect("utf8"));
 
-        let result = start_recording_linux(None, temp.path(), watchdog(500, 500));
+        let result = start_recording_linux(None, temp.path(), watchdog(500, 500), vad_cfg());
         assert!(matches!(result, Err(AppError::BinaryMissing { .. })));
     }
 
     #[test]
     fn watchdog_arming_timeout_detection() {
         let _guard = crate::test_support::lock_env();
+        let _backend = subprocess_backend_guard();
         let temp = tempfile::TempDir::new().expect("tempdir");
         let bin = temp.path().join("bin");
         fs::create_dir_all(&bin).expect("mkdir");
@@ -697,7 +1233,7 @@ sleep 30
 "#,
         );
 
-        let recording = start_recording_linux(None, temp.path(), watchdog(40, 500)).expect("start");
+        let recording = start_recording_linux(None, temp.path(), watchdog(40, 500), vad_cfg()).expect("start");
         std::thread::sleep(Duration::from_millis(80));
         let snapshot = recording.watchdog_snapshot();
         assert!(!snapshot.armed);
@@ -708,6 +1244,7 @@ sleep 30
     #[test]
     fn watchdog_stall_detection_after_initial_growth() {
         let _guard = crate::test_support::lock_env();
+        let _backend = subprocess_backend_guard();
         let temp = tempfile::TempDir::new().expect("tempdir");
         let bin = temp.path().join("bin");
         fs::create_dir_all(&bin).expect("mkdir");
@@ -723,7 +1260,7 @@ sleep 30
 "#,
         );
 
-        let recording = start_recording_linux(None, temp.path(), watchdog(500, 50)).expect("start");
+        let recording = start_recording_linux(None, temp.path(), watchdog(500, 50), vad_cfg()).expect("start");
         std::thread::sleep(Duration::from_millis(60));
         let first = recording.watchdog_snapshot();
         assert!(first.armed);
@@ -735,9 +1272,43 @@ sleep 30
         let _ = recording.stop();
     }
 
+    #[test]
+    fn watchdog_reports_silent_after_sustained_zero_level() {
+        let _guard = crate::test_support::lock_env();
+        let _backend = subprocess_backend_guard();
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let bin = temp.path().join("bin");
+        fs::create_dir_all(&bin).expect("mkdir");
+        let _path = EnvVarGuard::set("PATH", bin.to_str().expect("utf8"));
+
+        write_script(
+            &bin.join("arecord"),
+            r#"#!/bin/sh
+for arg in "$@"; do out="$arg"; done
+printf "RIFF0000WAVE................................" > "$out"
+dd if=/dev/zero bs=1 count=256 >> "$out" 2>/dev/null
+sleep 30
+"#,
+        );
+
+        let recording =
+            start_recording_linux(None, temp.path(), watchdog(50, 500), vad_cfg()).expect("start");
+        std::thread::sleep(Duration::from_millis(60));
+        let first = recording.watchdog_snapshot();
+        assert!(first.first_frame_seen);
+        assert!(!first.silent, "must wait out arming_timeout before declaring silence");
+
+        std::thread::sleep(Duration::from_millis(80));
+        let snapshot = recording.watchdog_snapshot();
+        assert!(snapshot.silent);
+        assert!(snapshot.rms_dbfs < -50.0);
+        let _ = recording.stop();
+    }
+
     #[test]
     fn stop_terminates_child_and_returns_wav_path() {
         let _guard = crate::test_support::lock_env();
+        let _backend = subprocess_backend_guard();
         let temp = tempfile::TempDir::new().expect("tempdir");
         let bin = temp.path().join("bin");
         fs::create_dir_all(&bin).expect("mkdir");
@@ -758,7 +1329,7 @@ sleep 30
         );
 
         let recording =
-            start_recording_linux(Some("default"), temp.path(), watchdog(500, 500)).expect("start");
+            start_recording_linux(Some("default"), temp.path(), watchdog(500, 500), vad_cfg()).expect("start");
         std::thread::sleep(Duration::from_millis(80));
         let wav = recording.stop().expect("stop");
         assert_eq!(wav.extension().and_then(|e| e.to_str()), Some("wav"));
@@ -0,0 +1,10 @@
+pub mod analysis;
+pub mod denoise;
+pub mod devices;
+pub mod loudness;
+pub mod mic;
+pub mod vad;
+
+pub use devices::InputDeviceInfo;
+pub use mic::{CaptureWatchdogConfig, MicrophoneCapture};
+pub use vad::VadConfig;
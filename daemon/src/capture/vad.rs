@@ -0,0 +1,559 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use realfft::num_complex::Complex;
+use realfft::{RealFftPlanner, RealToComplex};
+
+use crate::error::{AppError, AppResult};
+
+/// Width of one classification frame. Matches
+/// `capture::analysis::RMS_WINDOW_MS` so the live auto-stop gate and the
+/// post-stop silence gate agree on what "one window of audio" means.
+const FRAME_MS: u64 = 20;
+
+/// Configuration for the live speech/silence classifier fed by the capture
+/// pipeline; see `VoiceActivityTracker`.
+#[derive(Debug, Clone, Copy)]
+pub struct VadConfig {
+    pub energy_threshold: f32,
+    pub high_band_ratio_threshold: f32,
+    pub auto_stop_silence: Duration,
+}
+
+/// Result of classifying the frames seen so far.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VadSnapshot {
+    pub speech_seen: bool,
+    pub should_stop: bool,
+}
+
+/// Rolling speech/silence classifier driven by successive audio samples.
+/// Combines short-term RMS energy with a high-band/low-band energy ratio
+/// from a real FFT, so steady background noise (fans, hum) that clears the
+/// energy threshold but carries little high-frequency content is not
+/// mistaken for speech. Tracks a hangover counter: once at least one frame
+/// has been classified as speech, continuous silence must persist for
+/// `config.auto_stop_silence` before `snapshot().should_stop` reports true, so
+/// brief pauses between words don't cut a speaker off.
+pub struct VoiceActivityTracker {
+    config: VadConfig,
+    frame_samples: usize,
+    fft: std::sync::Arc<dyn RealToComplex<f32>>,
+    spectrum: Vec<Complex<f32>>,
+    pending: Vec<f32>,
+    speech_seen: bool,
+    silent_ms_since_speech: u64,
+}
+
+impl VoiceActivityTracker {
+    pub fn new(config: VadConfig, sample_rate: u32) -> Self {
+        let frame_samples = (((u64::from(sample_rate.max(1)) * FRAME_MS) / 1000).max(2)) as usize;
+        let fft = RealFftPlanner::<f32>::new().plan_fft_forward(frame_samples);
+        let spectrum = fft.make_output_vec();
+        Self {
+            config,
+            frame_samples,
+            fft,
+            spectrum,
+            pending: Vec::with_capacity(frame_samples),
+            speech_seen: false,
+            silent_ms_since_speech: 0,
+        }
+    }
+
+    /// Feeds newly captured samples (interleaved, normalized to -1.0..=1.0)
+    /// into the classifier, splitting them into `FRAME_MS` frames as enough
+    /// samples accumulate. A trailing partial frame is carried over to the
+    /// next call.
+    pub fn push_samples(&mut self, samples: &[f32]) {
+        self.pending.extend_from_slice(samples);
+        while self.pending.len() >= self.frame_samples {
+            let frame: Vec<f32> = self.pending.drain(..self.frame_samples).collect();
+            self.classify_frame(&frame);
+        }
+    }
+
+    fn classify_frame(&mut self, frame: &[f32]) {
+        let energy = rms(frame);
+        let mut spectral_input = frame.to_vec();
+        let is_speech = match self.fft.process(&mut spectral_input, &mut self.spectrum) {
+            Ok(()) => {
+                energy >= self.config.energy_threshold
+                    && high_band_ratio(&self.spectrum) >= self.config.high_band_ratio_threshold
+            }
+            Err(error) => {
+                tracing::warn!("vad fft failed, falling back to energy only: {error}");
+                energy >= self.config.energy_threshold
+            }
+        };
+
+        if is_speech {
+            self.speech_seen = true;
+            self.silent_ms_since_speech = 0;
+        } else if self.speech_seen {
+            self.silent_ms_since_speech += FRAME_MS;
+        }
+    }
+
+    pub fn snapshot(&self) -> VadSnapshot {
+        VadSnapshot {
+            speech_seen: self.speech_seen,
+            should_stop: self.speech_seen
+                && self.silent_ms_since_speech >= self.config.auto_stop_silence.as_millis() as u64,
+        }
+    }
+}
+
+/// Frame width and hop for `trim_silence_and_segment`'s offline
+/// speech-boundary detection below. Wider and more finely-hopped than
+/// `FRAME_MS`'s live auto-stop tracker, since this runs once over a whole
+/// finished capture rather than on every incoming audio callback.
+const BOUNDARY_FRAME_MS: u64 = 30;
+const BOUNDARY_HOP_MS: u64 = 10;
+
+/// The speech formant band a frame's energy is compared against; matches
+/// `capture::analysis::SPEECH_BAND_HZ`.
+const BOUNDARY_SPEECH_BAND_HZ: (f64, f64) = (300.0, 3_400.0);
+
+/// How quickly the rolling noise-floor estimate is allowed to rise when a
+/// frame is louder than it; matches `capture::analysis`'s tracker (fall
+/// instantly to a new minimum, rise slowly).
+const BOUNDARY_NOISE_FLOOR_RISE: f64 = 0.1;
+
+/// Consecutive speech frames required to open a segment, and consecutive
+/// silence frames required to close one, so a single loud click doesn't open
+/// a segment and a single short pause inside a sentence doesn't close one.
+const BOUNDARY_OPEN_FRAMES: usize = 3;
+const BOUNDARY_CLOSE_FRAMES: usize = 5;
+
+/// Configuration for `trim_silence_and_segment`'s offline speech-boundary
+/// detection, run once over a finished capture; distinct from `VadConfig`
+/// above, which drives the live auto-stop tracker fed by the capture
+/// pipeline while recording is still in progress.
+#[derive(Debug, Clone, Copy)]
+pub struct SpeechBoundaryConfig {
+    /// How far, in dB, a frame's speech-band energy must clear the rolling
+    /// noise floor to be classified as speech.
+    pub margin_db: f64,
+    /// How much each detected speech span is padded on both sides before
+    /// trimming, so word onsets/offsets aren't clipped.
+    pub pad_ms: u64,
+    /// Total voiced duration beyond which `trim_silence_and_segment` writes
+    /// one sibling WAV per speech span instead of concatenating them into a
+    /// single trimmed file; `None` never splits.
+    pub split_above_ms: Option<u64>,
+}
+
+/// One detected speech span, in milliseconds from the start of the
+/// original (untrimmed) capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpeechSpan {
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// What `trim_silence_and_segment` found and did to `path`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VadTrimReport {
+    /// Every speech span detected, after hangover smoothing and padding,
+    /// merged where overlapping.
+    pub spans: Vec<SpeechSpan>,
+    /// Total voiced duration retained, summed across `spans`.
+    pub trimmed_duration_ms: u64,
+    /// One sibling WAV per span, written next to `path` and meant to be
+    /// transcribed sequentially with their transcripts concatenated in
+    /// order, populated only when `trimmed_duration_ms` exceeded
+    /// `SpeechBoundaryConfig::split_above_ms`. Empty otherwise, meaning
+    /// `path` itself was rewritten in place with the spans concatenated.
+    pub segment_paths: Vec<PathBuf>,
+}
+
+/// Reads `path`, classifies `BOUNDARY_FRAME_MS` frames (hopped every
+/// `BOUNDARY_HOP_MS`) as speech or silence via the same
+/// band-energy-over-rolling-noise-floor approach as
+/// `capture::analysis::classify_speech_frames`, and smooths the result with
+/// hangover (`BOUNDARY_OPEN_FRAMES`/`BOUNDARY_CLOSE_FRAMES`) to find
+/// contiguous speech spans. Each span is padded by `config.pad_ms`,
+/// overlapping spans are merged, and `path` is rewritten to the
+/// concatenation of the spans in order — or, if their total duration
+/// exceeds `config.split_above_ms`, left untouched and instead written out
+/// as one sibling WAV per span in `VadTrimReport::segment_paths`. A capture
+/// with no detected speech is left untouched with an empty `spans`. Assumes
+/// 16-bit PCM, the only format `capture::mic` ever writes.
+pub fn trim_silence_and_segment(path: &Path, config: &SpeechBoundaryConfig) -> AppResult<VadTrimReport> {
+    let mut reader = hound::WavReader::open(path).map_err(|error| {
+        AppError::Capture(format!("failed to open {} for VAD trimming: {error}", path.display()))
+    })?;
+    let spec = reader.spec();
+    let sample_rate = spec.sample_rate.max(1);
+    let channels = spec.channels.max(1) as usize;
+
+    let samples: Vec<i16> = reader.samples::<i16>().collect::<Result<_, _>>().map_err(|error| {
+        AppError::Capture(format!(
+            "failed to read samples from {} for VAD trimming: {error}",
+            path.display()
+        ))
+    })?;
+
+    let mono: Vec<f32> = samples
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().map(|&s| f32::from(s) / f32::from(i16::MAX)).sum::<f32>() / frame.len() as f32)
+        .collect();
+
+    let spans = detect_speech_spans(&mono, sample_rate, config);
+
+    if spans.is_empty() {
+        return Ok(VadTrimReport { spans, trimmed_duration_ms: 0, segment_paths: Vec::new() });
+    }
+
+    let trimmed_duration_ms = spans.iter().map(|span| span.end_ms - span.start_ms).sum();
+
+    if config.split_above_ms.is_some_and(|max| trimmed_duration_ms > max) {
+        let mut segment_paths = Vec::with_capacity(spans.len());
+        for (index, span) in spans.iter().enumerate() {
+            let segment_path = sibling_segment_path(path, index);
+            write_span(&segment_path, &samples, channels, sample_rate, spec, span)?;
+            segment_paths.push(segment_path);
+        }
+        return Ok(VadTrimReport { spans, trimmed_duration_ms, segment_paths });
+    }
+
+    let mut trimmed_samples = Vec::with_capacity(samples.len());
+    for span in &spans {
+        let start_frame = ((span.start_ms * u64::from(sample_rate)) / 1000) as usize;
+        let end_frame = ((span.end_ms * u64::from(sample_rate)) / 1000) as usize;
+        let start_sample = start_frame * channels;
+        let end_sample = (end_frame * channels).min(samples.len());
+        trimmed_samples.extend_from_slice(&samples[start_sample..end_sample]);
+    }
+
+    let mut writer = hound::WavWriter::create(path, spec).map_err(|error| {
+        AppError::Capture(format!("failed to rewrite {} with VAD trimming: {error}", path.display()))
+    })?;
+    for sample in trimmed_samples {
+        writer.write_sample(sample).map_err(|error| {
+            AppError::Capture(format!("failed to write trimmed sample to {}: {error}", path.display()))
+        })?;
+    }
+    writer.finalize().map_err(|error| {
+        AppError::Capture(format!("failed to finalize trimmed wav {}: {error}", path.display()))
+    })?;
+
+    Ok(VadTrimReport { spans, trimmed_duration_ms, segment_paths: Vec::new() })
+}
+
+fn sibling_segment_path(path: &Path, index: usize) -> PathBuf {
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("capture");
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("wav");
+    path.with_file_name(format!("{stem}.vad_segment_{index}.{extension}"))
+}
+
+fn write_span(
+    segment_path: &Path,
+    samples: &[i16],
+    channels: usize,
+    sample_rate: u32,
+    spec: hound::WavSpec,
+    span: &SpeechSpan,
+) -> AppResult<()> {
+    let start_frame = ((span.start_ms * u64::from(sample_rate)) / 1000) as usize;
+    let end_frame = ((span.end_ms * u64::from(sample_rate)) / 1000) as usize;
+    let start_sample = start_frame * channels;
+    let end_sample = (end_frame * channels).min(samples.len());
+
+    let mut writer = hound::WavWriter::create(segment_path, spec).map_err(|error| {
+        AppError::Capture(format!("failed to create VAD segment {}: {error}", segment_path.display()))
+    })?;
+    for &sample in &samples[start_sample..end_sample] {
+        writer.write_sample(sample).map_err(|error| {
+            AppError::Capture(format!("failed to write VAD segment {}: {error}", segment_path.display()))
+        })?;
+    }
+    writer.finalize().map_err(|error| {
+        AppError::Capture(format!("failed to finalize VAD segment {}: {error}", segment_path.display()))
+    })?;
+    Ok(())
+}
+
+/// Runs the frame classification + hangover smoothing + padding/merging
+/// pipeline described on `trim_silence_and_segment` over `mono`, returning
+/// the resulting speech spans in playback-order, non-overlapping.
+fn detect_speech_spans(mono: &[f32], sample_rate: u32, config: &SpeechBoundaryConfig) -> Vec<SpeechSpan> {
+    let frame_len = (((u64::from(sample_rate) * BOUNDARY_FRAME_MS) / 1000).max(2)) as usize;
+    let hop_len = (((u64::from(sample_rate) * BOUNDARY_HOP_MS) / 1000).max(1)) as usize;
+    if mono.len() < frame_len {
+        return Vec::new();
+    }
+
+    let window: Vec<f32> = (0..frame_len)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (frame_len - 1) as f32).cos())
+        .collect();
+
+    let mut planner = RealFftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(frame_len);
+    let bin_hz = f64::from(sample_rate) / frame_len as f64;
+    let band_bins: Vec<usize> = (0..frame_len / 2 + 1)
+        .filter(|&bin| {
+            let freq = bin as f64 * bin_hz;
+            freq >= BOUNDARY_SPEECH_BAND_HZ.0 && freq <= BOUNDARY_SPEECH_BAND_HZ.1
+        })
+        .collect();
+
+    let mut noise_floor_db = f64::NEG_INFINITY;
+    let mut flags = Vec::new();
+    let mut frame_start_samples = Vec::new();
+
+    let mut start = 0usize;
+    while start + frame_len <= mono.len() {
+        let windowed: Vec<f32> =
+            mono[start..start + frame_len].iter().zip(&window).map(|(sample, w)| sample * w).collect();
+
+        let mut input = fft.make_input_vec();
+        input.copy_from_slice(&windowed);
+        let mut spectrum = fft.make_output_vec();
+        let is_speech = match fft.process(&mut input, &mut spectrum) {
+            Ok(()) => {
+                let band_energy: f64 =
+                    band_bins.iter().map(|&bin| f64::from(spectrum[bin].norm_sqr())).sum();
+                let band_energy_db = 10.0 * (band_energy / band_bins.len().max(1) as f64 + 1e-12).log10();
+
+                if noise_floor_db.is_infinite() {
+                    noise_floor_db = band_energy_db;
+                } else if band_energy_db < noise_floor_db {
+                    noise_floor_db = band_energy_db;
+                } else {
+                    noise_floor_db += (band_energy_db - noise_floor_db) * BOUNDARY_NOISE_FLOOR_RISE;
+                }
+
+                band_energy_db > noise_floor_db + config.margin_db
+            }
+            Err(error) => {
+                tracing::warn!("vad boundary fft failed, treating frame as silence: {error}");
+                false
+            }
+        };
+
+        flags.push(is_speech);
+        frame_start_samples.push(start);
+        start += hop_len;
+    }
+
+    // Hangover smoothing: a segment opens once `BOUNDARY_OPEN_FRAMES`
+    // consecutive frames look like speech, and only closes once
+    // `BOUNDARY_CLOSE_FRAMES` consecutive frames look like silence, so a
+    // short pause inside a sentence doesn't split it in two.
+    let mut in_speech = false;
+    let mut speech_run = 0usize;
+    let mut silence_run = 0usize;
+    let mut smoothed = Vec::with_capacity(flags.len());
+    for &is_speech in &flags {
+        if is_speech {
+            speech_run += 1;
+            silence_run = 0;
+        } else {
+            silence_run += 1;
+            speech_run = 0;
+        }
+        if !in_speech && speech_run >= BOUNDARY_OPEN_FRAMES {
+            in_speech = true;
+        } else if in_speech && silence_run >= BOUNDARY_CLOSE_FRAMES {
+            in_speech = false;
+        }
+        smoothed.push(in_speech);
+    }
+
+    let mut raw_spans = Vec::new();
+    let mut span_start: Option<usize> = None;
+    for (index, &is_speech) in smoothed.iter().enumerate() {
+        if is_speech && span_start.is_none() {
+            span_start = Some(index);
+        } else if !is_speech {
+            if let Some(start_index) = span_start.take() {
+                raw_spans.push((start_index, index));
+            }
+        }
+    }
+    if let Some(start_index) = span_start {
+        raw_spans.push((start_index, smoothed.len()));
+    }
+
+    let to_ms = |sample_index: usize| (sample_index as u64 * 1000) / u64::from(sample_rate);
+    let duration_ms = to_ms(mono.len());
+
+    let mut padded: Vec<SpeechSpan> = raw_spans
+        .into_iter()
+        .map(|(start_index, end_index)| {
+            let start_sample = frame_start_samples[start_index];
+            let end_sample = frame_start_samples[end_index - 1] + frame_len;
+            let start_ms = to_ms(start_sample).saturating_sub(config.pad_ms);
+            let end_ms = (to_ms(end_sample) + config.pad_ms).min(duration_ms);
+            SpeechSpan { start_ms, end_ms }
+        })
+        .collect();
+
+    padded.sort_by_key(|span| span.start_ms);
+    let mut merged: Vec<SpeechSpan> = Vec::with_capacity(padded.len());
+    for span in padded {
+        match merged.last_mut() {
+            Some(last) if span.start_ms <= last.end_ms => {
+                last.end_ms = last.end_ms.max(span.end_ms);
+            }
+            _ => merged.push(span),
+        }
+    }
+    merged
+}
+
+fn rms(frame: &[f32]) -> f32 {
+    let sum_sq: f32 = frame.iter().map(|sample| sample * sample).sum();
+    (sum_sq / frame.len() as f32).sqrt()
+}
+
+/// Fraction of total spectral energy carried by the upper half of the
+/// spectrum. Steady low-frequency noise concentrates energy near DC and
+/// scores low; speech's broadband, harmonic content scores higher.
+fn high_band_ratio(spectrum: &[Complex<f32>]) -> f32 {
+    let total: f32 = spectrum.iter().map(Complex::norm_sqr).sum::<f32>().max(1e-9);
+    let midpoint = spectrum.len() / 2;
+    let high: f32 = spectrum[midpoint..].iter().map(Complex::norm_sqr).sum();
+    high / total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{trim_silence_and_segment, SpeechBoundaryConfig, VadConfig, VoiceActivityTracker};
+    use std::time::Duration;
+
+    fn write_wav(path: &std::path::Path, samples: &[i16], sample_rate: u32) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).expect("create wav");
+        for sample in samples {
+            writer.write_sample(*sample).expect("write sample");
+        }
+        writer.finalize().expect("finalize wav");
+    }
+
+    fn read_wav(path: &std::path::Path) -> Vec<i16> {
+        let mut reader = hound::WavReader::open(path).expect("reopen wav");
+        reader.samples::<i16>().map(|sample| sample.expect("sample")).collect()
+    }
+
+    fn boundary_config() -> SpeechBoundaryConfig {
+        SpeechBoundaryConfig { margin_db: 6.0, pad_ms: 100, split_above_ms: None }
+    }
+
+    fn tone_i16(len: usize, freq_hz: f64, sample_rate: u32) -> Vec<i16> {
+        (0..len)
+            .map(|i| {
+                let t = i as f64 / f64::from(sample_rate);
+                ((2.0 * std::f64::consts::PI * freq_hz * t).sin() * f64::from(i16::MAX) * 0.8) as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn leaves_pure_silence_untouched_with_no_spans() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("silence.wav");
+        write_wav(&path, &[0_i16; 16_000], 16_000);
+
+        let report = trim_silence_and_segment(&path, &boundary_config()).expect("trim");
+        assert!(report.spans.is_empty());
+        assert_eq!(report.trimmed_duration_ms, 0);
+        assert!(report.segment_paths.is_empty());
+    }
+
+    #[test]
+    fn trims_leading_and_trailing_silence_around_speech() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("capture.wav");
+        let mut samples = vec![0_i16; 16_000];
+        samples.extend(tone_i16(16_000, 1_000.0, 16_000));
+        samples.extend(vec![0_i16; 16_000]);
+        let original_len = samples.len();
+        write_wav(&path, &samples, 16_000);
+
+        let report = trim_silence_and_segment(&path, &boundary_config()).expect("trim");
+        assert!(!report.spans.is_empty());
+        assert!(report.segment_paths.is_empty());
+
+        let trimmed = read_wav(&path);
+        assert!(
+            trimmed.len() < original_len,
+            "trimmed capture ({}) should be shorter than the original ({original_len})",
+            trimmed.len()
+        );
+    }
+
+    #[test]
+    fn splits_into_sibling_segments_once_voiced_duration_exceeds_the_threshold() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let path = temp.path().join("capture.wav");
+        // Leading silence lets the noise floor settle below the tone.
+        let mut samples = vec![0_i16; 8_000];
+        samples.extend(tone_i16(16_000, 1_000.0, 16_000));
+        samples.extend(vec![0_i16; 8_000]);
+        samples.extend(tone_i16(16_000, 1_000.0, 16_000));
+        write_wav(&path, &samples, 16_000);
+
+        let config = SpeechBoundaryConfig { margin_db: 6.0, pad_ms: 50, split_above_ms: Some(500) };
+        let report = trim_silence_and_segment(&path, &config).expect("trim");
+
+        assert_eq!(report.spans.len(), 2);
+        assert_eq!(report.segment_paths.len(), 2);
+        for segment_path in &report.segment_paths {
+            assert!(segment_path.exists());
+        }
+    }
+
+    fn config() -> VadConfig {
+        VadConfig {
+            energy_threshold: 0.05,
+            high_band_ratio_threshold: 0.05,
+            auto_stop_silence: Duration::from_millis(60),
+        }
+    }
+
+    fn tone_frame(len: usize, freq_hz: f32, sample_rate: u32) -> Vec<f32> {
+        (0..len)
+            .map(|i| {
+                let t = i as f32 / sample_rate as f32;
+                (2.0 * std::f32::consts::PI * freq_hz * t).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stays_silent_without_any_speech_frame() {
+        let mut tracker = VoiceActivityTracker::new(config(), 16_000);
+        tracker.push_samples(&vec![0.0_f32; 16_000]);
+        let snapshot = tracker.snapshot();
+        assert!(!snapshot.speech_seen);
+        assert!(!snapshot.should_stop);
+    }
+
+    #[test]
+    fn stops_after_sustained_silence_following_speech() {
+        let mut tracker = VoiceActivityTracker::new(config(), 16_000);
+        tracker.push_samples(&tone_frame(16_000, 6_000.0, 16_000));
+        assert!(tracker.snapshot().speech_seen);
+        assert!(!tracker.snapshot().should_stop);
+
+        tracker.push_samples(&vec![0.0_f32; 16_000]);
+        assert!(tracker.snapshot().should_stop);
+    }
+
+    #[test]
+    fn brief_pause_does_not_trigger_stop() {
+        let mut tracker = VoiceActivityTracker::new(config(), 16_000);
+        tracker.push_samples(&tone_frame(320, 6_000.0, 16_000));
+        tracker.push_samples(&vec![0.0_f32; 320]);
+        tracker.push_samples(&tone_frame(320, 6_000.0, 16_000));
+        assert!(!tracker.snapshot().should_stop);
+    }
+}
@@ -3,7 +3,10 @@ use std::path::PathBuf;
 use clap::{Parser, Subcommand};
 use franken_whisper::BackendKind;
 
-use crate::config::{CliOverrides, OutputMode};
+use crate::config::{
+    CliOverrides, ClipboardProviderKind, ClipboardSelectionTarget, HotkeyAction, HotkeyBinding,
+    HotkeyMode, LogFormat, OutputMode,
+};
 
 #[derive(Debug, Parser)]
 #[command(name = "quedo-daemon")]
@@ -12,6 +15,9 @@ pub struct Cli {
     #[arg(long)]
     pub config: Option<PathBuf>,
 
+    #[arg(long)]
+    pub profile: Option<String>,
+
     #[arg(long, value_enum)]
     pub backend: Option<BackendKind>,
 
@@ -33,9 +39,24 @@ pub struct Cli {
     #[arg(long)]
     pub hotkey_binding: Option<String>,
 
+    #[arg(long)]
+    pub hotkey_mode: Option<String>,
+
     #[arg(long)]
     pub output_mode: Option<String>,
 
+    #[arg(long)]
+    pub clipboard_provider: Option<String>,
+
+    #[arg(long)]
+    pub selection_target: Option<String>,
+
+    /// Formatter for the daily-rotating log file under `AppPaths::logs_dir`
+    /// (`text` or `json`); the interactive stderr log stays compact text
+    /// either way. See `main::init_tracing`.
+    #[arg(long)]
+    pub log_format: Option<String>,
+
     #[command(subcommand)]
     pub command: Command,
 }
@@ -46,42 +67,170 @@ pub enum Command {
     Doctor {
         #[arg(long)]
         json: bool,
+        /// Print a remediation plan for failing checks (package-manager
+        /// install commands) instead of just the report; see
+        /// `doctor::build_fix_plan`.
+        #[arg(long)]
+        fix: bool,
+        /// Only meaningful with `--fix`: actually runs the resolved
+        /// commands instead of printing a dry-run plan.
+        #[arg(long)]
+        execute: bool,
+        /// Only meaningful with `--fix --execute`: skips the confirmation
+        /// prompt before running each resolved command, for unattended use
+        /// (e.g. scripted installs/CI).
+        #[arg(long)]
+        yes: bool,
+        /// Runs the microphone-signal and Metal/whisper smoke-test checks
+        /// against this specific input device instead of the configured
+        /// default. Pass `?` to print the enumerated device list and exit
+        /// without running any checks.
+        #[arg(long)]
+        device: Option<String>,
+        /// Machine-readable output for scripting: `json` emits a JSON array
+        /// of `{name, status, required, detail}`; any other value is a
+        /// stat(1)-style template expanded per check (`%n`/`%s`/`%r`/`%d`
+        /// for name/status/required/detail), e.g. `--format '%n:%s'`. Wins
+        /// over `--json` when both are set; ignored under `--fix`.
+        #[arg(long)]
+        format: Option<String>,
     },
     Install,
     Status,
+    /// Loads and merges the config without starting the daemon, reporting
+    /// every problem it finds (malformed fields, an unrunnable backend)
+    /// instead of failing on the first one.
+    CheckConfig {
+        #[arg(long)]
+        json: bool,
+    },
+    /// Internal: runs as the out-of-process transcription worker, spawned by
+    /// `ProcessEngine::spawn` rather than invoked directly.
+    #[command(hide = true)]
+    EngineWorker {
+        #[arg(long)]
+        socket: String,
+    },
+    /// Replays a directory of `*.wav`/`*.txt` fixture pairs through the
+    /// transcription pipeline and reports mean WER/CER for the configured
+    /// backend; see `evaluation::run_evaluation_suite`.
+    Evaluate {
+        fixtures_dir: PathBuf,
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 impl Cli {
     pub fn to_overrides(&self) -> CliOverrides {
         CliOverrides {
             config_path: self.config.clone(),
+            profile: self.profile.clone(),
             backend: self.backend,
             model_id: self.model_id.clone(),
             language: self.language.clone(),
             timeout_seconds: self.timeout_seconds,
             diarize: self.diarize,
             translate: self.translate,
-            hotkey_binding: self.hotkey_binding.clone(),
+            hotkey_bindings: self
+                .hotkey_binding
+                .as_deref()
+                .and_then(parse_hotkey_binding_override),
+            hotkey_mode: self
+                .hotkey_mode
+                .as_deref()
+                .and_then(parse_hotkey_mode_override),
             output_mode: self
                 .output_mode
                 .as_deref()
                 .and_then(parse_output_mode_override),
+            clipboard_provider: self
+                .clipboard_provider
+                .as_deref()
+                .and_then(parse_clipboard_provider_override),
+            selection_target: self
+                .selection_target
+                .as_deref()
+                .and_then(parse_selection_target_override),
+            log_format: self
+                .log_format
+                .as_deref()
+                .and_then(parse_log_format_override),
         }
     }
 }
 
+/// Validates `--hotkey-binding` against the shared hotkey grammar and wraps
+/// it as a `toggle` override, the one action the legacy single-binding flag
+/// can address; malformed strings are dropped like any other CLI override.
+fn parse_hotkey_binding_override(raw: &str) -> Option<Vec<HotkeyBinding>> {
+    crate::config::parse_binding(raw).ok()?;
+    Some(vec![HotkeyBinding {
+        action: HotkeyAction::Toggle,
+        binding: raw.to_owned(),
+    }])
+}
+
+fn parse_hotkey_mode_override(raw: &str) -> Option<HotkeyMode> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "toggle" => Some(HotkeyMode::Toggle),
+        "push_to_talk" | "push-to-talk" | "ptt" => Some(HotkeyMode::PushToTalk),
+        _ => None,
+    }
+}
+
 fn parse_output_mode_override(raw: &str) -> Option<OutputMode> {
     match raw.trim().to_ascii_lowercase().as_str() {
         "clipboard_only" | "clipboard-only" => Some(OutputMode::ClipboardOnly),
+        "type" | "inject" | "keyboard" | "type_text" | "type-text" => Some(OutputMode::TypeText),
+        "command" | "exec" => Some(OutputMode::Command),
         "disabled" | "none" => Some(OutputMode::Disabled),
         _ => None,
     }
 }
 
+fn parse_clipboard_provider_override(raw: &str) -> Option<ClipboardProviderKind> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "auto" => Some(ClipboardProviderKind::Auto),
+        "wayland" | "wl-copy" | "wl_copy" | "wl-clipboard" | "wl_clipboard" => {
+            Some(ClipboardProviderKind::Wayland)
+        }
+        "xclip" => Some(ClipboardProviderKind::Xclip),
+        "xsel" => Some(ClipboardProviderKind::Xsel),
+        "macos" | "pbcopy" => Some(ClipboardProviderKind::Macos),
+        "windows" | "win32yank" => Some(ClipboardProviderKind::Windows),
+        "internal" | "in-memory" | "in_memory" => Some(ClipboardProviderKind::Internal),
+        "arboard" => Some(ClipboardProviderKind::Arboard),
+        _ => None,
+    }
+}
+
+fn parse_selection_target_override(raw: &str) -> Option<ClipboardSelectionTarget> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "clipboard" => Some(ClipboardSelectionTarget::Clipboard),
+        "primary" => Some(ClipboardSelectionTarget::Primary),
+        _ => None,
+    }
+}
+
+fn parse_log_format_override(raw: &str) -> Option<LogFormat> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "text" | "plain" => Some(LogFormat::Text),
+        "json" => Some(LogFormat::Json),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{parse_output_mode_override, Cli, Command};
-    use crate::config::OutputMode;
+    use super::{
+        parse_clipboard_provider_override, parse_hotkey_mode_override, parse_log_format_override,
+        parse_output_mode_override, parse_selection_target_override, Cli, Command,
+    };
+    use crate::config::{
+        ClipboardProviderKind, ClipboardSelectionTarget, HotkeyAction, HotkeyBinding, HotkeyMode,
+        LogFormat, OutputMode,
+    };
     use franken_whisper::BackendKind;
     use std::path::PathBuf;
 
@@ -100,13 +249,84 @@ mod tests {
             Some(OutputMode::Disabled)
         );
         assert_eq!(parse_output_mode_override("none"), Some(OutputMode::Disabled));
+        assert_eq!(parse_output_mode_override("type"), Some(OutputMode::TypeText));
+        assert_eq!(parse_output_mode_override("inject"), Some(OutputMode::TypeText));
+        assert_eq!(parse_output_mode_override("keyboard"), Some(OutputMode::TypeText));
+        assert_eq!(parse_output_mode_override("command"), Some(OutputMode::Command));
+        assert_eq!(parse_output_mode_override("exec"), Some(OutputMode::Command));
         assert_eq!(parse_output_mode_override("unknown"), None);
     }
 
+    #[test]
+    fn clipboard_provider_aliases_parse() {
+        assert_eq!(
+            parse_clipboard_provider_override("auto"),
+            Some(ClipboardProviderKind::Auto)
+        );
+        assert_eq!(
+            parse_clipboard_provider_override("wl-copy"),
+            Some(ClipboardProviderKind::Wayland)
+        );
+        assert_eq!(
+            parse_clipboard_provider_override("xclip"),
+            Some(ClipboardProviderKind::Xclip)
+        );
+        assert_eq!(
+            parse_clipboard_provider_override("xsel"),
+            Some(ClipboardProviderKind::Xsel)
+        );
+        assert_eq!(
+            parse_clipboard_provider_override("pbcopy"),
+            Some(ClipboardProviderKind::Macos)
+        );
+        assert_eq!(
+            parse_clipboard_provider_override("win32yank"),
+            Some(ClipboardProviderKind::Windows)
+        );
+        assert_eq!(
+            parse_clipboard_provider_override("internal"),
+            Some(ClipboardProviderKind::Internal)
+        );
+        assert_eq!(
+            parse_clipboard_provider_override("arboard"),
+            Some(ClipboardProviderKind::Arboard)
+        );
+        assert_eq!(parse_clipboard_provider_override("unknown"), None);
+    }
+
+    #[test]
+    fn selection_target_aliases_parse() {
+        assert_eq!(
+            parse_selection_target_override("clipboard"),
+            Some(ClipboardSelectionTarget::Clipboard)
+        );
+        assert_eq!(
+            parse_selection_target_override("primary"),
+            Some(ClipboardSelectionTarget::Primary)
+        );
+        assert_eq!(parse_selection_target_override("unknown"), None);
+    }
+
+    #[test]
+    fn hotkey_mode_aliases_parse() {
+        assert_eq!(parse_hotkey_mode_override("toggle"), Some(HotkeyMode::Toggle));
+        assert_eq!(
+            parse_hotkey_mode_override("push_to_talk"),
+            Some(HotkeyMode::PushToTalk)
+        );
+        assert_eq!(
+            parse_hotkey_mode_override("push-to-talk"),
+            Some(HotkeyMode::PushToTalk)
+        );
+        assert_eq!(parse_hotkey_mode_override("ptt"), Some(HotkeyMode::PushToTalk));
+        assert_eq!(parse_hotkey_mode_override("unknown"), None);
+    }
+
     #[test]
     fn to_overrides_maps_all_fields() {
         let cli = Cli {
             config: Some(PathBuf::from("/tmp/config.toml")),
+            profile: Some("work".to_owned()),
             backend: Some(BackendKind::WhisperCpp),
             model_id: Some("model-a".to_owned()),
             language: Some("en".to_owned()),
@@ -114,7 +334,11 @@ mod tests {
             diarize: Some(true),
             translate: Some(true),
             hotkey_binding: Some("Ctrl+Shift+Space".to_owned()),
+            hotkey_mode: Some("push_to_talk".to_owned()),
             output_mode: Some("clipboard-only".to_owned()),
+            clipboard_provider: Some("xclip".to_owned()),
+            selection_target: Some("primary".to_owned()),
+            log_format: Some("json".to_owned()),
             command: Command::Status,
         };
 
@@ -126,14 +350,29 @@ mod tests {
         assert_eq!(overrides.timeout_seconds, Some(88));
         assert_eq!(overrides.diarize, Some(true));
         assert_eq!(overrides.translate, Some(true));
-        assert_eq!(overrides.hotkey_binding.as_deref(), Some("Ctrl+Shift+Space"));
+        assert_eq!(
+            overrides.hotkey_bindings,
+            Some(vec![HotkeyBinding {
+                action: HotkeyAction::Toggle,
+                binding: "Ctrl+Shift+Space".to_owned(),
+            }])
+        );
         assert_eq!(overrides.output_mode, Some(OutputMode::ClipboardOnly));
+        assert_eq!(overrides.clipboard_provider, Some(ClipboardProviderKind::Xclip));
+        assert_eq!(
+            overrides.selection_target,
+            Some(ClipboardSelectionTarget::Primary)
+        );
+        assert_eq!(overrides.hotkey_mode, Some(HotkeyMode::PushToTalk));
+        assert_eq!(overrides.profile.as_deref(), Some("work"));
+        assert_eq!(overrides.log_format, Some(LogFormat::Json));
     }
 
     #[test]
     fn invalid_output_mode_does_not_override() {
         let cli = Cli {
             config: None,
+            profile: None,
             backend: None,
             model_id: None,
             language: None,
@@ -141,11 +380,52 @@ mod tests {
             diarize: None,
             translate: None,
             hotkey_binding: None,
+            hotkey_mode: Some("invalid".to_owned()),
             output_mode: Some("invalid".to_owned()),
+            clipboard_provider: Some("invalid".to_owned()),
+            selection_target: Some("invalid".to_owned()),
+            log_format: Some("invalid".to_owned()),
             command: Command::Run,
         };
 
         let overrides = cli.to_overrides();
         assert!(overrides.output_mode.is_none());
+        assert!(overrides.clipboard_provider.is_none());
+        assert!(overrides.selection_target.is_none());
+        assert!(overrides.hotkey_mode.is_none());
+        assert!(overrides.log_format.is_none());
+    }
+
+    #[test]
+    fn invalid_hotkey_binding_does_not_override() {
+        let cli = Cli {
+            config: None,
+            profile: None,
+            backend: None,
+            model_id: None,
+            language: None,
+            timeout_seconds: None,
+            diarize: None,
+            translate: None,
+            hotkey_binding: Some("Ctrl+Nope".to_owned()),
+            hotkey_mode: None,
+            output_mode: None,
+            clipboard_provider: None,
+            selection_target: None,
+            log_format: None,
+            command: Command::Run,
+        };
+
+        let overrides = cli.to_overrides();
+        assert!(overrides.hotkey_bindings.is_none());
+    }
+
+    #[test]
+    fn log_format_aliases_parse() {
+        assert_eq!(parse_log_format_override("text"), Some(LogFormat::Text));
+        assert_eq!(parse_log_format_override("plain"), Some(LogFormat::Text));
+        assert_eq!(parse_log_format_override("json"), Some(LogFormat::Json));
+        assert_eq!(parse_log_format_override("JSON"), Some(LogFormat::Json));
+        assert_eq!(parse_log_format_override("other"), None);
     }
 }
@@ -0,0 +1,182 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+/// A point in time as seen by a `Clocks` implementation. This wraps a plain
+/// `Duration` rather than a `std::time::Instant` so `SimulatedClocks` can
+/// manufacture and advance one without a real monotonic clock underneath it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ClockInstant(Duration);
+
+impl ClockInstant {
+    pub fn saturating_duration_since(&self, earlier: ClockInstant) -> Duration {
+        self.0.saturating_sub(earlier.0)
+    }
+}
+
+/// Monotonic time source the controller and transcription timeout logic
+/// depend on instead of calling `Instant::now`/`thread::sleep` directly, so
+/// timeout and deadline behavior can be exercised deterministically via
+/// `SimulatedClocks` rather than waiting on the wall clock.
+pub trait Clocks: Send + Sync + std::fmt::Debug {
+    fn now(&self) -> ClockInstant;
+    fn sleep(&self, duration: Duration);
+
+    /// Wall-clock "now" as an RFC 3339 string, for the handful of places
+    /// (`DoctorReport::generated_at_rfc3339`, `NetworkStreamingEngine`'s
+    /// minted `RunReport` timestamps) that need a human-readable, calendar
+    /// timestamp rather than a `ClockInstant`; kept on this trait instead of
+    /// calling `chrono::Utc::now()` directly so those call sites are
+    /// deterministic under `SimulatedClocks`.
+    fn now_rfc3339(&self) -> String;
+}
+
+/// Production clock backed by `std::time::Instant`/`std::thread::sleep`.
+#[derive(Debug)]
+pub struct SystemClocks {
+    epoch: Instant,
+}
+
+impl SystemClocks {
+    pub fn new() -> Self {
+        Self { epoch: Instant::now() }
+    }
+}
+
+impl Default for SystemClocks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clocks for SystemClocks {
+    fn now(&self) -> ClockInstant {
+        ClockInstant(self.epoch.elapsed())
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+
+    fn now_rfc3339(&self) -> String {
+        Utc::now().to_rfc3339()
+    }
+}
+
+/// Test clock whose time only moves when `advance` is called, so
+/// time-dependent controller logic (recording-duration gating, the
+/// shutdown-drain deadline, `Processing` timeouts) can be driven
+/// deterministically instead of via real wall-clock waits.
+#[derive(Debug)]
+pub struct SimulatedClocks {
+    now: Mutex<Duration>,
+    /// `now_rfc3339` reports `wall_clock_start + now`, so advancing/resetting
+    /// the monotonic side via `advance`/`pin_rfc3339` keeps both views of
+    /// time in lockstep instead of drifting apart.
+    wall_clock_start: Mutex<DateTime<Utc>>,
+}
+
+impl Default for SimulatedClocks {
+    fn default() -> Self {
+        Self {
+            now: Mutex::new(Duration::ZERO),
+            wall_clock_start: Mutex::new(
+                DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+                    .expect("valid constant rfc3339 timestamp")
+                    .with_timezone(&Utc),
+            ),
+        }
+    }
+}
+
+impl SimulatedClocks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.now.lock().expect("lock simulated clock");
+        *now += duration;
+    }
+
+    /// Pins `now_rfc3339` to exactly `rfc3339` (and resets the monotonic
+    /// `now()`/`sleep()` side to zero from this point), for tests asserting
+    /// an exact `DoctorReport::generated_at_rfc3339` or run timestamp rather
+    /// than just relative ordering. Panics if `rfc3339` doesn't parse.
+    pub fn pin_rfc3339(&self, rfc3339: &str) {
+        let parsed = DateTime::parse_from_rfc3339(rfc3339)
+            .expect("valid rfc3339 timestamp")
+            .with_timezone(&Utc);
+        *self.wall_clock_start.lock().expect("lock simulated clock") = parsed;
+        *self.now.lock().expect("lock simulated clock") = Duration::ZERO;
+    }
+}
+
+impl Clocks for SimulatedClocks {
+    fn now(&self) -> ClockInstant {
+        ClockInstant(*self.now.lock().expect("lock simulated clock"))
+    }
+
+    /// Doesn't actually block; advances the simulated clock by `duration`
+    /// instead, since a real sleep here would defeat the point of injecting
+    /// this clock into a test in the first place.
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+
+    fn now_rfc3339(&self) -> String {
+        let start = *self.wall_clock_start.lock().expect("lock simulated clock");
+        let elapsed = *self.now.lock().expect("lock simulated clock");
+        let elapsed = chrono::Duration::from_std(elapsed).unwrap_or_else(|_| chrono::Duration::zero());
+        (start + elapsed).to_rfc3339()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Clocks, SimulatedClocks, SystemClocks};
+    use std::time::Duration;
+
+    #[test]
+    fn simulated_clock_only_advances_when_told() {
+        let clocks = SimulatedClocks::new();
+        let start = clocks.now();
+        assert_eq!(start.saturating_duration_since(start), Duration::ZERO);
+
+        clocks.advance(Duration::from_secs(5));
+        let after = clocks.now();
+        assert_eq!(after.saturating_duration_since(start), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn simulated_clock_sleep_advances_instead_of_blocking() {
+        let clocks = SimulatedClocks::new();
+        let start = clocks.now();
+        clocks.sleep(Duration::from_secs(60));
+        assert_eq!(clocks.now().saturating_duration_since(start), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn system_clock_reports_nonzero_elapsed_after_a_real_sleep() {
+        let clocks = SystemClocks::new();
+        let start = clocks.now();
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(clocks.now().saturating_duration_since(start) >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn simulated_clock_now_rfc3339_can_be_pinned() {
+        let clocks = SimulatedClocks::new();
+        clocks.pin_rfc3339("2026-03-01T12:00:00+00:00");
+        assert_eq!(clocks.now_rfc3339(), "2026-03-01T12:00:00+00:00");
+    }
+
+    #[test]
+    fn simulated_clock_now_rfc3339_advances_with_advance() {
+        let clocks = SimulatedClocks::new();
+        clocks.pin_rfc3339("2026-03-01T12:00:00+00:00");
+        clocks.advance(Duration::from_secs(90));
+        assert_eq!(clocks.now_rfc3339(), "2026-03-01T12:01:30+00:00");
+    }
+}
@@ -0,0 +1,163 @@
+use regex::Regex;
+
+use crate::config::load::merge_binding;
+use crate::config::schema::{AppConfig, ContextOverride, HotkeyAction, HotkeyBinding};
+use crate::error::{AppError, AppResult};
+
+/// Compiles every `[[overrides]]` entry's `match_pattern` as a regex,
+/// rejecting the config if any one pattern doesn't compile; see
+/// `resolve_for_context`, which assumes this has already run.
+pub fn validate_overrides(overrides: &[ContextOverride]) -> AppResult<()> {
+    for entry in overrides {
+        Regex::new(&entry.match_pattern).map_err(|error| {
+            AppError::Config(format!(
+                "overrides entry has an invalid match pattern `{}`: {error}",
+                entry.match_pattern
+            ))
+        })?;
+    }
+    Ok(())
+}
+
+/// Applies every `[[overrides]]` entry whose `match_pattern` matches
+/// `app_id` or `title` on top of `base`, in order, so a later match's fields
+/// win over an earlier one's; a field an entry leaves unset keeps whatever
+/// `base` (or a prior match) already had. Lets a user dictate code in one
+/// editor and prose in a chat app with different languages/backends
+/// automatically, without having to switch profiles by hand.
+pub fn resolve_for_context(base: &AppConfig, app_id: &str, title: &str) -> AppConfig {
+    let mut resolved = base.clone();
+
+    for entry in &base.overrides {
+        let Ok(pattern) = Regex::new(&entry.match_pattern) else {
+            continue;
+        };
+        if !pattern.is_match(app_id) && !pattern.is_match(title) {
+            continue;
+        }
+
+        if let Some(language) = &entry.language {
+            resolved.transcription.language = Some(language.clone());
+        }
+        if let Some(backend) = entry.backend {
+            resolved.transcription.backend = backend;
+        }
+        if let Some(output_mode) = &entry.output_mode {
+            resolved.output.mode = output_mode.clone();
+        }
+        if let Some(translate) = entry.translate {
+            resolved.transcription.translate = translate;
+        }
+        if let Some(diarize) = entry.diarize {
+            resolved.transcription.diarize = diarize;
+        }
+        if let Some(binding) = &entry.hotkey_binding {
+            merge_binding(
+                &mut resolved.hotkey.bindings,
+                HotkeyBinding {
+                    action: HotkeyAction::Toggle,
+                    binding: binding.clone(),
+                },
+            );
+        }
+    }
+
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve_for_context, validate_overrides};
+    use crate::config::schema::{AppConfig, ContextOverride, OutputMode};
+    use crate::error::AppError;
+    use franken_whisper::BackendKind;
+
+    fn override_for(match_pattern: &str) -> ContextOverride {
+        ContextOverride {
+            match_pattern: match_pattern.to_owned(),
+            ..ContextOverride::default()
+        }
+    }
+
+    #[test]
+    fn validate_overrides_rejects_bad_regex() {
+        let overrides = vec![override_for("(unclosed")];
+        let error = validate_overrides(&overrides).expect_err("must fail");
+        assert!(matches!(error, AppError::Config(message) if message.contains("invalid match pattern")));
+    }
+
+    #[test]
+    fn validate_overrides_accepts_good_regex() {
+        let overrides = vec![override_for(r"^code\b"), override_for(r"(?i)slack")];
+        assert!(validate_overrides(&overrides).is_ok());
+    }
+
+    #[test]
+    fn resolve_for_context_applies_first_matching_fields_in_order() {
+        let mut config = AppConfig::default();
+        config.transcription.language = Some("en".to_owned());
+        config.overrides = vec![
+            ContextOverride {
+                match_pattern: r"(?i)code".to_owned(),
+                language: Some("en".to_owned()),
+                backend: Some(BackendKind::WhisperCpp),
+                ..ContextOverride::default()
+            },
+            ContextOverride {
+                match_pattern: r"(?i)slack".to_owned(),
+                language: Some("fr".to_owned()),
+                output_mode: Some(OutputMode::TypeText),
+                ..ContextOverride::default()
+            },
+        ];
+
+        let resolved = resolve_for_context(&config, "com.app.slack", "general");
+        assert_eq!(resolved.transcription.language.as_deref(), Some("fr"));
+        assert_eq!(resolved.output.mode, OutputMode::TypeText);
+        // Unmatched entry's fields are left alone.
+        assert_eq!(resolved.transcription.backend, BackendKind::Auto);
+    }
+
+    #[test]
+    fn resolve_for_context_lets_a_later_match_win_over_an_earlier_one() {
+        let mut config = AppConfig::default();
+        config.overrides = vec![
+            ContextOverride {
+                match_pattern: r".*".to_owned(),
+                language: Some("en".to_owned()),
+                ..ContextOverride::default()
+            },
+            ContextOverride {
+                match_pattern: r"vscode".to_owned(),
+                language: Some("de".to_owned()),
+                ..ContextOverride::default()
+            },
+        ];
+
+        let resolved = resolve_for_context(&config, "vscode", "main.rs");
+        assert_eq!(resolved.transcription.language.as_deref(), Some("de"));
+    }
+
+    #[test]
+    fn resolve_for_context_matches_against_title_as_well_as_app_id() {
+        let mut config = AppConfig::default();
+        config.overrides = vec![ContextOverride {
+            match_pattern: r"(?i)chatgpt".to_owned(),
+            output_mode: Some(OutputMode::Disabled),
+            ..ContextOverride::default()
+        }];
+
+        let resolved = resolve_for_context(&config, "firefox", "ChatGPT - Mozilla Firefox");
+        assert_eq!(resolved.output.mode, OutputMode::Disabled);
+    }
+
+    #[test]
+    fn resolve_for_context_is_a_no_op_without_overrides() {
+        let config = AppConfig::default();
+        let resolved = resolve_for_context(&config, "anything", "anything");
+        assert_eq!(
+            resolved.transcription.language,
+            config.transcription.language
+        );
+    }
+}
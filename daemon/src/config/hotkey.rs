@@ -0,0 +1,454 @@
+use crate::config::schema::{HotkeyAction, HotkeyBinding, HotkeyMode};
+use crate::error::{AppError, AppResult};
+
+/// A single keyboard modifier recognized by the hotkey grammar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Modifier {
+    Control,
+    Shift,
+    Alt,
+    Super,
+}
+
+/// A deduplicated, order-independent set of modifiers for a parsed binding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ModifierSet(Vec<Modifier>);
+
+impl ModifierSet {
+    fn empty() -> Self {
+        Self(Vec::new())
+    }
+
+    fn insert_unique(&mut self, modifier: Modifier) -> Result<(), Modifier> {
+        if self.0.contains(&modifier) {
+            return Err(modifier);
+        }
+        self.0.push(modifier);
+        self.0.sort();
+        Ok(())
+    }
+
+    pub fn contains(&self, modifier: Modifier) -> bool {
+        self.0.contains(&modifier)
+    }
+}
+
+/// An xkeysym-style key table; covers the keys this daemon's hotkey bindings
+/// are expected to use (letters, digits, function keys, arrows, common
+/// editing/navigation keys, and common punctuation), named after the
+/// `global_hotkey::hotkey::Code` variant each maps onto in `ui::hotkey`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Keysym {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Digit0,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+    Up,
+    Down,
+    Left,
+    Right,
+    Space,
+    Enter,
+    Escape,
+    Tab,
+    Backspace,
+    Delete,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Backquote,
+    Minus,
+    Equal,
+    BracketLeft,
+    BracketRight,
+    Semicolon,
+    Quote,
+    Comma,
+    Period,
+    Slash,
+}
+
+/// A binding string parsed into its structured modifiers and key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParsedBinding {
+    pub modifiers: ModifierSet,
+    pub key: Keysym,
+}
+
+fn parse_modifier(token: &str) -> Option<Modifier> {
+    match token {
+        "ctrl" | "control" => Some(Modifier::Control),
+        "shift" => Some(Modifier::Shift),
+        "alt" | "option" => Some(Modifier::Alt),
+        "cmd" | "command" | "super" => Some(Modifier::Super),
+        _ => None,
+    }
+}
+
+/// Every recognized key token paired with the `Keysym` it parses to. A flat
+/// table instead of a giant match arm list, since most entries here are
+/// mechanical aliases (`"f1"` -> `F1`, `"1"` -> `Digit1`, ...) that don't
+/// benefit from match-arm syntax.
+const KEY_TABLE: &[(&str, Keysym)] = &[
+    ("a", Keysym::A),
+    ("b", Keysym::B),
+    ("c", Keysym::C),
+    ("d", Keysym::D),
+    ("e", Keysym::E),
+    ("f", Keysym::F),
+    ("g", Keysym::G),
+    ("h", Keysym::H),
+    ("i", Keysym::I),
+    ("j", Keysym::J),
+    ("k", Keysym::K),
+    ("l", Keysym::L),
+    ("m", Keysym::M),
+    ("n", Keysym::N),
+    ("o", Keysym::O),
+    ("p", Keysym::P),
+    ("q", Keysym::Q),
+    ("r", Keysym::R),
+    ("s", Keysym::S),
+    ("t", Keysym::T),
+    ("u", Keysym::U),
+    ("v", Keysym::V),
+    ("w", Keysym::W),
+    ("x", Keysym::X),
+    ("y", Keysym::Y),
+    ("z", Keysym::Z),
+    ("0", Keysym::Digit0),
+    ("1", Keysym::Digit1),
+    ("2", Keysym::Digit2),
+    ("3", Keysym::Digit3),
+    ("4", Keysym::Digit4),
+    ("5", Keysym::Digit5),
+    ("6", Keysym::Digit6),
+    ("7", Keysym::Digit7),
+    ("8", Keysym::Digit8),
+    ("9", Keysym::Digit9),
+    ("f1", Keysym::F1),
+    ("f2", Keysym::F2),
+    ("f3", Keysym::F3),
+    ("f4", Keysym::F4),
+    ("f5", Keysym::F5),
+    ("f6", Keysym::F6),
+    ("f7", Keysym::F7),
+    ("f8", Keysym::F8),
+    ("f9", Keysym::F9),
+    ("f10", Keysym::F10),
+    ("f11", Keysym::F11),
+    ("f12", Keysym::F12),
+    ("f13", Keysym::F13),
+    ("f14", Keysym::F14),
+    ("f15", Keysym::F15),
+    ("f16", Keysym::F16),
+    ("f17", Keysym::F17),
+    ("f18", Keysym::F18),
+    ("f19", Keysym::F19),
+    ("f20", Keysym::F20),
+    ("f21", Keysym::F21),
+    ("f22", Keysym::F22),
+    ("f23", Keysym::F23),
+    ("f24", Keysym::F24),
+    ("up", Keysym::Up),
+    ("down", Keysym::Down),
+    ("left", Keysym::Left),
+    ("right", Keysym::Right),
+    ("space", Keysym::Space),
+    ("enter", Keysym::Enter),
+    ("escape", Keysym::Escape),
+    ("esc", Keysym::Escape),
+    ("tab", Keysym::Tab),
+    ("backspace", Keysym::Backspace),
+    ("delete", Keysym::Delete),
+    ("home", Keysym::Home),
+    ("end", Keysym::End),
+    ("pageup", Keysym::PageUp),
+    ("pagedown", Keysym::PageDown),
+    ("`", Keysym::Backquote),
+    ("backquote", Keysym::Backquote),
+    ("-", Keysym::Minus),
+    ("=", Keysym::Equal),
+    ("[", Keysym::BracketLeft),
+    ("]", Keysym::BracketRight),
+    (";", Keysym::Semicolon),
+    ("'", Keysym::Quote),
+    (",", Keysym::Comma),
+    (".", Keysym::Period),
+    ("/", Keysym::Slash),
+];
+
+fn parse_key(token: &str) -> Option<Keysym> {
+    KEY_TABLE
+        .iter()
+        .find(|(name, _)| *name == token)
+        .map(|(_, key)| *key)
+}
+
+/// Parses a binding string (e.g. `"Ctrl+Shift+Space"`) into its structured
+/// form, rejecting unknown key names and duplicate/empty modifier tokens.
+pub fn parse_binding(raw: &str) -> AppResult<ParsedBinding> {
+    let tokens: Vec<&str> = raw.split('+').map(str::trim).collect();
+    if tokens.iter().any(|token| token.is_empty()) {
+        return Err(AppError::Config(format!(
+            "hotkey binding `{raw}` contains an empty modifier token"
+        )));
+    }
+
+    let mut modifiers = ModifierSet::empty();
+    let mut key = None;
+
+    for token in &tokens {
+        let lower = token.to_ascii_lowercase();
+        if let Some(modifier) = parse_modifier(&lower) {
+            modifiers.insert_unique(modifier).map_err(|duplicate| {
+                AppError::Config(format!(
+                    "hotkey binding `{raw}` repeats modifier `{duplicate:?}`"
+                ))
+            })?;
+        } else if let Some(parsed_key) = parse_key(&lower) {
+            if key.replace(parsed_key).is_some() {
+                return Err(AppError::Config(format!(
+                    "hotkey binding `{raw}` specifies more than one key"
+                )));
+            }
+        } else {
+            return Err(AppError::Config(format!(
+                "unsupported hotkey token `{token}` in binding `{raw}`"
+            )));
+        }
+    }
+
+    let key = key.ok_or_else(|| {
+        AppError::Config(format!(
+            "hotkey binding `{raw}` must include a key token (for example `Space`)"
+        ))
+    })?;
+
+    Ok(ParsedBinding { modifiers, key })
+}
+
+/// Parses every configured binding and rejects the set if any binding fails
+/// to parse or if two actions are bound to the same chord.
+pub fn validate_bindings(bindings: &[HotkeyBinding]) -> AppResult<Vec<(HotkeyAction, ParsedBinding)>> {
+    let mut parsed: Vec<(HotkeyAction, ParsedBinding)> = Vec::with_capacity(bindings.len());
+
+    for binding in bindings {
+        let chord = parse_binding(&binding.binding)?;
+        if let Some((existing_action, _)) = parsed
+            .iter()
+            .find(|(_, existing_chord)| *existing_chord == chord)
+        {
+            return Err(AppError::Config(format!(
+                "hotkey binding `{}` for action `{:?}` collides with action `{existing_action:?}`",
+                binding.binding, binding.action
+            )));
+        }
+        parsed.push((binding.action, chord));
+    }
+
+    Ok(parsed)
+}
+
+/// Whether the hotkey backend compiled for this platform can report
+/// key-release events (as opposed to only the press edge a global-hotkey
+/// registration gives you). `global-hotkey`'s macOS, Windows, and X11-Linux
+/// backends all report `HotKeyState::Released`; every other platform falls
+/// back to the no-op `HotkeyController` in `ui::hotkey`, which never tracks
+/// state at all, so `push_to_talk` is unavailable there.
+pub fn backend_supports_push_to_talk() -> bool {
+    cfg!(any(target_os = "macos", target_os = "windows", target_os = "linux"))
+}
+
+/// Rejects `push_to_talk` on a backend that cannot report releases, since
+/// there would be no way to know when to stop recording.
+pub fn validate_hotkey_mode(mode: HotkeyMode) -> AppResult<()> {
+    if mode == HotkeyMode::PushToTalk && !backend_supports_push_to_talk() {
+        return Err(AppError::Config(
+            "hotkey.mode = \"push_to_talk\" requires a hotkey backend that can report \
+             key-release events, which this platform's backend does not support"
+                .to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        backend_supports_push_to_talk, parse_binding, validate_bindings, validate_hotkey_mode,
+        Keysym, Modifier,
+    };
+    use crate::config::schema::{HotkeyAction, HotkeyBinding, HotkeyMode};
+
+    #[test]
+    fn parses_modifiers_and_key_regardless_of_order() {
+        let parsed = parse_binding("Shift+Ctrl+Space").expect("parse");
+        assert!(parsed.modifiers.contains(Modifier::Shift));
+        assert!(parsed.modifiers.contains(Modifier::Control));
+        assert_eq!(parsed.key, Keysym::Space);
+    }
+
+    #[test]
+    fn rejects_unknown_key() {
+        let error = parse_binding("Ctrl+Nope").expect_err("must fail");
+        assert!(error.to_string().contains("unsupported hotkey token"));
+    }
+
+    #[test]
+    fn rejects_duplicate_modifier() {
+        let error = parse_binding("Ctrl+Ctrl+Space").expect_err("must fail");
+        assert!(error.to_string().contains("repeats modifier"));
+    }
+
+    #[test]
+    fn rejects_empty_modifier_token() {
+        let error = parse_binding("Ctrl++Space").expect_err("must fail");
+        assert!(error.to_string().contains("empty modifier token"));
+    }
+
+    #[test]
+    fn rejects_missing_key() {
+        let error = parse_binding("Ctrl+Shift").expect_err("must fail");
+        assert!(error.to_string().contains("must include a key token"));
+    }
+
+    #[test]
+    fn escape_alone_is_a_valid_binding() {
+        let parsed = parse_binding("Escape").expect("parse");
+        assert_eq!(parsed.key, Keysym::Escape);
+    }
+
+    #[test]
+    fn parses_digits_function_keys_arrows_and_punctuation() {
+        assert_eq!(parse_binding("Cmd+Shift+1").expect("parse").key, Keysym::Digit1);
+        assert_eq!(
+            parse_binding("Ctrl+Alt+F12").expect("parse").key,
+            Keysym::F12
+        );
+        assert_eq!(parse_binding("Ctrl+Up").expect("parse").key, Keysym::Up);
+        assert_eq!(
+            parse_binding("Ctrl+Backquote").expect("parse").key,
+            Keysym::Backquote
+        );
+        assert_eq!(parse_binding("Ctrl+`").expect("parse").key, Keysym::Backquote);
+        assert_eq!(parse_binding("Ctrl+/").expect("parse").key, Keysym::Slash);
+        assert_eq!(parse_binding("Ctrl+Enter").expect("parse").key, Keysym::Enter);
+    }
+
+    #[test]
+    fn validate_bindings_detects_chord_collision() {
+        let bindings = vec![
+            HotkeyBinding {
+                action: HotkeyAction::Toggle,
+                binding: "Ctrl+Shift+Space".to_owned(),
+            },
+            HotkeyBinding {
+                action: HotkeyAction::Cancel,
+                binding: "Shift+Ctrl+Space".to_owned(),
+            },
+        ];
+        let error = validate_bindings(&bindings).expect_err("must fail");
+        assert!(error.to_string().contains("collides with action"));
+    }
+
+    #[test]
+    fn validate_bindings_accepts_distinct_chords() {
+        let bindings = vec![
+            HotkeyBinding {
+                action: HotkeyAction::Toggle,
+                binding: "Ctrl+Shift+Space".to_owned(),
+            },
+            HotkeyBinding {
+                action: HotkeyAction::Cancel,
+                binding: "Escape".to_owned(),
+            },
+        ];
+        let parsed = validate_bindings(&bindings).expect("validate");
+        assert_eq!(parsed.len(), 2);
+    }
+
+    #[test]
+    fn validate_bindings_surfaces_first_parse_failure() {
+        let bindings = vec![HotkeyBinding {
+            action: HotkeyAction::Toggle,
+            binding: "Ctrl+Nope".to_owned(),
+        }];
+        let error = validate_bindings(&bindings).expect_err("must fail");
+        assert!(error.to_string().contains("unsupported hotkey token"));
+    }
+
+    #[test]
+    fn toggle_mode_is_always_valid() {
+        assert!(validate_hotkey_mode(HotkeyMode::Toggle).is_ok());
+    }
+
+    #[test]
+    fn push_to_talk_requires_a_release_capable_backend() {
+        let result = validate_hotkey_mode(HotkeyMode::PushToTalk);
+        assert_eq!(result.is_ok(), backend_supports_push_to_talk());
+        if !backend_supports_push_to_talk() {
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("key-release events"));
+        }
+    }
+}
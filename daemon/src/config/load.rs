@@ -1,37 +1,93 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use franken_whisper::BackendKind;
 
 use crate::bootstrap::AppPaths;
-use crate::config::schema::{AppConfig, OutputMode};
+use crate::config::hotkey::{validate_bindings, validate_hotkey_mode};
+use crate::config::schema::{
+    AppConfig, ClipboardProviderKind, ClipboardSelectionTarget, HotkeyAction, HotkeyBinding,
+    HotkeyMode, LogFormat, OutputMode,
+};
 use crate::error::{AppError, AppResult};
 
 #[derive(Debug, Clone, Default)]
 pub struct CliOverrides {
     pub config_path: Option<PathBuf>,
+    /// Selects a `[profiles.<name>]` table from the config file to
+    /// deep-merge over the base config before env/CLI overrides are
+    /// applied; see `apply_profile`.
+    pub profile: Option<String>,
     pub backend: Option<BackendKind>,
     pub model_id: Option<String>,
     pub language: Option<String>,
     pub timeout_seconds: Option<u64>,
     pub diarize: Option<bool>,
     pub translate: Option<bool>,
-    pub hotkey_binding: Option<String>,
+    pub hotkey_bindings: Option<Vec<HotkeyBinding>>,
+    pub hotkey_mode: Option<HotkeyMode>,
     pub output_mode: Option<OutputMode>,
+    pub clipboard_provider: Option<ClipboardProviderKind>,
+    pub selection_target: Option<ClipboardSelectionTarget>,
+    pub log_format: Option<LogFormat>,
 }
 
 pub fn load_config(paths: &AppPaths, overrides: &CliOverrides) -> AppResult<AppConfig> {
+    let config = load_config_without_validation(paths, overrides)?;
+    validate(&config)?;
+    Ok(config)
+}
+
+/// Loads and merges every config layer exactly like `load_config` (defaults,
+/// system config, user config and its `include`s, profile overlay, env, CLI),
+/// but skips the final `validate` call. Used by the `--check-config` CLI
+/// mode, which wants a config to inspect even when it's invalid so it can
+/// report every problem rather than bail out on the first.
+pub fn load_config_without_validation(
+    paths: &AppPaths,
+    overrides: &CliOverrides,
+) -> AppResult<AppConfig> {
     let config_path = overrides
         .config_path
         .clone()
         .unwrap_or_else(|| paths.config_file.clone());
 
+    let profile = overrides
+        .profile
+        .clone()
+        .or_else(|| std::env::var("QUEDO_PROFILE").ok())
+        .filter(|name| !name.trim().is_empty());
+
+    // Layers merge in increasing order of precedence: built-in defaults,
+    // then the read-only system config (if an administrator has dropped one
+    // at `paths.system_config_file`), then the per-user config file. Each
+    // layer's own `include = [...]` files are folded in first, relative to
+    // that layer's own path; see `load_layer`.
+    let mut document = toml::Value::try_from(AppConfig::default())?;
+
+    if paths.system_config_file.exists() {
+        let system_layer = load_layer(&paths.system_config_file, &mut Vec::new())?;
+        deep_merge_toml(&mut document, system_layer);
+    }
+
     let mut config = if config_path.exists() {
-        let raw = std::fs::read_to_string(&config_path)?;
-        toml::from_str::<AppConfig>(&raw)?
+        let user_layer = load_layer(&config_path, &mut Vec::new())?;
+        deep_merge_toml(&mut document, user_layer);
+
+        if let Some(profile) = &profile {
+            apply_profile(&mut document, profile)?;
+        }
+        document.try_into::<AppConfig>()?
     } else {
         let defaults = AppConfig::default();
         write_default_config(&config_path, &defaults)?;
-        defaults
+        if let Some(profile) = &profile {
+            return Err(AppError::Config(format!(
+                "profile \"{profile}\" not found in config"
+            )));
+        }
+        // No per-user file yet, but the system layer merged above (if any)
+        // still applies on top of the defaults just written to disk.
+        document.try_into::<AppConfig>()?
     };
 
     if config.history.db_path.is_none() {
@@ -41,7 +97,6 @@ pub fn load_config(paths: &AppPaths, overrides: &CliOverrides) -> AppResult<AppC
     apply_env_overrides(&mut config);
     apply_cli_overrides(&mut config, overrides);
 
-    validate(&config)?;
     Ok(config)
 }
 
@@ -65,20 +120,222 @@ fn write_default_config(path: &PathBuf, defaults: &AppConfig) -> AppResult<()> {
     Ok(())
 }
 
-fn validate(config: &AppConfig) -> AppResult<()> {
+/// Writes `threads`/`processors` into `[transcription]` of the user's config
+/// file at `config_file`, preserving every other key already there (parsed
+/// generically as a `toml::Value` rather than round-tripped through
+/// `AppConfig`, so keys this crate doesn't know about, like `[profiles.*]`
+/// overlays, survive). Creates a fresh document if `config_file` doesn't
+/// exist yet. Used by `calibration::calibrate` to make a chosen
+/// configuration durable across restarts instead of re-running the
+/// benchmark on every launch.
+pub fn persist_backend_params(config_file: &Path, threads: u32, processors: u32) -> AppResult<()> {
+    let mut document = if config_file.exists() {
+        std::fs::read_to_string(config_file)?.parse::<toml::Value>()?
+    } else {
+        toml::Value::Table(toml::value::Table::new())
+    };
+
+    let table = document.as_table_mut().ok_or_else(|| {
+        AppError::Config(format!("{} is not a TOML table", config_file.display()))
+    })?;
+    let transcription = table
+        .entry("transcription")
+        .or_insert_with(|| toml::Value::Table(toml::value::Table::new()));
+    let transcription_table = transcription
+        .as_table_mut()
+        .ok_or_else(|| AppError::Config("`transcription` is not a table".to_owned()))?;
+    transcription_table.insert("threads".to_owned(), toml::Value::Integer(threads.into()));
+    transcription_table.insert("processors".to_owned(), toml::Value::Integer(processors.into()));
+
+    if let Some(parent) = config_file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(config_file, toml::to_string_pretty(&document)?)?;
+
+    Ok(())
+}
+
+/// Loads `path` as a TOML document and folds in its own `include = [...]`
+/// array (paths resolved relative to `path`'s directory, each merged in
+/// listed order before `path`'s own keys so `path` always wins over
+/// whatever it includes). `visited` tracks the canonicalized path of every
+/// file still open along the current include chain, so an include cycle
+/// (e.g. `a.toml` including `b.toml` including `a.toml`) is rejected
+/// instead of recursing forever; this is the only place that can actually
+/// check for one, since by the time a document reaches `validate` its
+/// `include` arrays have already been consumed here.
+fn load_layer(path: &Path, visited: &mut Vec<PathBuf>) -> AppResult<toml::Value> {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if visited.contains(&canonical) {
+        return Err(AppError::Config(format!(
+            "config include cycle detected at `{}`",
+            path.display()
+        )));
+    }
+    visited.push(canonical);
+
+    let raw = std::fs::read_to_string(path)?;
+    let mut document: toml::Value = raw.parse()?;
+    let includes = take_includes(&mut document);
+    let base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    let mut merged = toml::Value::Table(toml::value::Table::new());
+    for include in includes {
+        let include_path = base_dir.join(&include);
+        if !include_path.exists() {
+            return Err(AppError::Config(format!(
+                "config include `{}` (from `{}`) does not exist",
+                include_path.display(),
+                path.display()
+            )));
+        }
+        let included = load_layer(&include_path, visited)?;
+        deep_merge_toml(&mut merged, included);
+    }
+    deep_merge_toml(&mut merged, document);
+
+    visited.pop();
+    Ok(merged)
+}
+
+/// Removes and returns the top-level `include` array (a list of paths
+/// relative to the including file) from `document`, if present, so it
+/// doesn't end up deserialized as an unknown `AppConfig` field.
+fn take_includes(document: &mut toml::Value) -> Vec<String> {
+    let Some(table) = document.as_table_mut() else {
+        return Vec::new();
+    };
+    table
+        .remove("include")
+        .and_then(|value| value.as_array().cloned())
+        .map(|items| {
+            items
+                .into_iter()
+                .filter_map(|item| item.as_str().map(str::to_owned))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Looks up `[profiles.<name>]` in the freshly-parsed config document and
+/// deep-merges it over the base document in place, so a profile's
+/// `[transcription]`/`[output]`/`[hotkey]` overrides win while everything it
+/// doesn't mention falls through to the base; see `deep_merge_toml`. Errors
+/// if `name` doesn't match any table under `[profiles]`.
+fn apply_profile(document: &mut toml::Value, name: &str) -> AppResult<()> {
+    let profile_table = document
+        .get("profiles")
+        .and_then(|profiles| profiles.get(name))
+        .cloned()
+        .ok_or_else(|| AppError::Config(format!("profile \"{name}\" not found in config")))?;
+
+    deep_merge_toml(document, profile_table);
+    Ok(())
+}
+
+/// Merges `overlay` onto `base` in place: tables are merged key by key,
+/// recursing into nested tables; any other value (scalar, array, or a table
+/// overlaid onto a non-table) replaces the base value outright.
+fn deep_merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base_table), toml::Value::Table(overlay_table)) => {
+            for (key, value) in overlay_table {
+                match base_table.get_mut(&key) {
+                    Some(existing) => deep_merge_toml(existing, value),
+                    None => {
+                        base_table.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base_slot, overlay_value) => {
+            *base_slot = overlay_value;
+        }
+    }
+}
+
+/// Runs every config-shape validation rule and collects every problem it
+/// finds, instead of stopping at the first one like `validate` does. Used by
+/// the `--check-config` CLI mode so a user can fix every mistake in their
+/// config in one pass rather than one `quedo-daemon` invocation per mistake.
+pub(crate) fn collect_validation_problems(config: &AppConfig) -> Vec<String> {
+    let mut problems = Vec::new();
+
     if config.transcription.timeout_seconds == 0 {
-        return Err(AppError::Config(
-            "transcription.timeout_seconds must be > 0".to_owned(),
-        ));
+        problems.push("transcription.timeout_seconds must be > 0".to_owned());
     }
 
     if config.audio.max_recording_seconds == 0 {
-        return Err(AppError::Config(
-            "audio.max_recording_seconds must be > 0".to_owned(),
+        problems.push("audio.max_recording_seconds must be > 0".to_owned());
+    }
+
+    if config.transcription.worker_count == 0 {
+        problems.push("transcription.worker_count must be > 0".to_owned());
+    }
+
+    if config.transcription.max_queued_jobs == 0 {
+        problems.push("transcription.max_queued_jobs must be > 0".to_owned());
+    }
+
+    if let Err(error) = validate_bindings(&config.hotkey.bindings) {
+        problems.push(error.to_string());
+    }
+    if let Err(error) = validate_hotkey_mode(config.hotkey.mode) {
+        problems.push(error.to_string());
+    }
+    if !crate::output::clipboard::supports_target(
+        config.output.clipboard_provider,
+        config.output.selection_target,
+    ) {
+        problems.push(format!(
+            "output.selection_target = \"primary\" requires output.clipboard_provider to be \
+             \"xclip\" or \"xsel\" (the only backends that support a PRIMARY selection \
+             distinct from CLIPBOARD), but it is set to {:?}",
+            config.output.clipboard_provider
         ));
     }
+    if config.output.mode == OutputMode::Command
+        && config
+            .output
+            .command_template
+            .as_deref()
+            .map_or(true, str::is_empty)
+    {
+        problems.push(
+            "output.mode is \"command\" but output.command_template is not set".to_owned(),
+        );
+    }
+    if let Some(addr) = &config.service.control_tcp_addr {
+        match addr.parse::<std::net::SocketAddr>() {
+            Ok(parsed) if !parsed.ip().is_loopback() => {
+                problems.push(format!(
+                    "service.control_tcp_addr {addr} is not a loopback address; the control \
+                     socket protocol has no authentication, so binding it to a non-loopback \
+                     address would expose an unauthenticated remote control plane"
+                ));
+            }
+            Ok(_) => {}
+            Err(error) => {
+                problems.push(format!("service.control_tcp_addr {addr} is invalid: {error}"));
+            }
+        }
+    }
+    if let Err(error) = crate::config::context::validate_overrides(&config.overrides) {
+        problems.push(error.to_string());
+    }
+    #[cfg(feature = "scripting")]
+    if let Err(error) = crate::scripting::validate_config(&config.scripting) {
+        problems.push(error.to_string());
+    }
 
-    Ok(())
+    problems
+}
+
+fn validate(config: &AppConfig) -> AppResult<()> {
+    match collect_validation_problems(config).into_iter().next() {
+        Some(problem) => Err(AppError::Config(problem)),
+        None => Ok(()),
+    }
 }
 
 fn apply_env_overrides(config: &mut AppConfig) {
@@ -121,8 +378,23 @@ fn apply_env_overrides(config: &mut AppConfig) {
             config.output.mode = parsed;
         }
     }
+    if let Ok(value) = std::env::var("QUEDO_CLIPBOARD_PROVIDER") {
+        if let Some(parsed) = parse_clipboard_provider(&value) {
+            config.output.clipboard_provider = parsed;
+        }
+    }
+    if let Ok(value) = std::env::var("QUEDO_SELECTION_TARGET") {
+        if let Some(parsed) = parse_selection_target(&value) {
+            config.output.selection_target = parsed;
+        }
+    }
     if let Ok(value) = std::env::var("QUEDO_HOTKEY_BINDING") {
-        config.hotkey.binding = value;
+        set_toggle_binding(&mut config.hotkey.bindings, value);
+    }
+    if let Ok(value) = std::env::var("QUEDO_HOTKEY_MODE") {
+        if let Some(parsed) = parse_hotkey_mode(&value) {
+            config.hotkey.mode = parsed;
+        }
     }
     if let Ok(value) = std::env::var("QUEDO_HISTORY_DB_PATH") {
         if !value.trim().is_empty() {
@@ -134,9 +406,24 @@ fn apply_env_overrides(config: &mut AppConfig) {
             config.service.autostart_enabled = parsed;
         }
     }
+    if let Ok(value) = std::env::var("QUEDO_CONTROL_TCP_ADDR") {
+        if !value.trim().is_empty() {
+            config.service.control_tcp_addr = Some(value);
+        }
+    }
     if let Ok(value) = std::env::var("QUEDO_LOG_LEVEL") {
         config.diagnostics.log_level = value;
     }
+    if let Ok(value) = std::env::var("QUEDO_LOG_FORMAT") {
+        if let Some(parsed) = parse_log_format(&value) {
+            config.diagnostics.log_format = parsed;
+        }
+    }
+    if let Ok(value) = std::env::var("QUEDO_EMIT_EVENTS") {
+        if let Some(parsed) = parse_bool(&value) {
+            config.diagnostics.emit_events = parsed;
+        }
+    }
     if let Ok(value) = std::env::var("QUEDO_MAX_RECORDING_SECONDS") {
         if let Ok(parsed) = value.parse::<u32>() {
             config.audio.max_recording_seconds = parsed;
@@ -163,12 +450,49 @@ fn apply_cli_overrides(config: &mut AppConfig, overrides: &CliOverrides) {
     if let Some(value) = overrides.translate {
         config.transcription.translate = value;
     }
-    if let Some(value) = &overrides.hotkey_binding {
-        config.hotkey.binding = value.clone();
+    if let Some(bindings) = &overrides.hotkey_bindings {
+        for override_binding in bindings {
+            merge_binding(&mut config.hotkey.bindings, override_binding.clone());
+        }
+    }
+    if let Some(value) = overrides.hotkey_mode {
+        config.hotkey.mode = value;
     }
     if let Some(value) = &overrides.output_mode {
         config.output.mode = value.clone();
     }
+    if let Some(value) = overrides.clipboard_provider {
+        config.output.clipboard_provider = value;
+    }
+    if let Some(value) = overrides.selection_target {
+        config.output.selection_target = value;
+    }
+    if let Some(value) = overrides.log_format {
+        config.diagnostics.log_format = value;
+    }
+}
+
+/// Sets the `toggle` action's binding, preserving backward-compatible
+/// single-binding overrides (`QUEDO_HOTKEY_BINDING`, `--hotkey-binding`)
+/// against the now-multi-binding `hotkey.bindings` list.
+fn set_toggle_binding(bindings: &mut Vec<HotkeyBinding>, value: String) {
+    merge_binding(
+        bindings,
+        HotkeyBinding {
+            action: HotkeyAction::Toggle,
+            binding: value,
+        },
+    );
+}
+
+pub(crate) fn merge_binding(bindings: &mut Vec<HotkeyBinding>, override_binding: HotkeyBinding) {
+    match bindings
+        .iter_mut()
+        .find(|existing| existing.action == override_binding.action)
+    {
+        Some(existing) => existing.binding = override_binding.binding,
+        None => bindings.push(override_binding),
+    }
 }
 
 fn parse_bool(value: &str) -> Option<bool> {
@@ -189,22 +513,68 @@ fn parse_backend_kind(value: &str) -> Option<BackendKind> {
     }
 }
 
+fn parse_hotkey_mode(value: &str) -> Option<HotkeyMode> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "toggle" => Some(HotkeyMode::Toggle),
+        "push_to_talk" | "push-to-talk" | "ptt" => Some(HotkeyMode::PushToTalk),
+        _ => None,
+    }
+}
+
 fn parse_output_mode(value: &str) -> Option<OutputMode> {
     match value.trim().to_ascii_lowercase().as_str() {
         "clipboard_only" | "clipboard-only" => Some(OutputMode::ClipboardOnly),
+        "type" | "inject" | "keyboard" | "type_text" | "type-text" => Some(OutputMode::TypeText),
+        "command" | "exec" => Some(OutputMode::Command),
         "disabled" | "none" => Some(OutputMode::Disabled),
         _ => None,
     }
 }
 
+fn parse_log_format(value: &str) -> Option<LogFormat> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "text" | "plain" => Some(LogFormat::Text),
+        "json" => Some(LogFormat::Json),
+        _ => None,
+    }
+}
+
+fn parse_clipboard_provider(value: &str) -> Option<ClipboardProviderKind> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "auto" => Some(ClipboardProviderKind::Auto),
+        "wayland" | "wl-copy" | "wl_copy" | "wl-clipboard" | "wl_clipboard" => {
+            Some(ClipboardProviderKind::Wayland)
+        }
+        "xclip" => Some(ClipboardProviderKind::Xclip),
+        "xsel" => Some(ClipboardProviderKind::Xsel),
+        "macos" | "pbcopy" => Some(ClipboardProviderKind::Macos),
+        "windows" | "win32yank" => Some(ClipboardProviderKind::Windows),
+        "arboard" => Some(ClipboardProviderKind::Arboard),
+        "internal" | "in-memory" | "in_memory" => Some(ClipboardProviderKind::Internal),
+        _ => None,
+    }
+}
+
+fn parse_selection_target(value: &str) -> Option<ClipboardSelectionTarget> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "clipboard" => Some(ClipboardSelectionTarget::Clipboard),
+        "primary" => Some(ClipboardSelectionTarget::Primary),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        apply_cli_overrides, apply_env_overrides, load_config, parse_backend_kind, parse_bool,
-        parse_output_mode, validate, CliOverrides,
+        apply_cli_overrides, apply_env_overrides, collect_validation_problems, load_config,
+        parse_backend_kind, parse_bool, parse_clipboard_provider, parse_hotkey_mode,
+        parse_output_mode, parse_selection_target, validate, CliOverrides,
     };
     use crate::bootstrap::paths::AppPaths;
-    use crate::config::schema::{AppConfig, OutputMode};
+    use crate::config::schema::{
+        AppConfig, ClipboardProviderKind, ClipboardSelectionTarget, ContextOverride, HotkeyAction,
+        HotkeyBinding, HotkeyMode, OutputMode,
+    };
     use crate::error::AppError;
     use franken_whisper::BackendKind;
     use std::path::{Path, PathBuf};
@@ -238,6 +608,16 @@ mod tests {
         }
     }
 
+    fn toggle_binding(config: &AppConfig) -> &str {
+        config
+            .hotkey
+            .bindings
+            .iter()
+            .find(|binding| binding.action == HotkeyAction::Toggle)
+            .map(|binding| binding.binding.as_str())
+            .expect("toggle binding present")
+    }
+
     fn paths_for(root: &Path) -> AppPaths {
         AppPaths {
             config_dir: root.join("config"),
@@ -248,6 +628,8 @@ mod tests {
             config_file: root.join("config/config.toml"),
             history_db: root.join("data/history.sqlite3"),
             autostart_file: root.join("autostart/quedo-daemon.desktop"),
+            ipc_socket: root.join("cache/quedo.sock"),
+            system_config_file: root.join("system-config.toml"),
         }
     }
 
@@ -260,11 +642,17 @@ mod tests {
             "QUEDO_DIARIZE",
             "QUEDO_TIMEOUT_SECONDS",
             "QUEDO_OUTPUT_MODE",
+            "QUEDO_CLIPBOARD_PROVIDER",
             "QUEDO_HOTKEY_BINDING",
+            "QUEDO_HOTKEY_MODE",
             "QUEDO_HISTORY_DB_PATH",
             "QUEDO_AUTOSTART_ENABLED",
+            "QUEDO_CONTROL_TCP_ADDR",
             "QUEDO_LOG_LEVEL",
+            "QUEDO_LOG_FORMAT",
+            "QUEDO_EMIT_EVENTS",
             "QUEDO_MAX_RECORDING_SECONDS",
+            "QUEDO_PROFILE",
         ]
         .iter()
         .map(|key| EnvVarGuard::clear(key))
@@ -328,6 +716,268 @@ mode = "disabled"
         assert_eq!(config.output.mode, OutputMode::ClipboardOnly);
     }
 
+    #[test]
+    fn profile_overlays_base_config_and_preserves_env_cli_precedence() {
+        let _guard = crate::test_support::lock_env();
+        let _clean = clear_quedo_env();
+        let tmp = tempfile::TempDir::new().expect("tempdir");
+        let paths = paths_for(tmp.path());
+        paths.ensure_dirs().expect("dirs");
+        let config_toml = r#"
+[transcription]
+backend = "auto"
+model_id = "from_base"
+timeout_seconds = 11
+language = "de"
+
+[output]
+mode = "disabled"
+
+[profiles.work]
+[profiles.work.transcription]
+model_id = "from_profile"
+timeout_seconds = 22
+
+[profiles.dictation]
+[profiles.dictation.output]
+mode = "type_text"
+"#;
+        std::fs::write(&paths.config_file, config_toml).expect("write config");
+
+        let overrides = CliOverrides {
+            profile: Some("work".to_owned()),
+            ..CliOverrides::default()
+        };
+        let config = load_config(&paths, &overrides).expect("load config");
+        assert_eq!(config.transcription.model_id.as_deref(), Some("from_profile"));
+        assert_eq!(config.transcription.timeout_seconds, 22);
+        // Unmentioned base keys fall through untouched.
+        assert_eq!(config.transcription.language.as_deref(), Some("de"));
+        assert_eq!(config.output.mode, OutputMode::Disabled);
+
+        let _timeout = EnvVarGuard::set("QUEDO_TIMEOUT_SECONDS", "33");
+        let cli_overrides = CliOverrides {
+            profile: Some("work".to_owned()),
+            model_id: Some("from_cli".to_owned()),
+            ..CliOverrides::default()
+        };
+        let config = load_config(&paths, &cli_overrides).expect("load config");
+        assert_eq!(config.transcription.model_id.as_deref(), Some("from_cli"));
+        assert_eq!(config.transcription.timeout_seconds, 33);
+    }
+
+    #[test]
+    fn profile_selected_via_env_var() {
+        let _guard = crate::test_support::lock_env();
+        let _clean = clear_quedo_env();
+        let tmp = tempfile::TempDir::new().expect("tempdir");
+        let paths = paths_for(tmp.path());
+        paths.ensure_dirs().expect("dirs");
+        std::fs::write(
+            &paths.config_file,
+            r#"
+[profiles.dictation]
+[profiles.dictation.output]
+mode = "type_text"
+"#,
+        )
+        .expect("write config");
+
+        let _profile = EnvVarGuard::set("QUEDO_PROFILE", "dictation");
+        let config = load_config(&paths, &CliOverrides::default()).expect("load config");
+        assert_eq!(config.output.mode, OutputMode::TypeText);
+    }
+
+    #[test]
+    fn unknown_profile_fails_to_load() {
+        let _guard = crate::test_support::lock_env();
+        let _clean = clear_quedo_env();
+        let tmp = tempfile::TempDir::new().expect("tempdir");
+        let paths = paths_for(tmp.path());
+        paths.ensure_dirs().expect("dirs");
+        std::fs::write(
+            &paths.config_file,
+            r#"
+[profiles.work]
+[profiles.work.transcription]
+timeout_seconds = 22
+"#,
+        )
+        .expect("write config");
+
+        let overrides = CliOverrides {
+            profile: Some("does-not-exist".to_owned()),
+            ..CliOverrides::default()
+        };
+        let error = load_config(&paths, &overrides).expect_err("must fail");
+        assert!(matches!(error, AppError::Config(message) if message.contains("does-not-exist")));
+    }
+
+    #[test]
+    fn unknown_profile_fails_even_without_a_config_file() {
+        let _guard = crate::test_support::lock_env();
+        let _clean = clear_quedo_env();
+        let tmp = tempfile::TempDir::new().expect("tempdir");
+        let paths = paths_for(tmp.path());
+        paths.ensure_dirs().expect("dirs");
+        assert!(!paths.config_file.exists());
+
+        let overrides = CliOverrides {
+            profile: Some("work".to_owned()),
+            ..CliOverrides::default()
+        };
+        let error = load_config(&paths, &overrides).expect_err("must fail");
+        assert!(matches!(error, AppError::Config(message) if message.contains("work")));
+    }
+
+    #[test]
+    fn system_config_layer_is_overridden_by_user_config() {
+        let _guard = crate::test_support::lock_env();
+        let _clean = clear_quedo_env();
+        let tmp = tempfile::TempDir::new().expect("tempdir");
+        let mut paths = paths_for(tmp.path());
+        paths.system_config_file = tmp.path().join("system-config.toml");
+        paths.ensure_dirs().expect("dirs");
+
+        std::fs::write(
+            &paths.system_config_file,
+            r#"
+[transcription]
+model_id = "from_system"
+timeout_seconds = 11
+"#,
+        )
+        .expect("write system config");
+        std::fs::write(
+            &paths.config_file,
+            r#"
+[transcription]
+model_id = "from_user"
+"#,
+        )
+        .expect("write user config");
+
+        let config = load_config(&paths, &CliOverrides::default()).expect("load config");
+        // The user config wins on keys it sets...
+        assert_eq!(config.transcription.model_id.as_deref(), Some("from_user"));
+        // ...but the system layer still fills in whatever the user didn't mention.
+        assert_eq!(config.transcription.timeout_seconds, 11);
+    }
+
+    #[test]
+    fn system_config_applies_even_without_a_user_config_file() {
+        let _guard = crate::test_support::lock_env();
+        let _clean = clear_quedo_env();
+        let tmp = tempfile::TempDir::new().expect("tempdir");
+        let mut paths = paths_for(tmp.path());
+        paths.system_config_file = tmp.path().join("system-config.toml");
+        paths.ensure_dirs().expect("dirs");
+
+        std::fs::write(
+            &paths.system_config_file,
+            r#"
+[transcription]
+timeout_seconds = 42
+"#,
+        )
+        .expect("write system config");
+        assert!(!paths.config_file.exists());
+
+        let config = load_config(&paths, &CliOverrides::default()).expect("load config");
+        assert_eq!(config.transcription.timeout_seconds, 42);
+        // A starter user config is still written, untouched by the system layer.
+        assert!(paths.config_file.exists());
+        let written = std::fs::read_to_string(&paths.config_file).expect("read written config");
+        assert!(!written.contains("42"));
+    }
+
+    #[test]
+    fn include_directive_merges_relative_to_including_file() {
+        let _guard = crate::test_support::lock_env();
+        let _clean = clear_quedo_env();
+        let tmp = tempfile::TempDir::new().expect("tempdir");
+        let paths = paths_for(tmp.path());
+        paths.ensure_dirs().expect("dirs");
+
+        std::fs::write(
+            paths.config_dir.join("shared.toml"),
+            r#"
+[transcription]
+model_id = "from_include"
+language = "de"
+"#,
+        )
+        .expect("write include");
+        std::fs::write(
+            &paths.config_file,
+            r#"
+include = ["shared.toml"]
+
+[transcription]
+language = "en"
+"#,
+        )
+        .expect("write config");
+
+        let config = load_config(&paths, &CliOverrides::default()).expect("load config");
+        assert_eq!(config.transcription.model_id.as_deref(), Some("from_include"));
+        // The including file's own keys still win over whatever it includes.
+        assert_eq!(config.transcription.language.as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn missing_include_file_is_rejected() {
+        let _guard = crate::test_support::lock_env();
+        let _clean = clear_quedo_env();
+        let tmp = tempfile::TempDir::new().expect("tempdir");
+        let paths = paths_for(tmp.path());
+        paths.ensure_dirs().expect("dirs");
+
+        std::fs::write(&paths.config_file, r#"include = ["does-not-exist.toml"]"#)
+            .expect("write config");
+
+        let error = load_config(&paths, &CliOverrides::default()).expect_err("must fail");
+        assert!(
+            matches!(error, AppError::Config(message) if message.contains("does-not-exist.toml"))
+        );
+    }
+
+    #[test]
+    fn include_cycle_is_rejected() {
+        let _guard = crate::test_support::lock_env();
+        let _clean = clear_quedo_env();
+        let tmp = tempfile::TempDir::new().expect("tempdir");
+        let paths = paths_for(tmp.path());
+        paths.ensure_dirs().expect("dirs");
+
+        std::fs::write(
+            paths.config_dir.join("a.toml"),
+            r#"include = ["b.toml"]"#,
+        )
+        .expect("write a.toml");
+        std::fs::write(
+            &paths.config_file,
+            r#"include = ["a.toml"]"#,
+        )
+        .expect("write config");
+        // a.toml includes b.toml, which includes the user config right back.
+        std::fs::write(
+            paths.config_dir.join("b.toml"),
+            format!(
+                r#"include = ["{}"]"#,
+                paths
+                    .config_file
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .expect("utf8 filename")
+            ),
+        )
+        .expect("write b.toml");
+
+        let error = load_config(&paths, &CliOverrides::default()).expect_err("must fail");
+        assert!(matches!(error, AppError::Config(message) if message.contains("cycle")));
+    }
+
     #[test]
     fn validate_rejects_zero_timeout_and_max_recording() {
         let mut config = AppConfig::default();
@@ -339,6 +989,44 @@ mode = "disabled"
         assert!(
             matches!(validate(&config), Err(AppError::Config(message)) if message.contains("max_recording_seconds"))
         );
+
+        config.audio.max_recording_seconds = 1;
+        config.transcription.worker_count = 0;
+        assert!(
+            matches!(validate(&config), Err(AppError::Config(message)) if message.contains("worker_count"))
+        );
+
+        config.transcription.worker_count = 1;
+        config.transcription.max_queued_jobs = 0;
+        assert!(
+            matches!(validate(&config), Err(AppError::Config(message)) if message.contains("max_queued_jobs"))
+        );
+    }
+
+    #[test]
+    fn collect_validation_problems_reports_every_problem_not_just_the_first() {
+        let mut config = AppConfig::default();
+        config.transcription.timeout_seconds = 0;
+        config.audio.max_recording_seconds = 0;
+        config.hotkey.bindings = vec![HotkeyBinding {
+            action: HotkeyAction::Toggle,
+            binding: "Ctrl+Nope".to_owned(),
+        }];
+
+        let problems = collect_validation_problems(&config);
+        assert!(problems.iter().any(|p| p.contains("timeout_seconds")));
+        assert!(problems.iter().any(|p| p.contains("max_recording_seconds")));
+        assert!(problems.iter().any(|p| p.contains("unsupported hotkey token")));
+        // validate() only ever surfaces the first of these.
+        assert!(matches!(
+            validate(&config),
+            Err(AppError::Config(message)) if message.contains("timeout_seconds")
+        ));
+    }
+
+    #[test]
+    fn collect_validation_problems_is_empty_for_the_default_config() {
+        assert!(collect_validation_problems(&AppConfig::default()).is_empty());
     }
 
     #[test]
@@ -359,7 +1047,7 @@ timeout_seconds = 99
         let config = load_config(&paths, &CliOverrides::default()).expect("load");
         assert_eq!(config.transcription.timeout_seconds, 99);
         assert_eq!(config.output.mode, OutputMode::ClipboardOnly);
-        assert_eq!(config.hotkey.binding, "Ctrl+Shift+Space");
+        assert_eq!(toggle_binding(&config), "Ctrl+Shift+Space");
     }
 
     #[test]
@@ -430,9 +1118,72 @@ timeout_seconds = "abc"
         assert_eq!(parse_output_mode("clipboard-only"), Some(OutputMode::ClipboardOnly));
         assert_eq!(parse_output_mode("disabled"), Some(OutputMode::Disabled));
         assert_eq!(parse_output_mode("none"), Some(OutputMode::Disabled));
+        assert_eq!(parse_output_mode("type"), Some(OutputMode::TypeText));
+        assert_eq!(parse_output_mode("inject"), Some(OutputMode::TypeText));
+        assert_eq!(parse_output_mode("command"), Some(OutputMode::Command));
+        assert_eq!(parse_output_mode("exec"), Some(OutputMode::Command));
         assert_eq!(parse_output_mode("other"), None);
     }
 
+    #[test]
+    fn log_format_parser_supports_aliases() {
+        assert_eq!(parse_log_format("text"), Some(LogFormat::Text));
+        assert_eq!(parse_log_format("plain"), Some(LogFormat::Text));
+        assert_eq!(parse_log_format("json"), Some(LogFormat::Json));
+        assert_eq!(parse_log_format("JSON"), Some(LogFormat::Json));
+        assert_eq!(parse_log_format("other"), None);
+    }
+
+    #[test]
+    fn clipboard_provider_parser_supports_aliases() {
+        assert_eq!(
+            parse_clipboard_provider("auto"),
+            Some(ClipboardProviderKind::Auto)
+        );
+        assert_eq!(
+            parse_clipboard_provider("wl-copy"),
+            Some(ClipboardProviderKind::Wayland)
+        );
+        assert_eq!(
+            parse_clipboard_provider("xclip"),
+            Some(ClipboardProviderKind::Xclip)
+        );
+        assert_eq!(
+            parse_clipboard_provider("xsel"),
+            Some(ClipboardProviderKind::Xsel)
+        );
+        assert_eq!(
+            parse_clipboard_provider("pbcopy"),
+            Some(ClipboardProviderKind::Macos)
+        );
+        assert_eq!(
+            parse_clipboard_provider("win32yank"),
+            Some(ClipboardProviderKind::Windows)
+        );
+        assert_eq!(
+            parse_clipboard_provider("internal"),
+            Some(ClipboardProviderKind::Internal)
+        );
+        assert_eq!(
+            parse_clipboard_provider("arboard"),
+            Some(ClipboardProviderKind::Arboard)
+        );
+        assert_eq!(parse_clipboard_provider("nope"), None);
+    }
+
+    #[test]
+    fn selection_target_parser_supports_aliases() {
+        assert_eq!(
+            parse_selection_target("clipboard"),
+            Some(ClipboardSelectionTarget::Clipboard)
+        );
+        assert_eq!(
+            parse_selection_target("primary"),
+            Some(ClipboardSelectionTarget::Primary)
+        );
+        assert_eq!(parse_selection_target("nope"), None);
+    }
+
     #[test]
     fn env_overrides_update_fields() {
         let _guard = crate::test_support::lock_env();
@@ -444,10 +1195,16 @@ timeout_seconds = "abc"
         let _diarize = EnvVarGuard::set("QUEDO_DIARIZE", "true");
         let _timeout = EnvVarGuard::set("QUEDO_TIMEOUT_SECONDS", "77");
         let _output = EnvVarGuard::set("QUEDO_OUTPUT_MODE", "disabled");
+        let _clipboard = EnvVarGuard::set("QUEDO_CLIPBOARD_PROVIDER", "xclip");
+        let _selection_target = EnvVarGuard::set("QUEDO_SELECTION_TARGET", "primary");
         let _hotkey = EnvVarGuard::set("QUEDO_HOTKEY_BINDING", "Ctrl+Alt+Q");
+        let _hotkey_mode = EnvVarGuard::set("QUEDO_HOTKEY_MODE", "push_to_talk");
         let _history = EnvVarGuard::set("QUEDO_HISTORY_DB_PATH", "/tmp/h.sqlite3");
         let _autostart = EnvVarGuard::set("QUEDO_AUTOSTART_ENABLED", "1");
+        let _control_tcp = EnvVarGuard::set("QUEDO_CONTROL_TCP_ADDR", "127.0.0.1:4756");
         let _log = EnvVarGuard::set("QUEDO_LOG_LEVEL", "debug");
+        let _log_format = EnvVarGuard::set("QUEDO_LOG_FORMAT", "json");
+        let _emit_events = EnvVarGuard::set("QUEDO_EMIT_EVENTS", "1");
         let _max = EnvVarGuard::set("QUEDO_MAX_RECORDING_SECONDS", "123");
 
         let mut config = AppConfig::default();
@@ -459,13 +1216,25 @@ timeout_seconds = "abc"
         assert!(config.transcription.diarize);
         assert_eq!(config.transcription.timeout_seconds, 77);
         assert_eq!(config.output.mode, OutputMode::Disabled);
-        assert_eq!(config.hotkey.binding, "Ctrl+Alt+Q");
+        assert_eq!(config.output.clipboard_provider, ClipboardProviderKind::Xclip);
+        assert_eq!(
+            config.output.selection_target,
+            ClipboardSelectionTarget::Primary
+        );
+        assert_eq!(toggle_binding(&config), "Ctrl+Alt+Q");
+        assert_eq!(config.hotkey.mode, HotkeyMode::PushToTalk);
         assert_eq!(
             config.history.db_path.as_ref(),
             Some(&PathBuf::from("/tmp/h.sqlite3"))
         );
         assert!(config.service.autostart_enabled);
+        assert_eq!(
+            config.service.control_tcp_addr.as_deref(),
+            Some("127.0.0.1:4756")
+        );
         assert_eq!(config.diagnostics.log_level, "debug");
+        assert_eq!(config.diagnostics.log_format, LogFormat::Json);
+        assert!(config.diagnostics.emit_events);
         assert_eq!(config.audio.max_recording_seconds, 123);
     }
 
@@ -479,8 +1248,14 @@ timeout_seconds = "abc"
             timeout_seconds: Some(66),
             diarize: Some(true),
             translate: Some(true),
-            hotkey_binding: Some("Ctrl+Shift+R".to_owned()),
+            hotkey_bindings: Some(vec![HotkeyBinding {
+                action: HotkeyAction::Toggle,
+                binding: "Ctrl+Shift+R".to_owned(),
+            }]),
+            hotkey_mode: Some(HotkeyMode::PushToTalk),
             output_mode: Some(OutputMode::Disabled),
+            clipboard_provider: Some(ClipboardProviderKind::Internal),
+            selection_target: Some(ClipboardSelectionTarget::Primary),
             ..CliOverrides::default()
         };
         apply_cli_overrides(&mut config, &overrides);
@@ -490,7 +1265,122 @@ timeout_seconds = "abc"
         assert_eq!(config.transcription.timeout_seconds, 66);
         assert!(config.transcription.diarize);
         assert!(config.transcription.translate);
-        assert_eq!(config.hotkey.binding, "Ctrl+Shift+R");
+        assert_eq!(toggle_binding(&config), "Ctrl+Shift+R");
+        assert_eq!(config.hotkey.mode, HotkeyMode::PushToTalk);
         assert_eq!(config.output.mode, OutputMode::Disabled);
+        assert_eq!(config.output.clipboard_provider, ClipboardProviderKind::Internal);
+        assert_eq!(
+            config.output.selection_target,
+            ClipboardSelectionTarget::Primary
+        );
+    }
+
+    #[test]
+    fn validate_rejects_invalid_override_pattern() {
+        let mut config = AppConfig::default();
+        config.overrides.push(ContextOverride {
+            match_pattern: "(unclosed".to_owned(),
+            ..ContextOverride::default()
+        });
+        assert!(
+            matches!(validate(&config), Err(AppError::Config(message)) if message.contains("invalid match pattern"))
+        );
+    }
+
+    #[test]
+    fn validate_rejects_unparseable_hotkey_binding() {
+        let mut config = AppConfig::default();
+        config.hotkey.bindings[0].binding = "Ctrl+Nope".to_owned();
+        assert!(
+            matches!(validate(&config), Err(AppError::Config(message)) if message.contains("unsupported hotkey token"))
+        );
+    }
+
+    #[test]
+    fn validate_rejects_colliding_hotkey_bindings() {
+        let mut config = AppConfig::default();
+        config.hotkey.bindings.push(HotkeyBinding {
+            action: HotkeyAction::Cancel,
+            binding: "Ctrl+Shift+Space".to_owned(),
+        });
+        assert!(
+            matches!(validate(&config), Err(AppError::Config(message)) if message.contains("collides with action"))
+        );
+    }
+
+    #[test]
+    fn validate_rejects_push_to_talk_on_unsupported_backend() {
+        let mut config = AppConfig::default();
+        config.hotkey.mode = HotkeyMode::PushToTalk;
+        let result = validate(&config);
+        if crate::config::hotkey::backend_supports_push_to_talk() {
+            assert!(result.is_ok());
+        } else {
+            assert!(
+                matches!(result, Err(AppError::Config(message)) if message.contains("key-release events"))
+            );
+        }
+    }
+
+    #[test]
+    fn validate_rejects_primary_selection_on_unsupported_clipboard_provider() {
+        let mut config = AppConfig::default();
+        config.output.clipboard_provider = ClipboardProviderKind::Wayland;
+        config.output.selection_target = ClipboardSelectionTarget::Primary;
+        let result = validate(&config);
+        assert!(
+            matches!(result, Err(AppError::Config(message)) if message.contains("selection_target"))
+        );
+
+        config.output.clipboard_provider = ClipboardProviderKind::Xclip;
+        assert!(validate(&config).is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_command_mode_without_a_template() {
+        let mut config = AppConfig::default();
+        config.output.mode = OutputMode::Command;
+        let result = validate(&config);
+        assert!(
+            matches!(result, Err(AppError::Config(message)) if message.contains("command_template"))
+        );
+
+        config.output.command_template = Some("notify-send {transcript}".to_owned());
+        assert!(validate(&config).is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_non_loopback_control_tcp_addr() {
+        let mut config = AppConfig::default();
+        config.service.control_tcp_addr = Some("0.0.0.0:4756".to_owned());
+        assert!(
+            matches!(validate(&config), Err(AppError::Config(message)) if message.contains("loopback"))
+        );
+
+        config.service.control_tcp_addr = Some("not an addr".to_owned());
+        assert!(
+            matches!(validate(&config), Err(AppError::Config(message)) if message.contains("is invalid"))
+        );
+
+        config.service.control_tcp_addr = Some("127.0.0.1:4756".to_owned());
+        assert!(validate(&config).is_ok());
+
+        config.service.control_tcp_addr = Some("[::1]:4756".to_owned());
+        assert!(validate(&config).is_ok());
+    }
+
+    #[test]
+    fn hotkey_mode_parser_supports_aliases() {
+        assert_eq!(parse_hotkey_mode("toggle"), Some(HotkeyMode::Toggle));
+        assert_eq!(
+            parse_hotkey_mode("push_to_talk"),
+            Some(HotkeyMode::PushToTalk)
+        );
+        assert_eq!(
+            parse_hotkey_mode("push-to-talk"),
+            Some(HotkeyMode::PushToTalk)
+        );
+        assert_eq!(parse_hotkey_mode("ptt"), Some(HotkeyMode::PushToTalk));
+        assert_eq!(parse_hotkey_mode("nope"), None);
     }
 }
@@ -1,5 +1,17 @@
+pub mod context;
+pub mod hotkey;
 pub mod load;
 pub mod schema;
 
-pub use load::{load_config, CliOverrides};
-pub use schema::{AppConfig, OutputMode, TranscriptionConfig};
+pub use context::resolve_for_context;
+pub use hotkey::{
+    backend_supports_push_to_talk, parse_binding, validate_bindings, validate_hotkey_mode, Keysym,
+    Modifier, ModifierSet, ParsedBinding,
+};
+pub use load::{load_config, load_config_without_validation, persist_backend_params, CliOverrides};
+pub use schema::{
+    AppConfig, BusyUpdatePolicy, ClipboardProviderKind, ClipboardSelectionTarget, ContextOverride,
+    HistoryConfig, HotkeyAction, HotkeyBinding, HotkeyConfig, HotkeyMode, LogFormat,
+    NetworkStreamingConfig, OutputMode, RetentionPolicy, ScriptingConfig, StreamingStability,
+    TranscriptionConfig, VocabularyFilter, VocabularyFilterMethod,
+};
@@ -3,6 +3,8 @@ use std::path::PathBuf;
 use franken_whisper::BackendKind;
 use serde::{Deserialize, Serialize};
 
+use crate::controller::output_format::OutputFormat;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
 pub struct AppConfig {
@@ -14,24 +16,87 @@ pub struct AppConfig {
     pub service: ServiceConfig,
     pub diagnostics: DiagnosticsConfig,
     pub permissions: PermissionsConfig,
+    /// Context-aware overrides applied on top of the rest of this config
+    /// depending on the focused window's app-id/title; see
+    /// `config::context::resolve_for_context`.
+    pub overrides: Vec<ContextOverride>,
+    pub scripting: ScriptingConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct HotkeyConfig {
-    pub binding: String,
+    pub bindings: Vec<HotkeyBinding>,
     pub retry_strategy: HotkeyRetryStrategy,
+    pub mode: HotkeyMode,
 }
 
 impl Default for HotkeyConfig {
     fn default() -> Self {
         Self {
-            binding: "Ctrl+Shift+Space".to_owned(),
+            bindings: vec![HotkeyBinding {
+                action: HotkeyAction::Toggle,
+                binding: "Ctrl+Shift+Space".to_owned(),
+            }],
             retry_strategy: HotkeyRetryStrategy::Immediate,
+            mode: HotkeyMode::Toggle,
         }
     }
 }
 
+/// How the `toggle` action's chord starts and stops recording. `toggle`
+/// starts on one press and stops on the next, regardless of how long the
+/// chord is held. `push_to_talk` instead runs only while the chord is
+/// physically held: it requires the registered backend to report a
+/// key-*release* event (not just the press edge a global-hotkey
+/// registration normally gives you), so it is only selectable on backends
+/// that can report releases — see
+/// `config::hotkey::backend_supports_push_to_talk` and
+/// `config::hotkey::validate_hotkey_mode`, which rejects the config
+/// otherwise.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyMode {
+    Toggle,
+    PushToTalk,
+}
+
+/// One chord-to-action mapping. `binding` is the human-editable grammar
+/// string (see `config::hotkey::parse_binding`); it is parsed and validated
+/// at config-load time rather than stored in structured form, so the TOML
+/// file stays hand-editable.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HotkeyBinding {
+    pub action: HotkeyAction,
+    pub binding: String,
+}
+
+/// Which daemon action a hotkey chord triggers. `toggle` starts or stops
+/// recording depending on the current controller state; `start`/`stop`/
+/// `cancel` only act from their one specific state, so they can be bound to
+/// distinct chords (e.g. `Escape` for `cancel`) without fighting `toggle`.
+/// `copy_previous`/`re_transcribe`/`open_history`/`quit` are one-shot
+/// actions unrelated to the recording state machine, reusing whatever
+/// `ControllerEvent` the IPC control socket already sends for the
+/// equivalent command; see `ui::hotkey`'s `event_for`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyAction {
+    Toggle,
+    Start,
+    Stop,
+    Cancel,
+    /// Re-copies the last completed run's transcript to the clipboard.
+    CopyPrevious,
+    /// Re-enqueues the last completed run's capture for transcription,
+    /// without re-recording.
+    ReTranscribe,
+    /// Requests the default-size run history report.
+    OpenHistory,
+    /// Shuts the daemon down, flushing any in-flight recording first.
+    Quit,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum HotkeyRetryStrategy {
@@ -48,6 +113,65 @@ pub struct AudioConfig {
     pub retain_audio: bool,
     pub arming_timeout_ms: u64,
     pub stall_timeout_ms: u64,
+    /// Captures shorter than this are discarded after `stop` instead of
+    /// being enqueued for transcription; see `capture::analysis::analyze_wav`.
+    pub min_recording_ms: u64,
+    /// Captures whose peak short-term RMS never exceeds this are treated as
+    /// silence and discarded instead of being enqueued for transcription.
+    pub silence_rms_threshold: f32,
+    /// Minimum short-term RMS energy, on the same 0.0-1.0 scale as
+    /// `silence_rms_threshold`, for a live frame to be considered a
+    /// candidate speech frame during recording; see
+    /// `capture::vad::VoiceActivityTracker`.
+    pub vad_energy_threshold: f32,
+    /// Minimum fraction of a frame's spectral energy that must fall in the
+    /// upper half of the spectrum for it to be classified as speech rather
+    /// than steady background noise.
+    pub vad_high_band_ratio_threshold: f32,
+    /// Once speech has been seen during a recording, how long continuous
+    /// silence must persist before the controller auto-stops it, exactly as
+    /// if the user had triggered the stop action manually.
+    pub auto_stop_silence_ms: u64,
+    /// How often, in milliseconds, the controller polls the active
+    /// recording's capture watchdog for an arming timeout or stall while
+    /// `Recording`. Driven by the controller's own heartbeat rather than
+    /// whatever cadence the host app happens to send `Tick` events at.
+    pub watchdog_poll_ms: u64,
+    /// RMS level, in dBFS, below which the capture watchdog considers the
+    /// live signal silent; see `WatchdogSnapshot::silent`. Unlike
+    /// `silence_rms_threshold`, which gates a finished recording, this
+    /// drives a live meter and early-abort for a muted or gain-zero mic.
+    pub watchdog_silence_dbfs: f32,
+    /// Whether a finished recording is gain-adjusted towards `target_lufs`
+    /// before transcription; see `capture::loudness::normalize_wav_loudness`.
+    pub normalize_loudness: bool,
+    /// Integrated loudness, in LUFS, `normalize_wav_loudness` targets when
+    /// `normalize_loudness` is enabled. Defaults to
+    /// `capture::loudness::SPEECH_TARGET_LUFS`, louder than the EBU R128
+    /// broadcast reference level since this is speech dictation, not
+    /// broadcast mastering.
+    pub target_lufs: f64,
+    /// Whether a finished recording runs through a spectral noise-suppression
+    /// pass before transcription; see `capture::denoise::denoise_wav`. Off by
+    /// default since it trades a little fidelity on already-clean captures
+    /// for less background noise on noisy ones, and that's a tradeoff worth
+    /// opting into rather than assuming.
+    pub denoise: bool,
+    /// Which algorithm `denoise` runs when enabled; see `DenoiseMethod`.
+    pub denoise_method: DenoiseMethod,
+    /// Over-subtraction factor applied to the estimated noise magnitude when
+    /// `denoise_method` is `SpectralSubtraction`; see
+    /// `capture::denoise::spectral_subtract_wav`. Higher values remove more
+    /// noise at the cost of more musical-noise artifacts.
+    pub denoise_alpha: f64,
+    /// How far, in dB, a frame's energy in the speech band must exceed the
+    /// rolling noise floor to be classified as speech; see
+    /// `capture::analysis::analyze_wav`.
+    pub speech_band_margin_db: f64,
+    /// Minimum fraction of frames that must be classified as speech for a
+    /// finished recording to be transcribed; below this it's discarded as
+    /// "no speech detected" instead of being sent to the whisper worker.
+    pub min_speech_fraction: f32,
 }
 
 impl Default for AudioConfig {
@@ -59,10 +183,45 @@ impl Default for AudioConfig {
             retain_audio: false,
             arming_timeout_ms: 2_000,
             stall_timeout_ms: 750,
+            min_recording_ms: 300,
+            silence_rms_threshold: 0.01,
+            vad_energy_threshold: 0.02,
+            vad_high_band_ratio_threshold: 0.15,
+            auto_stop_silence_ms: 1_200,
+            watchdog_poll_ms: 250,
+            watchdog_silence_dbfs: -50.0,
+            normalize_loudness: true,
+            target_lufs: crate::capture::loudness::SPEECH_TARGET_LUFS,
+            denoise: false,
+            denoise_method: DenoiseMethod::Wiener,
+            denoise_alpha: 1.75,
+            speech_band_margin_db: 6.0,
+            min_speech_fraction: 0.05,
         }
     }
 }
 
+/// Which noise-suppression algorithm `AudioConfig::denoise` runs.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DenoiseMethod {
+    /// Bark-band Wiener gain approximating RNNoise; see
+    /// `capture::denoise::denoise_wav`. Smooths well across frames but can
+    /// leave more residual hiss than spectral subtraction on steady noise.
+    Wiener,
+    /// Classic STFT spectral subtraction against a leading-silence noise
+    /// profile; see `capture::denoise::spectral_subtract_wav`. Removes
+    /// steady noise more aggressively but is more prone to musical-noise
+    /// artifacts if `denoise_alpha` is pushed too high.
+    SpectralSubtraction,
+}
+
+impl Default for DenoiseMethod {
+    fn default() -> Self {
+        Self::Wiener
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct TranscriptionConfig {
@@ -74,6 +233,111 @@ pub struct TranscriptionConfig {
     pub timeout_seconds: u64,
     pub threads: Option<u32>,
     pub processors: Option<u32>,
+    /// Number of `FrankenEngine`-backed worker threads the controller's job
+    /// pool spawns; see `controller::spawn_transcription_workers`. Bounds how
+    /// many recordings can transcribe concurrently.
+    pub worker_count: usize,
+    /// When `Some(interval_ms)`, the controller periodically submits the
+    /// in-progress recording for a provisional decode while `Recording`,
+    /// stabilizing each decode against the last (per `streaming_stability`)
+    /// and emitting `ControllerOutput::PartialTranscript` so the UI can show
+    /// live text before the user stops. `None` disables streaming partials.
+    pub partial_interval_ms: Option<u64>,
+    /// Caps how many finished recordings may wait in the controller's job
+    /// queue behind `worker_count` in-flight jobs; see `controller::queue::JobQueue`.
+    /// Once full, a new recording's job is dropped with a "queue full"
+    /// notification instead of growing the backlog without bound.
+    pub max_queued_jobs: usize,
+    /// What a new `Toggle`/`Start` should do when it arrives while a
+    /// transcription is still `Processing`; see `BusyUpdatePolicy`.
+    pub busy_update_policy: BusyUpdatePolicy,
+    /// How long a single `transcribe_request` attempt may run before it's
+    /// considered stuck; modeled on nextest's per-test `slow-timeout`.
+    /// Paired with `slow_timeout_terminate_after`, which bounds how many of
+    /// these periods are tolerated before the attempt is cancelled outright;
+    /// see `transcription::retry::RetryingEngine`.
+    pub slow_timeout_ms: u64,
+    /// How many consecutive `slow_timeout_ms` periods an attempt may run for
+    /// before it's terminated and treated as a timeout.
+    pub slow_timeout_terminate_after: u32,
+    /// How many times a transient failure is retried before giving up; see
+    /// `transcription::retry::is_transient`.
+    pub max_transcribe_retries: u32,
+    /// How many times the controller re-enqueues a whole job after a
+    /// terminal `ErrorSeverity::Recoverable` failure (one that already
+    /// exhausted `max_transcribe_retries` within a single attempt) before
+    /// giving up and degrading; see the `TranscriptionFinished` handling in
+    /// `controller::run_controller_loop_with`.
+    pub max_recoverable_job_retries: u32,
+    /// How eagerly a trailing word of a partial decode is reported as stable
+    /// rather than held back for the next one; shared by
+    /// `transcription::streaming::run_streaming_transcription_job` and the
+    /// controller's own `partial_interval_ms` flow (see
+    /// `transcription::streaming::stabilize` and `StreamingStability`).
+    pub streaming_stability: StreamingStability,
+    /// Overrides `streaming_stability`'s preset holdback with an exact item
+    /// count (0 reports every item immediately, trading accuracy for the
+    /// lowest possible latency). `None` defers to the preset; see
+    /// `TranscriptionConfig::holdback_words`.
+    pub streaming_stability_window: Option<usize>,
+    /// Domain terms to bias decoding toward (jargon, names, acronyms), passed
+    /// through to the backend via `backend_params`; see `build_request`.
+    pub vocabulary: Option<Vec<String>>,
+    /// When set, redacts matches against `VocabularyFilter::terms` from the
+    /// transcript and segments; see `transcription::scheduler::apply_vocabulary_filter`.
+    pub vocabulary_filter: Option<VocabularyFilter>,
+    /// Endpoint/credentials for `transcription::network_streaming::NetworkStreamingEngine`,
+    /// a fallback backend used when no local model is available. `None`
+    /// means network streaming is not configured.
+    pub network_streaming: Option<NetworkStreamingConfig>,
+    /// Shifts every emitted segment/word's `start_ms`/`end_ms` forward by
+    /// this many milliseconds to compensate for engine processing delay,
+    /// before monotonic ordering is enforced; see
+    /// `transcription::scheduler::apply_lateness_and_ordering`.
+    pub lateness_ms: u64,
+    /// Whether `transcription::scheduler::run_transcription_job` trims
+    /// leading/trailing silence (and, for very long captures, splits into
+    /// sequential voiced segments) before handing the capture to the
+    /// backend; see `capture::vad::trim_silence_and_segment`.
+    pub vad_trim: bool,
+    /// How far, in dB, a frame's speech-band energy must clear the rolling
+    /// noise floor to be classified as speech, for `vad_trim`.
+    pub vad_margin_db: f64,
+    /// How much padding, in milliseconds, `vad_trim` leaves on both sides of
+    /// each detected speech span so word onsets/offsets aren't clipped.
+    pub vad_pad_ms: u64,
+    /// Total voiced duration beyond which `vad_trim` splits the capture into
+    /// separate sequential segments instead of one concatenated trimmed
+    /// file; `None` never splits.
+    pub vad_split_above_ms: Option<u64>,
+}
+
+/// Modeled on the four `--on-busy-update` modes watchexec exposes for a
+/// command that's still running when a new event arrives: controls what the
+/// controller does with a `Toggle`/`Start` that lands while a transcription
+/// job is still `ControllerState::Processing`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BusyUpdatePolicy {
+    /// Defer the request: remember it and start recording once every
+    /// in-flight transcription has finished, instead of starting a second
+    /// recording track immediately.
+    Queue,
+    /// Drop the request; the user has to retry once the controller is idle.
+    DoNothing,
+    /// Cancel the in-flight transcription(s) via `EngineAdapter::cancel` and
+    /// start recording right away.
+    Restart,
+    /// Ask the in-flight engine(s) to finalize early via `EngineAdapter::cancel`
+    /// but, unlike `Restart`, still deliver whatever result comes back instead
+    /// of discarding it, then start recording right away.
+    Signal,
+}
+
+impl Default for BusyUpdatePolicy {
+    fn default() -> Self {
+        Self::Queue
+    }
 }
 
 impl Default for TranscriptionConfig {
@@ -87,14 +351,137 @@ impl Default for TranscriptionConfig {
             timeout_seconds: 45,
             threads: None,
             processors: None,
+            worker_count: 2,
+            partial_interval_ms: None,
+            max_queued_jobs: 8,
+            busy_update_policy: BusyUpdatePolicy::Queue,
+            slow_timeout_ms: 60_000,
+            slow_timeout_terminate_after: 3,
+            max_transcribe_retries: 2,
+            max_recoverable_job_retries: 1,
+            streaming_stability: StreamingStability::Medium,
+            streaming_stability_window: None,
+            vocabulary: None,
+            vocabulary_filter: None,
+            network_streaming: None,
+            lateness_ms: 0,
+            vad_trim: false,
+            vad_margin_db: 6.0,
+            vad_pad_ms: 200,
+            vad_split_above_ms: Some(60_000),
+        }
+    }
+}
+
+/// Endpoint/credentials for a remote speech-to-text websocket service; see
+/// `transcription::network_streaming::NetworkStreamingEngine`. franken_whisper's
+/// `BackendKind` has no variant of its own for this backend (its source
+/// isn't vendored into this tree to extend), so runs through this engine
+/// report `BackendKind::Auto` rather than a dedicated `NetworkStreaming`
+/// value.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct NetworkStreamingConfig {
+    pub endpoint_url: String,
+    pub api_key: Option<String>,
+    pub region: Option<String>,
+    pub language: Option<String>,
+    pub sample_rate_hz: u32,
+}
+
+impl Default for NetworkStreamingConfig {
+    fn default() -> Self {
+        Self {
+            endpoint_url: String::new(),
+            api_key: None,
+            region: None,
+            language: None,
+            sample_rate_hz: 16_000,
+        }
+    }
+}
+
+/// Redacts `terms` matched against individual words of the transcript and
+/// segments; see `transcription::scheduler::apply_vocabulary_filter`, which
+/// applies this as a fallback whenever the backend doesn't (or can't) honor
+/// it natively.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct VocabularyFilter {
+    pub terms: Vec<String>,
+    pub method: VocabularyFilterMethod,
+}
+
+impl Default for VocabularyFilter {
+    fn default() -> Self {
+        Self {
+            terms: Vec::new(),
+            method: VocabularyFilterMethod::Mask,
         }
     }
 }
 
+/// How `VocabularyFilter` handles a matched word.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VocabularyFilterMethod {
+    /// Replace the matched word with `***`.
+    Mask,
+    /// Drop the matched word entirely.
+    Remove,
+    /// Keep the matched word but annotate it as flagged.
+    Tag,
+}
+
+impl Default for VocabularyFilterMethod {
+    fn default() -> Self {
+        Self::Mask
+    }
+}
+
+/// Modeled on partial-results stabilization: how many trailing words of a
+/// streaming decode `transcription::streaming::stabilize` holds back as
+/// still liable to change, rather than reporting them as stable right away.
+/// There's no per-word confidence signal to key off of (each snapshot is a
+/// full, independent decode; see `run_streaming_transcription_job`'s doc
+/// comment), so this tunes a trailing-word holdback heuristic instead:
+/// `High` holds back the fewest words, trading a little accuracy (an
+/// occasional word reported stable that a later decode revises) for text
+/// that appears sooner.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamingStability {
+    Low,
+    Medium,
+    High,
+}
+
+impl Default for StreamingStability {
+    fn default() -> Self {
+        Self::Medium
+    }
+}
+
 impl TranscriptionConfig {
     pub fn timeout_ms(&self) -> u64 {
         self.timeout_seconds.saturating_mul(1_000)
     }
+
+    /// How many trailing items of a streaming decode to hold back as still
+    /// liable to change; `streaming_stability_window` when set, otherwise
+    /// `streaming_stability`'s preset. See `transcription::streaming::stabilize`.
+    pub fn holdback_words(&self) -> usize {
+        self.streaming_stability_window
+            .unwrap_or_else(|| self.streaming_stability.holdback_words())
+    }
+
+    /// Total deadline for one `transcribe_request` attempt before
+    /// `transcription::retry::RetryingEngine` cancels it and either retries
+    /// or gives up: `slow_timeout_ms * slow_timeout_terminate_after`.
+    pub fn attempt_deadline_ms(&self) -> u64 {
+        self.slow_timeout_ms
+            .saturating_mul(self.slow_timeout_terminate_after as u64)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -102,7 +489,23 @@ impl TranscriptionConfig {
 pub struct OutputConfig {
     pub mode: OutputMode,
     pub enable_notifications: bool,
+    /// How long `output::inject::paste_fallback` waits after writing to the
+    /// clipboard before sending the paste keystroke, giving the focused app
+    /// time to notice the clipboard change. Only applies to `TypeText`'s
+    /// clipboard-paste fallback path.
     pub auto_paste_delay_ms: u64,
+    pub clipboard_provider: ClipboardProviderKind,
+    /// Which selection a clipboard write targets; see
+    /// `output::clipboard::supports_target` for which
+    /// `clipboard_provider`s can honor `Primary`.
+    pub selection_target: ClipboardSelectionTarget,
+    pub type_text_delay_ms: u64,
+    /// Shell command line run when `mode` is `Command`; required in that
+    /// case, see `output::command::CommandOutput`. Expands `{transcript}`,
+    /// `{run_id}`, `{language}`, and `{backend}` placeholders, and also
+    /// receives the transcript on stdin so a template that never references
+    /// `{transcript}` (e.g. a plain `my-formatter`) still gets it.
+    pub command_template: Option<String>,
 }
 
 impl Default for OutputConfig {
@@ -111,6 +514,10 @@ impl Default for OutputConfig {
             mode: OutputMode::ClipboardOnly,
             enable_notifications: true,
             auto_paste_delay_ms: 0,
+            clipboard_provider: ClipboardProviderKind::Auto,
+            selection_target: ClipboardSelectionTarget::Clipboard,
+            type_text_delay_ms: 8,
+            command_template: None,
         }
     }
 }
@@ -119,45 +526,111 @@ impl Default for OutputConfig {
 #[serde(rename_all = "snake_case")]
 pub enum OutputMode {
     ClipboardOnly,
+    /// Synthesizes the transcript as keystrokes at the focused cursor instead
+    /// of writing to the clipboard, for apps that ignore programmatic paste.
+    TypeText,
+    /// Pipes the transcript into `OutputConfig::command_template`, see
+    /// `output::command::CommandOutput`.
+    Command,
     Disabled,
 }
 
+/// Which `output::clipboard::ClipboardProvider` implementation to use. `Auto`
+/// probes the session (`$WAYLAND_DISPLAY`/`$DISPLAY`) and `PATH` to pick the
+/// first working command-backed provider, falling back to `Internal`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClipboardProviderKind {
+    Auto,
+    Wayland,
+    Xclip,
+    Xsel,
+    Macos,
+    Windows,
+    /// Forces the cross-platform `arboard` crate instead of shelling out to
+    /// a platform command; `Auto` only falls back to this when no
+    /// command-backed provider is found on `PATH`.
+    Arboard,
+    Internal,
+}
+
+/// Which X11/Wayland selection a clipboard write lands in. `Clipboard` is
+/// the normal Ctrl+V paste buffer; `Primary` is the separate X11 selection
+/// that middle-click pastes from, letting a dictated transcript land there
+/// without clobbering whatever the user last copied. See
+/// `output::clipboard::supports_target`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClipboardSelectionTarget {
+    Clipboard,
+    Primary,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct HistoryConfig {
     pub db_path: Option<PathBuf>,
-    pub max_entries: usize,
-    pub prune_policy: PrunePolicy,
+    pub retention: RetentionPolicy,
 }
 
 impl Default for HistoryConfig {
     fn default() -> Self {
         Self {
             db_path: None,
-            max_entries: 1_000,
-            prune_policy: PrunePolicy::NoPrune,
+            retention: RetentionPolicy::default(),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum PrunePolicy {
-    NoPrune,
-    KeepRecent,
+/// Caps applied by `HistoryStore::prune`, run automatically after each
+/// persisted transcription completes (see the `DeliverOutcome::Success` arm
+/// in `controller::run_controller_loop_with`). The two caps are independent
+/// and additive: either, both, or (the default) neither may be set. Leaving
+/// both `None` disables automatic pruning, matching the behavior before
+/// this field existed.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    /// Keep only the newest `max_entries` runs; older rows are deleted.
+    pub max_entries: Option<usize>,
+    /// Delete runs whose `finished_at` is older than this many days.
+    pub max_age_days: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
 pub struct ServiceConfig {
     pub autostart_enabled: bool,
+    /// Address an optional localhost TCP listener binds to, mirroring the
+    /// always-on `controller::ipc` Unix-domain socket for tooling that can't
+    /// reach a Unix socket, e.g. `"127.0.0.1:4756"`. `None` (the default)
+    /// leaves it disabled, since a network-reachable control port is a
+    /// materially different exposure than a user-only Unix socket. The
+    /// protocol has no authentication, so `config::load::collect_validation_problems`
+    /// rejects any address whose IP isn't loopback.
+    pub control_tcp_addr: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct DiagnosticsConfig {
     pub log_level: String,
+    /// How many daily-rotated log files `main::init_tracing` keeps under
+    /// `AppPaths::logs_dir` before the oldest is deleted.
     pub log_retention_days: u32,
+    /// Formatter for that rotated log file; the interactive stderr layer
+    /// stays compact text regardless, since it's read by a human either
+    /// way. See `main::init_tracing`.
+    pub log_format: LogFormat,
+    /// Writes one line per `ControllerOutput` (state transitions,
+    /// notifications, doctor reports, `TranscriptReady`) to stdout from
+    /// `runtime::app::run_app`, encoded as `event_format`, so editors,
+    /// scripts, and test harnesses can consume daemon activity without
+    /// parsing `tracing` text.
+    pub emit_events: bool,
+    /// Encoding used for each `emit_events` line; see
+    /// `controller::output_format::serialize_output`.
+    pub event_format: OutputFormat,
 }
 
 impl Default for DiagnosticsConfig {
@@ -165,10 +638,22 @@ impl Default for DiagnosticsConfig {
         Self {
             log_level: "info".to_owned(),
             log_retention_days: 14,
+            log_format: LogFormat::Text,
+            emit_events: false,
+            event_format: OutputFormat::Json,
         }
     }
 }
 
+/// Output format for the daemon's `tracing` subscriber; see
+/// `DiagnosticsConfig::log_format`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct PermissionsConfig {
@@ -184,3 +669,49 @@ impl Default for PermissionsConfig {
         }
     }
 }
+
+/// One `[[overrides]]` entry: a regex tested against the focused window's
+/// app-id/title, plus a partial set of fields to apply on top of the rest of
+/// the config when it matches. Entries are walked in order by
+/// `config::context::resolve_for_context`, with a later match's fields
+/// winning over an earlier one's.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ContextOverride {
+    /// Regex tested against the focused window's app-id and title; compiled
+    /// and validated at load time by `config::context::validate_overrides`.
+    pub match_pattern: String,
+    pub language: Option<String>,
+    pub backend: Option<BackendKind>,
+    pub output_mode: Option<OutputMode>,
+    pub translate: Option<bool>,
+    pub diarize: Option<bool>,
+    /// Overrides the `toggle` action's chord, same grammar as
+    /// `HotkeyBinding::binding`.
+    pub hotkey_binding: Option<String>,
+}
+
+/// Settings for the optional Lua post-processing hook; see the `scripting`
+/// module (built only with the `scripting` cargo feature).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScriptingConfig {
+    pub enabled: bool,
+    /// Path to a Lua script that rewrites the final transcript before it
+    /// reaches the configured output sink; loaded and syntax-checked once at
+    /// startup when `enabled`, via `scripting::validate_config`.
+    pub post_transcript_script: Option<PathBuf>,
+    /// How long the script may run on a single transcript before it is
+    /// aborted; see `scripting::TranscriptScript::run`.
+    pub timeout_ms: u64,
+}
+
+impl Default for ScriptingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            post_transcript_script: None,
+            timeout_ms: 2_000,
+        }
+    }
+}
@@ -2,38 +2,268 @@ use std::path::PathBuf;
 
 use serde::Serialize;
 
+use crate::controller::queue::JobId;
 use crate::controller::state::ControllerState;
 use crate::doctor::DoctorReport;
-use crate::transcription::TranscriptResult;
+use crate::history::RunSummary;
+use crate::transcription::{CaptionFormat, ErrorSeverity, TranscriptResult, TranscriptionFailure};
+
+/// How urgently a `ControllerNotification` should be surfaced to the user.
+/// `Info` covers routine status chatter (e.g. "Recording started") that a UI
+/// can display quietly; `Warning`/`Error` are meant to interrupt, typically
+/// as a real desktop toast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationSeverity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+/// A stable, machine-matchable label for what kind of event produced a
+/// notification, independent of the human-readable `detail` text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationCategory {
+    RecordingStarted,
+    RecordingCanceled,
+    RecordingDiscarded,
+    RecordingFailed,
+    WatchdogAbort,
+    VoiceActivityStopped,
+    TranscriptionComplete,
+    TranscriptionFailed,
+    TranscriptionCancelled,
+    ClipboardFailed,
+    TypeTextFailed,
+    /// `OutputMode::Command` failed to spawn `OutputConfig::command_template`
+    /// or it exited non-zero; see `output::command::CommandOutput`.
+    CommandOutputFailed,
+    WorkerUnavailable,
+    /// `TranscriptionConfig::vad_trim` found no speech anywhere in the
+    /// capture, so the backend was never invoked; distinct from
+    /// `RecordingDiscarded` (which drops the capture before it's ever
+    /// queued) because this fires after a job has run through the queue, and
+    /// distinct from a generic `Degraded`/`TranscriptionFailed` because
+    /// nothing actually failed. See `transcription::scheduler::no_speech_result`.
+    NoSpeechDetected,
+    /// `AudioConfig::normalize_loudness` measured and corrected the
+    /// capture's integrated loudness; see `capture::loudness::LoudnessReport`
+    /// and `should_discard_recording`.
+    LoudnessNormalized,
+    /// The configured `scripting.post_transcript_script` failed to compile,
+    /// raised an error, or timed out; see `apply_transcript_script`.
+    TranscriptScriptFailed,
+    /// `ControllerEvent::ReloadConfig` re-read `config.transcription` and
+    /// re-ran the backend availability check.
+    ConfigReloaded,
+    /// `ControllerEvent::ReloadConfig` failed to read the config file.
+    ConfigReloadFailed,
+    /// `ControllerEvent::Calibrate` picked a `threads`/`processors` pair and
+    /// persisted it; see `calibration::calibrate`.
+    CalibrationComplete,
+    /// `ControllerEvent::Calibrate` couldn't produce a result, e.g. the
+    /// reference fixture isn't installed.
+    CalibrationFailed,
+    /// The job queue was already at `TranscriptionConfig::max_queued_jobs`
+    /// when a recording finished, so it was discarded instead of transcribed.
+    QueueFull,
+    /// A no-op acknowledgement of an event the controller ignored, e.g.
+    /// "Already recording".
+    Status,
+    /// `ControllerEvent::ExportCaptions` was sent before any run finished;
+    /// see `last_transcript` in `run_controller_loop_with`.
+    CaptionsUnavailable,
+    /// `ControllerEvent::CopyPrevious` was sent before any run finished, or
+    /// after `ControllerEvent::DiscardLastTranscript` cleared it.
+    CopyPreviousUnavailable,
+    /// `ControllerEvent::ReTranscribe` was sent before any run finished, or
+    /// the last run's capture was already deleted (see
+    /// `AudioConfig::retain_audio`).
+    ReTranscribeUnavailable,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ControllerNotification {
+    pub severity: NotificationSeverity,
+    pub category: NotificationCategory,
+    pub detail: String,
+}
+
+/// How `ControllerEvent::Shutdown` treats an active recording. `Discard`
+/// drops it immediately, exactly as every shutdown used to behave.
+/// `FlushPending` finalizes it, enqueues one last transcription job, and
+/// blocks shutdown (bounded by `TranscriptionConfig::timeout_ms`) until that
+/// job completes, so the user doesn't lose whatever they were dictating when
+/// they quit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownMode {
+    Discard,
+    FlushPending,
+}
 
 #[derive(Debug, Clone)]
 pub enum ControllerEvent {
     Toggle,
+    /// Begins recording from idle or degraded; while `Processing`,
+    /// `TranscriptionConfig::busy_update_policy` decides whether it starts
+    /// now, is deferred, or is dropped. A no-op in any other state.
+    Start,
+    /// Finalizes the active recording for transcription; a no-op unless the
+    /// controller is currently recording.
+    Stop,
+    /// Aborts the active recording without transcribing it; while
+    /// `Processing`, drops queued jobs and discards the results of in-flight
+    /// ones instead. A no-op in any other state.
+    Cancel,
     RunDoctor,
+    /// Re-reads `config.transcription` (threads/processors/backend_params)
+    /// from disk and re-runs `doctor::backend_availability_problems` against
+    /// it, so a SIGHUP-triggered reload (see `runtime::signals`) can move the
+    /// controller in or out of `ControllerState::Unavailable` without a
+    /// restart. A no-op for every other field: everything besides
+    /// `transcription` is only read once at startup.
+    ReloadConfig,
+    /// Runs `calibration::calibrate` against the bundled reference fixture
+    /// and persists the chosen `threads`/`processors` to the config file; a
+    /// no-op while `Recording`/`Processing`, since it needs the worker
+    /// engine it would otherwise be busy transcribing for. Sent once by
+    /// `runtime::app::run_app` on first run (see `AppPaths::state_dir`'s
+    /// calibration marker), or on demand.
+    Calibrate,
+    /// Re-emits the controller's current `ControllerState` as a
+    /// `ControllerOutput::StateChanged` without changing anything, so a
+    /// client that just connected (see `controller::ipc`'s `"status"`
+    /// command) can learn where things stand instead of waiting for the
+    /// next real transition.
+    QueryStatus,
+    /// Queues `path` for transcription the same way a finished recording is,
+    /// without a capture ever having produced it; see `controller::ipc`'s
+    /// `"enqueue"` command. Unlike a recording's own wav file, `path` is
+    /// never deleted if the queue is full — it's the caller's file, not a
+    /// throwaway capture artifact.
+    Enqueue { path: PathBuf },
+    /// Reads up to `limit` rows from `HistoryStore` and replies with a
+    /// `ControllerOutput::HistoryReport`; see `controller::ipc`'s
+    /// `"history"` command.
+    QueryHistory { limit: usize },
+    /// Renders the most recently completed run's `segments` as `format` and
+    /// replies with `ControllerOutput::CaptionsReady`, or a `Notification`
+    /// if nothing has finished transcribing yet; see `controller::ipc`'s
+    /// `"captions"` command. Only the latest run is addressable, since
+    /// `HistoryStore` doesn't persist per-segment timing — just the flat
+    /// preview `RunSummary` needs.
+    ExportCaptions { format: CaptionFormat },
+    /// Re-writes `last_transcript`'s text to the clipboard without
+    /// rerunning transcription; a no-op with a `CopyPreviousUnavailable`
+    /// notification if nothing has finished transcribing yet. Triggered by the "Copy
+    /// again" button on the `TranscriptionComplete` desktop notification
+    /// (see `ui::notify::DesktopNotificationSink`) or a bound
+    /// `HotkeyAction::CopyPrevious`.
+    CopyPrevious,
+    /// Clears `last_transcript` so a stale result can't be re-copied or
+    /// re-exported. Triggered by the "Discard" button on the
+    /// `TranscriptionComplete` desktop notification.
+    DiscardLastTranscript,
+    /// Re-enqueues the last completed run's capture (see `last_wav_path` in
+    /// `run_controller_loop_with`) for a fresh transcription pass, without
+    /// re-recording. A no-op with a `ReTranscribeUnavailable` notification
+    /// if nothing has finished yet, or if `AudioConfig::retain_audio` was
+    /// off and the capture has already been deleted. Triggered by a bound
+    /// `HotkeyAction::ReTranscribe`.
+    ReTranscribe,
     Tick,
-    Shutdown,
+    Shutdown(ShutdownMode),
     TranscriptionFinished {
+        job_id: JobId,
         wav_path: PathBuf,
-        result: Result<TranscriptResult, String>,
+        result: Result<TranscriptResult, TranscriptionFailure>,
+        /// Distinguishes a provisional decode of the still-recording capture
+        /// (see `TranscriptionConfig::partial_interval_ms`) from the
+        /// authoritative transcript produced once the recording stops; the
+        /// two are routed very differently by the controller.
+        partial: bool,
     },
 }
 
+/// Guards against duplicate or out-of-order redelivery of offset-tagged
+/// client commands (see `controller::ipc`), mirroring the contiguous-offset
+/// check the tandem engine uses for durably-received client events: a
+/// command is only applied if its offset is exactly one past the last
+/// applied offset, or `0` for the very first command ever seen. Anything
+/// out of order or already seen is still acknowledged by the caller, just
+/// not re-executed, so a reconnecting client can always safely resend from
+/// its last un-acked offset.
+#[derive(Debug, Default)]
+pub struct OffsetTracker {
+    last_applied: Option<u64>,
+}
+
+impl OffsetTracker {
+    /// Returns whether `offset` should be applied, advancing the tracker to
+    /// `offset` if so.
+    pub fn accept(&mut self, offset: u64) -> bool {
+        let expected = self.last_applied.map_or(0, |last| last + 1);
+        if offset == expected {
+            self.last_applied = Some(offset);
+            true
+        } else {
+            false
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type", content = "payload", rename_all = "snake_case")]
 pub enum ControllerOutput {
     StateChanged(ControllerState),
-    Notification(String),
+    Notification(ControllerNotification),
     DoctorReport(DoctorReport),
+    /// A provisional decode of the still-recording capture, split by
+    /// `transcription::streaming::stabilize` into words the controller now
+    /// considers committed (`stable_text`, newly firmed up since the
+    /// previous update — the UI should append it) and the still-changing
+    /// tail (`provisional_text`, which replaces whatever was shown for the
+    /// previous update). Superseded by the authoritative `TranscriptReady`
+    /// once the recording stops.
+    PartialTranscript {
+        run_id: String,
+        stable_text: String,
+        provisional_text: String,
+    },
     TranscriptReady(TranscriptResult),
+    /// A terminal transcription failure, emitted alongside the matching
+    /// `Notification` so a programmatic consumer can switch on `severity`
+    /// instead of pattern-matching `Notification::detail` text; see
+    /// `TranscriptionFailure`. `job_id` identifies the failed job rather
+    /// than a `run_id`, since `TranscriptResult::run_id` is only minted by
+    /// the backend on a successful decode.
+    Error {
+        severity: ErrorSeverity,
+        message: String,
+        job_id: JobId,
+    },
+    /// Reply to `ControllerEvent::QueryHistory`, the newest run first.
+    HistoryReport(Vec<RunSummary>),
+    /// Reply to `ControllerEvent::ExportCaptions`: `body` rendered in
+    /// `format` from the latest run's segments; see
+    /// `transcription::captions::render`.
+    CaptionsReady { format: CaptionFormat, body: String },
     Stopped,
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ControllerOutput;
+    use super::{
+        ControllerNotification, ControllerOutput, NotificationCategory, NotificationSeverity,
+        OffsetTracker,
+    };
     use crate::controller::state::ControllerState;
     use crate::doctor::report::{CheckResult, CheckStatus, DoctorReport, DoctorState};
+    use crate::history::RunSummary;
     use crate::transcription::scheduler::TranscriptResult;
+    use crate::transcription::{CaptionFormat, ErrorSeverity};
     use franken_whisper::BackendKind;
     use serde_json::Value;
 
@@ -55,8 +285,11 @@ mod tests {
             backend: BackendKind::WhisperCpp,
             transcript: "hello".to_owned(),
             language: Some("en".to_owned()),
+            segments: vec![],
             warnings: vec!["warn".to_owned()],
             finished_at_rfc3339: "2026-02-25T00:00:01Z".to_owned(),
+            no_speech: false,
+            elapsed_ms: 120,
         };
 
         let state_changed = serde_json::to_value(ControllerOutput::StateChanged(
@@ -84,17 +317,64 @@ mod tests {
             Some("missing ffmpeg")
         );
 
-        let notification = serde_json::to_value(ControllerOutput::Notification("note".to_owned()))
-            .expect("serialize");
+        let notification = serde_json::to_value(ControllerOutput::Notification(
+            ControllerNotification {
+                severity: NotificationSeverity::Warning,
+                category: NotificationCategory::WatchdogAbort,
+                detail: "note".to_owned(),
+            },
+        ))
+        .expect("serialize");
         assert_eq!(
             notification.get("type").and_then(Value::as_str),
             Some("notification")
         );
+        let notification_payload = notification
+            .get("payload")
+            .and_then(Value::as_object)
+            .expect("notification payload");
         assert_eq!(
-            notification.get("payload").and_then(Value::as_str),
+            notification_payload.get("severity").and_then(Value::as_str),
+            Some("warning")
+        );
+        assert_eq!(
+            notification_payload.get("category").and_then(Value::as_str),
+            Some("watchdog_abort")
+        );
+        assert_eq!(
+            notification_payload.get("detail").and_then(Value::as_str),
             Some("note")
         );
 
+        let partial = serde_json::to_value(ControllerOutput::PartialTranscript {
+            run_id: "run-1".to_owned(),
+            stable_text: "committed words".to_owned(),
+            provisional_text: "still changing".to_owned(),
+        })
+        .expect("serialize");
+        assert_eq!(
+            partial.get("type").and_then(Value::as_str),
+            Some("partial_transcript")
+        );
+        let partial_payload = partial
+            .get("payload")
+            .and_then(Value::as_object)
+            .expect("partial transcript payload");
+        assert_eq!(
+            partial_payload.get("run_id").and_then(Value::as_str),
+            Some("run-1")
+        );
+        assert_eq!(
+            partial_payload.get("stable_text").and_then(Value::as_str),
+            Some("committed words")
+        );
+        assert_eq!(
+            partial_payload
+                .get("provisional_text")
+                .and_then(Value::as_str),
+            Some("still changing")
+        );
+
         let doctor = serde_json::to_value(ControllerOutput::DoctorReport(report.clone()))
             .expect("serialize");
         assert_eq!(
@@ -169,5 +449,78 @@ mod tests {
             serde_json::to_value(ControllerOutput::Stopped).expect("serialize stopped output");
         assert_eq!(stopped.get("type").and_then(Value::as_str), Some("stopped"));
         assert!(stopped.get("payload").is_none());
+
+        let error = serde_json::to_value(ControllerOutput::Error {
+            severity: ErrorSeverity::Fatal,
+            message: "transcription job 1 failed: missing model".to_owned(),
+            job_id: 1,
+        })
+        .expect("serialize error output");
+        assert_eq!(error.get("type").and_then(Value::as_str), Some("error"));
+        let error_payload = error
+            .get("payload")
+            .and_then(Value::as_object)
+            .expect("error payload");
+        assert_eq!(
+            error_payload.get("severity").and_then(Value::as_str),
+            Some("fatal")
+        );
+        assert_eq!(error_payload.get("job_id").and_then(Value::as_u64), Some(1));
+
+        let history = serde_json::to_value(ControllerOutput::HistoryReport(vec![RunSummary {
+            run_id: "run-1".to_owned(),
+            started_at_rfc3339: "2026-02-25T00:00:00Z".to_owned(),
+            finished_at_rfc3339: "2026-02-25T00:00:01Z".to_owned(),
+            backend: BackendKind::WhisperCpp,
+            transcript_preview: "hello".to_owned(),
+        }]))
+        .expect("serialize history report");
+        assert_eq!(
+            history.get("type").and_then(Value::as_str),
+            Some("history_report")
+        );
+        let history_rows = history
+            .get("payload")
+            .and_then(Value::as_array)
+            .expect("history payload");
+        assert_eq!(history_rows.len(), 1);
+        assert_eq!(
+            history_rows[0].get("run_id").and_then(Value::as_str),
+            Some("run-1")
+        );
+
+        let captions = serde_json::to_value(ControllerOutput::CaptionsReady {
+            format: CaptionFormat::Srt,
+            body: "1\n00:00:00,000 --> 00:00:01,000\nhello\n".to_owned(),
+        })
+        .expect("serialize captions ready");
+        assert_eq!(
+            captions.get("type").and_then(Value::as_str),
+            Some("captions_ready")
+        );
+        let captions_payload = captions
+            .get("payload")
+            .and_then(Value::as_object)
+            .expect("captions payload");
+        assert_eq!(
+            captions_payload.get("format").and_then(Value::as_str),
+            Some("srt")
+        );
+        assert!(captions_payload
+            .get("body")
+            .and_then(Value::as_str)
+            .unwrap()
+            .contains("hello"));
+    }
+
+    #[test]
+    fn offset_tracker_accepts_only_the_next_contiguous_offset() {
+        let mut tracker = OffsetTracker::default();
+
+        assert!(tracker.accept(0));
+        assert!(!tracker.accept(0), "a repeated offset must not re-apply");
+        assert!(!tracker.accept(2), "a gap must not apply out of order");
+        assert!(tracker.accept(1));
+        assert!(tracker.accept(2));
     }
 }
@@ -0,0 +1,711 @@
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crossbeam_channel::Sender;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::controller::events::{ControllerEvent, ControllerOutput, OffsetTracker, ShutdownMode};
+use crate::error::{AppError, AppResult};
+use crate::transcription::CaptionFormat;
+
+/// Line-delimited JSON command accepted from a connected client, e.g.
+/// `{"cmd":"toggle"}`, `{"cmd":"enqueue","path":"..."}`, or
+/// `{"cmd":"history","limit":20}`. An optional monotonic `offset` opts the
+/// command into the contiguous-offset redelivery guard (see
+/// `OffsetTracker`), letting a client that lost its connection safely
+/// resend everything from its last un-acked offset without double-applying
+/// whatever the daemon already processed; clients that omit it keep the
+/// original at-most-once-per-line behavior. An optional `id`, in the same
+/// spirit as JSON-RPC/a debug-adapter-protocol request, is echoed back on
+/// the `ControllerOutput` the command eventually produces (see
+/// `PendingIds`); a client that omits it gets the original
+/// broadcast-with-no-id behavior.
+#[derive(Debug, Deserialize)]
+struct IpcCommand {
+    cmd: String,
+    #[serde(default)]
+    offset: Option<u64>,
+    #[serde(default)]
+    id: Option<Value>,
+    /// Required by `"enqueue"`; the file to push into the transcription
+    /// queue.
+    #[serde(default)]
+    path: Option<PathBuf>,
+    /// Row cap for `"history"`, forwarded as-is to
+    /// `HistoryStore::list_recent_runs` (where `0` means unbounded);
+    /// defaults to 20 if the client omits it.
+    #[serde(default)]
+    limit: Option<usize>,
+    /// Subtitle container for `"captions"`; defaults to `"srt"` if the
+    /// client omits it.
+    #[serde(default)]
+    format: Option<CaptionFormat>,
+}
+
+/// A connected client's FIFO of correlation ids still awaiting a reply,
+/// shared between its `handle_client` reader (which pushes an id when a
+/// command carries one) and the broadcaster (which pops one off for the
+/// next `ControllerOutput` it writes to that client). This is a best-effort
+/// pairing rather than a real request/response match: it assumes a client
+/// doesn't pipeline a second id-bearing command before the first one's
+/// reply arrives, and an unrelated unsolicited event (e.g. another
+/// client's `TranscriptReady`) that lands in between still consumes the
+/// front id. Good enough for the single-outstanding-request clients this
+/// protocol targets (editor plugins, status bars); a client that needs
+/// strict correlation under pipelining should open one connection per
+/// in-flight request.
+type PendingIds = Arc<Mutex<VecDeque<Value>>>;
+
+/// One broadcaster-visible client: its writer half plus the `PendingIds`
+/// queue `handle_client` feeds as commands with an `id` arrive.
+struct IpcClient<S> {
+    writer: S,
+    pending_ids: PendingIds,
+}
+
+/// Spawns the Unix-socket control API at `socket_path`. Any stale socket
+/// file left behind by a previous run is removed before binding. Connected
+/// clients may send line-delimited JSON commands, which are mapped onto
+/// `ControllerEvent`s and forwarded to `event_tx`; every `ControllerOutput`
+/// later sent to the returned sender is fanned out to all connected clients
+/// as a JSON line, the same shape already produced for `ControllerOutput`
+/// elsewhere (see `controller::events`). A command that carries an `id` gets
+/// it echoed back on the reply line (see `PendingIds`), the way a debug
+/// adapter correlates a request with its response; an unsolicited event
+/// (e.g. another client's `TranscriptReady`) carries no id unless it is
+/// written while this client still has an id pending, in which case it
+/// takes that id (see `PendingIds`).
+///
+/// Both the accept loop and the broadcaster run detached, like the stdin
+/// command thread in `runtime::app`; they exit with the process rather than
+/// being joined on shutdown.
+pub fn spawn_ipc_server(
+    socket_path: PathBuf,
+    event_tx: Sender<ControllerEvent>,
+) -> AppResult<Sender<ControllerOutput>> {
+    if socket_path.exists() {
+        std::fs::remove_file(&socket_path)?;
+    }
+
+    let listener = UnixListener::bind(&socket_path).map_err(|error| {
+        AppError::Controller(format!(
+            "failed to bind ipc socket {}: {error}",
+            socket_path.display()
+        ))
+    })?;
+
+    let clients: Arc<Mutex<Vec<IpcClient<UnixStream>>>> = Arc::new(Mutex::new(Vec::new()));
+
+    let clients_for_listener = clients.clone();
+    thread::Builder::new()
+        .name("quedo-ipc-listener".to_owned())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let Ok(writer) = stream.try_clone() else {
+                    continue;
+                };
+                let pending_ids: PendingIds = Arc::new(Mutex::new(VecDeque::new()));
+                clients_for_listener
+                    .lock()
+                    .expect("lock ipc clients")
+                    .push(IpcClient {
+                        writer,
+                        pending_ids: pending_ids.clone(),
+                    });
+
+                let event_tx = event_tx.clone();
+                let _ = thread::Builder::new()
+                    .name("quedo-ipc-client".to_owned())
+                    .spawn(move || handle_client(stream, event_tx, pending_ids));
+            }
+        })
+        .map_err(|error| {
+            AppError::Controller(format!("failed to spawn ipc listener thread: {error}"))
+        })?;
+
+    let (output_tx, output_rx) = crossbeam_channel::unbounded::<ControllerOutput>();
+    thread::Builder::new()
+        .name("quedo-ipc-broadcaster".to_owned())
+        .spawn(move || {
+            while let Ok(output) = output_rx.recv() {
+                let mut clients = clients.lock().expect("lock ipc clients");
+                clients.retain_mut(|client| write_output(client, &output));
+            }
+        })
+        .map_err(|error| {
+            AppError::Controller(format!("failed to spawn ipc broadcaster thread: {error}"))
+        })?;
+
+    Ok(output_tx)
+}
+
+/// Spawns the optional localhost TCP control API at `addr` (see
+/// `ServiceConfig::control_tcp_addr`), for tooling that can't reach a
+/// Unix-domain socket (e.g. a sandboxed editor plugin or a status bar
+/// running under a different user). Protocol and fan-out behavior are
+/// identical to `spawn_ipc_server`; only the listener/broadcaster's
+/// transport differs, since `UnixListener`/`TcpListener` and
+/// `UnixStream`/`TcpStream` don't share a common standard trait to bind or
+/// accept through.
+pub fn spawn_ipc_tcp_server(
+    addr: SocketAddr,
+    event_tx: Sender<ControllerEvent>,
+) -> AppResult<Sender<ControllerOutput>> {
+    let listener = TcpListener::bind(addr)
+        .map_err(|error| AppError::Controller(format!("failed to bind ipc tcp {addr}: {error}")))?;
+
+    let clients: Arc<Mutex<Vec<IpcClient<TcpStream>>>> = Arc::new(Mutex::new(Vec::new()));
+
+    let clients_for_listener = clients.clone();
+    thread::Builder::new()
+        .name("quedo-ipc-tcp-listener".to_owned())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let Ok(writer) = stream.try_clone() else {
+                    continue;
+                };
+                let pending_ids: PendingIds = Arc::new(Mutex::new(VecDeque::new()));
+                clients_for_listener
+                    .lock()
+                    .expect("lock ipc tcp clients")
+                    .push(IpcClient {
+                        writer,
+                        pending_ids: pending_ids.clone(),
+                    });
+
+                let event_tx = event_tx.clone();
+                let _ = thread::Builder::new()
+                    .name("quedo-ipc-tcp-client".to_owned())
+                    .spawn(move || handle_client(stream, event_tx, pending_ids));
+            }
+        })
+        .map_err(|error| {
+            AppError::Controller(format!("failed to spawn ipc tcp listener thread: {error}"))
+        })?;
+
+    let (output_tx, output_rx) = crossbeam_channel::unbounded::<ControllerOutput>();
+    thread::Builder::new()
+        .name("quedo-ipc-tcp-broadcaster".to_owned())
+        .spawn(move || {
+            while let Ok(output) = output_rx.recv() {
+                let mut clients = clients.lock().expect("lock ipc tcp clients");
+                clients.retain_mut(|client| write_output(client, &output));
+            }
+        })
+        .map_err(|error| {
+            AppError::Controller(format!("failed to spawn ipc tcp broadcaster thread: {error}"))
+        })?;
+
+    Ok(output_tx)
+}
+
+/// Writes `output` to `client`, merging in the next pending correlation id
+/// (if any) as an `"id"` field alongside the existing `type`/`payload`
+/// envelope; returns whether the write succeeded, so the broadcaster can
+/// drop a disconnected client via `retain_mut`.
+fn write_output<S: Write>(client: &mut IpcClient<S>, output: &ControllerOutput) -> bool {
+    let Ok(mut value) = serde_json::to_value(output) else {
+        return true;
+    };
+    let id = client
+        .pending_ids
+        .lock()
+        .expect("lock pending ids")
+        .pop_front();
+    if let (Some(id), Value::Object(map)) = (id, &mut value) {
+        map.insert("id".to_owned(), id);
+    }
+    let Ok(mut line) = serde_json::to_vec(&value) else {
+        return true;
+    };
+    line.push(b'\n');
+    client.writer.write_all(&line).is_ok()
+}
+
+/// A stream type this module can clone to hand a writer half to
+/// `send_ack` while still reading commands off the original, implemented
+/// for both the Unix-domain and TCP transports `handle_client` serves.
+trait CloneableStream: Read + Write + Sized {
+    fn try_clone_stream(&self) -> std::io::Result<Self>;
+}
+
+impl CloneableStream for UnixStream {
+    fn try_clone_stream(&self) -> std::io::Result<Self> {
+        self.try_clone()
+    }
+}
+
+impl CloneableStream for TcpStream {
+    fn try_clone_stream(&self) -> std::io::Result<Self> {
+        self.try_clone()
+    }
+}
+
+fn handle_client<S: CloneableStream>(
+    stream: S,
+    event_tx: Sender<ControllerEvent>,
+    pending_ids: PendingIds,
+) {
+    let mut ack_writer = match stream.try_clone_stream() {
+        Ok(writer) => writer,
+        Err(error) => {
+            tracing::warn!("failed to clone ipc client stream for acks: {error}");
+            return;
+        }
+    };
+    let mut offsets = OffsetTracker::default();
+
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let command = match serde_json::from_str::<IpcCommand>(trimmed) {
+            Ok(command) => command,
+            Err(error) => {
+                tracing::warn!("ignoring malformed ipc command `{trimmed}`: {error}");
+                continue;
+            }
+        };
+
+        let event = match command.cmd.as_str() {
+            "toggle" => Some(ControllerEvent::Toggle),
+            "start" => Some(ControllerEvent::Start),
+            "stop" => Some(ControllerEvent::Stop),
+            "cancel" => Some(ControllerEvent::Cancel),
+            "run_doctor" => Some(ControllerEvent::RunDoctor),
+            // "get_state" is the JSON-RPC-style name for exactly what
+            // "status" already does (re-emit the current `ControllerState`);
+            // both names share this one arm, so either is discoverable from
+            // this match alone.
+            "status" | "get_state" => Some(ControllerEvent::QueryStatus),
+            "shutdown" => Some(ControllerEvent::Shutdown(ShutdownMode::FlushPending)),
+            "enqueue" => match command.path.clone() {
+                Some(path) => Some(ControllerEvent::Enqueue { path }),
+                None => {
+                    tracing::warn!("ignoring ipc `enqueue` command missing a `path`");
+                    None
+                }
+            },
+            "history" => Some(ControllerEvent::QueryHistory {
+                limit: command.limit.unwrap_or(20),
+            }),
+            "captions" => Some(ControllerEvent::ExportCaptions {
+                format: command.format.unwrap_or(CaptionFormat::Srt),
+            }),
+            other => {
+                tracing::warn!("ignoring unknown ipc command: {other}");
+                None
+            }
+        };
+
+        let Some(event) = event else { continue };
+
+        // A command without an offset keeps the original at-most-once
+        // behavior; one with an offset is only applied if it's next in
+        // sequence, so a resent duplicate is acknowledged but dropped.
+        let applied = command.offset.map_or(true, |offset| offsets.accept(offset));
+
+        if applied && event_tx.send(event).is_err() {
+            break;
+        }
+
+        // Queue the id (if any) so the broadcaster echoes it back on the
+        // `ControllerOutput` this command eventually produces; see
+        // `PendingIds`. Queued even when the command wasn't applied (a
+        // redelivered duplicate), since the caller still expects exactly
+        // one correlated reply per id it sent.
+        if let Some(id) = command.id {
+            pending_ids.lock().expect("lock pending ids").push_back(id);
+        }
+
+        if let Some(offset) = command.offset {
+            send_ack(&mut ack_writer, offset, applied);
+        }
+    }
+}
+
+fn send_ack<W: Write>(writer: &mut W, offset: u64, applied: bool) {
+    let Ok(mut line) = serde_json::to_vec(&serde_json::json!({
+        "type": "ack",
+        "offset": offset,
+        "applied": applied,
+    })) else {
+        return;
+    };
+    line.push(b'\n');
+    let _ = writer.write_all(&line);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::spawn_ipc_server;
+    use crate::controller::events::{ControllerEvent, ControllerOutput};
+    use crate::controller::state::ControllerState;
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+
+    fn connect_with_retry(socket_path: &std::path::Path) -> UnixStream {
+        let deadline = std::time::Instant::now() + Duration::from_secs(2);
+        loop {
+            match UnixStream::connect(socket_path) {
+                Ok(stream) => return stream,
+                Err(_) if std::time::Instant::now() < deadline => {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+                Err(error) => panic!("failed to connect to ipc socket: {error}"),
+            }
+        }
+    }
+
+    #[test]
+    fn client_command_is_forwarded_as_controller_event() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let socket_path = temp.path().join("quedo.sock");
+        let (event_tx, event_rx) = crossbeam_channel::unbounded::<ControllerEvent>();
+
+        let _output_tx =
+            spawn_ipc_server(socket_path.clone(), event_tx).expect("spawn ipc server");
+
+        let mut client = connect_with_retry(&socket_path);
+        client
+            .write_all(b"{\"cmd\":\"toggle\"}\n")
+            .expect("write command");
+
+        let event = event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("receive forwarded event");
+        assert!(matches!(event, ControllerEvent::Toggle));
+    }
+
+    #[test]
+    fn controller_output_is_broadcast_to_connected_clients() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let socket_path = temp.path().join("quedo.sock");
+        let (event_tx, _event_rx) = crossbeam_channel::unbounded::<ControllerEvent>();
+
+        let output_tx = spawn_ipc_server(socket_path.clone(), event_tx).expect("spawn ipc server");
+
+        let client = connect_with_retry(&socket_path);
+        // Give the listener thread time to register the connection before
+        // the broadcast goes out.
+        std::thread::sleep(Duration::from_millis(50));
+
+        output_tx
+            .send(ControllerOutput::StateChanged(ControllerState::Recording))
+            .expect("send output");
+
+        let mut reader = BufReader::new(client);
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .expect("read broadcast line");
+        let value: serde_json::Value = serde_json::from_str(&line).expect("parse json");
+        assert_eq!(value.get("type").and_then(|v| v.as_str()), Some("state_changed"));
+    }
+
+    #[test]
+    fn status_command_is_forwarded_as_query_status_event() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let socket_path = temp.path().join("quedo.sock");
+        let (event_tx, event_rx) = crossbeam_channel::unbounded::<ControllerEvent>();
+
+        let _output_tx =
+            spawn_ipc_server(socket_path.clone(), event_tx).expect("spawn ipc server");
+
+        let mut client = connect_with_retry(&socket_path);
+        client
+            .write_all(b"{\"cmd\":\"status\"}\n")
+            .expect("write command");
+
+        let event = event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("receive forwarded event");
+        assert!(matches!(event, ControllerEvent::QueryStatus));
+    }
+
+    #[test]
+    fn enqueue_command_is_forwarded_with_its_path() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let socket_path = temp.path().join("quedo.sock");
+        let (event_tx, event_rx) = crossbeam_channel::unbounded::<ControllerEvent>();
+
+        let _output_tx =
+            spawn_ipc_server(socket_path.clone(), event_tx).expect("spawn ipc server");
+
+        let mut client = connect_with_retry(&socket_path);
+        client
+            .write_all(b"{\"cmd\":\"enqueue\",\"path\":\"/tmp/clip.wav\"}\n")
+            .expect("write command");
+
+        let event = event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("receive forwarded event");
+        match event {
+            ControllerEvent::Enqueue { path } => {
+                assert_eq!(path, std::path::PathBuf::from("/tmp/clip.wav"))
+            }
+            other => panic!("expected Enqueue, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn enqueue_command_without_a_path_is_ignored() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let socket_path = temp.path().join("quedo.sock");
+        let (event_tx, event_rx) = crossbeam_channel::unbounded::<ControllerEvent>();
+
+        let _output_tx =
+            spawn_ipc_server(socket_path.clone(), event_tx).expect("spawn ipc server");
+
+        let mut client = connect_with_retry(&socket_path);
+        client
+            .write_all(b"{\"cmd\":\"enqueue\"}\n")
+            .expect("write command");
+        client
+            .write_all(b"{\"cmd\":\"status\"}\n")
+            .expect("write trailing command");
+
+        let event = event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("receive forwarded event");
+        assert!(matches!(event, ControllerEvent::QueryStatus));
+    }
+
+    #[test]
+    fn history_command_defaults_its_limit() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let socket_path = temp.path().join("quedo.sock");
+        let (event_tx, event_rx) = crossbeam_channel::unbounded::<ControllerEvent>();
+
+        let _output_tx =
+            spawn_ipc_server(socket_path.clone(), event_tx).expect("spawn ipc server");
+
+        let mut client = connect_with_retry(&socket_path);
+        client
+            .write_all(b"{\"cmd\":\"history\"}\n")
+            .expect("write command");
+
+        let event = event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("receive forwarded event");
+        assert!(matches!(event, ControllerEvent::QueryHistory { limit: 20 }));
+    }
+
+    #[test]
+    fn history_command_honors_an_explicit_limit() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let socket_path = temp.path().join("quedo.sock");
+        let (event_tx, event_rx) = crossbeam_channel::unbounded::<ControllerEvent>();
+
+        let _output_tx =
+            spawn_ipc_server(socket_path.clone(), event_tx).expect("spawn ipc server");
+
+        let mut client = connect_with_retry(&socket_path);
+        client
+            .write_all(b"{\"cmd\":\"history\",\"limit\":5}\n")
+            .expect("write command");
+
+        let event = event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("receive forwarded event");
+        assert!(matches!(event, ControllerEvent::QueryHistory { limit: 5 }));
+    }
+
+    #[test]
+    fn captions_command_defaults_to_srt() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let socket_path = temp.path().join("quedo.sock");
+        let (event_tx, event_rx) = crossbeam_channel::unbounded::<ControllerEvent>();
+
+        let _output_tx =
+            spawn_ipc_server(socket_path.clone(), event_tx).expect("spawn ipc server");
+
+        let mut client = connect_with_retry(&socket_path);
+        client
+            .write_all(b"{\"cmd\":\"captions\"}\n")
+            .expect("write command");
+
+        let event = event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("receive forwarded event");
+        assert!(matches!(
+            event,
+            ControllerEvent::ExportCaptions {
+                format: crate::transcription::CaptionFormat::Srt
+            }
+        ));
+    }
+
+    #[test]
+    fn captions_command_honors_an_explicit_format() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let socket_path = temp.path().join("quedo.sock");
+        let (event_tx, event_rx) = crossbeam_channel::unbounded::<ControllerEvent>();
+
+        let _output_tx =
+            spawn_ipc_server(socket_path.clone(), event_tx).expect("spawn ipc server");
+
+        let mut client = connect_with_retry(&socket_path);
+        client
+            .write_all(b"{\"cmd\":\"captions\",\"format\":\"vtt\"}\n")
+            .expect("write command");
+
+        let event = event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("receive forwarded event");
+        assert!(matches!(
+            event,
+            ControllerEvent::ExportCaptions {
+                format: crate::transcription::CaptionFormat::Vtt
+            }
+        ));
+    }
+
+    #[test]
+    fn removes_stale_socket_file_before_binding() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let socket_path = temp.path().join("quedo.sock");
+        std::fs::write(&socket_path, b"stale").expect("write stale file");
+
+        let (event_tx, _event_rx) = crossbeam_channel::unbounded::<ControllerEvent>();
+        spawn_ipc_server(socket_path, event_tx).expect("spawn ipc server over stale file");
+    }
+
+    #[test]
+    fn resent_offset_is_acked_but_not_reapplied() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let socket_path = temp.path().join("quedo.sock");
+        let (event_tx, event_rx) = crossbeam_channel::unbounded::<ControllerEvent>();
+
+        let _output_tx =
+            spawn_ipc_server(socket_path.clone(), event_tx).expect("spawn ipc server");
+
+        let mut client = connect_with_retry(&socket_path);
+        client
+            .write_all(b"{\"cmd\":\"toggle\",\"offset\":0}\n")
+            .expect("write first command");
+        client
+            .write_all(b"{\"cmd\":\"toggle\",\"offset\":0}\n")
+            .expect("resend same offset");
+
+        assert!(matches!(
+            event_rx
+                .recv_timeout(Duration::from_secs(2))
+                .expect("first toggle forwarded"),
+            ControllerEvent::Toggle
+        ));
+        assert!(
+            event_rx.recv_timeout(Duration::from_millis(200)).is_err(),
+            "a resent offset must not be forwarded a second time"
+        );
+
+        let mut reader = BufReader::new(client);
+        let mut acks = String::new();
+        for _ in 0..2 {
+            let mut line = String::new();
+            reader.read_line(&mut line).expect("read ack line");
+            acks.push_str(&line);
+        }
+        let lines: Vec<serde_json::Value> = acks
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("parse ack"))
+            .collect();
+        assert_eq!(lines[0]["type"], "ack");
+        assert_eq!(lines[0]["offset"], 0);
+        assert_eq!(lines[0]["applied"], true);
+        assert_eq!(lines[1]["offset"], 0);
+        assert_eq!(lines[1]["applied"], false);
+    }
+
+    #[test]
+    fn get_state_command_is_forwarded_as_query_status_event() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let socket_path = temp.path().join("quedo.sock");
+        let (event_tx, event_rx) = crossbeam_channel::unbounded::<ControllerEvent>();
+
+        let _output_tx =
+            spawn_ipc_server(socket_path.clone(), event_tx).expect("spawn ipc server");
+
+        let mut client = connect_with_retry(&socket_path);
+        client
+            .write_all(b"{\"cmd\":\"get_state\",\"id\":\"req-1\"}\n")
+            .expect("write command");
+
+        let event = event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("receive forwarded event");
+        assert!(matches!(event, ControllerEvent::QueryStatus));
+    }
+
+    #[test]
+    fn output_for_a_command_with_an_id_echoes_it_back() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let socket_path = temp.path().join("quedo.sock");
+        let (event_tx, _event_rx) = crossbeam_channel::unbounded::<ControllerEvent>();
+
+        let output_tx = spawn_ipc_server(socket_path.clone(), event_tx).expect("spawn ipc server");
+
+        let client = connect_with_retry(&socket_path);
+        let mut writer = client.try_clone().expect("clone client for writing");
+        writer
+            .write_all(b"{\"cmd\":\"get_state\",\"id\":\"req-1\"}\n")
+            .expect("write command");
+        // Give the listener thread time to register the id before the
+        // reply goes out.
+        std::thread::sleep(Duration::from_millis(50));
+
+        output_tx
+            .send(ControllerOutput::StateChanged(ControllerState::Recording))
+            .expect("send output");
+
+        let mut reader = BufReader::new(client);
+        let mut line = String::new();
+        reader.read_line(&mut line).expect("read reply line");
+        let value: serde_json::Value = serde_json::from_str(&line).expect("parse json");
+        assert_eq!(value.get("type").and_then(|v| v.as_str()), Some("state_changed"));
+        assert_eq!(value.get("id").and_then(|v| v.as_str()), Some("req-1"));
+    }
+
+    #[test]
+    fn unsolicited_output_carries_no_id() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let socket_path = temp.path().join("quedo.sock");
+        let (event_tx, _event_rx) = crossbeam_channel::unbounded::<ControllerEvent>();
+
+        let output_tx = spawn_ipc_server(socket_path.clone(), event_tx).expect("spawn ipc server");
+
+        let client = connect_with_retry(&socket_path);
+        std::thread::sleep(Duration::from_millis(50));
+
+        output_tx
+            .send(ControllerOutput::StateChanged(ControllerState::Recording))
+            .expect("send output");
+
+        let mut reader = BufReader::new(client);
+        let mut line = String::new();
+        reader.read_line(&mut line).expect("read broadcast line");
+        let value: serde_json::Value = serde_json::from_str(&line).expect("parse json");
+        assert!(
+            value.get("id").is_none(),
+            "an event with no preceding id-bearing command must not carry an id"
+        );
+    }
+}
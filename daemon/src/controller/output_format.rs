@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+use crate::controller::events::ControllerOutput;
+use crate::error::{AppError, AppResult};
+
+/// Which text encoding `serialize_output` renders a `ControllerOutput` as.
+/// Every consumer (stdout's `DiagnosticsConfig::emit_events` line,
+/// `controller::ipc`'s socket broadcast) already agrees on the same
+/// `type`/`payload` shape; this only changes how that shape is written out,
+/// so a user embedding the daemon in a script or config-driven pipeline can
+/// get human-readable YAML for the verbose `DoctorReport`/`TranscriptReady`
+/// payloads instead of a single dense JSON line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    Json,
+    Yaml,
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        OutputFormat::Json
+    }
+}
+
+/// Renders `output` as `format`, with no trailing newline (the caller
+/// appends its own line separator, the way `controller::ipc` already does
+/// for JSON). YAML rendering requires the `format-yaml` cargo feature;
+/// without it, `OutputFormat::Yaml` falls back to JSON rather than failing
+/// the whole line, since a malformed build flag shouldn't take down
+/// `emit_events` output.
+#[cfg(feature = "format-yaml")]
+pub fn serialize_output(output: &ControllerOutput, format: OutputFormat) -> AppResult<String> {
+    match format {
+        OutputFormat::Json => serde_json::to_string(output).map_err(AppError::from),
+        OutputFormat::Yaml => serde_yaml::to_string(output).map_err(|error| {
+            AppError::Config(format!("failed to serialize controller output as yaml: {error}"))
+        }),
+    }
+}
+
+#[cfg(not(feature = "format-yaml"))]
+pub fn serialize_output(output: &ControllerOutput, format: OutputFormat) -> AppResult<String> {
+    if format == OutputFormat::Yaml {
+        tracing::warn!(
+            "OutputFormat::Yaml requested but this build lacks the `format-yaml` cargo feature; falling back to json"
+        );
+    }
+    serde_json::to_string(output).map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{serialize_output, OutputFormat};
+    use crate::controller::events::ControllerOutput;
+    use crate::controller::state::ControllerState;
+
+    #[test]
+    fn json_format_produces_the_tagged_shape() {
+        let line = serialize_output(
+            &ControllerOutput::StateChanged(ControllerState::Recording),
+            OutputFormat::Json,
+        )
+        .expect("serialize json");
+        let value: serde_json::Value = serde_json::from_str(&line).expect("parse json");
+        assert_eq!(value.get("type").and_then(|v| v.as_str()), Some("state_changed"));
+    }
+
+    #[cfg(feature = "format-yaml")]
+    #[test]
+    fn yaml_format_preserves_the_same_tagged_shape() {
+        let body = serialize_output(
+            &ControllerOutput::StateChanged(ControllerState::Recording),
+            OutputFormat::Yaml,
+        )
+        .expect("serialize yaml");
+        let value: serde_yaml::Value = serde_yaml::from_str(&body).expect("parse yaml");
+        assert_eq!(
+            value.get("type").and_then(|v| v.as_str()),
+            Some("state_changed")
+        );
+    }
+
+    #[cfg(not(feature = "format-yaml"))]
+    #[test]
+    fn yaml_format_falls_back_to_json_without_the_feature() {
+        let line = serialize_output(
+            &ControllerOutput::StateChanged(ControllerState::Recording),
+            OutputFormat::Yaml,
+        )
+        .expect("serialize fallback");
+        assert!(serde_json::from_str::<serde_json::Value>(&line).is_ok());
+    }
+}
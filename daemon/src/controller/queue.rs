@@ -1,97 +1,384 @@
 use std::collections::VecDeque;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::error::{AppError, AppResult};
+use rusqlite::Connection;
 
+use crate::error::AppResult;
+
+/// Identifies one transcription job across its lifetime, from the moment it
+/// is enqueued to the `ControllerEvent::TranscriptionFinished` that reports
+/// its result. Assigned by `JobQueue::enqueue` and otherwise opaque; doubles
+/// as the `seq` primary key of the `queue` table when the queue is durable.
+pub type JobId = u64;
+
+/// Tracks pending and in-flight transcription jobs for the controller's
+/// worker pool. `max_in_flight` bounds how many jobs `start_next` will hand
+/// out before a matching `mark_finished`; jobs beyond that bound wait in
+/// `pending` in FIFO order, up to `max_queued`, past which `enqueue` refuses
+/// to grow the backlog further; see `TranscriptionConfig::max_queued_jobs`.
+///
+/// `pending`/`in_flight` are the fast, authoritative in-memory state for
+/// every method here; `db_path`, when set via `open_durable`, mirrors every
+/// mutation into a `queue` table so the backlog survives a crash or restart.
+/// Mirroring is best-effort: a write failure is logged and otherwise
+/// ignored rather than failing the caller, the same tradeoff
+/// `prune_history_if_configured` makes for `HistoryStore::prune`.
 #[derive(Debug)]
-pub struct SingleFlightQueue {
+pub struct JobQueue {
     max_in_flight: usize,
+    max_queued: usize,
     in_flight: usize,
-    pending: VecDeque<PathBuf>,
+    next_job_id: JobId,
+    pending: VecDeque<(JobId, PathBuf)>,
+    db_path: Option<PathBuf>,
 }
 
-impl SingleFlightQueue {
-    pub fn new(max_in_flight: usize) -> Self {
+impl JobQueue {
+    pub fn new(max_in_flight: usize, max_queued: usize) -> Self {
         Self {
             max_in_flight,
+            max_queued,
             in_flight: 0,
+            next_job_id: 0,
             pending: VecDeque::new(),
+            db_path: None,
+        }
+    }
+
+    /// Builds a `JobQueue` backed by a `queue` table in the SQLite database
+    /// at `db_path`, creating it if needed. Any row left `in_flight` from a
+    /// previous run is reset to `pending` first, since no worker is
+    /// actually running it anymore after a restart, then the full table
+    /// (now all `pending`) is loaded into `pending` in `seq` order so FIFO
+    /// ordering survives the restart too.
+    pub fn open_durable(
+        max_in_flight: usize,
+        max_queued: usize,
+        db_path: PathBuf,
+    ) -> AppResult<Self> {
+        let mut connection = Connection::open(&db_path)?;
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS queue (
+                seq INTEGER PRIMARY KEY,
+                path TEXT NOT NULL,
+                state TEXT NOT NULL
+            );",
+        )?;
+
+        let transaction = connection.transaction()?;
+        transaction.execute("UPDATE queue SET state = 'pending' WHERE state = 'in_flight'", [])?;
+
+        let mut pending = VecDeque::new();
+        let mut next_job_id: JobId = 0;
+        {
+            let mut statement = transaction.prepare("SELECT seq, path FROM queue ORDER BY seq ASC")?;
+            let mut rows = statement.query([])?;
+            while let Some(row) = rows.next()? {
+                let seq: i64 = row.get(0)?;
+                let path: String = row.get(1)?;
+                pending.push_back((seq as JobId, PathBuf::from(path)));
+                next_job_id = next_job_id.max(seq as JobId + 1);
+            }
         }
+        transaction.commit()?;
+
+        Ok(Self {
+            max_in_flight,
+            max_queued,
+            in_flight: 0,
+            next_job_id,
+            pending,
+            db_path: Some(db_path),
+        })
     }
 
-    pub fn enqueue(&mut self, path: PathBuf) -> AppResult<()> {
-        if self.in_flight + self.pending.len() >= self.max_in_flight {
-            return Err(AppError::Controller(
-                "single-flight queue full (max_in_flight=1)".to_owned(),
-            ));
+    /// Queues `path` for transcription, or returns `None` without enqueuing
+    /// it if `pending` is already at `max_queued` capacity; see
+    /// `ControllerEvent`'s handling of a full queue, which discards the
+    /// recording and notifies the user rather than growing the backlog
+    /// without bound.
+    pub fn enqueue(&mut self, path: PathBuf) -> Option<JobId> {
+        if self.pending.len() >= self.max_queued {
+            return None;
         }
-        self.pending.push_back(path);
-        Ok(())
+
+        let job_id = self.next_job_id;
+        self.next_job_id += 1;
+        persist(self.db_path.as_deref(), job_id, |connection| {
+            connection.execute(
+                "INSERT INTO queue (seq, path, state) VALUES (?1, ?2, 'pending')",
+                (job_id as i64, path.to_string_lossy().into_owned()),
+            )?;
+            Ok(())
+        });
+        self.pending.push_back((job_id, path));
+        Some(job_id)
     }
 
-    pub fn start_next(&mut self) -> Option<PathBuf> {
+    pub fn start_next(&mut self) -> Option<(JobId, PathBuf)> {
         if self.in_flight >= self.max_in_flight {
             return None;
         }
 
         let next = self.pending.pop_front();
-        if next.is_some() {
+        if let Some((job_id, _)) = next {
             self.in_flight += 1;
+            persist(self.db_path.as_deref(), job_id, |connection| {
+                connection.execute(
+                    "UPDATE queue SET state = 'in_flight' WHERE seq = ?1",
+                    [job_id as i64],
+                )?;
+                Ok(())
+            });
         }
         next
     }
 
-    pub fn mark_finished(&mut self) {
+    /// Marks `job_id` finished, deleting its `queue` row if the queue is
+    /// durable. Callers pass the id `start_next` handed them, so a stale or
+    /// already-finished id never decrements `in_flight` twice.
+    pub fn mark_finished(&mut self, job_id: JobId) {
         if self.in_flight > 0 {
             self.in_flight -= 1;
         }
+        persist(self.db_path.as_deref(), job_id, |connection| {
+            connection.execute("DELETE FROM queue WHERE seq = ?1", [job_id as i64])?;
+            Ok(())
+        });
+    }
+
+    /// Drops every job still waiting in `pending` (i.e. not yet handed to a
+    /// worker by `start_next`) and returns their ids. Jobs already in flight
+    /// are untouched, since they have no way to be recalled once a worker
+    /// has started on them; see `ControllerEvent::Cancel`.
+    pub fn cancel_pending(&mut self) -> Vec<JobId> {
+        let cancelled: Vec<JobId> = self.pending.drain(..).map(|(job_id, _)| job_id).collect();
+        for &job_id in &cancelled {
+            persist(self.db_path.as_deref(), job_id, |connection| {
+                connection.execute("DELETE FROM queue WHERE seq = ?1", [job_id as i64])?;
+                Ok(())
+            });
+        }
+        cancelled
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    pub fn queued(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+/// Runs `operation` against a fresh connection to `db_path` and logs a
+/// warning on failure rather than propagating it: the in-memory queue
+/// already reflects the authoritative state for this process, so a failed
+/// mirror write only risks losing durability across a crash, not correctness
+/// of the running daemon.
+fn persist(
+    db_path: Option<&Path>,
+    job_id: JobId,
+    operation: impl FnOnce(&Connection) -> AppResult<()>,
+) {
+    let Some(db_path) = db_path else {
+        return;
+    };
+
+    match Connection::open(db_path).map_err(crate::error::AppError::from) {
+        Ok(connection) => {
+            if let Err(error) = operation(&connection) {
+                tracing::warn!("failed to persist queue job {job_id}: {error}");
+            }
+        }
+        Err(error) => tracing::warn!("failed to open queue database for job {job_id}: {error}"),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::SingleFlightQueue;
-    use crate::error::AppError;
+    use super::JobQueue;
     use std::path::PathBuf;
 
     #[test]
     fn enqueue_and_start_on_empty_queue() {
-        let mut queue = SingleFlightQueue::new(1);
+        let mut queue = JobQueue::new(1, 8);
         let first = PathBuf::from("/tmp/a.wav");
-        queue.enqueue(first.clone()).expect("enqueue");
-        assert_eq!(queue.start_next(), Some(first));
+        let job_id = queue.enqueue(first.clone()).expect("queue has room");
+        assert_eq!(queue.start_next(), Some((job_id, first)));
         assert_eq!(queue.start_next(), None);
     }
 
     #[test]
-    fn queue_rejects_when_full() {
-        let mut queue = SingleFlightQueue::new(1);
-        queue.enqueue(PathBuf::from("/tmp/a.wav")).expect("enqueue");
-        let error = queue
-            .enqueue(PathBuf::from("/tmp/b.wav"))
-            .expect_err("must be full");
-        assert!(matches!(error, AppError::Controller(message) if message.contains("queue full")));
+    fn start_next_gates_on_max_in_flight() {
+        let mut queue = JobQueue::new(1, 8);
+        queue.enqueue(PathBuf::from("/tmp/a.wav"));
+        queue.enqueue(PathBuf::from("/tmp/b.wav"));
+
+        assert!(queue.start_next().is_some());
+        assert_eq!(
+            queue.start_next(),
+            None,
+            "second job must wait for the first to finish"
+        );
     }
 
     #[test]
     fn queue_fifo_ordering() {
-        let mut queue = SingleFlightQueue::new(2);
+        let mut queue = JobQueue::new(2, 8);
         let a = PathBuf::from("/tmp/a.wav");
         let b = PathBuf::from("/tmp/b.wav");
-        queue.enqueue(a.clone()).expect("enqueue a");
-        queue.enqueue(b.clone()).expect("enqueue b");
+        let job_a = queue.enqueue(a.clone()).expect("queue has room");
+        let job_b = queue.enqueue(b.clone()).expect("queue has room");
+        assert_ne!(job_a, job_b);
 
-        assert_eq!(queue.start_next(), Some(a));
-        queue.mark_finished();
-        assert_eq!(queue.start_next(), Some(b));
+        assert_eq!(queue.start_next(), Some((job_a, a)));
+        queue.mark_finished(job_a);
+        assert_eq!(queue.start_next(), Some((job_b, b)));
     }
 
     #[test]
     fn mark_finished_underflow_safe() {
-        let mut queue = SingleFlightQueue::new(1);
-        queue.mark_finished();
-        queue.mark_finished();
-        queue.enqueue(PathBuf::from("/tmp/a.wav")).expect("enqueue");
+        let mut queue = JobQueue::new(1, 8);
+        queue.mark_finished(0);
+        queue.mark_finished(0);
+        queue.enqueue(PathBuf::from("/tmp/a.wav"));
         assert!(queue.start_next().is_some());
     }
+
+    #[test]
+    fn cancel_pending_drains_queue_without_touching_in_flight() {
+        let mut queue = JobQueue::new(1, 8);
+        let a = PathBuf::from("/tmp/a.wav");
+        let b = PathBuf::from("/tmp/b.wav");
+        let c = PathBuf::from("/tmp/c.wav");
+        let job_a = queue.enqueue(a).expect("queue has room");
+        queue.enqueue(b).expect("queue has room");
+        queue.enqueue(c).expect("queue has room");
+
+        assert_eq!(queue.start_next().map(|(job_id, _)| job_id), Some(job_a));
+        assert_eq!(queue.in_flight(), 1);
+        assert_eq!(queue.queued(), 2);
+
+        let cancelled = queue.cancel_pending();
+        assert_eq!(cancelled.len(), 2);
+        assert_eq!(queue.queued(), 0);
+        assert_eq!(queue.in_flight(), 1, "in-flight job is left running");
+        assert_eq!(queue.start_next(), None, "nothing left to dispatch");
+    }
+
+    #[test]
+    fn pool_of_workers_runs_jobs_concurrently_and_queues_the_rest() {
+        let mut queue = JobQueue::new(2, 8);
+        let a = PathBuf::from("/tmp/a.wav");
+        let b = PathBuf::from("/tmp/b.wav");
+        let c = PathBuf::from("/tmp/c.wav");
+        queue.enqueue(a.clone());
+        queue.enqueue(b.clone());
+        queue.enqueue(c.clone());
+
+        assert_eq!(queue.start_next().map(|(_, path)| path), Some(a));
+        assert_eq!(queue.start_next().map(|(_, path)| path), Some(b));
+        assert_eq!(queue.in_flight(), 2);
+        assert_eq!(queue.queued(), 1);
+        assert!(queue.start_next().is_none(), "pool is at capacity");
+
+        queue.mark_finished(0);
+        assert_eq!(queue.start_next().map(|(_, path)| path), Some(c));
+        assert_eq!(queue.in_flight(), 2);
+        assert_eq!(queue.queued(), 0);
+    }
+
+    #[test]
+    fn enqueue_rejects_past_max_queued_without_touching_in_flight() {
+        let mut queue = JobQueue::new(1, 2);
+        queue.enqueue(PathBuf::from("/tmp/a.wav"))
+            .expect("first job starts");
+        assert!(queue.start_next().is_some());
+
+        queue
+            .enqueue(PathBuf::from("/tmp/b.wav"))
+            .expect("queue has room for one pending job");
+        queue
+            .enqueue(PathBuf::from("/tmp/c.wav"))
+            .expect("queue has room for a second pending job");
+        assert_eq!(queue.queued(), 2);
+
+        assert_eq!(
+            queue.enqueue(PathBuf::from("/tmp/d.wav")),
+            None,
+            "queue is already at max_queued capacity"
+        );
+        assert_eq!(queue.queued(), 2, "rejected job must not be queued");
+        assert_eq!(queue.in_flight(), 1, "in-flight job is unaffected");
+    }
+
+    fn rows(connection: &rusqlite::Connection) -> Vec<(i64, String, String)> {
+        let mut statement = connection
+            .prepare("SELECT seq, path, state FROM queue ORDER BY seq ASC")
+            .expect("prepare");
+        statement
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .expect("query")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("collect")
+    }
+
+    #[test]
+    fn durable_queue_persists_enqueue_start_and_finish() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let db_path = temp.path().join("queue.sqlite3");
+        let mut queue = JobQueue::open_durable(1, 8, db_path.clone()).expect("open durable");
+
+        let job_id = queue
+            .enqueue(PathBuf::from("/tmp/a.wav"))
+            .expect("queue has room");
+        let connection = rusqlite::Connection::open(&db_path).expect("open");
+        assert_eq!(
+            rows(&connection),
+            vec![(job_id as i64, "/tmp/a.wav".to_owned(), "pending".to_owned())]
+        );
+
+        queue.start_next();
+        assert_eq!(
+            rows(&connection),
+            vec![(job_id as i64, "/tmp/a.wav".to_owned(), "in_flight".to_owned())]
+        );
+
+        queue.mark_finished(job_id);
+        assert!(rows(&connection).is_empty());
+    }
+
+    #[test]
+    fn durable_queue_recovers_orphaned_in_flight_rows_as_pending() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let db_path = temp.path().join("queue.sqlite3");
+        {
+            let connection = rusqlite::Connection::open(&db_path).expect("open");
+            connection
+                .execute_batch(
+                    "CREATE TABLE queue (seq INTEGER PRIMARY KEY, path TEXT NOT NULL, state TEXT NOT NULL);
+                     INSERT INTO queue (seq, path, state) VALUES (0, '/tmp/a.wav', 'in_flight');
+                     INSERT INTO queue (seq, path, state) VALUES (1, '/tmp/b.wav', 'pending');",
+                )
+                .expect("seed");
+        }
+
+        let mut queue = JobQueue::open_durable(1, 8, db_path.clone()).expect("open durable");
+        assert_eq!(queue.in_flight(), 0, "nothing is really running after a restart");
+        assert_eq!(queue.queued(), 2);
+
+        assert_eq!(
+            queue.start_next(),
+            Some((0, PathBuf::from("/tmp/a.wav"))),
+            "recovered row keeps its original seq-ordered place in line"
+        );
+
+        let connection = rusqlite::Connection::open(&db_path).expect("open");
+        let recovered_states: Vec<String> = rows(&connection)
+            .into_iter()
+            .map(|(_, _, state)| state)
+            .collect();
+        assert_eq!(recovered_states, vec!["in_flight".to_owned(), "pending".to_owned()]);
+    }
 }
@@ -5,8 +5,18 @@ use serde::{Deserialize, Serialize};
 pub enum ControllerState {
     Idle,
     Recording,
-    Processing,
+    /// A transcription job-dispatch subsystem is active: `in_flight` jobs are
+    /// running on worker threads and `queued` more are waiting for a free
+    /// worker; see `controller::queue::JobQueue`.
+    Processing { in_flight: usize, queued: usize },
     Degraded(String),
+    /// A required capability the configured backend depends on (see
+    /// `doctor::backend_availability_problems`) is missing, so the
+    /// controller cannot transcribe at all until the operator fixes it and
+    /// triggers `ControllerEvent::ReloadConfig` (e.g. via SIGHUP); distinct
+    /// from `Degraded`, which still allows recording/transcription to
+    /// proceed in a reduced capacity.
+    Unavailable(String),
 }
 
 #[cfg(test)]
@@ -18,8 +28,12 @@ mod tests {
         let cases = vec![
             ControllerState::Idle,
             ControllerState::Recording,
-            ControllerState::Processing,
+            ControllerState::Processing {
+                in_flight: 1,
+                queued: 2,
+            },
             ControllerState::Degraded("oops".to_owned()),
+            ControllerState::Unavailable("missing whisper-cli".to_owned()),
         ];
 
         for state in cases {
@@ -29,6 +43,22 @@ mod tests {
             let parsed: ControllerState = serde_json::from_str(&json).expect("deserialize");
             match (state, parsed) {
                 (ControllerState::Degraded(a), ControllerState::Degraded(b)) => assert_eq!(a, b),
+                (ControllerState::Unavailable(a), ControllerState::Unavailable(b)) => {
+                    assert_eq!(a, b)
+                }
+                (
+                    ControllerState::Processing {
+                        in_flight: a_flight,
+                        queued: a_queued,
+                    },
+                    ControllerState::Processing {
+                        in_flight: b_flight,
+                        queued: b_queued,
+                    },
+                ) => {
+                    assert_eq!(a_flight, b_flight);
+                    assert_eq!(a_queued, b_queued);
+                }
                 (lhs, rhs) => assert_eq!(format!("{lhs:?}"), format!("{rhs:?}")),
             }
         }
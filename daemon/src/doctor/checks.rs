@@ -1,14 +1,135 @@
 use std::process::Command;
+use std::thread;
+use std::time::Duration;
 
-use chrono::Utc;
+use franken_whisper::BackendKind;
+use realfft::RealFftPlanner;
 use regex::Regex;
 
 use crate::bootstrap::AppPaths;
-use crate::capture::devices::list_input_devices;
+use crate::capture::devices::{dedupe_by_group, describe_input_devices};
+use crate::capture::{CaptureWatchdogConfig, MicrophoneCapture, VadConfig};
+use crate::clock::Clocks;
+use crate::config::schema::{AudioConfig, OutputMode};
 use crate::config::AppConfig;
 use crate::doctor::report::{CheckResult, CheckStatus, DoctorReport, DoctorState};
+use crate::error::{AppError, AppResult};
+use crate::output::ClipboardOutput;
+use crate::transcription::engine::{backend_compiled_in, backend_feature_name};
+
+const CLIPBOARD_PROBE_SENTINEL: &str = "quedo-doctor-clipboard-probe";
+
+/// Default time budget for a `doctor` probe subprocess before `run_probe_command`
+/// kills it. Generous enough for a slow `--version`/`-e` invocation under
+/// load, short enough that one wedged driver (a stalled `arecord`, a hung
+/// `swift -e`) doesn't hang the whole `doctor` run.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time budget for the Metal smoke test's own subprocesses (`ffmpeg`
+/// synthesizing the probe WAV, `whisper-cli` transcribing it), which
+/// legitimately take longer than a `--version` probe but must still be
+/// bounded rather than hanging `doctor` forever.
+const SMOKE_TEST_PROBE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often `run_probe_command` polls `Child::try_wait` while a probe is
+/// still running.
+const PROBE_POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+/// Outcome of a `run_probe_command` invocation: either the child exited
+/// (successfully or not, same as `Command::output`) within `timeout`, or it
+/// didn't and was killed.
+enum ProbeOutcome {
+    Completed(std::process::Output),
+    TimedOut,
+}
+
+/// Runs `command` to completion or until `timeout` elapses, whichever comes
+/// first, without the deadlock `Command::output` would hit against a child
+/// that wedges: stdout/stderr are piped and drained on dedicated threads
+/// concurrently with polling `Child::try_wait`, so a child that fills a pipe
+/// buffer without exiting still gets killed at the deadline instead of
+/// blocking this thread on a full pipe. Past the deadline the child is sent
+/// SIGTERM then, if it hasn't exited shortly after, SIGKILL (plain
+/// `Child::kill` on non-Unix) and reaped with `wait()` so it doesn't become
+/// a zombie.
+fn run_probe_command(command: &mut Command, timeout: Duration) -> std::io::Result<ProbeOutcome> {
+    let mut child = command
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()?;
+
+    let mut stdout_pipe = child.stdout.take();
+    let mut stderr_pipe = child.stderr.take();
+    let stdout_reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(pipe) = stdout_pipe.as_mut() {
+            let _ = std::io::Read::read_to_end(pipe, &mut buf);
+        }
+        buf
+    });
+    let stderr_reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(pipe) = stderr_pipe.as_mut() {
+            let _ = std::io::Read::read_to_end(pipe, &mut buf);
+        }
+        buf
+    });
+
+    let deadline = std::time::Instant::now() + timeout;
+    loop {
+        match child.try_wait()? {
+            Some(status) => {
+                let stdout = stdout_reader.join().unwrap_or_default();
+                let stderr = stderr_reader.join().unwrap_or_default();
+                return Ok(ProbeOutcome::Completed(std::process::Output {
+                    status,
+                    stdout,
+                    stderr,
+                }));
+            }
+            None if std::time::Instant::now() >= deadline => {
+                kill_probe(&mut child);
+                let _ = child.wait();
+                let _ = stdout_reader.join();
+                let _ = stderr_reader.join();
+                return Ok(ProbeOutcome::TimedOut);
+            }
+            None => thread::sleep(PROBE_POLL_INTERVAL),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn kill_probe(child: &mut std::process::Child) {
+    let pid = child.id() as libc::pid_t;
+    unsafe {
+        libc::kill(pid, libc::SIGTERM);
+    }
+    thread::sleep(Duration::from_millis(200));
+    if matches!(child.try_wait(), Ok(None)) {
+        unsafe {
+            libc::kill(pid, libc::SIGKILL);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_probe(child: &mut std::process::Child) {
+    let _ = child.kill();
+}
 
-pub fn run_doctor(paths: &AppPaths, config: &AppConfig) -> DoctorReport {
+/// Runs every doctor check. `device`, when set, overrides `config.audio.device`
+/// for the microphone-signal and Metal/whisper smoke-test checks, letting a
+/// user validate one specific input device end-to-end instead of whatever
+/// `audio.device` happens to be configured; see `check_microphone_signal_quality`
+/// and `check_macos_metal`.
+pub fn run_doctor(
+    paths: &AppPaths,
+    config: &AppConfig,
+    clocks: &dyn Clocks,
+    device: Option<&str>,
+) -> DoctorReport {
     let mut checks = vec![
         check_binary_version(
             "ffmpeg",
@@ -22,43 +143,73 @@ pub fn run_doctor(paths: &AppPaths, config: &AppConfig) -> DoctorReport {
             true,
             Some("Install ffmpeg package, which includes ffprobe."),
         ),
-        check_binary_version(
+        check_backend_binary(
             "whisper-cli",
             "1.7.2",
             true,
             Some("Install whisper.cpp and ensure whisper-cli is in PATH."),
+            backend_compiled_in(BackendKind::WhisperCpp)
+                || backend_compiled_in(BackendKind::WhisperDiarization),
+            &[BackendKind::WhisperCpp, BackendKind::WhisperDiarization],
         ),
-        check_binary_version(
+        check_backend_binary(
             "insanely-fast-whisper",
             "0.0.15",
             false,
             Some("Install with pipx install insanely-fast-whisper if you want fallback backend."),
+            backend_compiled_in(BackendKind::InsanelyFast),
+            &[BackendKind::InsanelyFast],
         ),
     ];
 
     let python_required = config.transcription.diarize;
-    checks.push(check_binary_version(
+    checks.push(check_backend_binary(
         "python3",
         "3.10",
         python_required,
         Some("Install python3 >= 3.10 for diarization backend support."),
+        backend_compiled_in(BackendKind::WhisperDiarization),
+        &[BackendKind::WhisperDiarization],
     ));
 
     checks.push(check_microphone_permission(
         config.permissions.microphone_required,
     ));
     checks.push(check_recording_backend_capability());
-    checks.extend(check_macos_metal(paths));
+    checks.push(check_audio_server());
+    checks.push(check_microphone_signal_quality(&config.audio, device));
+    checks.push(check_hotkey_bindings(config));
+    checks.push(check_hotkey_registration(config));
+    checks.push(check_clipboard_pipeline(config));
+    checks.push(check_keystroke_injector(config));
+    checks.extend(check_macos_metal(paths, device));
 
     let state = derive_state(&checks);
 
     DoctorReport {
-        generated_at_rfc3339: Utc::now().to_rfc3339(),
+        generated_at_rfc3339: clocks.now_rfc3339(),
         state,
         checks,
     }
 }
 
+/// Re-runs the full `run_doctor` pass and returns just the named check's
+/// updated result, so `doctor --fix` can report whether a just-applied
+/// remediation actually took effect without hand-maintaining a name -> check
+/// function lookup table of its own.
+pub fn rerun_check(
+    name: &str,
+    paths: &AppPaths,
+    config: &AppConfig,
+    clocks: &dyn Clocks,
+    device: Option<&str>,
+) -> Option<CheckResult> {
+    run_doctor(paths, config, clocks, device)
+        .checks
+        .into_iter()
+        .find(|check| check.name == name)
+}
+
 fn derive_state(checks: &[CheckResult]) -> DoctorState {
     let required_failed = checks
         .iter()
@@ -77,36 +228,814 @@ fn derive_state(checks: &[CheckResult]) -> DoctorState {
 }
 
 fn check_recording_backend_capability() -> CheckResult {
-    match list_input_devices() {
-        Ok(devices) if devices.is_empty() => CheckResult {
-            name: "recording_backend".to_owned(),
+    match describe_input_devices() {
+        Ok(devices) if devices.is_empty() => match probe_non_alsa_capture_backend() {
+            Some((backend, count)) => CheckResult {
+                name: "recording_backend".to_owned(),
+                status: CheckStatus::Pass,
+                detail: format!("{count} capture source(s) discovered via {backend}"),
+                required: true,
+                remediation: None,
+            },
+            None => CheckResult {
+                name: "recording_backend".to_owned(),
+                status: CheckStatus::Warn,
+                detail: "no recording devices discovered".to_owned(),
+                required: true,
+                remediation: Some(
+                    "Connect a microphone and verify audio subsystem configuration.".to_owned(),
+                ),
+            },
+        },
+        Ok(devices) => {
+            let physical_count = dedupe_by_group(&devices);
+            let detail = match devices.iter().find(|device| device.is_default) {
+                Some(default_device) => match &default_device.group_id {
+                    Some(group) => format!(
+                        "{physical_count} device(s) discovered (default group `{group}`)"
+                    ),
+                    None => format!("{physical_count} device(s) discovered"),
+                },
+                None => format!("{physical_count} device(s) discovered"),
+            };
+            CheckResult {
+                name: "recording_backend".to_owned(),
+                status: CheckStatus::Pass,
+                detail,
+                required: true,
+                remediation: None,
+            }
+        }
+        Err(error) => match probe_non_alsa_capture_backend() {
+            Some((backend, count)) => CheckResult {
+                name: "recording_backend".to_owned(),
+                status: CheckStatus::Pass,
+                detail: format!("{count} capture source(s) discovered via {backend}"),
+                required: true,
+                remediation: None,
+            },
+            None => CheckResult {
+                name: "recording_backend".to_owned(),
+                status: CheckStatus::Fail,
+                detail: format!("recording backend unavailable: {error}"),
+                required: true,
+                remediation: Some(
+                    "Install/enable `arecord` or `ffmpeg` recording support for Linux capture."
+                        .to_owned(),
+                ),
+            },
+        },
+    }
+}
+
+/// Tries, in order, the PulseAudio/PipeWire-pulse compatibility layer
+/// (`pactl`), native PipeWire (`pw-cli`), and WirePlumber (`wpctl`) for a
+/// capture-capable source, returning the first backend that reports one or
+/// more. `describe_input_devices`/`arecord -l` only see raw ALSA devices, so
+/// a system routed exclusively through Pulse/PipeWire with no ALSA-visible
+/// capture card reads as having no microphone even though one is reachable
+/// through the sound server; this is the fallback `check_recording_backend_capability`
+/// and `check_microphone_permission`'s `microphone_probe` reach for before
+/// giving up.
+#[cfg(target_os = "linux")]
+fn probe_non_alsa_capture_backend() -> Option<(&'static str, usize)> {
+    let probes: [(&str, fn() -> Option<usize>); 3] = [
+        ("PulseAudio/PipeWire (pactl)", pactl_capture_source_count),
+        ("PipeWire (pw-cli)", pw_cli_capture_node_count),
+        ("PipeWire (wpctl)", wpctl_capture_source_count),
+    ];
+    probes
+        .into_iter()
+        .find_map(|(backend, probe)| probe().filter(|count| *count > 0).map(|count| (backend, count)))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn probe_non_alsa_capture_backend() -> Option<(&'static str, usize)> {
+    None
+}
+
+/// Counts capture sources from `pactl list short sources`, one per line.
+/// PulseAudio and PipeWire-pulse both list a `.monitor` source per playback
+/// sink (a loopback of what's playing, not a microphone), so those are
+/// filtered out; otherwise a speakers-only system would read as having
+/// capture capability it doesn't.
+#[cfg(target_os = "linux")]
+fn pactl_capture_source_count() -> Option<usize> {
+    if which::which("pactl").is_err() {
+        return None;
+    }
+
+    match run_probe_command(
+        Command::new("pactl").args(["list", "short", "sources"]),
+        PROBE_TIMEOUT,
+    )
+    .ok()?
+    {
+        ProbeOutcome::Completed(output) if output.status.success() => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            Some(
+                stdout
+                    .lines()
+                    .filter(|line| !line.trim().is_empty() && !line.contains(".monitor"))
+                    .count(),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Counts PipeWire nodes whose `media.class` is `Audio/Source` (a capture
+/// device) from `pw-cli ls Node`, for PipeWire-only systems without the
+/// `pactl` compatibility layer installed.
+#[cfg(target_os = "linux")]
+fn pw_cli_capture_node_count() -> Option<usize> {
+    if which::which("pw-cli").is_err() {
+        return None;
+    }
+
+    match run_probe_command(Command::new("pw-cli").args(["ls", "Node"]), PROBE_TIMEOUT).ok()? {
+        ProbeOutcome::Completed(output) if output.status.success() => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            Some(stdout.matches("\"Audio/Source\"").count())
+        }
+        _ => None,
+    }
+}
+
+/// Counts device entries under the `Sources:` heading of `wpctl status`, the
+/// WirePlumber CLI most PipeWire desktops actually ship (unlike raw
+/// `pw-cli`). Stops at the first line that isn't a numbered tree entry,
+/// which is how `wpctl status` delimits each device-group section.
+#[cfg(target_os = "linux")]
+fn wpctl_capture_source_count() -> Option<usize> {
+    if which::which("wpctl").is_err() {
+        return None;
+    }
+
+    match run_probe_command(Command::new("wpctl").arg("status"), PROBE_TIMEOUT).ok()? {
+        ProbeOutcome::Completed(output) if output.status.success() => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let count = stdout
+                .lines()
+                .skip_while(|line| !line.contains("Sources:"))
+                .skip(1)
+                .take_while(|line| {
+                    let trimmed = line.trim_start_matches(['│', '├', '└', '─', ' ']);
+                    trimmed.chars().next().is_some_and(|c| c.is_ascii_digit())
+                })
+                .count();
+            Some(count)
+        }
+        _ => None,
+    }
+}
+
+/// Lowest PulseAudio-protocol version (shared by PulseAudio and PipeWire's
+/// `pipewire-pulse` compatibility layer) this check treats as known-good for
+/// the capture backend; older servers have been seen to negotiate capture
+/// formats cpal doesn't expect. Below this, `check_audio_server` warns
+/// instead of failing outright, since capture may still work.
+const MIN_PULSE_PROTOCOL_VERSION: u32 = 32;
+
+/// Identifies which server (PulseAudio or PipeWire-via-pulse) is actually
+/// serving audio on Linux, rather than assuming raw ALSA: `arecord`/`cpal`
+/// talk to whichever server owns the ALSA device, but `check_recording_backend_capability`
+/// has no way to say which server that is or whether its protocol is new
+/// enough. Shells out to `pactl info`, which both PulseAudio and PipeWire
+/// implement identically for client compatibility.
+#[cfg(target_os = "linux")]
+fn check_audio_server() -> CheckResult {
+    if which::which("pactl").is_err() {
+        return CheckResult {
+            name: "audio_server".to_owned(),
+            status: CheckStatus::Skip,
+            detail: "pactl not installed; cannot identify the PulseAudio/PipeWire server"
+                .to_owned(),
+            required: false,
+            remediation: Some(
+                "Install `pulseaudio-utils` (provided by PipeWire too) to identify the audio server."
+                    .to_owned(),
+            ),
+        };
+    }
+
+    let output = match run_probe_command(Command::new("pactl").arg("info"), PROBE_TIMEOUT) {
+        Ok(ProbeOutcome::Completed(output)) if output.status.success() => output,
+        Ok(ProbeOutcome::Completed(_)) => {
+            return CheckResult {
+                name: "audio_server".to_owned(),
+                status: CheckStatus::Skip,
+                detail: "pactl info failed; no PulseAudio/PipeWire server reachable, capture likely going straight to ALSA".to_owned(),
+                required: false,
+                remediation: None,
+            };
+        }
+        Ok(ProbeOutcome::TimedOut) => {
+            return CheckResult {
+                name: "audio_server".to_owned(),
+                status: CheckStatus::Warn,
+                detail: format!("pactl info timed out after {PROBE_TIMEOUT:?}"),
+                required: false,
+                remediation: Some("Verify the PulseAudio/PipeWire server is running and healthy.".to_owned()),
+            };
+        }
+        Err(error) => {
+            return CheckResult {
+                name: "audio_server".to_owned(),
+                status: CheckStatus::Warn,
+                detail: format!("failed to execute pactl info: {error}"),
+                required: false,
+                remediation: Some("Verify pactl is installed and executable.".to_owned()),
+            };
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let server_name = pactl_info_field(&stdout, "Server Name");
+    let protocol_version = pactl_info_field(&stdout, "Server Protocol Version")
+        .and_then(|value| value.parse::<u32>().ok());
+
+    match (server_name, protocol_version) {
+        (Some(name), Some(version)) => {
+            let detail = format!("{name}, protocol {version} (negotiated over the PulseAudio-compatible socket)");
+            if version < MIN_PULSE_PROTOCOL_VERSION {
+                CheckResult {
+                    name: "audio_server".to_owned(),
+                    status: CheckStatus::Warn,
+                    detail: format!(
+                        "{detail}, below the known-good minimum of {MIN_PULSE_PROTOCOL_VERSION}"
+                    ),
+                    required: false,
+                    remediation: Some(format!(
+                        "Update {name} to a release exposing protocol {MIN_PULSE_PROTOCOL_VERSION} or newer."
+                    )),
+                }
+            } else {
+                CheckResult {
+                    name: "audio_server".to_owned(),
+                    status: CheckStatus::Pass,
+                    detail,
+                    required: false,
+                    remediation: None,
+                }
+            }
+        }
+        _ => CheckResult {
+            name: "audio_server".to_owned(),
             status: CheckStatus::Warn,
-            detail: "no recording devices discovered".to_owned(),
-            required: true,
+            detail: "pactl info did not report a server name/protocol version".to_owned(),
+            required: false,
+            remediation: Some("Verify the PulseAudio/PipeWire server is running and healthy.".to_owned()),
+        },
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn check_audio_server() -> CheckResult {
+    CheckResult {
+        name: "audio_server".to_owned(),
+        status: CheckStatus::Skip,
+        detail: "not Linux".to_owned(),
+        required: false,
+        remediation: None,
+    }
+}
+
+/// Extracts the value of a `Key: value` line from `pactl info` output, which
+/// formats every field this way regardless of server.
+#[cfg(target_os = "linux")]
+fn pactl_info_field(text: &str, field: &str) -> Option<String> {
+    text.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        if key.trim() == field {
+            Some(value.trim().to_owned())
+        } else {
+            None
+        }
+    })
+}
+
+/// How long `check_microphone_signal_quality` records before analyzing the
+/// buffer. Long enough for a one-shot FFT over the whole capture to resolve
+/// the speech-relevant part of the spectrum, short enough that `doctor`
+/// doesn't feel hung.
+const SIGNAL_PROBE_DURATION: Duration = Duration::from_secs(1);
+
+/// RMS floor, in dBFS, used for the capture probe's watchdog instead of
+/// `AudioConfig::watchdog_silence_dbfs`: this check wants to record the full
+/// second regardless of how quiet it is, and makes its own silence call
+/// afterwards from the analyzed buffer.
+const SIGNAL_PROBE_WATCHDOG_SILENCE_DBFS: f32 = -90.0;
+
+/// Fraction of a buffer's total spectral energy that must sit in the DC bin
+/// for it to be flagged as a constant/stuck device rather than live (however
+/// quiet) signal.
+const DC_ENERGY_FRACTION_WARN: f64 = 0.98;
+
+/// RMS level, in dBFS, below which a non-DC, non-zero buffer is still
+/// treated as "no usable signal" rather than a pass, so a gain set far too
+/// low doesn't silently read as healthy.
+const BROADBAND_RMS_FLOOR_DBFS: f32 = -70.0;
+
+const SIGNAL_PROBE_DBFS_FLOOR: f32 = -120.0;
+
+fn signal_probe_linear_to_dbfs(linear: f32) -> f32 {
+    if linear <= 0.0 {
+        SIGNAL_PROBE_DBFS_FLOOR
+    } else {
+        (20.0 * linear.log10()).max(SIGNAL_PROBE_DBFS_FLOOR)
+    }
+}
+
+/// RMS and FFT summary of a `check_microphone_signal_quality` probe buffer.
+struct SignalQualityReport {
+    rms_dbfs: f32,
+    peak_bin_hz: f64,
+    dc_energy_fraction: f64,
+    all_zero: bool,
+}
+
+/// Records ~1 second from the configured input device and analyzes it with
+/// RMS + a real FFT, catching a dead/stuck device that
+/// `check_recording_backend_capability` can't: that check only confirms a
+/// device is *listed*, never that it actually produces usable audio.
+/// `device`, when set, overrides `audio.device` for this one probe, so
+/// `doctor --device <name>` can target a specific microphone without
+/// changing the persisted config.
+fn check_microphone_signal_quality(audio: &AudioConfig, device: Option<&str>) -> CheckResult {
+    let temp_dir = match tempfile::TempDir::new() {
+        Ok(dir) => dir,
+        Err(error) => {
+            return CheckResult {
+                name: "microphone_signal".to_owned(),
+                status: CheckStatus::Warn,
+                detail: format!("unable to create temp directory for capture probe: {error}"),
+                required: false,
+                remediation: None,
+            }
+        }
+    };
+
+    let capture = MicrophoneCapture::new(
+        device.map(ToOwned::to_owned).or_else(|| audio.device.clone()),
+    );
+    let watchdog = CaptureWatchdogConfig {
+        arming_timeout: Duration::from_millis(audio.arming_timeout_ms),
+        stall_timeout: Duration::from_millis(audio.stall_timeout_ms),
+        silence_threshold_dbfs: SIGNAL_PROBE_WATCHDOG_SILENCE_DBFS,
+    };
+    let vad = VadConfig {
+        energy_threshold: audio.vad_energy_threshold,
+        high_band_ratio_threshold: audio.vad_high_band_ratio_threshold,
+        auto_stop_silence: Duration::from_millis(audio.auto_stop_silence_ms),
+    };
+
+    let recording = match capture.start_recording(temp_dir.path(), watchdog, vad) {
+        Ok(recording) => recording,
+        Err(error) => {
+            return CheckResult {
+                name: "microphone_signal".to_owned(),
+                status: CheckStatus::Fail,
+                detail: format!("failed to start capture probe: {error}"),
+                required: false,
+                remediation: Some(
+                    "Verify the configured `audio.device` exists and is not held by another \
+                     application."
+                        .to_owned(),
+                ),
+            }
+        }
+    };
+
+    thread::sleep(SIGNAL_PROBE_DURATION);
+
+    let wav_path = match recording.stop() {
+        Ok(path) => path,
+        Err(error) => {
+            return CheckResult {
+                name: "microphone_signal".to_owned(),
+                status: CheckStatus::Fail,
+                detail: format!("capture probe failed: {error}"),
+                required: false,
+                remediation: Some(
+                    "Re-run doctor after checking the recording_backend result above.".to_owned(),
+                ),
+            }
+        }
+    };
+
+    match analyze_signal_quality(&wav_path) {
+        Ok(report) => signal_quality_result(&report),
+        Err(error) => CheckResult {
+            name: "microphone_signal".to_owned(),
+            status: CheckStatus::Fail,
+            detail: format!("failed to analyze capture probe: {error}"),
+            required: false,
+            remediation: None,
+        },
+    }
+}
+
+fn analyze_signal_quality(path: &std::path::Path) -> AppResult<SignalQualityReport> {
+    let mut reader = hound::WavReader::open(path).map_err(|error| {
+        AppError::Capture(format!("failed to open capture probe {}: {error}", path.display()))
+    })?;
+    let spec = reader.spec();
+    let sample_rate = spec.sample_rate.max(1);
+    let channels = spec.channels.max(1) as usize;
+
+    let samples: Vec<i16> = reader.samples::<i16>().collect::<Result<_, _>>().map_err(|error| {
+        AppError::Capture(format!("failed to read capture probe sample: {error}"))
+    })?;
+
+    let mono: Vec<f32> = samples
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().map(|&s| f32::from(s) / f32::from(i16::MAX)).sum::<f32>() / frame.len() as f32)
+        .collect();
+
+    let frame_len = mono.len() & !1;
+    if frame_len < 2 {
+        return Ok(SignalQualityReport {
+            rms_dbfs: SIGNAL_PROBE_DBFS_FLOOR,
+            peak_bin_hz: 0.0,
+            dc_energy_fraction: 0.0,
+            all_zero: true,
+        });
+    }
+
+    let sum_sq: f64 = mono[..frame_len].iter().map(|&s| f64::from(s) * f64::from(s)).sum();
+    let rms = (sum_sq / frame_len as f64).sqrt() as f32;
+    let rms_dbfs = signal_probe_linear_to_dbfs(rms);
+
+    let mut planner = RealFftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(frame_len);
+    let mut input = fft.make_input_vec();
+    input.copy_from_slice(&mono[..frame_len]);
+    let mut spectrum = fft.make_output_vec();
+    if fft.process(&mut input, &mut spectrum).is_err() {
+        return Err(AppError::Capture("fft over capture probe buffer failed".to_owned()));
+    }
+
+    let bin_hz = f64::from(sample_rate) / frame_len as f64;
+    let total_energy: f64 = spectrum.iter().map(|bin| f64::from(bin.norm_sqr())).sum();
+    let dc_energy = f64::from(spectrum[0].norm_sqr());
+    let dc_energy_fraction = if total_energy > 0.0 { dc_energy / total_energy } else { 0.0 };
+
+    let peak_bin = spectrum
+        .iter()
+        .enumerate()
+        .skip(1)
+        .max_by(|(_, a), (_, b)| a.norm_sqr().partial_cmp(&b.norm_sqr()).unwrap())
+        .map(|(index, _)| index)
+        .unwrap_or(0);
+
+    Ok(SignalQualityReport {
+        rms_dbfs,
+        peak_bin_hz: peak_bin as f64 * bin_hz,
+        dc_energy_fraction,
+        all_zero: total_energy == 0.0,
+    })
+}
+
+fn signal_quality_result(report: &SignalQualityReport) -> CheckResult {
+    if report.all_zero {
+        return CheckResult {
+            name: "microphone_signal".to_owned(),
+            status: CheckStatus::Warn,
+            detail: "captured buffer is exactly zero; the device may be muted or disconnected"
+                .to_owned(),
+            required: false,
+            remediation: Some(
+                "Check the input device's hardware mute switch and the OS input volume."
+                    .to_owned(),
+            ),
+        };
+    }
+
+    if report.dc_energy_fraction >= DC_ENERGY_FRACTION_WARN {
+        return CheckResult {
+            name: "microphone_signal".to_owned(),
+            status: CheckStatus::Warn,
+            detail: format!(
+                "{:.1} dBFS RMS, but {:.0}% of spectral energy sits in the DC bin; device may be \
+                 returning a constant value instead of live audio",
+                report.rms_dbfs,
+                report.dc_energy_fraction * 100.0
+            ),
+            required: false,
             remediation: Some(
-                "Connect a microphone and verify audio subsystem configuration.".to_owned(),
+                "Try a different input device or sample format; a constant-value stream usually \
+                 means the wrong device was selected."
+                    .to_owned(),
             ),
+        };
+    }
+
+    if report.rms_dbfs < BROADBAND_RMS_FLOOR_DBFS {
+        return CheckResult {
+            name: "microphone_signal".to_owned(),
+            status: CheckStatus::Warn,
+            detail: format!(
+                "{:.1} dBFS RMS, peak energy near {:.0} Hz, but no broadband energy clears the \
+                 {:.0} dBFS floor",
+                report.rms_dbfs, report.peak_bin_hz, BROADBAND_RMS_FLOOR_DBFS
+            ),
+            required: false,
+            remediation: Some("Input gain looks far too low; check mic volume/gain settings.".to_owned()),
+        };
+    }
+
+    CheckResult {
+        name: "microphone_signal".to_owned(),
+        status: CheckStatus::Pass,
+        detail: format!(
+            "{:.1} dBFS RMS, broadband energy peaking near {:.0} Hz",
+            report.rms_dbfs, report.peak_bin_hz
+        ),
+        required: false,
+        remediation: None,
+    }
+}
+
+fn check_hotkey_bindings(config: &AppConfig) -> CheckResult {
+    match crate::config::validate_bindings(&config.hotkey.bindings) {
+        Ok(parsed) => CheckResult {
+            name: "hotkey_bindings".to_owned(),
+            status: CheckStatus::Pass,
+            detail: format!("{} binding(s) parsed without collisions", parsed.len()),
+            required: false,
+            remediation: None,
         },
-        Ok(devices) => CheckResult {
-            name: "recording_backend".to_owned(),
+        Err(error) => CheckResult {
+            name: "hotkey_bindings".to_owned(),
+            status: CheckStatus::Fail,
+            detail: error.to_string(),
+            required: false,
+            remediation: Some(
+                "Fix the offending entry in `hotkey.bindings` in config.toml.".to_owned(),
+            ),
+        },
+    }
+}
+
+/// Attempts to actually register the configured chords with the platform
+/// hotkey backend (rather than just parsing them), surfacing a conflict
+/// with an already-grabbed system shortcut as a failed check instead of a
+/// silent no-op at runtime.
+fn check_hotkey_registration(config: &AppConfig) -> CheckResult {
+    match crate::ui::hotkey::HotkeyController::new(&config.hotkey.bindings, config.hotkey.mode) {
+        Ok(_controller) => CheckResult {
+            name: "hotkey_registration".to_owned(),
             status: CheckStatus::Pass,
-            detail: format!("{} device(s) discovered", devices.len()),
-            required: true,
+            detail: format!(
+                "{} binding(s) registered with the platform hotkey backend",
+                config.hotkey.bindings.len()
+            ),
+            required: false,
             remediation: None,
         },
         Err(error) => CheckResult {
-            name: "recording_backend".to_owned(),
+            name: "hotkey_registration".to_owned(),
             status: CheckStatus::Fail,
-            detail: format!("recording backend unavailable: {error}"),
-            required: true,
+            detail: error.to_string(),
+            required: false,
             remediation: Some(
-                "Install/enable `arecord` or `ffmpeg` recording support for Linux capture."
+                "Pick a different chord in `hotkey.bindings`; the current one is already \
+                 grabbed by another application or the OS."
                     .to_owned(),
             ),
         },
     }
 }
 
+/// Round-trips a sentinel string through the resolved clipboard provider,
+/// since the provider binary being on `PATH` doesn't guarantee it can
+/// actually read/write the session clipboard (e.g. a headless X11 server).
+fn check_clipboard_pipeline(config: &AppConfig) -> CheckResult {
+    if config.output.mode == OutputMode::Disabled {
+        return CheckResult {
+            name: "clipboard_pipeline".to_owned(),
+            status: CheckStatus::Skip,
+            detail: "output.mode is disabled; clipboard pipeline not in use".to_owned(),
+            required: false,
+            remediation: None,
+        };
+    }
+
+    match ClipboardOutput::round_trip(
+        config.output.clipboard_provider,
+        config.output.selection_target,
+        CLIPBOARD_PROBE_SENTINEL,
+    ) {
+        Ok(()) => CheckResult {
+            name: "clipboard_pipeline".to_owned(),
+            status: CheckStatus::Pass,
+            detail: format!(
+                "sentinel string round-tripped through the {:?} provider",
+                config.output.clipboard_provider
+            ),
+            required: false,
+            remediation: None,
+        },
+        Err(error) => CheckResult {
+            name: "clipboard_pipeline".to_owned(),
+            status: CheckStatus::Fail,
+            detail: error.to_string(),
+            required: false,
+            remediation: Some(
+                "Verify the configured `output.clipboard_provider` is installed and reachable \
+                 from this session (e.g. `$DISPLAY`/`$WAYLAND_DISPLAY` set)."
+                    .to_owned(),
+            ),
+        },
+    }
+}
+
+/// Confirms the keystroke injector for `output.mode = type_text` can open
+/// its virtual-device/event sink; skipped entirely when typing isn't
+/// selected, since there's nothing to probe.
+fn check_keystroke_injector(config: &AppConfig) -> CheckResult {
+    if config.output.mode != OutputMode::TypeText {
+        return CheckResult {
+            name: "keystroke_injector".to_owned(),
+            status: CheckStatus::Skip,
+            detail: "output.mode is not type_text".to_owned(),
+            required: false,
+            remediation: None,
+        };
+    }
+
+    keystroke_injector_probe(config.permissions.accessibility_required)
+}
+
+#[cfg(target_os = "macos")]
+fn keystroke_injector_probe(required: bool) -> CheckResult {
+    use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+
+    match CGEventSource::new(CGEventSourceStateID::HIDSystemState) {
+        Ok(_) => CheckResult {
+            name: "keystroke_injector".to_owned(),
+            status: CheckStatus::Pass,
+            detail: "opened a CGEventSource HID event sink".to_owned(),
+            required,
+            remediation: None,
+        },
+        Err(()) => CheckResult {
+            name: "keystroke_injector".to_owned(),
+            status: CheckStatus::Fail,
+            detail: "failed to create a CGEventSource HID event sink".to_owned(),
+            required,
+            remediation: Some(
+                "Grant Quedo Accessibility/Input Monitoring permission in System Settings."
+                    .to_owned(),
+            ),
+        },
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn keystroke_injector_probe(required: bool) -> CheckResult {
+    let has_wayland_tool =
+        std::env::var_os("WAYLAND_DISPLAY").is_some() && which::which("ydotool").is_ok();
+    let has_x11_tool = std::env::var_os("DISPLAY").is_some() && which::which("xdotool").is_ok();
+
+    if has_wayland_tool || has_x11_tool {
+        CheckResult {
+            name: "keystroke_injector".to_owned(),
+            status: CheckStatus::Pass,
+            detail: "found a virtual keyboard tool for the active session".to_owned(),
+            required,
+            remediation: None,
+        }
+    } else {
+        CheckResult {
+            name: "keystroke_injector".to_owned(),
+            status: CheckStatus::Fail,
+            detail: "no virtual keyboard tool found (looked for ydotool/xdotool)".to_owned(),
+            required,
+            remediation: Some(
+                "Install `ydotool` for Wayland sessions or `xdotool` for X11 sessions.".to_owned(),
+            ),
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn keystroke_injector_probe(required: bool) -> CheckResult {
+    match which::which("powershell") {
+        Ok(_) => CheckResult {
+            name: "keystroke_injector".to_owned(),
+            status: CheckStatus::Pass,
+            detail: "powershell available for SendKeys injection".to_owned(),
+            required,
+            remediation: None,
+        },
+        Err(_) => CheckResult {
+            name: "keystroke_injector".to_owned(),
+            status: CheckStatus::Fail,
+            detail: "powershell not found in PATH".to_owned(),
+            required,
+            remediation: Some("Ensure PowerShell is installed and on PATH.".to_owned()),
+        },
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+fn keystroke_injector_probe(required: bool) -> CheckResult {
+    CheckResult {
+        name: "keystroke_injector".to_owned(),
+        status: CheckStatus::Fail,
+        detail: "keystroke injection is not implemented for this platform".to_owned(),
+        required,
+        remediation: None,
+    }
+}
+
+/// Maps a transcription backend to the external binary its execution
+/// ultimately shells out to. `BackendKind::Auto` is intentionally skipped:
+/// it's resolved to a concrete backend at runtime, so there's nothing fixed
+/// to check ahead of time.
+fn backend_binary(backend: BackendKind) -> Option<&'static str> {
+    match backend {
+        BackendKind::Auto => None,
+        BackendKind::WhisperCpp | BackendKind::WhisperDiarization => Some("whisper-cli"),
+        BackendKind::InsanelyFast => Some("insanely-fast-whisper"),
+    }
+}
+
+/// Checks that whatever `config.transcription.backend` is actually set to
+/// is runnable on this host: its backing binary is on `PATH`, and for
+/// `WhisperDiarization`, that `python3` is too. Returns one problem string
+/// per missing dependency, naming the config field it comes from, so the
+/// `--check-config` CLI mode can report all of them in one pass. Unlike
+/// `run_doctor`'s binary checks (which probe every backend's binary
+/// regardless of what's configured), this only looks at the one backend
+/// actually selected.
+pub fn backend_availability_problems(config: &AppConfig) -> Vec<String> {
+    let mut problems = Vec::new();
+    let backend = config.transcription.backend;
+
+    if !backend_compiled_in(backend) {
+        problems.push(format!(
+            "transcription.backend = \"{backend:?}\" is not compiled into this build; rebuild \
+             with the `{}` feature enabled",
+            backend_feature_name(backend).unwrap_or("matching")
+        ));
+        return problems;
+    }
+
+    if let Some(binary) = backend_binary(backend) {
+        if which::which(binary).is_err() {
+            problems.push(format!(
+                "transcription.backend = \"{backend:?}\" requires `{binary}` on PATH, but it was not found"
+            ));
+        }
+    }
+
+    if backend == BackendKind::WhisperDiarization && which::which("python3").is_err() {
+        problems.push(
+            "transcription.backend = \"WhisperDiarization\" requires `python3` on PATH, but it \
+             was not found"
+                .to_owned(),
+        );
+    }
+
+    problems
+}
+
+/// Like `check_binary_version`, but for a binary that backs one or more
+/// transcription backends gated by Cargo feature flags: if none of
+/// `gating_backends` was compiled into this build, the binary can never be
+/// used regardless of whether it happens to be on `PATH`, so the check is
+/// reported as `Skip` with remediation pointing at the feature(s) to enable
+/// instead of a `Fail`/`Pass` that would otherwise mislead a minimal install.
+fn check_backend_binary(
+    binary: &str,
+    min_version: &str,
+    required: bool,
+    remediation: Option<&str>,
+    any_backend_compiled_in: bool,
+    gating_backends: &[BackendKind],
+) -> CheckResult {
+    if any_backend_compiled_in {
+        return check_binary_version(binary, min_version, required, remediation);
+    }
+
+    let features = gating_backends
+        .iter()
+        .filter_map(|backend| backend_feature_name(*backend))
+        .collect::<Vec<_>>()
+        .join("` or `");
+
+    CheckResult {
+        name: binary.to_owned(),
+        status: CheckStatus::Skip,
+        detail: format!("no compiled-in backend needs `{binary}`"),
+        required: false,
+        remediation: Some(format!("Rebuild with the `{features}` Cargo feature enabled.")),
+    }
+}
+
 fn check_binary_version(
     binary: &str,
     min_version: &str,
@@ -126,34 +1055,47 @@ fn check_binary_version(
         Err(_) => return missing(),
     };
 
-    let output = version_output(binary);
-    let parsed = output.as_deref().and_then(parse_version_triplet);
-
-    match parsed {
-        Some(found) => {
-            if version_at_least(&found, &parse_target_version(min_version)) {
-                CheckResult {
-                    name: binary.to_owned(),
-                    status: CheckStatus::Pass,
-                    detail: format!(
-                        "{} (>= {}) at {}",
-                        version_triplet_string(&found),
-                        min_version,
-                        path.display()
-                    ),
-                    required,
-                    remediation: None,
-                }
-            } else {
-                CheckResult {
-                    name: binary.to_owned(),
-                    status: CheckStatus::Fail,
-                    detail: format!("{} (< {})", version_triplet_string(&found), min_version),
-                    required,
-                    remediation: remediation.map(ToOwned::to_owned),
+    match version_output(binary) {
+        Some(VersionProbe::TimedOut) => CheckResult {
+            name: binary.to_owned(),
+            status: CheckStatus::Warn,
+            detail: format!("`{binary}` version probe timed out after {PROBE_TIMEOUT:?}"),
+            required,
+            remediation: remediation.map(ToOwned::to_owned),
+        },
+        Some(VersionProbe::Output(text)) => match parse_version_triplet(&text) {
+            Some(found) => {
+                if version_at_least(&found, &parse_target_version(min_version)) {
+                    CheckResult {
+                        name: binary.to_owned(),
+                        status: CheckStatus::Pass,
+                        detail: format!(
+                            "{} (>= {}) at {}",
+                            version_triplet_string(&found),
+                            min_version,
+                            path.display()
+                        ),
+                        required,
+                        remediation: None,
+                    }
+                } else {
+                    CheckResult {
+                        name: binary.to_owned(),
+                        status: CheckStatus::Fail,
+                        detail: format!("{} (< {})", version_triplet_string(&found), min_version),
+                        required,
+                        remediation: remediation.map(ToOwned::to_owned),
+                    }
                 }
             }
-        }
+            None => CheckResult {
+                name: binary.to_owned(),
+                status: CheckStatus::Warn,
+                detail: format!("installed at {}, version parse failed", path.display()),
+                required,
+                remediation: remediation.map(ToOwned::to_owned),
+            },
+        },
         None => CheckResult {
             name: binary.to_owned(),
             status: CheckStatus::Warn,
@@ -164,18 +1106,30 @@ fn check_binary_version(
     }
 }
 
-fn version_output(binary: &str) -> Option<String> {
+/// Result of probing a single `--version`/`-V`/`version` invocation; kept
+/// distinct from a parse failure so `check_binary_version` can report a
+/// timeout instead of the misleading "version parse failed".
+enum VersionProbe {
+    Output(String),
+    TimedOut,
+}
+
+fn version_output(binary: &str) -> Option<VersionProbe> {
     let variants = [["--version"], ["-V"], ["version"]];
 
     for args in variants {
-        let output = Command::new(binary).args(args).output().ok()?;
-        let text = if output.stdout.is_empty() {
-            String::from_utf8_lossy(&output.stderr).to_string()
-        } else {
-            String::from_utf8_lossy(&output.stdout).to_string()
-        };
-        if !text.trim().is_empty() {
-            return Some(text);
+        match run_probe_command(Command::new(binary).args(args), PROBE_TIMEOUT).ok()? {
+            ProbeOutcome::Completed(output) => {
+                let text = if output.stdout.is_empty() {
+                    String::from_utf8_lossy(&output.stderr).to_string()
+                } else {
+                    String::from_utf8_lossy(&output.stdout).to_string()
+                };
+                if !text.trim().is_empty() {
+                    return Some(VersionProbe::Output(text));
+                }
+            }
+            ProbeOutcome::TimedOut => return Some(VersionProbe::TimedOut),
         }
     }
 
@@ -239,9 +1193,16 @@ let status = AVCaptureDevice.authorizationStatus(for: .audio)
 print(status.rawValue)
 "#;
 
-        let output = Command::new("swift").arg("-e").arg(script).output();
+        let output = run_probe_command(Command::new("swift").arg("-e").arg(script), PROBE_TIMEOUT);
         match output {
-            Ok(output) => {
+            Ok(ProbeOutcome::TimedOut) => CheckResult {
+                name: "microphone_permission".to_owned(),
+                status: CheckStatus::Warn,
+                detail: format!("permission probe timed out after {PROBE_TIMEOUT:?}"),
+                required,
+                remediation: Some("Retry permission probe with `quedo-daemon doctor`.".to_owned()),
+            },
+            Ok(ProbeOutcome::Completed(output)) => {
                 let raw = String::from_utf8_lossy(&output.stdout).trim().to_owned();
                 match raw.as_str() {
                     "3" => CheckResult {
@@ -296,12 +1257,16 @@ print(status.rawValue)
 
     #[cfg(not(target_os = "macos"))]
     {
+        if let Some(result) = check_microphone_portal_permission(required) {
+            return result;
+        }
+
         let probe = which::which("arecord");
         match probe {
             Ok(_) => {
-                let output = Command::new("arecord").arg("-l").output();
+                let output = run_probe_command(Command::new("arecord").arg("-l"), PROBE_TIMEOUT);
                 match output {
-                    Ok(output) => {
+                    Ok(ProbeOutcome::Completed(output)) => {
                         let stdout = String::from_utf8_lossy(&output.stdout);
                         if stdout.to_ascii_lowercase().contains("card") {
                             CheckResult {
@@ -311,6 +1276,14 @@ print(status.rawValue)
                                 required,
                                 remediation: None,
                             }
+                        } else if let Some((backend, count)) = probe_non_alsa_capture_backend() {
+                            CheckResult {
+                                name: "microphone_probe".to_owned(),
+                                status: CheckStatus::Pass,
+                                detail: format!("{count} capture source(s) detected via {backend}"),
+                                required,
+                                remediation: None,
+                            }
                         } else {
                             CheckResult {
                                 name: "microphone_probe".to_owned(),
@@ -328,6 +1301,13 @@ print(status.rawValue)
                             }
                         }
                     }
+                    Ok(ProbeOutcome::TimedOut) => CheckResult {
+                        name: "microphone_probe".to_owned(),
+                        status: CheckStatus::Warn,
+                        detail: format!("arecord -l timed out after {PROBE_TIMEOUT:?}"),
+                        required,
+                        remediation: Some("Verify ALSA device routing is responsive.".to_owned()),
+                    },
                     Err(error) => CheckResult {
                         name: "microphone_probe".to_owned(),
                         status: if required {
@@ -341,23 +1321,156 @@ print(status.rawValue)
                     },
                 }
             }
-            Err(_) => CheckResult {
-                name: "microphone_probe".to_owned(),
-                status: if required {
-                    CheckStatus::Warn
-                } else {
-                    CheckStatus::Skip
-                },
-                detail: "arecord not installed; cannot probe input device availability".to_owned(),
-                required,
-                remediation: Some("Install `alsa-utils` and rerun doctor.".to_owned()),
-            },
+            Err(_) => match probe_non_alsa_capture_backend() {
+                Some((backend, count)) => CheckResult {
+                    name: "microphone_probe".to_owned(),
+                    status: CheckStatus::Pass,
+                    detail: format!("{count} capture source(s) detected via {backend}"),
+                    required,
+                    remediation: None,
+                },
+                None => CheckResult {
+                    name: "microphone_probe".to_owned(),
+                    status: if required {
+                        CheckStatus::Warn
+                    } else {
+                        CheckStatus::Skip
+                    },
+                    detail: "arecord not installed; cannot probe input device availability"
+                        .to_owned(),
+                    required,
+                    remediation: Some("Install `alsa-utils` and rerun doctor.".to_owned()),
+                },
+            },
+        }
+    }
+}
+
+/// Queries `org.freedesktop.impl.portal.PermissionStore` over the session
+/// D-Bus for the `microphone` entry in the `devices` table, which is how a
+/// sandboxed (Flatpak) session tracks per-app device grants. Returns `None`
+/// when no portal is reachable on this bus at all (a bare X11/Wayland
+/// session without a sandbox), so the caller falls back to the unsandboxed
+/// `arecord -l` probe; returns `Some` whenever a portal answered, even if
+/// that answer maps to `Fail`, since a definitive portal answer should never
+/// be shadowed by the ALSA fallback.
+#[cfg(not(target_os = "macos"))]
+fn check_microphone_portal_permission(required: bool) -> Option<CheckResult> {
+    if which::which("gdbus").is_err() {
+        return None;
+    }
+
+    let has_portal = match run_probe_command(
+        Command::new("gdbus").args([
+            "call",
+            "--session",
+            "--dest",
+            "org.freedesktop.DBus",
+            "--object-path",
+            "/org/freedesktop/DBus",
+            "--method",
+            "org.freedesktop.DBus.NameHasOwner",
+            "org.freedesktop.portal.Desktop",
+        ]),
+        PROBE_TIMEOUT,
+    )
+    .ok()?
+    {
+        ProbeOutcome::Completed(output) => output,
+        ProbeOutcome::TimedOut => return None,
+    };
+    if !String::from_utf8_lossy(&has_portal.stdout).contains("true") {
+        return None;
+    }
+
+    let lookup = run_probe_command(
+        Command::new("gdbus").args([
+            "call",
+            "--session",
+            "--dest",
+            "org.freedesktop.portal.Desktop",
+            "--object-path",
+            "/org/freedesktop/portal/desktop",
+            "--method",
+            "org.freedesktop.impl.portal.PermissionStore.Lookup",
+            "devices",
+            "microphone",
+        ]),
+        PROBE_TIMEOUT,
+    );
+
+    let name = "microphone_permission".to_owned();
+    Some(match lookup {
+        Ok(ProbeOutcome::Completed(output)) if output.status.success() => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            if stdout.contains("'yes'") {
+                CheckResult {
+                    name,
+                    status: CheckStatus::Pass,
+                    detail: "portal permission store reports microphone access granted"
+                        .to_owned(),
+                    required,
+                    remediation: None,
+                }
+            } else if stdout.contains("'no'") {
+                CheckResult {
+                    name,
+                    status: CheckStatus::Fail,
+                    detail: "portal permission store reports microphone access denied"
+                        .to_owned(),
+                    required,
+                    remediation: Some(
+                        "Grant microphone access via the desktop's portal permission settings (e.g. `flatpak permission-reset` or the Settings app's Application Permissions page)."
+                            .to_owned(),
+                    ),
+                }
+            } else {
+                CheckResult {
+                    name,
+                    status: if required {
+                        CheckStatus::Warn
+                    } else {
+                        CheckStatus::Skip
+                    },
+                    detail: "portal permission store has not recorded a microphone decision yet"
+                        .to_owned(),
+                    required,
+                    remediation: Some(
+                        "Start Quedo and grant microphone access when the portal prompts."
+                            .to_owned(),
+                    ),
+                }
+            }
         }
-    }
+        Ok(ProbeOutcome::TimedOut) => CheckResult {
+            name,
+            status: CheckStatus::Warn,
+            detail: format!("portal permission store lookup timed out after {PROBE_TIMEOUT:?}"),
+            required,
+            remediation: Some(
+                "Verify xdg-desktop-portal is running and reachable on the session bus."
+                    .to_owned(),
+            ),
+        },
+        _ => CheckResult {
+            name,
+            status: if required {
+                CheckStatus::Warn
+            } else {
+                CheckStatus::Skip
+            },
+            detail: "portal present but permission store lookup failed".to_owned(),
+            required,
+            remediation: Some(
+                "Verify xdg-desktop-portal is running and reachable on the session bus."
+                    .to_owned(),
+            ),
+        },
+    })
 }
 
 #[cfg(not(target_os = "macos"))]
-fn check_macos_metal(_paths: &AppPaths) -> Vec<CheckResult> {
+fn check_macos_metal(_paths: &AppPaths, _device: Option<&str>) -> Vec<CheckResult> {
     vec![CheckResult {
         name: "metal_backend".to_owned(),
         status: CheckStatus::Skip,
@@ -367,8 +1480,39 @@ fn check_macos_metal(_paths: &AppPaths) -> Vec<CheckResult> {
     }]
 }
 
+/// Records ~1 second from `device_name` into `dir` the same way
+/// `check_microphone_signal_quality` does, using conservative defaults
+/// rather than the configured `AudioConfig` since `check_macos_metal` isn't
+/// threaded the full config. Backs the `--device` path of `check_macos_metal`.
+#[cfg(target_os = "macos")]
+fn record_metal_smoke_device_sample(
+    device_name: &str,
+    dir: &std::path::Path,
+) -> AppResult<std::path::PathBuf> {
+    let capture = MicrophoneCapture::new(Some(device_name.to_owned()));
+    let watchdog = CaptureWatchdogConfig {
+        arming_timeout: Duration::from_millis(2_000),
+        stall_timeout: Duration::from_millis(750),
+        silence_threshold_dbfs: SIGNAL_PROBE_WATCHDOG_SILENCE_DBFS,
+    };
+    let vad = VadConfig {
+        energy_threshold: 0.02,
+        high_band_ratio_threshold: 0.15,
+        auto_stop_silence: Duration::from_millis(1_200),
+    };
+
+    let recording = capture.start_recording(dir, watchdog, vad)?;
+    thread::sleep(SIGNAL_PROBE_DURATION);
+    recording.stop()
+}
+
+/// `device`, when set, records the smoke-test WAV from that input device via
+/// `MicrophoneCapture` instead of synthesizing silence with ffmpeg's
+/// `anullsrc`, so `doctor --device <name>` exercises the real
+/// capture-to-transcribe pipeline end to end rather than just confirming
+/// whisper-cli runs.
 #[cfg(target_os = "macos")]
-fn check_macos_metal(paths: &AppPaths) -> Vec<CheckResult> {
+fn check_macos_metal(paths: &AppPaths, device: Option<&str>) -> Vec<CheckResult> {
     let mut results = Vec::new();
 
     let whisper_path = match which::which("whisper-cli") {
@@ -385,9 +1529,9 @@ fn check_macos_metal(paths: &AppPaths) -> Vec<CheckResult> {
         }
     };
 
-    let link_check = Command::new("otool").arg("-L").arg(&whisper_path).output();
+    let link_check = run_probe_command(Command::new("otool").arg("-L").arg(&whisper_path), PROBE_TIMEOUT);
     match link_check {
-        Ok(output) => {
+        Ok(ProbeOutcome::Completed(output)) => {
             let text = String::from_utf8_lossy(&output.stdout).to_ascii_lowercase();
             if text.contains("metal.framework") {
                 results.push(CheckResult {
@@ -407,6 +1551,13 @@ fn check_macos_metal(paths: &AppPaths) -> Vec<CheckResult> {
                 });
             }
         }
+        Ok(ProbeOutcome::TimedOut) => results.push(CheckResult {
+            name: "metal_link".to_owned(),
+            status: CheckStatus::Warn,
+            detail: format!("otool -L timed out after {PROBE_TIMEOUT:?}"),
+            required: true,
+            remediation: Some("Install Xcode command line tools.".to_owned()),
+        }),
         Err(error) => results.push(CheckResult {
             name: "metal_link".to_owned(),
             status: CheckStatus::Warn,
@@ -442,17 +1593,6 @@ fn check_macos_metal(paths: &AppPaths) -> Vec<CheckResult> {
         return results;
     }
 
-    if which::which("ffmpeg").is_err() {
-        results.push(CheckResult {
-            name: "metal_smoke".to_owned(),
-            status: CheckStatus::Fail,
-            detail: "ffmpeg missing; cannot generate smoke-test audio".to_owned(),
-            required: true,
-            remediation: Some("Install ffmpeg and rerun doctor.".to_owned()),
-        });
-        return results;
-    }
-
     let temp_dir = match tempfile::TempDir::new() {
         Ok(dir) => dir,
         Err(error) => {
@@ -467,48 +1607,109 @@ fn check_macos_metal(paths: &AppPaths) -> Vec<CheckResult> {
         }
     };
 
-    let wav_path = temp_dir.path().join("metal-smoke.wav");
-    let ffmpeg_result = Command::new("ffmpeg")
-        .args([
-            "-hide_banner",
-            "-loglevel",
-            "error",
-            "-f",
-            "lavfi",
-            "-i",
-            "anullsrc=r=16000:cl=mono",
-            "-t",
-            "1",
-        ])
-        .arg(&wav_path)
-        .output();
-
-    if ffmpeg_result.is_err() {
-        results.push(CheckResult {
-            name: "metal_smoke".to_owned(),
-            status: CheckStatus::Fail,
-            detail: "ffmpeg command failed while preparing smoke test".to_owned(),
-            required: true,
-            remediation: Some("Verify ffmpeg installation.".to_owned()),
-        });
-        return results;
-    }
+    let wav_path = match device {
+        Some(device_name) => {
+            match record_metal_smoke_device_sample(device_name, temp_dir.path()) {
+                Ok(path) => path,
+                Err(error) => {
+                    results.push(CheckResult {
+                        name: "metal_smoke".to_owned(),
+                        status: CheckStatus::Fail,
+                        detail: format!("failed to record from `{device_name}`: {error}"),
+                        required: true,
+                        remediation: Some(
+                            "Verify the device name matches `doctor --device ?` and is not held by another application."
+                                .to_owned(),
+                        ),
+                    });
+                    return results;
+                }
+            }
+        }
+        None => {
+            if which::which("ffmpeg").is_err() {
+                results.push(CheckResult {
+                    name: "metal_smoke".to_owned(),
+                    status: CheckStatus::Fail,
+                    detail: "ffmpeg missing; cannot generate smoke-test audio".to_owned(),
+                    required: true,
+                    remediation: Some("Install ffmpeg and rerun doctor.".to_owned()),
+                });
+                return results;
+            }
+
+            let wav_path = temp_dir.path().join("metal-smoke.wav");
+            let ffmpeg_result = run_probe_command(
+                Command::new("ffmpeg")
+                    .args([
+                        "-hide_banner",
+                        "-loglevel",
+                        "error",
+                        "-f",
+                        "lavfi",
+                        "-i",
+                        "anullsrc=r=16000:cl=mono",
+                        "-t",
+                        "1",
+                    ])
+                    .arg(&wav_path),
+                SMOKE_TEST_PROBE_TIMEOUT,
+            );
+
+            match ffmpeg_result {
+                Ok(ProbeOutcome::Completed(_)) => {}
+                Ok(ProbeOutcome::TimedOut) => {
+                    results.push(CheckResult {
+                        name: "metal_smoke".to_owned(),
+                        status: CheckStatus::Warn,
+                        detail: format!(
+                            "ffmpeg smoke-test audio generation timed out after {SMOKE_TEST_PROBE_TIMEOUT:?}"
+                        ),
+                        required: true,
+                        remediation: Some("Verify ffmpeg installation.".to_owned()),
+                    });
+                    return results;
+                }
+                Err(_) => {
+                    results.push(CheckResult {
+                        name: "metal_smoke".to_owned(),
+                        status: CheckStatus::Fail,
+                        detail: "ffmpeg command failed while preparing smoke test".to_owned(),
+                        required: true,
+                        remediation: Some("Verify ffmpeg installation.".to_owned()),
+                    });
+                    return results;
+                }
+            }
+
+            wav_path
+        }
+    };
 
     let output_prefix = temp_dir.path().join("out");
-    let smoke = Command::new("whisper-cli")
-        .arg("-m")
-        .arg(&model_path)
-        .arg("-f")
-        .arg(&wav_path)
-        .arg("-l")
-        .arg("en")
-        .arg("-otxt")
-        .arg("-of")
-        .arg(&output_prefix)
-        .output();
+    let smoke = run_probe_command(
+        Command::new("whisper-cli")
+            .arg("-m")
+            .arg(&model_path)
+            .arg("-f")
+            .arg(&wav_path)
+            .arg("-l")
+            .arg("en")
+            .arg("-otxt")
+            .arg("-of")
+            .arg(&output_prefix),
+        SMOKE_TEST_PROBE_TIMEOUT,
+    );
 
     match smoke {
-        Ok(output) => {
+        Ok(ProbeOutcome::TimedOut) => results.push(CheckResult {
+            name: "metal_smoke".to_owned(),
+            status: CheckStatus::Warn,
+            detail: format!("whisper-cli smoke test timed out after {SMOKE_TEST_PROBE_TIMEOUT:?}"),
+            required: true,
+            remediation: Some("Run whisper-cli manually to inspect backend logs.".to_owned()),
+        }),
+        Ok(ProbeOutcome::Completed(output)) => {
             if !output.status.success() {
                 results.push(CheckResult {
                     name: "metal_smoke".to_owned(),
@@ -565,14 +1766,24 @@ fn check_macos_metal(paths: &AppPaths) -> Vec<CheckResult> {
 #[cfg(test)]
 mod tests {
     use super::{
-        check_binary_version, check_microphone_permission, check_recording_backend_capability,
-        derive_state, parse_target_version, parse_version_triplet, run_doctor, version_at_least,
+        backend_availability_problems, check_audio_server, check_backend_binary,
+        check_binary_version, check_clipboard_pipeline, check_hotkey_bindings,
+        check_hotkey_registration, check_keystroke_injector, check_microphone_permission,
+        check_recording_backend_capability, derive_state, parse_target_version,
+        parse_version_triplet, run_doctor, run_probe_command, version_at_least, ProbeOutcome,
+        PROBE_TIMEOUT,
     };
+    #[cfg(target_os = "linux")]
+    use super::probe_non_alsa_capture_backend;
     use crate::bootstrap::paths::AppPaths;
-    use crate::config::schema::AppConfig;
+    use crate::clock::SystemClocks;
+    use crate::config::schema::{AppConfig, HotkeyAction, HotkeyBinding, OutputMode};
     use crate::doctor::report::{CheckResult, CheckStatus, DoctorState};
+    use franken_whisper::BackendKind;
     use std::fs;
     use std::path::Path;
+    use std::process::Command;
+    use std::time::Duration;
 
     struct EnvVarGuard {
         key: &'static str,
@@ -618,6 +1829,8 @@ mod tests {
             config_file: root.join("config/config.toml"),
             history_db: root.join("data/history.sqlite3"),
             autostart_file: root.join("autostart/quedo-daemon.desktop"),
+            ipc_socket: root.join("cache/quedo.sock"),
+            system_config_file: root.join("system-config.toml"),
         }
     }
 
@@ -630,6 +1843,39 @@ mod tests {
         assert!(!version_at_least(&[1, 0, 0], &parse_target_version("1.7.2")));
     }
 
+    #[test]
+    fn probe_command_kills_and_reaps_a_hanging_process() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let script = temp.path().join("hang.sh");
+        write_script(
+            &script,
+            r#"#!/bin/sh
+trap '' TERM
+sleep 30
+"#,
+        );
+
+        let start = std::time::Instant::now();
+        let outcome = run_probe_command(Command::new("sh").arg(&script), Duration::from_millis(100))
+            .expect("run probe command");
+        assert!(matches!(outcome, ProbeOutcome::TimedOut));
+        // SIGTERM is ignored by the script, so reaping it should still fall
+        // through to the SIGKILL escalation rather than hang the test.
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn probe_command_returns_completed_output_for_fast_commands() {
+        let outcome = run_probe_command(Command::new("echo").arg("hello"), PROBE_TIMEOUT)
+            .expect("run probe command");
+        match outcome {
+            ProbeOutcome::Completed(output) => {
+                assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "hello");
+            }
+            ProbeOutcome::TimedOut => panic!("expected the probe to complete"),
+        }
+    }
+
     #[test]
     fn state_derivation_matches_contract() {
         let checks = vec![CheckResult {
@@ -718,6 +1964,99 @@ echo "this is not a version"
         assert!(result.detail.contains("version parse failed"));
     }
 
+    #[test]
+    fn backend_binary_check_skips_when_no_gating_backend_is_compiled_in() {
+        let result = check_backend_binary(
+            "whisper-cli",
+            "1.7.2",
+            true,
+            Some("install"),
+            false,
+            &[BackendKind::WhisperCpp, BackendKind::WhisperDiarization],
+        );
+        assert_eq!(result.status, CheckStatus::Skip);
+        assert!(!result.required);
+        assert!(result
+            .remediation
+            .expect("remediation")
+            .contains("backend-whisper-cpp"));
+    }
+
+    #[test]
+    fn backend_binary_check_runs_the_normal_probe_when_a_gating_backend_is_compiled_in() {
+        let result = check_backend_binary(
+            "definitely-not-a-binary",
+            "1.0",
+            true,
+            Some("install"),
+            true,
+            &[BackendKind::InsanelyFast],
+        );
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.detail.contains("binary not found"));
+    }
+
+    #[test]
+    fn backend_availability_skips_auto() {
+        let _guard = crate::test_support::lock_env();
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let _path = EnvVarGuard::set("PATH", temp.path().to_str().expect("utf8"));
+
+        let mut config = AppConfig::default();
+        config.transcription.backend = BackendKind::Auto;
+        assert!(backend_availability_problems(&config).is_empty());
+    }
+
+    #[test]
+    fn backend_availability_reports_missing_binary() {
+        let _guard = crate::test_support::lock_env();
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let _path = EnvVarGuard::set("PATH", temp.path().to_str().expect("utf8"));
+
+        let mut config = AppConfig::default();
+        config.transcription.backend = BackendKind::WhisperCpp;
+        let problems = backend_availability_problems(&config);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("whisper-cli"));
+        assert!(problems[0].contains("transcription.backend"));
+    }
+
+    #[test]
+    fn backend_availability_passes_when_binary_present() {
+        let _guard = crate::test_support::lock_env();
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        write_script(
+            &temp.path().join("whisper-cli"),
+            r#"#!/bin/sh
+echo "mock version 9.9.9"
+"#,
+        );
+        let _path = EnvVarGuard::set("PATH", temp.path().to_str().expect("utf8"));
+
+        let mut config = AppConfig::default();
+        config.transcription.backend = BackendKind::WhisperCpp;
+        assert!(backend_availability_problems(&config).is_empty());
+    }
+
+    #[test]
+    fn backend_availability_requires_python_for_diarization() {
+        let _guard = crate::test_support::lock_env();
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        write_script(
+            &temp.path().join("whisper-cli"),
+            r#"#!/bin/sh
+echo "mock version 9.9.9"
+"#,
+        );
+        let _path = EnvVarGuard::set("PATH", temp.path().to_str().expect("utf8"));
+
+        let mut config = AppConfig::default();
+        config.transcription.backend = BackendKind::WhisperDiarization;
+        let problems = backend_availability_problems(&config);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("python3"));
+    }
+
     #[test]
     fn python_required_flag_toggles_with_diarize() {
         let _guard = crate::test_support::lock_env();
@@ -746,7 +2085,8 @@ echo "card 0: Device [Mock Device], device 0: Mock [Mock]"
 
         let mut config = AppConfig::default();
         config.transcription.diarize = false;
-        let report = run_doctor(&paths, &config);
+        let clocks = SystemClocks::new();
+        let report = run_doctor(&paths, &config, &clocks, None);
         let python = report
             .checks
             .iter()
@@ -755,7 +2095,7 @@ echo "card 0: Device [Mock Device], device 0: Mock [Mock]"
         assert!(!python.required);
 
         config.transcription.diarize = true;
-        let report = run_doctor(&paths, &config);
+        let report = run_doctor(&paths, &config, &clocks, None);
         let python = report
             .checks
             .iter()
@@ -794,6 +2134,112 @@ echo "card 0: Device [Mock Device], device 0: Mock [Mock]"
         assert_eq!(warn_error.status, CheckStatus::Warn);
     }
 
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn linux_microphone_portal_permission_outcomes() {
+        let _guard = crate::test_support::lock_env();
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let bin = temp.path().join("bin");
+        fs::create_dir_all(&bin).expect("mkdir");
+        let _path = EnvVarGuard::set("PATH", bin.to_str().expect("utf8"));
+
+        fn write_gdbus_mock(bin: &Path, lookup_reply: &str) {
+            write_script(
+                &bin.join("gdbus"),
+                &format!(
+                    r#"#!/bin/sh
+case "$*" in
+  *NameHasOwner*) echo "(true,)" ;;
+  *PermissionStore.Lookup*) echo "{lookup_reply}" ;;
+esac
+"#
+                ),
+            );
+        }
+
+        write_gdbus_mock(&bin, "({'microphone': ['yes']}, <@ay []>)");
+        let pass = check_microphone_permission(true);
+        assert_eq!(pass.status, CheckStatus::Pass);
+        assert_eq!(pass.name, "microphone_permission");
+
+        write_gdbus_mock(&bin, "({'microphone': ['no']}, <@ay []>)");
+        let fail = check_microphone_permission(true);
+        assert_eq!(fail.status, CheckStatus::Fail);
+
+        write_gdbus_mock(&bin, "({}, <@ay []>)");
+        let warn = check_microphone_permission(true);
+        assert_eq!(warn.status, CheckStatus::Warn);
+    }
+
+    #[test]
+    fn hotkey_bindings_check_reports_collisions_and_parse_errors() {
+        let mut config = AppConfig::default();
+        let pass = check_hotkey_bindings(&config);
+        assert_eq!(pass.status, CheckStatus::Pass);
+        assert!(!pass.required);
+
+        config.hotkey.bindings = vec![HotkeyBinding {
+            action: HotkeyAction::Toggle,
+            binding: "Ctrl+NotAKey".to_owned(),
+        }];
+        let parse_failure = check_hotkey_bindings(&config);
+        assert_eq!(parse_failure.status, CheckStatus::Fail);
+
+        config.hotkey.bindings = vec![
+            HotkeyBinding {
+                action: HotkeyAction::Toggle,
+                binding: "Ctrl+Shift+Space".to_owned(),
+            },
+            HotkeyBinding {
+                action: HotkeyAction::Start,
+                binding: "Ctrl+Shift+Space".to_owned(),
+            },
+        ];
+        let collision = check_hotkey_bindings(&config);
+        assert_eq!(collision.status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn hotkey_registration_attempts_real_registration() {
+        let config = AppConfig::default();
+        let result = check_hotkey_registration(&config);
+        assert_ne!(result.status, CheckStatus::Skip);
+    }
+
+    #[test]
+    fn clipboard_pipeline_skips_when_output_disabled() {
+        let mut config = AppConfig::default();
+        config.output.mode = OutputMode::Disabled;
+        let result = check_clipboard_pipeline(&config);
+        assert_eq!(result.status, CheckStatus::Skip);
+    }
+
+    #[test]
+    fn clipboard_pipeline_round_trips_with_internal_provider() {
+        let mut config = AppConfig::default();
+        config.output.mode = OutputMode::ClipboardOnly;
+        config.output.clipboard_provider = crate::config::ClipboardProviderKind::Internal;
+        let result = check_clipboard_pipeline(&config);
+        assert_eq!(result.status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn keystroke_injector_skips_outside_type_text_mode() {
+        let mut config = AppConfig::default();
+        config.output.mode = OutputMode::ClipboardOnly;
+        let result = check_keystroke_injector(&config);
+        assert_eq!(result.status, CheckStatus::Skip);
+    }
+
+    #[test]
+    fn keystroke_injector_honors_accessibility_required() {
+        let mut config = AppConfig::default();
+        config.output.mode = OutputMode::TypeText;
+        config.permissions.accessibility_required = true;
+        let result = check_keystroke_injector(&config);
+        assert!(result.required);
+    }
+
     #[cfg(not(target_os = "macos"))]
     #[test]
     fn recording_backend_capability_outcomes() {
@@ -825,4 +2271,96 @@ echo "card 0: Device [Mock Device], device 0: Mock [Mock]"
         let fail = check_recording_backend_capability();
         assert_eq!(fail.status, CheckStatus::Fail);
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn recording_backend_falls_back_to_pulse_pipewire_when_alsa_is_empty() {
+        let _guard = crate::test_support::lock_env();
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let bin = temp.path().join("bin");
+        fs::create_dir_all(&bin).expect("mkdir");
+        let _path = EnvVarGuard::set("PATH", bin.to_str().expect("utf8"));
+
+        write_script(&bin.join("arecord"), "#!/bin/sh\necho \"\"\n");
+        assert!(probe_non_alsa_capture_backend().is_none());
+
+        write_script(
+            &bin.join("pactl"),
+            r#"#!/bin/sh
+echo "0	alsa_output.pci-0000_00_1f.3.analog-stereo.monitor	module-alsa-card.c	s16le 2ch 44100Hz	SUSPENDED"
+echo "1	alsa_input.pci-0000_00_1f.3.analog-stereo	module-alsa-card.c	s16le 2ch 44100Hz	SUSPENDED"
+"#,
+        );
+        let (backend, count) = probe_non_alsa_capture_backend().expect("pactl source");
+        assert_eq!(backend, "PulseAudio/PipeWire (pactl)");
+        assert_eq!(count, 1);
+
+        let pass = check_recording_backend_capability();
+        assert_eq!(pass.status, CheckStatus::Pass);
+        assert!(pass.detail.contains("pactl"));
+
+        fs::remove_file(bin.join("pactl")).expect("remove pactl");
+        write_script(
+            &bin.join("wpctl"),
+            r#"#!/bin/sh
+cat <<'EOF'
+Audio
+ ├─ Sinks:
+ │      50. Built-in Audio Analog Stereo [vol: 0.50]
+ │
+ ├─ Sources:
+ │      51. Built-in Audio Analog Stereo [vol: 0.50]
+ │
+ └─ Filters:
+EOF
+"#,
+        );
+        let (backend, count) = probe_non_alsa_capture_backend().expect("wpctl source");
+        assert_eq!(backend, "PipeWire (wpctl)");
+        assert_eq!(count, 1);
+
+        fs::remove_file(bin.join("wpctl")).expect("remove wpctl");
+        assert!(probe_non_alsa_capture_backend().is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn audio_server_check_outcomes() {
+        let _guard = crate::test_support::lock_env();
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let bin = temp.path().join("bin");
+        fs::create_dir_all(&bin).expect("mkdir");
+        let _path = EnvVarGuard::set("PATH", bin.to_str().expect("utf8"));
+
+        write_script(
+            &bin.join("pactl"),
+            r#"#!/bin/sh
+echo "Server String: /run/user/1000/pulse/native"
+echo "Library Protocol Version: 35"
+echo "Server Protocol Version: 35"
+echo "Server Name: PulseAudio (on PipeWire 1.0.1)"
+"#,
+        );
+        let pass = check_audio_server();
+        assert_eq!(pass.status, CheckStatus::Pass);
+        assert!(pass.detail.contains("PipeWire"));
+
+        write_script(
+            &bin.join("pactl"),
+            r#"#!/bin/sh
+echo "Server Protocol Version: 12"
+echo "Server Name: PulseAudio"
+"#,
+        );
+        let warn = check_audio_server();
+        assert_eq!(warn.status, CheckStatus::Warn);
+
+        write_script(&bin.join("pactl"), "#!/bin/sh\nexit 1\n");
+        let skip = check_audio_server();
+        assert_eq!(skip.status, CheckStatus::Skip);
+
+        fs::remove_file(bin.join("pactl")).expect("remove");
+        let missing = check_audio_server();
+        assert_eq!(missing.status, CheckStatus::Skip);
+    }
 }
@@ -0,0 +1,358 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use crate::doctor::report::{CheckStatus, DoctorReport};
+
+/// One known-package-manager install action for a `CheckResult`, keyed by
+/// its `name` in `remediation_action_for`. Each field is the argument string
+/// passed to that package manager's binary; `None` means there's no known
+/// package for it on that manager (e.g. `whisper-cli` isn't apt-packaged
+/// anywhere). The freeform `CheckResult::remediation` string remains the
+/// source of truth for checks with no package-manager fix at all
+/// (permissions, hotkeys, clipboard pipeline).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemediationAction {
+    pub apt: Option<&'static str>,
+    pub brew: Option<&'static str>,
+    pub dnf: Option<&'static str>,
+    pub pacman: Option<&'static str>,
+    pub winget: Option<&'static str>,
+    pub pipx: Option<&'static str>,
+}
+
+/// Host package manager `detect_host_package_manager` found, carrying
+/// whatever is needed to build the concrete command line for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostPackageManager {
+    /// Homebrew's binary lives at a different prefix on Intel vs Apple
+    /// Silicon Macs; the path is resolved once here instead of re-probed at
+    /// every command build.
+    Brew(PathBuf),
+    Apt,
+    Dnf,
+    Pacman,
+    Winget,
+}
+
+/// One entry in a `run_doctor_fix` plan: the failing check it addresses, the
+/// resolved shell command (if any), and whether that command was actually
+/// run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FixPlanEntry {
+    pub check_name: String,
+    pub command: Option<String>,
+    /// Why `command` is `None`: no known remediation for this check, or no
+    /// supported package manager was detected on this host.
+    pub skip_reason: Option<String>,
+    pub executed: bool,
+}
+
+/// Known Homebrew binary locations, in no particular priority order: a host
+/// with both paths populated (e.g. a Rosetta-installed Intel brew alongside
+/// a native Apple Silicon one) is rare enough that picking the first match
+/// is fine.
+const BREW_PATHS: [&str; 2] = ["/usr/local/bin/brew", "/opt/homebrew/bin/brew"];
+
+fn detect_brew() -> Option<PathBuf> {
+    BREW_PATHS.iter().map(PathBuf::from).find(|path| path.is_file())
+}
+
+/// Probes for a package manager this host can actually run a command
+/// through, checking Homebrew first since `which::which` alone can't tell
+/// an Intel-prefix `brew` from an Apple Silicon one.
+fn detect_host_package_manager() -> Option<HostPackageManager> {
+    if let Some(brew_path) = detect_brew() {
+        return Some(HostPackageManager::Brew(brew_path));
+    }
+    if which::which("apt-get").is_ok() {
+        return Some(HostPackageManager::Apt);
+    }
+    if which::which("dnf").is_ok() {
+        return Some(HostPackageManager::Dnf);
+    }
+    if which::which("pacman").is_ok() {
+        return Some(HostPackageManager::Pacman);
+    }
+    if which::which("winget").is_ok() {
+        return Some(HostPackageManager::Winget);
+    }
+    None
+}
+
+/// Builds the concrete command line for `action` given `package_manager`,
+/// falling back to `pipx` (not itself a system package manager, so it's
+/// resolved independently of OS) when the detected manager has no entry for
+/// this action but a `pipx`-installable one exists.
+fn resolve_command(action: &RemediationAction, package_manager: Option<&HostPackageManager>) -> Option<String> {
+    let from_manager = match package_manager {
+        Some(HostPackageManager::Brew(brew_path)) => {
+            action.brew.map(|args| format!("{} {args}", brew_path.display()))
+        }
+        Some(HostPackageManager::Apt) => action.apt.map(|args| format!("apt-get {args}")),
+        Some(HostPackageManager::Dnf) => action.dnf.map(|args| format!("dnf {args}")),
+        Some(HostPackageManager::Pacman) => action.pacman.map(|args| format!("pacman {args}")),
+        Some(HostPackageManager::Winget) => action.winget.map(|args| format!("winget {args}")),
+        None => None,
+    };
+
+    from_manager.or_else(|| {
+        action
+            .pipx
+            .filter(|_| which::which("pipx").is_ok())
+            .map(|args| format!("pipx {args}"))
+    })
+}
+
+/// Known install actions for the external binaries `doctor::checks` probes.
+/// Anything not listed here (hotkey/clipboard/permission checks) has no
+/// package-manager fix and is reported with `skip_reason` instead.
+fn remediation_action_for(check_name: &str) -> Option<RemediationAction> {
+    match check_name {
+        "ffmpeg" | "ffprobe" => Some(RemediationAction {
+            apt: Some("install -y ffmpeg"),
+            brew: Some("install ffmpeg"),
+            dnf: Some("install -y ffmpeg"),
+            pacman: Some("-S --noconfirm ffmpeg"),
+            winget: Some("install --id Gyan.FFmpeg -e --silent"),
+            pipx: None,
+        }),
+        "whisper-cli" => Some(RemediationAction {
+            apt: None,
+            brew: Some("install whisper-cpp"),
+            dnf: None,
+            pacman: None,
+            winget: None,
+            pipx: None,
+        }),
+        "insanely-fast-whisper" => Some(RemediationAction {
+            apt: None,
+            brew: None,
+            dnf: None,
+            pacman: None,
+            winget: None,
+            pipx: Some("install insanely-fast-whisper"),
+        }),
+        "python3" => Some(RemediationAction {
+            apt: Some("install -y python3"),
+            brew: Some("install python3"),
+            dnf: Some("install -y python3"),
+            pacman: Some("-S --noconfirm python"),
+            winget: Some("install --id Python.Python.3.12 -e --silent"),
+            pipx: None,
+        }),
+        _ => None,
+    }
+}
+
+/// Resolves a fix plan for every `Fail`ed check in `report`, without running
+/// anything; see `run_doctor_fix` for the execute-gated entry point.
+pub fn build_fix_plan(report: &DoctorReport) -> Vec<FixPlanEntry> {
+    let package_manager = detect_host_package_manager();
+
+    report
+        .checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Fail)
+        .map(|check| {
+            let action = remediation_action_for(&check.name);
+            let command = action
+                .as_ref()
+                .and_then(|action| resolve_command(action, package_manager.as_ref()));
+
+            let skip_reason = if command.is_some() {
+                None
+            } else if action.is_none() {
+                Some("no known package-manager remediation for this check".to_owned())
+            } else {
+                Some(
+                    "no supported package manager (apt/dnf/pacman/brew/winget/pipx) detected"
+                        .to_owned(),
+                )
+            };
+
+            FixPlanEntry {
+                check_name: check.name.clone(),
+                command,
+                skip_reason,
+                executed: false,
+            }
+        })
+        .collect()
+}
+
+/// Builds the fix plan for `report` and, only when `execute` is true, runs
+/// each resolvable command through the shell. Dry-run (`execute = false`)
+/// just returns the plan, so `doctor --fix` without `--execute` preserves
+/// the existing read-only doctor behavior. Before running each command,
+/// `confirm` is asked whether to proceed, so a caller can gate execution on
+/// a per-action prompt (`doctor --fix --execute`) or skip the prompt
+/// entirely (`doctor --fix --execute --yes`, via `confirm: |_| true`).
+pub fn run_doctor_fix(
+    report: &DoctorReport,
+    execute: bool,
+    mut confirm: impl FnMut(&FixPlanEntry) -> bool,
+) -> Vec<FixPlanEntry> {
+    let mut plan = build_fix_plan(report);
+    if !execute {
+        return plan;
+    }
+
+    for entry in &mut plan {
+        let Some(command) = &entry.command else {
+            continue;
+        };
+        if !confirm(entry) {
+            entry.skip_reason = Some("declined by user".to_owned());
+            continue;
+        }
+        let status = Command::new("sh").arg("-c").arg(command).status();
+        entry.executed = matches!(status, Ok(status) if status.success());
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_fix_plan, remediation_action_for, resolve_command, run_doctor_fix,
+        HostPackageManager,
+    };
+    use crate::doctor::report::{CheckResult, CheckStatus, DoctorReport, DoctorState};
+
+    #[test]
+    fn ffmpeg_resolves_on_every_known_package_manager() {
+        let action = remediation_action_for("ffmpeg").expect("ffmpeg action");
+        assert!(resolve_command(&action, Some(&HostPackageManager::Apt)).is_some());
+        assert!(resolve_command(&action, Some(&HostPackageManager::Dnf)).is_some());
+        assert!(resolve_command(&action, Some(&HostPackageManager::Pacman)).is_some());
+        assert!(resolve_command(&action, Some(&HostPackageManager::Winget)).is_some());
+        assert!(resolve_command(
+            &action,
+            Some(&HostPackageManager::Brew(std::path::PathBuf::from(
+                "/opt/homebrew/bin/brew"
+            )))
+        )
+        .is_some());
+    }
+
+    #[test]
+    fn unknown_check_has_no_remediation_action() {
+        assert!(remediation_action_for("hotkey_bindings").is_none());
+    }
+
+    #[test]
+    fn fix_plan_only_covers_failing_checks() {
+        let report = DoctorReport {
+            generated_at_rfc3339: "2026-02-25T00:00:00Z".to_owned(),
+            state: DoctorState::Degraded,
+            checks: vec![
+                CheckResult {
+                    name: "ffmpeg".to_owned(),
+                    status: CheckStatus::Fail,
+                    detail: "missing".to_owned(),
+                    required: true,
+                    remediation: Some("install ffmpeg".to_owned()),
+                },
+                CheckResult {
+                    name: "hotkey_bindings".to_owned(),
+                    status: CheckStatus::Warn,
+                    detail: "no collisions".to_owned(),
+                    required: false,
+                    remediation: None,
+                },
+            ],
+        };
+
+        let plan = build_fix_plan(&report);
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].check_name, "ffmpeg");
+    }
+
+    #[test]
+    fn fix_plan_reports_a_skip_reason_for_checks_with_no_known_action() {
+        let report = DoctorReport {
+            generated_at_rfc3339: "2026-02-25T00:00:00Z".to_owned(),
+            state: DoctorState::Unavailable,
+            checks: vec![CheckResult {
+                name: "hotkey_registration".to_owned(),
+                status: CheckStatus::Fail,
+                detail: "chord already grabbed".to_owned(),
+                required: false,
+                remediation: Some("pick a different chord".to_owned()),
+            }],
+        };
+
+        let plan = build_fix_plan(&report);
+        assert_eq!(plan.len(), 1);
+        assert!(plan[0].command.is_none());
+        assert!(plan[0].skip_reason.is_some());
+    }
+
+    struct EnvVarGuard {
+        key: &'static str,
+        old: Option<String>,
+    }
+
+    impl EnvVarGuard {
+        fn set(key: &'static str, value: &str) -> Self {
+            let old = std::env::var(key).ok();
+            std::env::set_var(key, value);
+            Self { key, old }
+        }
+    }
+
+    impl Drop for EnvVarGuard {
+        fn drop(&mut self) {
+            if let Some(value) = self.old.as_ref() {
+                std::env::set_var(self.key, value);
+            } else {
+                std::env::remove_var(self.key);
+            }
+        }
+    }
+
+    #[test]
+    fn run_doctor_fix_never_executes_an_action_confirm_declines() {
+        let _guard = crate::test_support::lock_env();
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let bin = temp.path().join("bin");
+        std::fs::create_dir_all(&bin).expect("mkdir");
+        let marker = temp.path().join("ran");
+        std::fs::write(
+            bin.join("apt-get"),
+            format!("#!/bin/sh\ntouch {}\n", marker.display()),
+        )
+        .expect("write apt-get");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = std::fs::metadata(bin.join("apt-get"))
+                .expect("metadata")
+                .permissions();
+            perms.set_mode(0o755);
+            std::fs::set_permissions(bin.join("apt-get"), perms).expect("chmod");
+        }
+        let _path = EnvVarGuard::set("PATH", bin.to_str().expect("utf8"));
+
+        let report = DoctorReport {
+            generated_at_rfc3339: "2026-02-25T00:00:00Z".to_owned(),
+            state: DoctorState::Unavailable,
+            checks: vec![CheckResult {
+                name: "ffmpeg".to_owned(),
+                status: CheckStatus::Fail,
+                detail: "missing".to_owned(),
+                required: true,
+                remediation: Some("install ffmpeg".to_owned()),
+            }],
+        };
+
+        let plan = run_doctor_fix(&report, true, |_entry| false);
+        assert!(plan[0].command.is_some());
+        assert!(!plan[0].executed);
+        assert_eq!(plan[0].skip_reason.as_deref(), Some("declined by user"));
+        assert!(!marker.exists());
+    }
+}
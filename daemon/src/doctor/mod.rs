@@ -1,5 +1,8 @@
 pub mod checks;
+pub mod fix;
 pub mod report;
 
-pub use checks::run_doctor;
+pub use checks::{backend_availability_problems, rerun_check, run_doctor};
+pub use fix::{build_fix_plan, run_doctor_fix, FixPlanEntry, HostPackageManager, RemediationAction};
 pub use report::{CheckResult, CheckStatus, DoctorReport, DoctorState};
+pub(crate) use report::status_label;
@@ -33,6 +33,17 @@ pub struct DoctorReport {
     pub checks: Vec<CheckResult>,
 }
 
+/// The subset of `CheckResult` CI scripts care about for `doctor --format
+/// json`: `remediation` is dropped since that output is meant for a
+/// machine gating a pipeline on pass/fail, not for following up on a fix.
+#[derive(Debug, Clone, Serialize)]
+struct CheckSummary<'a> {
+    name: &'a str,
+    status: CheckStatus,
+    required: bool,
+    detail: &'a str,
+}
+
 impl DoctorReport {
     pub fn render_text(&self) -> String {
         let mut out = String::new();
@@ -62,9 +73,57 @@ impl DoctorReport {
 
         out
     }
+
+    /// Serializes `checks` (minus `remediation`) as a JSON array for
+    /// `doctor --format json`.
+    pub fn render_format_json(&self) -> serde_json::Result<String> {
+        let summaries: Vec<CheckSummary> = self
+            .checks
+            .iter()
+            .map(|check| CheckSummary {
+                name: &check.name,
+                status: check.status,
+                required: check.required,
+                detail: &check.detail,
+            })
+            .collect();
+        serde_json::to_string_pretty(&summaries)
+    }
+
+    /// Expands a stat(1)-style template against every check, one line per
+    /// check: `%n` (name), `%s` (status), `%r` (required flag, `yes`/`no`),
+    /// `%d` (detail). Lets `doctor --format '%n:%s'` be scripted over.
+    pub fn render_format(&self, template: &str) -> String {
+        let mut out = String::new();
+        for check in &self.checks {
+            let line = template
+                .replace("%n", &check.name)
+                .replace("%s", status_label(check.status))
+                .replace("%r", if check.required { "yes" } else { "no" })
+                .replace("%d", &check.detail);
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out
+    }
+
+    /// The process exit code CI pipelines should gate on: 0 when every
+    /// required check passed (non-required checks and warnings never fail
+    /// the build), nonzero when any required check is a hard `Fail`.
+    pub fn exit_code(&self) -> i32 {
+        let any_required_failed = self
+            .checks
+            .iter()
+            .any(|check| check.required && check.status == CheckStatus::Fail);
+        if any_required_failed {
+            1
+        } else {
+            0
+        }
+    }
 }
 
-fn status_label(status: CheckStatus) -> &'static str {
+pub(crate) fn status_label(status: CheckStatus) -> &'static str {
     match status {
         CheckStatus::Pass => "PASS",
         CheckStatus::Warn => "WARN",
@@ -110,4 +169,56 @@ mod tests {
         assert!(text.contains("FAIL"));
         assert!(text.contains("remediation: install whisper.cpp"));
     }
+
+    fn sample_report(fail_required: bool) -> DoctorReport {
+        DoctorReport {
+            generated_at_rfc3339: "2026-02-25T00:00:00Z".to_owned(),
+            state: DoctorState::Degraded,
+            checks: vec![
+                CheckResult {
+                    name: "ffmpeg".to_owned(),
+                    status: CheckStatus::Pass,
+                    detail: "ok".to_owned(),
+                    required: true,
+                    remediation: None,
+                },
+                CheckResult {
+                    name: "whisper-cli".to_owned(),
+                    status: if fail_required {
+                        CheckStatus::Fail
+                    } else {
+                        CheckStatus::Warn
+                    },
+                    detail: "missing".to_owned(),
+                    required: true,
+                    remediation: Some("install whisper.cpp".to_owned()),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn render_format_expands_tokens_per_check() {
+        let report = sample_report(true);
+        let text = report.render_format("%n:%s:%r:%d");
+        assert_eq!(
+            text,
+            "ffmpeg:PASS:yes:ok\nwhisper-cli:FAIL:yes:missing\n"
+        );
+    }
+
+    #[test]
+    fn render_format_json_drops_remediation() {
+        let report = sample_report(true);
+        let json = report.render_format_json().expect("serialize");
+        assert!(json.contains("\"name\": \"ffmpeg\""));
+        assert!(json.contains("\"status\": \"pass\""));
+        assert!(!json.contains("remediation"));
+    }
+
+    #[test]
+    fn exit_code_is_zero_unless_a_required_check_fails() {
+        assert_eq!(sample_report(false).exit_code(), 0);
+        assert_eq!(sample_report(true).exit_code(), 1);
+    }
 }
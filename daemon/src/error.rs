@@ -29,6 +29,12 @@ pub enum AppError {
     #[error("clipboard output failed: {0}")]
     Clipboard(String),
 
+    #[error("keystroke injection failed: {0}")]
+    TypeText(String),
+
+    #[error("command output failed: {0}")]
+    CommandOutput(String),
+
     #[error("controller error: {0}")]
     Controller(String),
 
@@ -40,6 +46,12 @@ pub enum AppError {
 
     #[error("sqlite error: {0}")]
     Sqlite(#[from] rusqlite::Error),
+
+    #[error("transcript script failed: {0}")]
+    Scripting(String),
+
+    #[error("history database error: {0}")]
+    History(String),
 }
 
 pub type AppResult<T> = Result<T, AppError>;
@@ -90,6 +102,14 @@ mod tests {
                 AppError::Clipboard("clipboard dead".to_owned()),
                 "clipboard output failed: clipboard dead",
             ),
+            (
+                AppError::TypeText("no virtual keyboard tool found".to_owned()),
+                "keystroke injection failed: no virtual keyboard tool found",
+            ),
+            (
+                AppError::CommandOutput("formatter exited with status 1".to_owned()),
+                "command output failed: formatter exited with status 1",
+            ),
             (
                 AppError::Controller("controller dead".to_owned()),
                 "controller error: controller dead",
@@ -102,6 +122,10 @@ mod tests {
                 AppError::Install("install failed".to_owned()),
                 "install failed: install failed",
             ),
+            (
+                AppError::Scripting("script boom".to_owned()),
+                "transcript script failed: script boom",
+            ),
             (
                 AppError::Sqlite(rusqlite::Error::SqliteFailure(
                     rusqlite::ffi::Error {
@@ -112,6 +136,10 @@ mod tests {
                 )),
                 "sqlite error: ",
             ),
+            (
+                AppError::History("schema version 9 is newer than this build supports".to_owned()),
+                "history database error: schema version 9 is newer than this build supports",
+            ),
         ];
 
         for (error, expected_prefix) in cases {
@@ -0,0 +1,426 @@
+use std::path::{Path, PathBuf};
+
+use franken_whisper::BackendKind;
+use serde::Serialize;
+
+use crate::config::TranscriptionConfig;
+use crate::error::{AppError, AppResult};
+use crate::transcription::engine::EngineAdapter;
+use crate::transcription::run_transcription_job;
+
+/// How many reference tokens were substituted, inserted, or deleted to turn
+/// a hypothesis into the reference, per the Levenshtein alignment
+/// `levenshtein_with_ops` backtracks out of its DP matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EditCounts {
+    pub substitutions: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+impl EditCounts {
+    fn total(&self) -> usize {
+        self.substitutions + self.insertions + self.deletions
+    }
+}
+
+/// Word error rate and character error rate for one hypothesis transcript
+/// against its golden reference, plus the word-level edit breakdown behind
+/// `wer`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorRateReport {
+    pub wer: f64,
+    pub cer: f64,
+    pub ops: EditCounts,
+}
+
+/// Lowercases, drops everything but ASCII alphanumerics and apostrophes, and
+/// collapses whitespace, so differences in punctuation/capitalization
+/// between a backend's transcript and a hand-typed golden text don't count
+/// as errors.
+pub fn normalize_text(raw: &str) -> String {
+    raw.to_ascii_lowercase()
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '\'' {
+                ch
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Computes the Levenshtein edit distance between `reference` and
+/// `hypothesis`, then backtracks the DP matrix to classify each edit as a
+/// substitution, insertion, or deletion rather than only returning the
+/// total distance.
+fn levenshtein_with_ops<T: PartialEq>(reference: &[T], hypothesis: &[T]) -> EditCounts {
+    let rows = reference.len() + 1;
+    let cols = hypothesis.len() + 1;
+    let mut dp = vec![vec![0_usize; cols]; rows];
+
+    for (row, item) in dp.iter_mut().enumerate() {
+        item[0] = row;
+    }
+    for (col, item) in dp[0].iter_mut().enumerate() {
+        *item = col;
+    }
+
+    for row in 1..rows {
+        for col in 1..cols {
+            let substitution_cost = if reference[row - 1] == hypothesis[col - 1] { 0 } else { 1 };
+            let substitution = dp[row - 1][col - 1] + substitution_cost;
+            let deletion = dp[row - 1][col] + 1;
+            let insertion = dp[row][col - 1] + 1;
+            dp[row][col] = substitution.min(deletion).min(insertion);
+        }
+    }
+
+    let mut counts = EditCounts::default();
+    let mut row = rows - 1;
+    let mut col = cols - 1;
+    while row > 0 || col > 0 {
+        if row > 0 && col > 0 {
+            let substitution_cost = if reference[row - 1] == hypothesis[col - 1] { 0 } else { 1 };
+            if dp[row][col] == dp[row - 1][col - 1] + substitution_cost {
+                if substitution_cost == 1 {
+                    counts.substitutions += 1;
+                }
+                row -= 1;
+                col -= 1;
+                continue;
+            }
+        }
+        if row > 0 && dp[row][col] == dp[row - 1][col] + 1 {
+            counts.deletions += 1;
+            row -= 1;
+            continue;
+        }
+        if col > 0 && dp[row][col] == dp[row][col - 1] + 1 {
+            counts.insertions += 1;
+            col -= 1;
+            continue;
+        }
+        // The DP table always has a path back to (0, 0) via one of the three
+        // arms above; this is just a guard against looping forever if it
+        // somehow doesn't.
+        break;
+    }
+    counts
+}
+
+/// Scores `hypothesis` (a produced transcript) against `reference` (its
+/// golden text): word error rate with a substitution/insertion/deletion
+/// breakdown, and character error rate. Both texts are run through
+/// `normalize_text` first so case, punctuation, and spacing differences
+/// aren't counted as errors.
+pub fn score_transcript(reference: &str, hypothesis: &str) -> ErrorRateReport {
+    let reference = normalize_text(reference);
+    let hypothesis = normalize_text(hypothesis);
+
+    let reference_words: Vec<&str> = reference.split_whitespace().collect();
+    let hypothesis_words: Vec<&str> = hypothesis.split_whitespace().collect();
+    let ops = levenshtein_with_ops(&reference_words, &hypothesis_words);
+    let wer = if reference_words.is_empty() {
+        if hypothesis_words.is_empty() { 0.0 } else { 1.0 }
+    } else {
+        ops.total() as f64 / reference_words.len() as f64
+    };
+
+    let reference_chars: Vec<char> = reference.chars().collect();
+    let hypothesis_chars: Vec<char> = hypothesis.chars().collect();
+    let char_ops = levenshtein_with_ops(&reference_chars, &hypothesis_chars);
+    let cer = if reference_chars.is_empty() {
+        if hypothesis_chars.is_empty() { 0.0 } else { 1.0 }
+    } else {
+        char_ops.total() as f64 / reference_chars.len() as f64
+    };
+
+    ErrorRateReport { wer, cer, ops }
+}
+
+/// One fixture in an offline evaluation suite: a capture paired with the
+/// golden transcript it should produce.
+#[derive(Debug, Clone)]
+pub struct EvalCase {
+    pub wav_path: PathBuf,
+    pub golden_text: String,
+}
+
+/// Mean accuracy of one backend over an evaluation suite; see
+/// `run_evaluation_suite`.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct BackendEvalSummary {
+    pub backend: BackendKind,
+    pub case_count: usize,
+    pub mean_wer: f64,
+    pub mean_cer: f64,
+}
+
+/// Pairs every `*.wav` fixture directly inside `fixtures_dir` with a sibling
+/// `*.txt` of the same stem holding its golden transcript. A `.wav` with no
+/// matching golden file is skipped with a warning rather than failing the
+/// whole directory, since a CI fixture set grows incrementally.
+pub fn discover_eval_cases(fixtures_dir: &Path) -> AppResult<Vec<EvalCase>> {
+    let entries = std::fs::read_dir(fixtures_dir).map_err(|error| {
+        AppError::Transcription(format!(
+            "failed to read eval fixtures dir {}: {error}",
+            fixtures_dir.display()
+        ))
+    })?;
+
+    let mut cases = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|error| {
+            AppError::Transcription(format!("failed to read entry in {}: {error}", fixtures_dir.display()))
+        })?;
+        let wav_path = entry.path();
+        if wav_path.extension().and_then(|extension| extension.to_str()) != Some("wav") {
+            continue;
+        }
+
+        let golden_path = wav_path.with_extension("txt");
+        match std::fs::read_to_string(&golden_path) {
+            Ok(golden_text) => cases.push(EvalCase { wav_path, golden_text }),
+            Err(error) => {
+                tracing::warn!(
+                    "skipping eval fixture {} with no golden transcript at {}: {error}",
+                    wav_path.display(),
+                    golden_path.display()
+                );
+            }
+        }
+    }
+    cases.sort_by(|a, b| a.wav_path.cmp(&b.wav_path));
+    Ok(cases)
+}
+
+/// Replays every case in `cases` through `run_transcription_job`, the same
+/// entry point the live controller pipeline uses, scores each resulting
+/// transcript against its golden text, and aggregates mean WER/CER for
+/// `config.backend` so a CI job can track accuracy regressions over time and
+/// compare backends against each other by running this once per backend
+/// under test.
+///
+/// franken_whisper's own `eval.ok` event, surfaced through
+/// `RunStore::load_run_details`, would be the natural home for these scores
+/// since they're per-run, but that event stream belongs to franken_whisper's
+/// engine process, which this evaluation pass runs independently of; each
+/// case's WER/CER and edit counts are logged here instead, as the closest
+/// analog this crate can surface on its own.
+pub fn run_evaluation_suite(
+    cases: &[EvalCase],
+    engine: &dyn EngineAdapter,
+    config: &TranscriptionConfig,
+    db_path: &Path,
+) -> AppResult<BackendEvalSummary> {
+    let mut wer_sum = 0.0;
+    let mut cer_sum = 0.0;
+
+    for case in cases {
+        let result = run_transcription_job(engine, case.wav_path.clone(), db_path.to_path_buf(), config, false)?;
+        let report = score_transcript(&case.golden_text, &result.transcript);
+        tracing::info!(
+            wav = %case.wav_path.display(),
+            wer = report.wer,
+            cer = report.cer,
+            substitutions = report.ops.substitutions,
+            insertions = report.ops.insertions,
+            deletions = report.ops.deletions,
+            "evaluated transcript against golden text"
+        );
+        wer_sum += report.wer;
+        cer_sum += report.cer;
+    }
+
+    let case_count = cases.len();
+    Ok(BackendEvalSummary {
+        backend: config.backend,
+        case_count,
+        mean_wer: if case_count > 0 { wer_sum / case_count as f64 } else { 0.0 },
+        mean_cer: if case_count > 0 { cer_sum / case_count as f64 } else { 0.0 },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use franken_whisper::{ReplayEnvelope, RunReport, TranscribeRequest, TranscriptionResult};
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn normalize_text_strips_punctuation_and_case() {
+        assert_eq!(normalize_text("Hello,  WORLD!!"), "hello world");
+        assert_eq!(normalize_text("it's fine."), "it's fine");
+    }
+
+    #[test]
+    fn identical_transcripts_score_zero() {
+        let report = score_transcript("the quick brown fox", "the quick brown fox");
+        assert_eq!(report.wer, 0.0);
+        assert_eq!(report.cer, 0.0);
+        assert_eq!(report.ops, EditCounts::default());
+    }
+
+    #[test]
+    fn scores_a_single_substitution() {
+        let report = score_transcript("the quick brown fox", "the quick brown fax");
+        assert_eq!(
+            report.ops,
+            EditCounts {
+                substitutions: 1,
+                insertions: 0,
+                deletions: 0,
+            }
+        );
+        assert!((report.wer - 0.25).abs() < 1e-9);
+        assert!(report.cer > 0.0);
+    }
+
+    #[test]
+    fn scores_an_insertion_and_a_deletion() {
+        // Reference has no "very"; hypothesis drops "brown".
+        let report = score_transcript("the quick fox", "the quick very fox");
+        assert_eq!(
+            report.ops,
+            EditCounts {
+                substitutions: 0,
+                insertions: 1,
+                deletions: 0,
+            }
+        );
+
+        let report = score_transcript("the quick brown fox", "the quick fox");
+        assert_eq!(
+            report.ops,
+            EditCounts {
+                substitutions: 0,
+                insertions: 0,
+                deletions: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn empty_reference_with_nonempty_hypothesis_scores_full_error() {
+        let report = score_transcript("", "hello");
+        assert_eq!(report.wer, 1.0);
+        assert_eq!(report.cer, 1.0);
+    }
+
+    fn write_wav(path: &std::path::Path) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).expect("create wav");
+        for _ in 0..1_600 {
+            writer.write_sample(0_i16).expect("write sample");
+        }
+        writer.finalize().expect("finalize wav");
+    }
+
+    #[test]
+    fn discover_eval_cases_pairs_wavs_with_golden_text_and_skips_unpaired_ones() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        write_wav(&temp.path().join("a.wav"));
+        std::fs::write(temp.path().join("a.txt"), "hello world").expect("write golden");
+        write_wav(&temp.path().join("b.wav"));
+        // No "b.txt" golden file: "b.wav" should be skipped.
+
+        let cases = discover_eval_cases(temp.path()).expect("discover cases");
+        assert_eq!(cases.len(), 1);
+        assert_eq!(cases[0].wav_path, temp.path().join("a.wav"));
+        assert_eq!(cases[0].golden_text, "hello world");
+    }
+
+    struct SequencedEngine {
+        transcripts: Mutex<std::collections::VecDeque<String>>,
+    }
+
+    impl SequencedEngine {
+        fn new(transcripts: Vec<&str>) -> Self {
+            Self {
+                transcripts: Mutex::new(transcripts.into_iter().map(str::to_owned).collect()),
+            }
+        }
+    }
+
+    impl EngineAdapter for SequencedEngine {
+        fn transcribe_request(&self, request: TranscribeRequest) -> AppResult<RunReport> {
+            let transcript = self
+                .transcripts
+                .lock()
+                .expect("lock")
+                .pop_front()
+                .expect("configured transcript");
+            Ok(RunReport {
+                run_id: "run-eval".to_owned(),
+                trace_id: "trace-eval".to_owned(),
+                started_at_rfc3339: "2026-02-25T00:00:00Z".to_owned(),
+                finished_at_rfc3339: "2026-02-25T00:00:01Z".to_owned(),
+                input_path: "/tmp/in.wav".to_owned(),
+                normalized_wav_path: "/tmp/normalized.wav".to_owned(),
+                request,
+                result: TranscriptionResult {
+                    backend: BackendKind::WhisperCpp,
+                    transcript,
+                    language: Some("en".to_owned()),
+                    segments: vec![],
+                    acceleration: None,
+                    raw_output: json!({}),
+                    artifact_paths: vec![],
+                },
+                events: vec![],
+                warnings: vec![],
+                evidence: vec![],
+                replay: ReplayEnvelope::default(),
+            })
+        }
+
+        fn cancel(&self) -> AppResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn run_evaluation_suite_aggregates_mean_wer_across_cases() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let first_wav = temp.path().join("first.wav");
+        let second_wav = temp.path().join("second.wav");
+        write_wav(&first_wav);
+        write_wav(&second_wav);
+
+        let cases = vec![
+            EvalCase {
+                wav_path: first_wav,
+                golden_text: "the quick brown fox".to_owned(),
+            },
+            EvalCase {
+                wav_path: second_wav,
+                golden_text: "the quick brown fox".to_owned(),
+            },
+        ];
+
+        // First case is a perfect match (wer 0.0); second substitutes one
+        // word out of four (wer 0.25), so the mean should land at 0.125.
+        let engine = SequencedEngine::new(vec!["the quick brown fox", "the quick brown fax"]);
+        let config = TranscriptionConfig::default();
+        let db_path = temp.path().join("history.sqlite3");
+
+        let summary = run_evaluation_suite(&cases, &engine, &config, &db_path).expect("run suite");
+        assert_eq!(summary.backend, config.backend);
+        assert_eq!(summary.case_count, 2);
+        assert!((summary.mean_wer - 0.125).abs() < 1e-9);
+        assert!(summary.mean_cer > 0.0, "one mismatched case should pull the mean cer above zero");
+    }
+}
@@ -0,0 +1,142 @@
+use std::path::Path;
+
+use rusqlite::Connection;
+
+use crate::error::{AppError, AppResult};
+
+/// One ordered, idempotent schema change applied to `history.sqlite3`.
+/// Tracked via the SQLite `user_version` pragma rather than a bookkeeping
+/// table, so a brand-new or pre-migration database (where it defaults to
+/// `0`) needs no special-casing beyond "run everything".
+struct Migration {
+    version: i64,
+    description: &'static str,
+    sql: &'static str,
+}
+
+/// The schema version this build reads and writes. Bump this and append a
+/// `Migration` to `MIGRATIONS` (never edit a past one) when adding a column
+/// like `language`, `duration_ms`, or `model_id` to `runs`, or a full-text
+/// index for searching past transcripts.
+pub const CURRENT_SCHEMA_VERSION: i64 = 1;
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "create the runs table HistoryStore reads and franken_whisper writes",
+    sql: "CREATE TABLE IF NOT EXISTS runs (
+        id TEXT NOT NULL,
+        started_at TEXT NOT NULL,
+        finished_at TEXT NOT NULL,
+        backend TEXT NOT NULL,
+        transcript TEXT NOT NULL
+    );",
+}];
+
+/// Opens (creating if absent) the sqlite file at `db_path` and applies every
+/// `MIGRATIONS` entry newer than its current `user_version`, each inside its
+/// own transaction so a failure partway through leaves the previous version
+/// intact. Called from `bootstrap::bootstrap_env` before anything else
+/// touches the history database.
+///
+/// Fails closed with `AppError::History` if the on-disk version is already
+/// newer than `CURRENT_SCHEMA_VERSION` (e.g. this binary was downgraded
+/// against a database a newer release already migrated), rather than risk
+/// silently corrupting a shape it doesn't understand.
+pub fn run_pending_migrations(db_path: &Path) -> AppResult<()> {
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut connection = Connection::open(db_path)?;
+    let on_disk_version = schema_version(&connection)?;
+
+    if on_disk_version > CURRENT_SCHEMA_VERSION {
+        return Err(AppError::History(format!(
+            "history database {} is schema version {on_disk_version}, newer than the {CURRENT_SCHEMA_VERSION} this build supports",
+            db_path.display()
+        )));
+    }
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > on_disk_version) {
+        let tx = connection.transaction()?;
+        tx.execute_batch(migration.sql)?;
+        tx.pragma_update(None, "user_version", migration.version)?;
+        tx.commit()?;
+        tracing::info!(
+            version = migration.version,
+            "applied history db migration: {}",
+            migration.description
+        );
+    }
+
+    Ok(())
+}
+
+/// Reads the history database's current `user_version`; used both by
+/// `run_pending_migrations` and by `runtime::app::status_report` to surface
+/// the schema version a user can quote back in a bug report.
+pub fn schema_version(connection: &Connection) -> AppResult<i64> {
+    connection
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(AppError::Sqlite)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_pending_migrations, schema_version, CURRENT_SCHEMA_VERSION};
+    use rusqlite::Connection;
+
+    #[test]
+    fn fresh_database_is_migrated_to_current_version() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let db_path = temp.path().join("history.sqlite3");
+
+        run_pending_migrations(&db_path).expect("migrate");
+
+        let connection = Connection::open(&db_path).expect("open");
+        assert_eq!(
+            schema_version(&connection).expect("version"),
+            CURRENT_SCHEMA_VERSION
+        );
+        connection
+            .execute(
+                "INSERT INTO runs (id, started_at, finished_at, backend, transcript)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                ("run-1", "2026-02-25T00:00:00Z", "2026-02-25T00:00:01Z", "auto", "hi"),
+            )
+            .expect("runs table exists and is writable");
+    }
+
+    #[test]
+    fn rerunning_migrations_is_a_no_op() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let db_path = temp.path().join("history.sqlite3");
+
+        run_pending_migrations(&db_path).expect("first migrate");
+        run_pending_migrations(&db_path).expect("second migrate");
+
+        let connection = Connection::open(&db_path).expect("open");
+        assert_eq!(
+            schema_version(&connection).expect("version"),
+            CURRENT_SCHEMA_VERSION
+        );
+    }
+
+    #[test]
+    fn newer_on_disk_version_fails_closed() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let db_path = temp.path().join("history.sqlite3");
+
+        let connection = Connection::open(&db_path).expect("open");
+        connection
+            .pragma_update(None, "user_version", CURRENT_SCHEMA_VERSION + 1)
+            .expect("bump version");
+        drop(connection);
+
+        let error = run_pending_migrations(&db_path).expect_err("should refuse to downgrade");
+        assert!(
+            format!("{error}").contains("newer than"),
+            "unexpected error: {error}"
+        );
+    }
+}
@@ -0,0 +1,6 @@
+pub mod migrations;
+pub mod models;
+pub mod store;
+
+pub use models::RunSummary;
+pub use store::HistoryStore;
@@ -1,8 +1,10 @@
 use std::path::PathBuf;
 
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use franken_whisper::BackendKind;
 use rusqlite::Connection;
 
+use crate::config::RetentionPolicy;
 use crate::error::{AppError, AppResult};
 use crate::history::models::RunSummary;
 
@@ -57,16 +59,89 @@ impl HistoryStore {
         let mut runs = self.list_recent_runs(1)?;
         Ok(runs.pop())
     }
+
+    /// Deletes rows beyond `policy`'s caps in a single transaction and
+    /// returns the number removed. A no-op (returns `Ok(0)`) when neither
+    /// cap is set, when the db file doesn't exist yet, or when the `runs`
+    /// table/columns are missing, mirroring `list_recent_runs`'s graceful
+    /// handling of the not-yet-migrated case; safe to call repeatedly, since
+    /// a policy that's already satisfied simply deletes nothing.
+    pub fn prune(&self, policy: &RetentionPolicy, now_rfc3339: &str) -> AppResult<usize> {
+        if !self.db_path.exists() {
+            return Ok(0);
+        }
+        if policy.max_entries.is_none() && policy.max_age_days.is_none() {
+            return Ok(0);
+        }
+
+        let mut connection = Connection::open(&self.db_path)?;
+        let transaction = match connection.transaction() {
+            Ok(transaction) => transaction,
+            Err(error) => return handle_missing_schema_prune(error),
+        };
+        let mut removed = 0usize;
+
+        if let Some(max_age_days) = policy.max_age_days {
+            if let Some(cutoff) = cutoff_rfc3339(now_rfc3339, max_age_days) {
+                match transaction.execute("DELETE FROM runs WHERE finished_at < ?1", [cutoff]) {
+                    Ok(count) => removed += count,
+                    Err(error) => return handle_missing_schema_prune(error),
+                }
+            }
+        }
+
+        if let Some(max_entries) = policy.max_entries {
+            let sql = "DELETE FROM runs WHERE id NOT IN \
+                       (SELECT id FROM runs ORDER BY started_at DESC LIMIT ?1)";
+            match transaction.execute(sql, [max_entries as i64]) {
+                Ok(count) => removed += count,
+                Err(error) => return handle_missing_schema_prune(error),
+            }
+        }
+
+        transaction.commit()?;
+        Ok(removed)
+    }
 }
 
+/// Computes the rfc3339 instant `max_age_days` before `now_rfc3339`, used as
+/// the `finished_at` cutoff for age-based pruning. Returns `None` (skipping
+/// the age-based delete rather than failing the whole `prune` call) if
+/// `now_rfc3339` isn't parseable, which should only happen if a caller
+/// passes a malformed clock reading.
+fn cutoff_rfc3339(now_rfc3339: &str, max_age_days: u64) -> Option<String> {
+    let now: DateTime<Utc> = DateTime::parse_from_rfc3339(now_rfc3339)
+        .ok()?
+        .with_timezone(&Utc);
+    let cutoff = now - ChronoDuration::days(max_age_days as i64);
+    Some(cutoff.to_rfc3339())
+}
+
+/// Reconstructs the `BackendKind` a historical run actually used. This is a
+/// record of fact, not a capability check: a run recorded before the
+/// current binary was rebuilt with a narrower backend feature set stays
+/// attributed to the backend it really ran on, never silently reattributed
+/// to `Auto`. If that backend isn't compiled into this build (see
+/// `transcription::engine::backend_compiled_in`), a warning is logged so
+/// the gap between "this run used it" and "this build can" stays visible,
+/// rather than advertising a capability this install no longer has.
 fn parse_backend(raw: &str) -> BackendKind {
-    match raw {
+    let backend = match raw {
         "auto" => BackendKind::Auto,
         "whisper_cpp" => BackendKind::WhisperCpp,
         "insanely_fast" => BackendKind::InsanelyFast,
         "whisper_diarization" => BackendKind::WhisperDiarization,
         _ => BackendKind::Auto,
+    };
+
+    if !crate::transcription::engine::backend_compiled_in(backend) {
+        tracing::warn!(
+            ?backend,
+            "history run used a backend not compiled into this build"
+        );
     }
+
+    backend
 }
 
 fn handle_missing_schema(error: rusqlite::Error) -> AppResult<Vec<RunSummary>> {
@@ -80,9 +155,21 @@ fn handle_missing_schema(error: rusqlite::Error) -> AppResult<Vec<RunSummary>> {
     }
 }
 
+fn handle_missing_schema_prune(error: rusqlite::Error) -> AppResult<usize> {
+    match &error {
+        rusqlite::Error::SqliteFailure(_, Some(message))
+            if message.contains("no such table") || message.contains("no such column") =>
+        {
+            Ok(0)
+        }
+        _ => Err(AppError::Sqlite(error)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{parse_backend, HistoryStore};
+    use crate::config::RetentionPolicy;
     use franken_whisper::BackendKind;
     use rusqlite::Connection;
     use std::path::PathBuf;
@@ -207,4 +294,151 @@ mod tests {
         assert_eq!(latest.run_id, "new");
         assert_eq!(latest.backend, BackendKind::InsanelyFast);
     }
+
+    fn insert_run(conn: &Connection, id: &str, started_at: &str, finished_at: &str) {
+        conn.execute(
+            "INSERT INTO runs (id, started_at, finished_at, backend, transcript)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            (id, started_at, finished_at, "auto", "transcript"),
+        )
+        .expect("insert run");
+    }
+
+    #[test]
+    fn prune_is_a_noop_when_neither_cap_is_set() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let db = temp.path().join("history.sqlite3");
+        let conn = Connection::open(&db).expect("open");
+        conn.execute_batch(
+            "CREATE TABLE runs (
+                id TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                finished_at TEXT NOT NULL,
+                backend TEXT NOT NULL,
+                transcript TEXT NOT NULL
+            );",
+        )
+        .expect("schema");
+        insert_run(&conn, "run-1", "2026-02-25T00:00:00Z", "2026-02-25T00:00:01Z");
+
+        let store = build_store(db);
+        let removed = store
+            .prune(&RetentionPolicy::default(), "2026-03-01T00:00:00Z")
+            .expect("prune");
+        assert_eq!(removed, 0);
+        assert_eq!(store.list_recent_runs(10).expect("list").len(), 1);
+    }
+
+    #[test]
+    fn prune_keeps_only_the_newest_max_entries_runs() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let db = temp.path().join("history.sqlite3");
+        let conn = Connection::open(&db).expect("open");
+        conn.execute_batch(
+            "CREATE TABLE runs (
+                id TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                finished_at TEXT NOT NULL,
+                backend TEXT NOT NULL,
+                transcript TEXT NOT NULL
+            );",
+        )
+        .expect("schema");
+        insert_run(&conn, "oldest", "2026-02-25T00:00:00Z", "2026-02-25T00:00:01Z");
+        insert_run(&conn, "middle", "2026-02-25T01:00:00Z", "2026-02-25T01:00:01Z");
+        insert_run(&conn, "newest", "2026-02-25T02:00:00Z", "2026-02-25T02:00:01Z");
+
+        let store = build_store(db);
+        let policy = RetentionPolicy {
+            max_entries: Some(2),
+            max_age_days: None,
+        };
+        let removed = store.prune(&policy, "2026-03-01T00:00:00Z").expect("prune");
+        assert_eq!(removed, 1);
+
+        let remaining: Vec<String> = store
+            .list_recent_runs(10)
+            .expect("list")
+            .into_iter()
+            .map(|run| run.run_id)
+            .collect();
+        assert_eq!(remaining, vec!["newest".to_owned(), "middle".to_owned()]);
+    }
+
+    #[test]
+    fn prune_deletes_runs_older_than_max_age_days() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let db = temp.path().join("history.sqlite3");
+        let conn = Connection::open(&db).expect("open");
+        conn.execute_batch(
+            "CREATE TABLE runs (
+                id TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                finished_at TEXT NOT NULL,
+                backend TEXT NOT NULL,
+                transcript TEXT NOT NULL
+            );",
+        )
+        .expect("schema");
+        insert_run(&conn, "ancient", "2026-01-01T00:00:00Z", "2026-01-01T00:00:01Z");
+        insert_run(&conn, "recent", "2026-02-28T00:00:00Z", "2026-02-28T00:00:01Z");
+
+        let store = build_store(db);
+        let policy = RetentionPolicy {
+            max_entries: None,
+            max_age_days: Some(7),
+        };
+        let removed = store.prune(&policy, "2026-03-01T00:00:00Z").expect("prune");
+        assert_eq!(removed, 1);
+
+        let remaining: Vec<String> = store
+            .list_recent_runs(10)
+            .expect("list")
+            .into_iter()
+            .map(|run| run.run_id)
+            .collect();
+        assert_eq!(remaining, vec!["recent".to_owned()]);
+    }
+
+    #[test]
+    fn prune_is_idempotent_once_the_policy_is_satisfied() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let db = temp.path().join("history.sqlite3");
+        let conn = Connection::open(&db).expect("open");
+        conn.execute_batch(
+            "CREATE TABLE runs (
+                id TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                finished_at TEXT NOT NULL,
+                backend TEXT NOT NULL,
+                transcript TEXT NOT NULL
+            );",
+        )
+        .expect("schema");
+        insert_run(&conn, "only", "2026-02-25T00:00:00Z", "2026-02-25T00:00:01Z");
+
+        let store = build_store(db);
+        let policy = RetentionPolicy {
+            max_entries: Some(5),
+            max_age_days: None,
+        };
+        assert_eq!(store.prune(&policy, "2026-03-01T00:00:00Z").expect("prune"), 0);
+        assert_eq!(store.prune(&policy, "2026-03-01T00:00:00Z").expect("prune"), 0);
+        assert_eq!(store.list_recent_runs(10).expect("list").len(), 1);
+    }
+
+    #[test]
+    fn prune_returns_zero_when_the_schema_is_missing() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let db = temp.path().join("history.sqlite3");
+        let _ = Connection::open(&db).expect("create db");
+
+        let store = build_store(db);
+        let policy = RetentionPolicy {
+            max_entries: Some(5),
+            max_age_days: None,
+        };
+        let removed = store.prune(&policy, "2026-03-01T00:00:00Z").expect("prune");
+        assert_eq!(removed, 0);
+    }
 }
@@ -1,26 +1,36 @@
 mod bootstrap;
+mod calibration;
 mod capture;
 mod cli;
+mod clock;
 mod config;
 mod controller;
 mod doctor;
 mod error;
+mod evaluation;
 mod history;
 mod output;
 mod runtime;
+#[cfg(feature = "scripting")]
+mod scripting;
 #[cfg(test)]
 mod test_support;
 mod transcription;
 mod ui;
 
 use clap::Parser;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 
 use crate::bootstrap::AppPaths;
 use crate::cli::{Cli, Command};
-use crate::config::load_config;
-use crate::doctor::run_doctor;
+use crate::clock::SystemClocks;
+use crate::config::{load_config, load_config_without_validation, CliOverrides};
+use crate::doctor::{backend_availability_problems, run_doctor, run_doctor_fix};
 use crate::error::AppResult;
+use crate::evaluation::{discover_eval_cases, run_evaluation_suite};
 use crate::runtime::{install_autostart, run_app, status_report};
+use crate::transcription::new_default_engine;
 
 fn main() {
     if let Err(error) = run() {
@@ -30,38 +40,95 @@ fn main() {
 }
 
 fn run() -> AppResult<()> {
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()),
-        )
-        .with_target(false)
-        .with_level(true)
-        .compact()
-        .init();
-
     let cli = Cli::parse();
 
     let paths = AppPaths::resolve()?;
     paths.ensure_dirs()?;
 
+    if let Command::CheckConfig { json } = &cli.command {
+        return check_config(&paths, &cli.to_overrides(), *json);
+    }
+
+    if let Command::EngineWorker { socket } = &cli.command {
+        return crate::transcription::run_engine_worker(socket);
+    }
+
     let config = load_config(&paths, &cli.to_overrides())?;
+    let _log_guard = init_tracing(&config.diagnostics, &paths.logs_dir);
 
     match cli.command {
         Command::Run => run_app(config, paths),
-        Command::Doctor { json } => {
-            let report = run_doctor(&paths, &config);
-            if json {
+        Command::Doctor { json, fix, execute, yes, device, format } => {
+            if device.as_deref() == Some("?") {
+                print_device_list();
+                return Ok(());
+            }
+
+            let report = run_doctor(&paths, &config, &SystemClocks::new(), device.as_deref());
+            if fix {
+                let plan = run_doctor_fix(&report, execute, |entry| {
+                    yes || confirm_fix_action(entry)
+                });
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&plan)?);
+                } else {
+                    print_fix_plan(&plan, execute);
+                    if execute {
+                        for entry in plan.iter().filter(|entry| entry.executed) {
+                            let rerun = crate::doctor::rerun_check(
+                                &entry.check_name,
+                                &paths,
+                                &config,
+                                &SystemClocks::new(),
+                                device.as_deref(),
+                            );
+                            match rerun {
+                                Some(check) => println!(
+                                    "[{}] now {}: {}",
+                                    check.name,
+                                    crate::doctor::status_label(check.status),
+                                    check.detail
+                                ),
+                                None => println!(
+                                    "[{}] re-check skipped: check no longer exists in this doctor run",
+                                    entry.check_name
+                                ),
+                            }
+                        }
+                    }
+                }
+                return Ok(());
+            }
+
+            if let Some(format) = format.as_deref() {
+                if format == "json" {
+                    println!("{}", report.render_format_json()?);
+                } else {
+                    print!("{}", report.render_format(format));
+                }
+            } else if json {
                 println!("{}", serde_json::to_string_pretty(&report)?);
             } else {
                 println!("{}", report.render_text());
             }
+
+            if report.exit_code() != 0 {
+                let failing = report
+                    .checks
+                    .iter()
+                    .filter(|check| check.required && check.status == crate::doctor::CheckStatus::Fail)
+                    .count();
+                return Err(crate::error::AppError::Config(format!(
+                    "doctor found {failing} failing required check(s)"
+                )));
+            }
             Ok(())
         }
         Command::Install => {
             let installed_path = install_autostart(&paths)?;
             println!("Installed autostart entry: {}", installed_path.display());
 
-            let report = run_doctor(&paths, &config);
+            let report = run_doctor(&paths, &config, &SystemClocks::new(), None);
             println!("{}", report.render_text());
 
             Ok(())
@@ -71,6 +138,185 @@ fn run() -> AppResult<()> {
             println!("{report}");
             Ok(())
         }
+        Command::Evaluate { fixtures_dir, json } => {
+            let cases = discover_eval_cases(&fixtures_dir)?;
+            let engine = new_default_engine()?;
+            let summary = run_evaluation_suite(&cases, engine.as_ref(), &config.transcription, &paths.history_db)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&summary)?);
+            } else {
+                println!(
+                    "{:?}: {} case(s), mean WER {:.3}, mean CER {:.3}",
+                    summary.backend, summary.case_count, summary.mean_wer, summary.mean_cer
+                );
+            }
+            Ok(())
+        }
+        Command::CheckConfig { .. } => unreachable!("handled above"),
+        Command::EngineWorker { .. } => unreachable!("handled above"),
+    }
+}
+
+/// Initializes the global `tracing` subscriber from `diagnostics`, deferred
+/// until after the config is loaded (rather than at the top of `run`) so
+/// `log_level`/`log_format` can drive it; `RUST_LOG` still wins if set, the
+/// same precedence the hardcoded `"info"` default used before this existed.
+///
+/// Layers a compact stderr subscriber for interactive use on top of a
+/// daily-rotating file subscriber under `logs_dir`, keeping up to
+/// `log_retention_days` files; `log_format` only toggles the file layer
+/// between that same compact text and JSON for machine parsing, since
+/// stderr is read by a human either way. The returned guard must be held
+/// for the process lifetime — dropping it early stops the non-blocking
+/// file writer's background thread and drops any buffered lines.
+fn init_tracing(
+    diagnostics: &crate::config::schema::DiagnosticsConfig,
+    logs_dir: &std::path::Path,
+) -> tracing_appender::non_blocking::WorkerGuard {
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| diagnostics.log_level.as_str().into());
+
+    let file_appender = tracing_appender::rolling::Builder::new()
+        .rotation(tracing_appender::rolling::Rotation::DAILY)
+        .filename_prefix("quedo-daemon")
+        .filename_suffix("log")
+        .max_log_files(diagnostics.log_retention_days.max(1) as usize)
+        .build(logs_dir)
+        .expect("failed to build rolling log file appender");
+    let (file_writer, guard) = tracing_appender::non_blocking(file_appender);
+
+    let stderr_layer = tracing_subscriber::fmt::layer()
+        .with_target(false)
+        .with_level(true)
+        .compact();
+    let file_layer = tracing_subscriber::fmt::layer()
+        .with_target(false)
+        .with_level(true)
+        .with_ansi(false)
+        .with_writer(file_writer);
+
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(stderr_layer);
+
+    match diagnostics.log_format {
+        crate::config::schema::LogFormat::Text => registry.with(file_layer.compact()).init(),
+        crate::config::schema::LogFormat::Json => registry.with(file_layer.json()).init(),
+    }
+
+    guard
+}
+
+/// Renders a `doctor --fix` plan as one line per failing check: the resolved
+/// command (prefixed with whether it ran or is only proposed), or why no
+/// command could be resolved.
+fn print_fix_plan(plan: &[crate::doctor::FixPlanEntry], execute: bool) {
+    if plan.is_empty() {
+        println!("no failing checks with a remediation to plan");
+        return;
+    }
+
+    for entry in plan {
+        match (&entry.command, &entry.skip_reason) {
+            (Some(command), Some(reason)) if execute && !entry.executed => {
+                println!("[{}] skipped ({reason}): {command}", entry.check_name);
+            }
+            (Some(command), _) if execute && entry.executed => {
+                println!("[{}] ran: {command}", entry.check_name);
+            }
+            (Some(command), _) if execute => {
+                println!("[{}] failed: {command}", entry.check_name);
+            }
+            (Some(command), _) => {
+                println!("[{}] would run: {command}", entry.check_name);
+            }
+            (None, Some(reason)) => {
+                println!("[{}] skipped: {reason}", entry.check_name);
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Asks the user on stdin/stdout whether to run `entry`'s resolved command,
+/// defaulting to "no" on an empty reply or an unreadable/closed stdin (e.g.
+/// piped into a non-interactive process) so `doctor --fix --execute` never
+/// runs a remediation the user didn't affirmatively approve; pass `--yes` to
+/// skip this prompt entirely.
+fn confirm_fix_action(entry: &crate::doctor::FixPlanEntry) -> bool {
+    let Some(command) = &entry.command else {
+        return false;
+    };
+
+    print!("[{}] run `{command}`? [y/N] ", entry.check_name);
+    if std::io::Write::flush(&mut std::io::stdout()).is_err() {
+        return false;
+    }
+
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+
+    matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
+/// Prints the enumerated input devices for `doctor --device ?`, the same
+/// usage-listing convention other flags use when asked for an unknown or
+/// placeholder value.
+fn print_device_list() {
+    match crate::capture::MicrophoneCapture::list_input_devices() {
+        Ok(devices) if devices.is_empty() => println!("no input devices discovered"),
+        Ok(devices) => {
+            for device in devices {
+                let marker = if device.is_default { " (default)" } else { "" };
+                println!("{}{marker}", device.name);
+            }
+        }
+        Err(error) => println!("failed to enumerate input devices: {error}"),
+    }
+}
+
+/// Backs `quedo-daemon check-config`: loads and merges the config without
+/// requiring it to pass `validate`, then reports every problem it finds
+/// (malformed fields plus, unlike normal startup, whether the selected
+/// backend is actually runnable on this host) instead of stopping at the
+/// first. Exits non-zero iff at least one problem was found.
+fn check_config(paths: &AppPaths, overrides: &CliOverrides, json: bool) -> AppResult<()> {
+    let mut problems = Vec::new();
+    let loaded = load_config_without_validation(paths, overrides);
+
+    let config = match &loaded {
+        Ok(config) => {
+            problems.extend(crate::config::load::collect_validation_problems(config));
+            Some(config)
+        }
+        Err(error) => {
+            problems.push(format!("failed to load config: {error}"));
+            None
+        }
+    };
+    if let Some(config) = config {
+        problems.extend(backend_availability_problems(config));
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&problems)?);
+    } else if problems.is_empty() {
+        println!("config is valid");
+    } else {
+        for problem in &problems {
+            println!("- {problem}");
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(crate::error::AppError::Config(format!(
+            "check-config found {} problem(s)",
+            problems.len()
+        )))
     }
 }
 
@@ -94,20 +340,38 @@ mod tests {
         let _capture_ctor: fn(Option<String>) -> crate::capture::MicrophoneCapture =
             crate::capture::MicrophoneCapture::new;
         let _transcribe_job: fn(
-            &crate::transcription::FrankenEngine,
+            &dyn crate::transcription::engine::EngineAdapter,
             std::path::PathBuf,
             std::path::PathBuf,
             &crate::config::TranscriptionConfig,
+            bool,
         ) -> crate::error::AppResult<crate::transcription::TranscriptResult> =
             crate::transcription::run_transcription_job;
         let _doctor: fn(
             &crate::bootstrap::AppPaths,
             &crate::config::AppConfig,
+            &dyn crate::clock::Clocks,
+            Option<&str>,
         ) -> crate::doctor::DoctorReport = crate::doctor::run_doctor;
         let _history_ctor: fn(std::path::PathBuf) -> crate::history::HistoryStore =
             crate::history::HistoryStore::new;
-        let _clipboard_write: fn(&str) -> crate::error::AppResult<()> =
-            crate::output::ClipboardOutput::write_text;
+        let _clipboard_write: fn(
+            &str,
+            crate::config::ClipboardProviderKind,
+            crate::config::ClipboardSelectionTarget,
+        ) -> crate::error::AppResult<()> = crate::output::ClipboardOutput::write_text;
+        let _type_text: fn(
+            &str,
+            std::time::Duration,
+            std::time::Duration,
+        ) -> crate::error::AppResult<()> = crate::output::TypeTextOutput::type_text;
+        let _command_run: fn(
+            &str,
+            &str,
+            &str,
+            Option<&str>,
+            franken_whisper::BackendKind,
+        ) -> crate::error::AppResult<()> = crate::output::CommandOutput::run;
         let _notifier_ctor: fn(bool) -> crate::ui::Notifier = crate::ui::Notifier::new;
     }
 }
@@ -1,56 +1,306 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
 use arboard::Clipboard;
 
+use crate::config::{ClipboardProviderKind, ClipboardSelectionTarget};
 use crate::error::{AppError, AppResult};
 
 pub struct ClipboardOutput;
 
-trait ClipboardBackend {
-    fn set_text(&mut self, text: String) -> Result<(), String>;
+/// A clipboard backend modeled on Helix's `ClipboardProvider`: something that
+/// can read and write the system clipboard, or stand in for one in tests.
+pub trait ClipboardProvider: Send {
+    fn get_contents(&mut self) -> Result<String, String>;
+    fn set_contents(&mut self, text: String) -> Result<(), String>;
 }
 
-struct ArboardClipboardBackend {
+struct ArboardProvider {
     inner: Clipboard,
 }
 
-impl ClipboardBackend for ArboardClipboardBackend {
-    fn set_text(&mut self, text: String) -> Result<(), String> {
+impl ClipboardProvider for ArboardProvider {
+    fn get_contents(&mut self) -> Result<String, String> {
+        self.inner.get_text().map_err(|error| error.to_string())
+    }
+
+    fn set_contents(&mut self, text: String) -> Result<(), String> {
         self.inner.set_text(text).map_err(|error| error.to_string())
     }
 }
 
+/// Shells out to a read command and a write command, feeding/collecting text
+/// over stdin/stdout. Covers `wl-copy`/`wl-paste`, `xclip`, `xsel`,
+/// `pbcopy`/`pbpaste`, and `win32yank`.
+struct CommandProvider {
+    read: &'static [&'static str],
+    write: &'static [&'static str],
+}
+
+impl CommandProvider {
+    const fn new(read: &'static [&'static str], write: &'static [&'static str]) -> Self {
+        Self { read, write }
+    }
+}
+
+impl ClipboardProvider for CommandProvider {
+    fn get_contents(&mut self) -> Result<String, String> {
+        let (program, args) = self
+            .read
+            .split_first()
+            .ok_or_else(|| "clipboard read command is empty".to_owned())?;
+        let output = Command::new(program)
+            .args(args)
+            .output()
+            .map_err(|error| format!("failed to execute `{program}`: {error}"))?;
+        if !output.status.success() {
+            return Err(format!("`{program}` exited with {}", output.status));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    fn set_contents(&mut self, text: String) -> Result<(), String> {
+        let (program, args) = self
+            .write
+            .split_first()
+            .ok_or_else(|| "clipboard write command is empty".to_owned())?;
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|error| format!("failed to spawn `{program}`: {error}"))?;
+
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| format!("failed to open stdin for `{program}`"))?;
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|error| format!("failed to write to `{program}` stdin: {error}"))?;
+        drop(stdin);
+
+        let status = child
+            .wait()
+            .map_err(|error| format!("failed waiting for `{program}`: {error}"))?;
+        if !status.success() {
+            return Err(format!("`{program}` exited with {status}"));
+        }
+        Ok(())
+    }
+}
+
+/// In-memory provider for tests and headless sessions where no real
+/// clipboard is reachable.
+#[derive(Default)]
+pub struct InternalProvider {
+    contents: Mutex<String>,
+}
+
+impl ClipboardProvider for InternalProvider {
+    fn get_contents(&mut self) -> Result<String, String> {
+        Ok(self.contents.lock().expect("lock contents").clone())
+    }
+
+    fn set_contents(&mut self, text: String) -> Result<(), String> {
+        *self.contents.lock().expect("lock contents") = text;
+        Ok(())
+    }
+}
+
+fn wayland_provider() -> Box<dyn ClipboardProvider> {
+    Box::new(CommandProvider::new(&["wl-paste"], &["wl-copy"]))
+}
+
+fn xclip_provider(target: ClipboardSelectionTarget) -> Box<dyn ClipboardProvider> {
+    match target {
+        ClipboardSelectionTarget::Clipboard => Box::new(CommandProvider::new(
+            &["xclip", "-selection", "clipboard", "-out"],
+            &["xclip", "-selection", "clipboard"],
+        )),
+        ClipboardSelectionTarget::Primary => Box::new(CommandProvider::new(
+            &["xclip", "-selection", "primary", "-out"],
+            &["xclip", "-selection", "primary"],
+        )),
+    }
+}
+
+fn xsel_provider(target: ClipboardSelectionTarget) -> Box<dyn ClipboardProvider> {
+    match target {
+        ClipboardSelectionTarget::Clipboard => Box::new(CommandProvider::new(
+            &["xsel", "--clipboard", "--output"],
+            &["xsel", "--clipboard", "--input"],
+        )),
+        ClipboardSelectionTarget::Primary => Box::new(CommandProvider::new(
+            &["xsel", "--primary", "--output"],
+            &["xsel", "--primary", "--input"],
+        )),
+    }
+}
+
+fn macos_provider() -> Box<dyn ClipboardProvider> {
+    Box::new(CommandProvider::new(&["pbpaste"], &["pbcopy"]))
+}
+
+fn windows_provider() -> Box<dyn ClipboardProvider> {
+    Box::new(CommandProvider::new(&["win32yank", "-o"], &["win32yank", "-i"]))
+}
+
+fn arboard_provider() -> AppResult<Box<dyn ClipboardProvider>> {
+    let inner = Clipboard::new()
+        .map_err(|error| AppError::Clipboard(format!("clipboard init failed: {error}")))?;
+    Ok(Box::new(ArboardProvider { inner }))
+}
+
+/// Probes the session (`$WAYLAND_DISPLAY`/`$DISPLAY`) and `PATH` for the
+/// first command-backed provider that is actually usable, falling back to
+/// `arboard` and finally to the in-memory provider.
+fn resolve_auto_provider() -> Box<dyn ClipboardProvider> {
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() && which::which("wl-copy").is_ok() {
+        return wayland_provider();
+    }
+
+    if std::env::var_os("DISPLAY").is_some() {
+        if which::which("xclip").is_ok() {
+            return xclip_provider(ClipboardSelectionTarget::Clipboard);
+        }
+        if which::which("xsel").is_ok() {
+            return xsel_provider(ClipboardSelectionTarget::Clipboard);
+        }
+    }
+
+    if cfg!(target_os = "macos") && which::which("pbcopy").is_ok() {
+        return macos_provider();
+    }
+
+    if cfg!(target_os = "windows") && which::which("win32yank").is_ok() {
+        return windows_provider();
+    }
+
+    if let Ok(provider) = arboard_provider() {
+        return provider;
+    }
+
+    Box::new(InternalProvider::default())
+}
+
+fn resolve_provider(
+    kind: ClipboardProviderKind,
+    target: ClipboardSelectionTarget,
+) -> AppResult<Box<dyn ClipboardProvider>> {
+    Ok(match kind {
+        ClipboardProviderKind::Auto => resolve_auto_provider(),
+        ClipboardProviderKind::Wayland => wayland_provider(),
+        ClipboardProviderKind::Xclip => xclip_provider(target),
+        ClipboardProviderKind::Xsel => xsel_provider(target),
+        ClipboardProviderKind::Macos => macos_provider(),
+        ClipboardProviderKind::Windows => windows_provider(),
+        ClipboardProviderKind::Arboard => arboard_provider()?,
+        ClipboardProviderKind::Internal => Box::new(InternalProvider::default()),
+    })
+}
+
+/// Whether `provider` can honor `target`. Only the X11 command backends
+/// understand a PRIMARY selection distinct from CLIPBOARD; every other
+/// backend, including `Auto` (which could resolve to any of them depending
+/// on the session), is restricted to `Clipboard`. Checked once at config
+/// load time by `config::load::collect_validation_problems` so a bad
+/// combination is rejected before it ever reaches a write.
+pub fn supports_target(provider: ClipboardProviderKind, target: ClipboardSelectionTarget) -> bool {
+    match target {
+        ClipboardSelectionTarget::Clipboard => true,
+        ClipboardSelectionTarget::Primary => {
+            matches!(provider, ClipboardProviderKind::Xclip | ClipboardProviderKind::Xsel)
+        }
+    }
+}
+
 impl ClipboardOutput {
-    pub fn write_text(text: &str) -> AppResult<()> {
-        Self::write_text_with(text, || {
-            let inner = Clipboard::new()
-                .map_err(|error| AppError::Clipboard(format!("clipboard init failed: {error}")))?;
-            Ok(Box::new(ArboardClipboardBackend { inner }) as Box<dyn ClipboardBackend>)
-        })
+    pub fn write_text(
+        text: &str,
+        provider: ClipboardProviderKind,
+        target: ClipboardSelectionTarget,
+    ) -> AppResult<()> {
+        Self::write_text_with(text, provider, target, resolve_provider)
     }
 
-    fn write_text_with<F>(text: &str, mut make_backend: F) -> AppResult<()>
+    fn write_text_with<F>(
+        text: &str,
+        provider: ClipboardProviderKind,
+        target: ClipboardSelectionTarget,
+        mut make_provider: F,
+    ) -> AppResult<()>
     where
-        F: FnMut() -> AppResult<Box<dyn ClipboardBackend>>,
+        F: FnMut(ClipboardProviderKind, ClipboardSelectionTarget) -> AppResult<Box<dyn ClipboardProvider>>,
     {
-        let mut backend = make_backend()?;
+        let mut backend = make_provider(provider, target)?;
         backend
-            .set_text(text.to_owned())
+            .set_contents(text.to_owned())
             .map_err(|error| AppError::Clipboard(format!("clipboard write failed: {error}")))
     }
+
+    /// Writes `sentinel` through the resolved provider and reads it back,
+    /// used by `doctor` to confirm the clipboard pipeline actually works
+    /// rather than just that a binary exists on `PATH`.
+    pub fn round_trip(
+        provider: ClipboardProviderKind,
+        target: ClipboardSelectionTarget,
+        sentinel: &str,
+    ) -> AppResult<()> {
+        Self::round_trip_with(provider, target, sentinel, resolve_provider)
+    }
+
+    fn round_trip_with<F>(
+        provider: ClipboardProviderKind,
+        target: ClipboardSelectionTarget,
+        sentinel: &str,
+        mut make_provider: F,
+    ) -> AppResult<()>
+    where
+        F: FnMut(ClipboardProviderKind, ClipboardSelectionTarget) -> AppResult<Box<dyn ClipboardProvider>>,
+    {
+        let mut backend = make_provider(provider, target)?;
+        backend
+            .set_contents(sentinel.to_owned())
+            .map_err(|error| AppError::Clipboard(format!("clipboard write failed: {error}")))?;
+        let read_back = backend
+            .get_contents()
+            .map_err(|error| AppError::Clipboard(format!("clipboard read failed: {error}")))?;
+        if read_back != sentinel {
+            return Err(AppError::Clipboard(format!(
+                "clipboard round-trip mismatch: wrote `{sentinel}`, read back `{read_back}`"
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ClipboardOutput;
+    use super::{ClipboardOutput, ClipboardProvider};
+    use crate::config::{ClipboardProviderKind, ClipboardSelectionTarget};
     use crate::error::AppError;
     use std::sync::{Arc, Mutex};
 
-    struct FakeClipboardBackend {
+    struct FakeProvider {
         writes: Arc<Mutex<Vec<String>>>,
         fail_with: Option<String>,
     }
 
-    impl super::ClipboardBackend for FakeClipboardBackend {
-        fn set_text(&mut self, text: String) -> Result<(), String> {
+    impl ClipboardProvider for FakeProvider {
+        fn get_contents(&mut self) -> Result<String, String> {
+            Ok(self
+                .writes
+                .lock()
+                .expect("lock writes")
+                .last()
+                .cloned()
+                .unwrap_or_default())
+        }
+
+        fn set_contents(&mut self, text: String) -> Result<(), String> {
             self.writes.lock().expect("lock writes").push(text);
             if let Some(error) = self.fail_with.take() {
                 return Err(error);
@@ -61,11 +311,16 @@ mod tests {
 
     #[test]
     fn write_text_reports_init_failure_with_stable_prefix() {
-        let error = ClipboardOutput::write_text_with("hello world", || {
-            Err(AppError::Clipboard(
-                "clipboard init failed: no display".to_owned(),
-            ))
-        })
+        let error = ClipboardOutput::write_text_with(
+            "hello world",
+            ClipboardProviderKind::Internal,
+            ClipboardSelectionTarget::Clipboard,
+            |_, _| {
+                Err(AppError::Clipboard(
+                    "clipboard init failed: no display".to_owned(),
+                ))
+            },
+        )
         .expect_err("init must fail");
         assert!(matches!(
             error,
@@ -76,13 +331,18 @@ mod tests {
     #[test]
     fn write_text_reports_write_failure_with_stable_prefix() {
         let writes = Arc::new(Mutex::new(Vec::new()));
-        let writes_for_backend = writes.clone();
-        let error = ClipboardOutput::write_text_with("hello world", move || {
-            Ok(Box::new(FakeClipboardBackend {
-                writes: writes_for_backend.clone(),
-                fail_with: Some("permission denied".to_owned()),
-            }) as Box<dyn super::ClipboardBackend>)
-        })
+        let writes_for_provider = writes.clone();
+        let error = ClipboardOutput::write_text_with(
+            "hello world",
+            ClipboardProviderKind::Internal,
+            ClipboardSelectionTarget::Clipboard,
+            move |_, _| {
+                Ok(Box::new(FakeProvider {
+                    writes: writes_for_provider.clone(),
+                    fail_with: Some("permission denied".to_owned()),
+                }) as Box<dyn ClipboardProvider>)
+            },
+        )
         .expect_err("write must fail");
 
         assert!(matches!(
@@ -90,27 +350,119 @@ mod tests {
             AppError::Clipboard(message)
                 if message == "clipboard write failed: permission denied"
         ));
-        assert_eq!(
-            writes.lock().expect("lock writes").as_slice(),
-            ["hello world"]
-        );
+        assert_eq!(writes.lock().expect("lock writes").as_slice(), ["hello world"]);
     }
 
     #[test]
-    fn write_text_succeeds_with_fake_backend() {
+    fn write_text_succeeds_with_fake_provider() {
         let writes = Arc::new(Mutex::new(Vec::new()));
-        let writes_for_backend = writes.clone();
-        ClipboardOutput::write_text_with("hello world", move || {
-            Ok(Box::new(FakeClipboardBackend {
-                writes: writes_for_backend.clone(),
-                fail_with: None,
-            }) as Box<dyn super::ClipboardBackend>)
-        })
+        let writes_for_provider = writes.clone();
+        ClipboardOutput::write_text_with(
+            "hello world",
+            ClipboardProviderKind::Internal,
+            ClipboardSelectionTarget::Clipboard,
+            move |_, _| {
+                Ok(Box::new(FakeProvider {
+                    writes: writes_for_provider.clone(),
+                    fail_with: None,
+                }) as Box<dyn ClipboardProvider>)
+            },
+        )
         .expect("write should succeed");
 
-        assert_eq!(
-            writes.lock().expect("lock writes").as_slice(),
-            ["hello world"]
-        );
+        assert_eq!(writes.lock().expect("lock writes").as_slice(), ["hello world"]);
+    }
+
+    #[test]
+    fn internal_provider_round_trips_contents() {
+        use super::InternalProvider;
+
+        let mut provider = InternalProvider::default();
+        provider.set_contents("hello".to_owned()).expect("set");
+        assert_eq!(provider.get_contents().expect("get"), "hello");
+    }
+
+    #[test]
+    fn round_trip_succeeds_when_read_back_matches() {
+        ClipboardOutput::round_trip_with(
+            ClipboardProviderKind::Internal,
+            ClipboardSelectionTarget::Clipboard,
+            "probe-sentinel",
+            |_, _| {
+                Ok(Box::new(FakeProvider {
+                    writes: Arc::new(Mutex::new(Vec::new())),
+                    fail_with: None,
+                }) as Box<dyn ClipboardProvider>)
+            },
+        )
+        .expect("round trip should succeed");
+    }
+
+    #[test]
+    fn round_trip_fails_when_read_back_diverges() {
+        struct MismatchProvider;
+        impl ClipboardProvider for MismatchProvider {
+            fn get_contents(&mut self) -> Result<String, String> {
+                Ok("not-the-sentinel".to_owned())
+            }
+            fn set_contents(&mut self, _text: String) -> Result<(), String> {
+                Ok(())
+            }
+        }
+
+        let error = ClipboardOutput::round_trip_with(
+            ClipboardProviderKind::Internal,
+            ClipboardSelectionTarget::Clipboard,
+            "probe-sentinel",
+            |_, _| Ok(Box::new(MismatchProvider) as Box<dyn ClipboardProvider>),
+        )
+        .expect_err("must fail");
+        assert!(matches!(
+            error,
+            AppError::Clipboard(message) if message.contains("round-trip mismatch")
+        ));
+    }
+
+    #[test]
+    fn supports_target_accepts_clipboard_for_every_backend() {
+        for provider in [
+            ClipboardProviderKind::Auto,
+            ClipboardProviderKind::Wayland,
+            ClipboardProviderKind::Xclip,
+            ClipboardProviderKind::Xsel,
+            ClipboardProviderKind::Macos,
+            ClipboardProviderKind::Windows,
+            ClipboardProviderKind::Arboard,
+            ClipboardProviderKind::Internal,
+        ] {
+            assert!(super::supports_target(
+                provider,
+                ClipboardSelectionTarget::Clipboard
+            ));
+        }
+    }
+
+    #[test]
+    fn supports_target_restricts_primary_to_x11_command_backends() {
+        assert!(super::supports_target(
+            ClipboardProviderKind::Xclip,
+            ClipboardSelectionTarget::Primary
+        ));
+        assert!(super::supports_target(
+            ClipboardProviderKind::Xsel,
+            ClipboardSelectionTarget::Primary
+        ));
+        assert!(!super::supports_target(
+            ClipboardProviderKind::Auto,
+            ClipboardSelectionTarget::Primary
+        ));
+        assert!(!super::supports_target(
+            ClipboardProviderKind::Wayland,
+            ClipboardSelectionTarget::Primary
+        ));
+        assert!(!super::supports_target(
+            ClipboardProviderKind::Macos,
+            ClipboardSelectionTarget::Primary
+        ));
     }
 }
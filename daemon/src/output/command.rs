@@ -0,0 +1,192 @@
+use std::io::Write;
+use std::process::Stdio;
+
+use franken_whisper::BackendKind;
+
+use crate::error::{AppError, AppResult};
+
+/// Expands `{transcript}`, `{run_id}`, `{language}`, and `{backend}`
+/// placeholders in an `OutputConfig::command_template`. `language` expands
+/// to an empty string when the backend didn't detect one.
+///
+/// The expanded string is handed to a shell (see [`spawn_shell`]), and
+/// `transcript` is live recognized speech, so every substituted value is
+/// shell-quoted before splicing: without it, metacharacters in the
+/// transcript (`;`, backticks, `$()`, quotes, `|`) would let arbitrary
+/// recognized speech run arbitrary shell commands under the user's account.
+fn expand_template(
+    template: &str,
+    transcript: &str,
+    run_id: &str,
+    language: Option<&str>,
+    backend: BackendKind,
+) -> String {
+    template
+        .replace("{transcript}", &shell_quote(transcript))
+        .replace("{run_id}", &shell_quote(run_id))
+        .replace("{language}", &shell_quote(language.unwrap_or("")))
+        .replace("{backend}", &shell_quote(&format!("{backend:?}")))
+}
+
+/// Quotes `value` so [`spawn_shell`]'s shell treats it as a single literal
+/// argument, independent of whatever metacharacters it contains.
+fn shell_quote(value: &str) -> String {
+    if cfg!(windows) {
+        format!("\"{}\"", value.replace('"', "\\\""))
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+pub struct CommandOutput;
+
+impl CommandOutput {
+    /// Expands `template`'s placeholders and runs the result through the
+    /// platform shell, piping `transcript` on stdin so a template that never
+    /// references `{transcript}` (e.g. a plain `my-formatter`) still
+    /// receives it the way a Unix pipeline would.
+    pub fn run(
+        template: &str,
+        transcript: &str,
+        run_id: &str,
+        language: Option<&str>,
+        backend: BackendKind,
+    ) -> AppResult<()> {
+        let expanded = expand_template(template, transcript, run_id, language, backend);
+        Self::run_shell(&expanded, transcript, spawn_shell)
+    }
+
+    fn run_shell<F>(expanded: &str, transcript: &str, mut spawn: F) -> AppResult<()>
+    where
+        F: FnMut(&str) -> std::io::Result<std::process::Child>,
+    {
+        let mut child = spawn(expanded)
+            .map_err(|error| AppError::CommandOutput(format!("failed to spawn `{expanded}`: {error}")))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(transcript.as_bytes());
+        }
+
+        let output = child.wait_with_output().map_err(|error| {
+            AppError::CommandOutput(format!("failed to wait on `{expanded}`: {error}"))
+        })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(AppError::CommandOutput(format!(
+                "`{expanded}` exited with {}: {}",
+                output.status,
+                stderr.trim()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+fn spawn_shell(expanded: &str) -> std::io::Result<std::process::Child> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+
+    std::process::Command::new(shell)
+        .arg(flag)
+        .arg(expanded)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand_template, CommandOutput};
+    use franken_whisper::BackendKind;
+    use std::process::Stdio;
+
+    #[test]
+    fn expand_template_substitutes_every_placeholder() {
+        let expanded = expand_template(
+            "{backend}/{run_id}: [{language}] {transcript}",
+            "hello world",
+            "run-1",
+            Some("en"),
+            BackendKind::WhisperCpp,
+        );
+        assert_eq!(expanded, "'WhisperCpp'/'run-1': ['en'] 'hello world'");
+    }
+
+    #[test]
+    fn expand_template_leaves_language_blank_when_unset() {
+        let expanded = expand_template(
+            "[{language}]",
+            "hello",
+            "run-1",
+            None,
+            BackendKind::WhisperCpp,
+        );
+        assert_eq!(expanded, "['']");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn expand_template_quotes_shell_metacharacters_in_the_transcript() {
+        let expanded = expand_template(
+            "echo {transcript}",
+            "hello; rm -rf / #`id`",
+            "run-1",
+            None,
+            BackendKind::WhisperCpp,
+        );
+        assert_eq!(expanded, "echo 'hello; rm -rf / #`id`'");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn expand_template_escapes_single_quotes_in_the_transcript() {
+        let expanded = expand_template(
+            "echo {transcript}",
+            "it's a trap",
+            "run-1",
+            None,
+            BackendKind::WhisperCpp,
+        );
+        assert_eq!(expanded, "echo 'it'\\''s a trap'");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_shell_pipes_transcript_on_stdin() {
+        CommandOutput::run_shell("cat > /dev/null", "hello world", |expanded| {
+            std::process::Command::new("sh")
+                .arg("-c")
+                .arg(expanded)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::piped())
+                .spawn()
+        })
+        .expect("shell command should succeed");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_shell_surfaces_a_nonzero_exit_status() {
+        let error = CommandOutput::run_shell("exit 3", "hello world", |expanded| {
+            std::process::Command::new("sh")
+                .arg("-c")
+                .arg(expanded)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::piped())
+                .spawn()
+        })
+        .expect_err("must fail");
+        assert!(matches!(
+            error,
+            crate::error::AppError::CommandOutput(message) if message.contains("exited with")
+        ));
+    }
+}
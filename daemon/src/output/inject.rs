@@ -0,0 +1,358 @@
+use std::time::Duration;
+
+use crate::config::{ClipboardProviderKind, ClipboardSelectionTarget};
+use crate::error::{AppError, AppResult};
+use crate::output::clipboard::ClipboardOutput;
+
+/// Synthesizes keystrokes at the OS level, the way a text expander like
+/// espanso injects output. Needed because many terminals, password fields,
+/// and remote-desktop clients ignore programmatic clipboard paste.
+pub trait KeystrokeInjector: Send {
+    fn type_text(
+        &mut self,
+        text: &str,
+        inter_char_delay: Duration,
+        auto_paste_delay: Duration,
+    ) -> Result<(), String>;
+}
+
+/// Writes `text` to the clipboard and injects the platform paste shortcut,
+/// used when a platform injector's own typing path fails (its typing
+/// command can't run or exits non-zero, or on macOS a keyboard event can't
+/// be created). The whole of `text` is pasted; characters are not checked
+/// for a keysym mapping beforehand. Waits `auto_paste_delay` between the
+/// write and the paste keystroke so the target app has time to notice the
+/// clipboard change before the paste shortcut fires.
+fn paste_fallback(
+    text: &str,
+    paste_key_command: &[&str],
+    auto_paste_delay: Duration,
+) -> Result<(), String> {
+    ClipboardOutput::write_text(
+        text,
+        ClipboardProviderKind::Auto,
+        ClipboardSelectionTarget::Clipboard,
+    )
+    .map_err(|error| format!("clipboard-paste fallback write failed: {error}"))?;
+
+    if !auto_paste_delay.is_zero() {
+        std::thread::sleep(auto_paste_delay);
+    }
+
+    let (program, args) = paste_key_command
+        .split_first()
+        .ok_or_else(|| "paste key command is empty".to_owned())?;
+    let status = std::process::Command::new(program)
+        .args(args)
+        .status()
+        .map_err(|error| format!("failed to execute `{program}`: {error}"))?;
+    if !status.success() {
+        return Err(format!("`{program}` exited with {status}"));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+mod macos_inject {
+    use core_graphics::event::{CGEvent, CGEventTapLocation};
+    use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+    use std::time::Duration;
+
+    use super::{paste_fallback, KeystrokeInjector};
+
+    #[derive(Default)]
+    pub struct CgKeyboardInjector;
+
+    impl CgKeyboardInjector {
+        pub fn new() -> Self {
+            Self
+        }
+    }
+
+    impl KeystrokeInjector for CgKeyboardInjector {
+        fn type_text(
+            &mut self,
+            text: &str,
+            inter_char_delay: Duration,
+            auto_paste_delay: Duration,
+        ) -> Result<(), String> {
+            let source = CGEventSource::new(CGEventSourceStateID::HIDSystemState)
+                .map_err(|()| "failed to create CGEventSource".to_owned())?;
+
+            for ch in text.chars() {
+                let Ok(event) = CGEvent::new_keyboard_event(source.clone(), 0, true) else {
+                    return paste_fallback(
+                        text,
+                        &["osascript", "-e", PASTE_APPLESCRIPT],
+                        auto_paste_delay,
+                    );
+                };
+                event.set_string(&ch.to_string());
+                event.post(CGEventTapLocation::HID);
+                if !inter_char_delay.is_zero() {
+                    std::thread::sleep(inter_char_delay);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    const PASTE_APPLESCRIPT: &str =
+        "tell application \"System Events\" to keystroke \"v\" using command down";
+}
+
+#[cfg(target_os = "macos")]
+pub use macos_inject::CgKeyboardInjector as PlatformInjector;
+
+#[cfg(target_os = "linux")]
+mod linux_inject {
+    use std::time::Duration;
+
+    use super::{paste_fallback, KeystrokeInjector};
+
+    #[derive(Default)]
+    pub struct X11OrWaylandInjector;
+
+    impl X11OrWaylandInjector {
+        pub fn new() -> Self {
+            Self
+        }
+    }
+
+    impl KeystrokeInjector for X11OrWaylandInjector {
+        fn type_text(
+            &mut self,
+            text: &str,
+            inter_char_delay: Duration,
+            auto_paste_delay: Duration,
+        ) -> Result<(), String> {
+            let delay_ms = inter_char_delay.as_millis().to_string();
+
+            if std::env::var_os("WAYLAND_DISPLAY").is_some() && which::which("ydotool").is_ok() {
+                return run_type_command(
+                    &["ydotool", "type", "--key-delay", &delay_ms, text],
+                    text,
+                    &["ydotool", "key", "ctrl+v"],
+                    auto_paste_delay,
+                );
+            }
+
+            if std::env::var_os("DISPLAY").is_some() && which::which("xdotool").is_ok() {
+                return run_type_command(
+                    &["xdotool", "type", "--delay", &delay_ms, "--", text],
+                    text,
+                    &["xdotool", "key", "ctrl+v"],
+                    auto_paste_delay,
+                );
+            }
+
+            Err("no virtual keyboard tool found (looked for ydotool/xdotool)".to_owned())
+        }
+    }
+
+    fn run_type_command(
+        type_command: &[&str],
+        text: &str,
+        paste_key_command: &[&str],
+        auto_paste_delay: Duration,
+    ) -> Result<(), String> {
+        let (program, args) = type_command
+            .split_first()
+            .ok_or_else(|| "type command is empty".to_owned())?;
+        match std::process::Command::new(program).args(args).status() {
+            Ok(status) if status.success() => Ok(()),
+            Ok(status) => {
+                let primary = format!("`{program}` exited with {status}");
+                paste_fallback(text, paste_key_command, auto_paste_delay)
+                    .map_err(|fallback| format!("{primary}; {fallback}"))
+            }
+            Err(error) => {
+                let primary = format!("failed to execute `{program}`: {error}");
+                paste_fallback(text, paste_key_command, auto_paste_delay)
+                    .map_err(|fallback| format!("{primary}; {fallback}"))
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub use linux_inject::X11OrWaylandInjector as PlatformInjector;
+
+#[cfg(target_os = "windows")]
+mod windows_inject {
+    use std::time::Duration;
+
+    use super::{paste_fallback, KeystrokeInjector};
+
+    #[derive(Default)]
+    pub struct SendInputInjector;
+
+    impl SendInputInjector {
+        pub fn new() -> Self {
+            Self
+        }
+    }
+
+    impl KeystrokeInjector for SendInputInjector {
+        fn type_text(
+            &mut self,
+            text: &str,
+            inter_char_delay: Duration,
+            auto_paste_delay: Duration,
+        ) -> Result<(), String> {
+            // SendKeys drives the same SendInput path the Win32 API exposes,
+            // without pulling in a raw winapi dependency just for this.
+            let escaped = text
+                .replace('{', "{{}")
+                .replace('}', "{}}")
+                .replace('+', "{+}")
+                .replace('^', "{^}")
+                .replace('%', "{%}")
+                .replace('~', "{~}")
+                .replace('(', "{(}")
+                .replace(')', "{)}");
+            let script = format!(
+                "Add-Type -AssemblyName System.Windows.Forms; \
+                 [System.Windows.Forms.SendKeys]::SendWait('{escaped}')"
+            );
+
+            let _ = inter_char_delay;
+            let status = std::process::Command::new("powershell")
+                .args(["-NoProfile", "-Command", &script])
+                .status()
+                .map_err(|error| format!("failed to execute `powershell`: {error}"))?;
+            if status.success() {
+                return Ok(());
+            }
+
+            paste_fallback(
+                text,
+                &["powershell", "-NoProfile", "-Command", "(New-Object -ComObject WScript.Shell).SendKeys('^v')"],
+                auto_paste_delay,
+            )
+            .map_err(|fallback| format!("`powershell` exited with {status}; {fallback}"))
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub use windows_inject::SendInputInjector as PlatformInjector;
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+pub struct NoopInjector;
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+impl NoopInjector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+impl KeystrokeInjector for NoopInjector {
+    fn type_text(
+        &mut self,
+        _text: &str,
+        _inter_char_delay: Duration,
+        _auto_paste_delay: Duration,
+    ) -> Result<(), String> {
+        Err("keystroke injection is not implemented for this platform".to_owned())
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+pub use NoopInjector as PlatformInjector;
+
+pub struct TypeTextOutput;
+
+impl TypeTextOutput {
+    pub fn type_text(
+        text: &str,
+        inter_char_delay: Duration,
+        auto_paste_delay: Duration,
+    ) -> AppResult<()> {
+        Self::type_text_with(
+            text,
+            inter_char_delay,
+            auto_paste_delay,
+            &mut PlatformInjector::new(),
+        )
+    }
+
+    fn type_text_with(
+        text: &str,
+        inter_char_delay: Duration,
+        auto_paste_delay: Duration,
+        injector: &mut dyn KeystrokeInjector,
+    ) -> AppResult<()> {
+        injector
+            .type_text(text, inter_char_delay, auto_paste_delay)
+            .map_err(AppError::TypeText)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeystrokeInjector, TypeTextOutput};
+    use crate::error::AppError;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    struct FakeInjector {
+        calls: Arc<Mutex<Vec<String>>>,
+        fail_with: Option<String>,
+    }
+
+    impl KeystrokeInjector for FakeInjector {
+        fn type_text(
+            &mut self,
+            text: &str,
+            _inter_char_delay: Duration,
+            _auto_paste_delay: Duration,
+        ) -> Result<(), String> {
+            self.calls.lock().expect("lock calls").push(text.to_owned());
+            if let Some(error) = self.fail_with.take() {
+                return Err(error);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn type_text_succeeds_with_fake_injector() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut injector = FakeInjector {
+            calls: calls.clone(),
+            fail_with: None,
+        };
+
+        TypeTextOutput::type_text_with(
+            "hello",
+            Duration::from_millis(5),
+            Duration::ZERO,
+            &mut injector,
+        )
+        .expect("type text should succeed");
+        assert_eq!(calls.lock().expect("lock calls").as_slice(), ["hello"]);
+    }
+
+    #[test]
+    fn type_text_reports_injector_failure_with_stable_prefix() {
+        let mut injector = FakeInjector {
+            calls: Arc::new(Mutex::new(Vec::new())),
+            fail_with: Some("no virtual keyboard tool found".to_owned()),
+        };
+
+        let error = TypeTextOutput::type_text_with(
+            "hello",
+            Duration::from_millis(5),
+            Duration::ZERO,
+            &mut injector,
+        )
+        .expect_err("must fail");
+        assert!(matches!(
+            error,
+            AppError::TypeText(message) if message == "no virtual keyboard tool found"
+        ));
+    }
+}
@@ -0,0 +1,7 @@
+pub mod clipboard;
+pub mod command;
+pub mod inject;
+
+pub use clipboard::{ClipboardOutput, ClipboardProvider};
+pub use command::CommandOutput;
+pub use inject::{KeystrokeInjector, TypeTextOutput};
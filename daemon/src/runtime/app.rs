@@ -7,11 +7,12 @@ use std::time::{Duration, Instant};
 use crossbeam_channel::TryRecvError;
 
 use crate::bootstrap::{bootstrap_env, AppPaths};
-use crate::capture::devices::list_input_devices;
+use crate::capture::devices::describe_input_devices;
 use crate::config::AppConfig;
-use crate::controller::events::{ControllerEvent, ControllerOutput};
-use crate::controller::{run_controller_loop, ControllerContext};
+use crate::controller::events::{ControllerEvent, ControllerOutput, ShutdownMode};
+use crate::controller::{output_format, run_controller_loop, ControllerContext};
 use crate::error::{AppError, AppResult};
+use crate::history::migrations::schema_version;
 use crate::history::HistoryStore;
 use crate::runtime::topology::RuntimeTopology;
 use crate::ui::{Notifier, UiFrontend};
@@ -20,15 +21,15 @@ pub fn run_app(config: AppConfig, paths: AppPaths) -> AppResult<()> {
     paths.ensure_dirs()?;
     bootstrap_env(&paths)?;
 
-    let RuntimeTopology {
-        controller_event_tx,
-        controller_event_rx,
-        controller_output_tx,
-        controller_output_rx,
-    } = RuntimeTopology::new();
+    let topology = RuntimeTopology::new();
+    let controller_event_tx = topology.controller_event_tx.clone();
+    let controller_event_rx = topology.controller_event_rx.clone();
+    let controller_output_tx = topology.controller_output_tx.clone();
+    let controller_output_rx = topology.controller_output_rx.clone();
     let controller_context = ControllerContext {
         config: config.clone(),
         paths: paths.clone(),
+        clocks: Arc::new(crate::clock::SystemClocks::new()),
     };
     let controller_event_tx_for_loop = controller_event_tx.clone();
     let (controller_result_tx, controller_result_rx) = crossbeam_channel::bounded(1);
@@ -50,8 +51,16 @@ pub fn run_app(config: AppConfig, paths: AppPaths) -> AppResult<()> {
             })?,
     );
 
+    // First run: no calibration marker yet, so ask the controller to pick
+    // `threads`/`processors` for this machine before anything else happens;
+    // see `calibration::calibrate` and its marker in `ControllerEvent::Calibrate`'s
+    // handler.
+    if !paths.state_dir.join("calibration-complete").exists() {
+        let _ = controller_event_tx.send(ControllerEvent::Calibrate);
+    }
+
     let notifier = Notifier::new(config.output.enable_notifications);
-    let ui = UiFrontend::new(&config.hotkey.binding)?;
+    let ui = UiFrontend::new(&config.hotkey.bindings, config.hotkey.mode)?;
 
     let shutdown = Arc::new(AtomicBool::new(false));
     let shutdown_flag = shutdown.clone();
@@ -60,9 +69,40 @@ pub fn run_app(config: AppConfig, paths: AppPaths) -> AppResult<()> {
     })
     .map_err(|error| AppError::Controller(format!("failed to register ctrl-c handler: {error}")))?;
 
+    // POSIX-only: forwards SIGUSR1/SIGTERM/SIGINT/SIGHUP directly into the
+    // controller's event queue for window-manager keybindings and service
+    // managers; `ctrlc` above remains the cross-platform SIGINT fallback.
+    #[cfg(unix)]
+    let _signal_bridge = crate::runtime::signals::spawn_signal_bridge(controller_event_tx.clone())?;
+
     #[cfg(not(target_os = "macos"))]
     spawn_stdin_command_thread(controller_event_tx.clone())?;
 
+    #[cfg(unix)]
+    let ipc_output_tx = crate::controller::ipc::spawn_ipc_server(
+        paths.ipc_socket.clone(),
+        controller_event_tx.clone(),
+    )?;
+
+    #[cfg(unix)]
+    let ipc_tcp_output_tx = match &config.service.control_tcp_addr {
+        Some(addr) => {
+            let addr = addr.parse().map_err(|error| {
+                AppError::Controller(format!("invalid service.control_tcp_addr {addr}: {error}"))
+            })?;
+            Some(crate::controller::ipc::spawn_ipc_tcp_server(
+                addr,
+                controller_event_tx.clone(),
+            )?)
+        }
+        None => None,
+    };
+
+    // Every thread is spawned and (on Unix) the IPC sockets are bound, so
+    // the runtime is fully up from here; unblocks any caller waiting on
+    // `RuntimeTopology::await_start`.
+    topology.mark_started();
+
     let mut last_tick = Instant::now();
     let mut stopping = false;
 
@@ -73,7 +113,7 @@ pub fn run_app(config: AppConfig, paths: AppPaths) -> AppResult<()> {
         }
 
         for event in ui.drain_events() {
-            if matches!(event, ControllerEvent::Shutdown) {
+            if matches!(event, ControllerEvent::Shutdown(_)) {
                 stopping = true;
             }
             let _ = controller_event_tx.send(event);
@@ -81,54 +121,98 @@ pub fn run_app(config: AppConfig, paths: AppPaths) -> AppResult<()> {
 
         if !stopping && shutdown.load(Ordering::SeqCst) {
             stopping = true;
-            let _ = controller_event_tx.send(ControllerEvent::Shutdown);
+            let _ = controller_event_tx.send(ControllerEvent::Shutdown(ShutdownMode::FlushPending));
         }
 
         loop {
             match controller_output_rx.try_recv() {
-                Ok(output) => match output {
-                    ControllerOutput::StateChanged(state) => {
-                        ui.set_state(&state)?;
-                        match &state {
-                            crate::controller::state::ControllerState::Degraded(reason) => {
-                                let _ = notifier.notify("Quedo Degraded", reason);
-                            }
-                            crate::controller::state::ControllerState::Unavailable(reason) => {
-                                let _ = notifier.notify("Quedo Unavailable", reason);
-                            }
-                            _ => {}
-                        }
-                    }
-                    ControllerOutput::Notification(message) => {
-                        tracing::info!("{message}");
-                        let _ = notifier.notify("Quedo", &message);
+                Ok(output) => {
+                    #[cfg(unix)]
+                    let _ = ipc_output_tx.send(output.clone());
+                    #[cfg(unix)]
+                    if let Some(tcp_tx) = &ipc_tcp_output_tx {
+                        let _ = tcp_tx.send(output.clone());
                     }
-                    ControllerOutput::DoctorReport(report) => {
-                        tracing::info!("doctor report emitted");
-                        println!("{}", report.render_text());
-                    }
-                    ControllerOutput::TranscriptReady(result) => {
-                        tracing::info!(run_id = %result.run_id, "transcript copied to clipboard");
-                        let _ = notifier.notify("Quedo", "Transcript copied to clipboard");
+                    if config.diagnostics.emit_events {
+                        // Same shape `controller::ipc` broadcasts to socket
+                        // clients, reused here so stdout, the Unix socket,
+                        // and the TCP control port all agree on one tagged
+                        // encoding of `ControllerOutput` instead of each
+                        // inventing its own; `event_format` only changes
+                        // whether that encoding is written as JSON or YAML.
+                        if let Ok(line) =
+                            output_format::serialize_output(&output, config.diagnostics.event_format)
+                        {
+                            println!("{line}");
+                        }
                     }
-                    ControllerOutput::Stopped => {
-                        let join_result = controller_join
-                            .take()
-                            .expect("controller join handle missing")
-                            .join()
-                            .map_err(|_| {
-                                AppError::Controller("controller thread panicked".to_owned())
-                            });
-                        let loop_result = controller_result_rx.recv().map_err(|_| {
-                            AppError::Controller(
-                                "controller result channel closed before completion".to_owned(),
-                            )
-                        })?;
-                        join_result?;
-                        loop_result?;
-                        return Ok(());
+
+                    match output {
+                        ControllerOutput::StateChanged(state) => {
+                            ui.set_state(&state)?;
+                            match &state {
+                                crate::controller::state::ControllerState::Degraded(reason) => {
+                                    let _ = notifier.notify("Quedo Degraded", reason);
+                                }
+                                crate::controller::state::ControllerState::Unavailable(
+                                    reason,
+                                ) => {
+                                    let _ = notifier.notify("Quedo Unavailable", reason);
+                                }
+                                _ => {}
+                            }
+                        }
+                        ControllerOutput::Notification(notification) => {
+                            // The desktop toast for this notification has already
+                            // been shown by the controller's injected
+                            // `DesktopNotificationSink`; this arm only logs it.
+                            tracing::info!("{}", notification.detail);
+                        }
+                        ControllerOutput::DoctorReport(report) => {
+                            tracing::info!("doctor report emitted");
+                            println!("{}", report.render_text());
+                        }
+                        ControllerOutput::PartialTranscript {
+                            run_id,
+                            stable_text,
+                            provisional_text,
+                        } => {
+                            tracing::debug!(
+                                run_id = %run_id,
+                                "partial transcript: {stable_text} [{provisional_text}]"
+                            );
+                        }
+                        ControllerOutput::TranscriptReady(result) => {
+                            tracing::info!(run_id = %result.run_id, "transcript copied to clipboard");
+                            let _ = notifier.notify("Quedo", "Transcript copied to clipboard");
+                        }
+                        ControllerOutput::Error { severity, message, job_id } => {
+                            tracing::warn!(job_id, ?severity, "{message}");
+                        }
+                        ControllerOutput::HistoryReport(_) => {}
+                        ControllerOutput::CaptionsReady { .. } => {}
+                        ControllerOutput::Stopped => {
+                            let join_result = controller_join
+                                .take()
+                                .expect("controller join handle missing")
+                                .join()
+                                .map_err(|_| {
+                                    AppError::Controller("controller thread panicked".to_owned())
+                                });
+                            let loop_result = controller_result_rx.recv().map_err(|_| {
+                                AppError::Controller(
+                                    "controller result channel closed before completion"
+                                        .to_owned(),
+                                )
+                            })?;
+                            join_result?;
+                            loop_result?;
+                            #[cfg(unix)]
+                            let _ = std::fs::remove_file(&paths.ipc_socket);
+                            return Ok(());
+                        }
                     }
-                },
+                }
                 Err(TryRecvError::Empty) => break,
                 Err(TryRecvError::Disconnected) => {
                     let join_result = controller_join
@@ -190,8 +274,11 @@ fn spawn_stdin_command_thread(
                 let command = line.trim().to_ascii_lowercase();
                 let event = match command.as_str() {
                     "toggle" => Some(ControllerEvent::Toggle),
+                    "start" => Some(ControllerEvent::Start),
+                    "stop" => Some(ControllerEvent::Stop),
+                    "cancel" => Some(ControllerEvent::Cancel),
                     "doctor" => Some(ControllerEvent::RunDoctor),
-                    "quit" | "exit" => Some(ControllerEvent::Shutdown),
+                    "quit" | "exit" => Some(ControllerEvent::Shutdown(ShutdownMode::FlushPending)),
                     _ => None,
                 };
 
@@ -258,7 +345,21 @@ pub fn status_report(config: &AppConfig, paths: &AppPaths) -> AppResult<String>
     let history = HistoryStore::new(db_path.clone());
     let recent = history.list_recent_runs(5)?;
     let latest = history.latest_run()?;
-    let recording_capability = match list_input_devices() {
+    // Read-only: unlike `bootstrap::bootstrap_env`, `status_report` never
+    // creates or migrates the database, so an as-yet-untouched db_path (no
+    // run has happened yet, or it's a fresh `history.db_path` override)
+    // reports as "none" instead of `Connection::open` creating an empty
+    // file just to answer a status query.
+    let schema_version_display = if db_path.exists() {
+        rusqlite::Connection::open(&db_path)
+            .map_err(AppError::from)
+            .and_then(|connection| schema_version(&connection))
+            .map(|version| version.to_string())
+            .unwrap_or_else(|error| format!("unknown ({error})"))
+    } else {
+        "none (not yet created)".to_owned()
+    };
+    let recording_capability = match describe_input_devices() {
         Ok(devices) if !devices.is_empty() => "available".to_owned(),
         Ok(_) => "unavailable (no input devices)".to_owned(),
         Err(error) => format!("unavailable ({error})"),
@@ -268,6 +369,7 @@ pub fn status_report(config: &AppConfig, paths: &AppPaths) -> AppResult<String>
     output.push_str("Quedo daemon status\n");
     output.push_str(&format!("  config: {}\n", paths.config_file.display()));
     output.push_str(&format!("  history_db: {}\n", db_path.display()));
+    output.push_str(&format!("  history_schema_version: {schema_version_display}\n"));
     output.push_str(&format!(
         "  franken_state_dir: {}\n",
         paths.state_dir.display()
@@ -306,6 +408,8 @@ mod tests {
             } else {
                 root.join("autostart/quedo-daemon.desktop")
             },
+            ipc_socket: root.join("cache/quedo.sock"),
+            system_config_file: root.join("system-config.toml"),
         }
     }
 
@@ -380,5 +484,35 @@ mod tests {
         assert!(report.contains("recording_backend:"));
         assert!(report.contains("recent_runs: 1"));
         assert!(report.contains("last_run: run-1"));
+        // This db was seeded by hand above without going through
+        // `history::migrations::run_pending_migrations`, so `user_version`
+        // is still its SQLite default of 0.
+        assert!(report.contains("history_schema_version: 0"));
+    }
+
+    #[test]
+    fn status_report_shows_none_for_untouched_history_db() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let paths = make_paths(temp.path());
+        paths.ensure_dirs().expect("dirs");
+
+        let config = AppConfig::default();
+        let report = status_report(&config, &paths).expect("report");
+        assert!(report.contains("history_schema_version: none (not yet created)"));
+    }
+
+    #[test]
+    fn status_report_shows_current_version_for_migrated_history_db() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let paths = make_paths(temp.path());
+        paths.ensure_dirs().expect("dirs");
+        crate::history::migrations::run_pending_migrations(&paths.history_db).expect("migrate");
+
+        let config = AppConfig::default();
+        let report = status_report(&config, &paths).expect("report");
+        assert!(report.contains(&format!(
+            "history_schema_version: {}",
+            crate::history::migrations::CURRENT_SCHEMA_VERSION
+        )));
     }
 }
@@ -0,0 +1,7 @@
+pub mod app;
+pub mod run_loop;
+#[cfg(unix)]
+pub mod signals;
+pub mod topology;
+
+pub use app::{install_autostart, run_app, status_report};
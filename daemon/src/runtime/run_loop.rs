@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{Receiver, RecvError};
+
+use crate::controller::events::{ControllerEvent, ControllerOutput};
+use crate::controller::queue::JobId;
+use crate::transcription::engine::EngineAdapter;
+
+/// One transcription dispatched to a worker but not yet finished. Tracked so
+/// a caller can answer "is anything in flight?" and cancel a specific
+/// request by id, something `cancel_in_flight_engines` in `controller::mod`
+/// cannot do (it only cancels every engine at once).
+struct PendingRequest {
+    engine: Arc<dyn EngineAdapter + Send + Sync>,
+    started_at: Instant,
+}
+
+/// Registry of in-flight transcriptions keyed by `JobId`. A caller adds an
+/// entry via `track` when it hands a job to a worker, and removes it via
+/// `complete` once the matching `ControllerEvent::TranscriptionFinished`
+/// arrives; `RuntimeLoop::select_next` does the latter automatically.
+#[derive(Default)]
+pub struct PendingRequests {
+    entries: HashMap<JobId, PendingRequest>,
+}
+
+impl PendingRequests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn track(&mut self, job_id: JobId, engine: Arc<dyn EngineAdapter + Send + Sync>) {
+        self.entries.insert(
+            job_id,
+            PendingRequest {
+                engine,
+                started_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Removes `job_id` without signalling its engine, for the normal case
+    /// where the job already finished on its own.
+    pub fn complete(&mut self, job_id: JobId) {
+        self.entries.remove(&job_id);
+    }
+
+    /// Signals `job_id`'s engine to abort and removes it from the registry,
+    /// returning whether it was actually in flight.
+    pub fn cancel(&mut self, job_id: JobId) -> bool {
+        match self.entries.remove(&job_id) {
+            Some(pending) => {
+                let _ = pending.engine.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_in_flight(&self, job_id: JobId) -> bool {
+        self.entries.contains_key(&job_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// How long `job_id` has been tracked, if it still is.
+    pub fn elapsed(&self, job_id: JobId) -> Option<Duration> {
+        self.entries
+            .get(&job_id)
+            .map(|pending| pending.started_at.elapsed())
+    }
+}
+
+/// One thing `RuntimeLoop::select_next` multiplexed off of its channels.
+#[derive(Debug)]
+pub enum RuntimeLoopEvent {
+    Controller(ControllerEvent),
+    Output(ControllerOutput),
+    Tick,
+    /// The controller event or output channel disconnected, or the dedicated
+    /// shutdown channel fired or disconnected; the caller should stop
+    /// calling `select_next`.
+    Shutdown,
+}
+
+/// Single authoritative dispatch point multiplexing the channels
+/// `RuntimeTopology` hands out, in the spirit of rust-analyzer's
+/// `main_loop`: one `select!` covering control events, controller output,
+/// a tick timer, and shutdown, so fast control events and long-running
+/// engine work never contend for the same blocking `recv`. Pairs with
+/// `PendingRequests` to track and cancel in-flight transcriptions by id.
+pub struct RuntimeLoop {
+    controller_event_rx: Receiver<ControllerEvent>,
+    controller_output_rx: Receiver<ControllerOutput>,
+    shutdown_rx: Receiver<()>,
+    tick_rx: Receiver<Instant>,
+    pending: PendingRequests,
+}
+
+impl RuntimeLoop {
+    pub fn new(
+        controller_event_rx: Receiver<ControllerEvent>,
+        controller_output_rx: Receiver<ControllerOutput>,
+        shutdown_rx: Receiver<()>,
+        tick_interval: Duration,
+    ) -> Self {
+        Self {
+            controller_event_rx,
+            controller_output_rx,
+            shutdown_rx,
+            tick_rx: crossbeam_channel::tick(tick_interval),
+            pending: PendingRequests::new(),
+        }
+    }
+
+    pub fn pending(&self) -> &PendingRequests {
+        &self.pending
+    }
+
+    pub fn pending_mut(&mut self) -> &mut PendingRequests {
+        &mut self.pending
+    }
+
+    /// Blocks until exactly one multiplexed channel is ready, applies any
+    /// bookkeeping this loop owns, and returns the resulting event.
+    pub fn select_next(&mut self) -> RuntimeLoopEvent {
+        crossbeam_channel::select! {
+            recv(self.controller_event_rx) -> event => self.handle_controller_event(event),
+            recv(self.controller_output_rx) -> output => match output {
+                Ok(output) => RuntimeLoopEvent::Output(output),
+                Err(_) => RuntimeLoopEvent::Shutdown,
+            },
+            recv(self.tick_rx) -> _ => RuntimeLoopEvent::Tick,
+            recv(self.shutdown_rx) -> _ => RuntimeLoopEvent::Shutdown,
+        }
+    }
+
+    fn handle_controller_event(
+        &mut self,
+        event: Result<ControllerEvent, RecvError>,
+    ) -> RuntimeLoopEvent {
+        let Ok(event) = event else {
+            return RuntimeLoopEvent::Shutdown;
+        };
+
+        // A finished job's completion is correlated back to the request
+        // that started it purely by `job_id`, so this is the one place the
+        // registry needs no help from the caller.
+        if let ControllerEvent::TranscriptionFinished { job_id, .. } = &event {
+            self.pending.complete(*job_id);
+        }
+
+        RuntimeLoopEvent::Controller(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PendingRequests, RuntimeLoop, RuntimeLoopEvent};
+    use crate::controller::events::{ControllerEvent, ControllerOutput};
+    use crate::controller::state::ControllerState;
+    use crate::error::{AppError, AppResult};
+    use crate::transcription::engine::EngineAdapter;
+    use crate::transcription::TranscriptionFailure;
+    use franken_whisper::{RunReport, TranscribeRequest};
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct FakeEngine {
+        cancelled: AtomicBool,
+    }
+
+    impl EngineAdapter for FakeEngine {
+        fn transcribe_request(&self, _request: TranscribeRequest) -> AppResult<RunReport> {
+            unimplemented!("not exercised by these tests")
+        }
+
+        fn cancel(&self) -> AppResult<()> {
+            self.cancelled.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn pending_requests_tracks_and_completes_by_job_id() {
+        let mut pending = PendingRequests::new();
+        let engine = Arc::new(FakeEngine::default());
+
+        pending.track(7, engine.clone());
+        assert!(pending.is_in_flight(7));
+        assert_eq!(pending.len(), 1);
+
+        pending.complete(7);
+        assert!(!pending.is_in_flight(7));
+        assert!(pending.is_empty());
+        assert!(
+            !engine.cancelled.load(Ordering::SeqCst),
+            "a normal completion must not cancel the engine"
+        );
+    }
+
+    #[test]
+    fn pending_requests_cancel_signals_engine_and_removes_entry() {
+        let mut pending = PendingRequests::new();
+        let engine = Arc::new(FakeEngine::default());
+        pending.track(3, engine.clone());
+
+        assert!(pending.cancel(3));
+        assert!(!pending.is_in_flight(3));
+        assert!(engine.cancelled.load(Ordering::SeqCst));
+
+        assert!(!pending.cancel(3), "already-removed id has nothing to cancel");
+    }
+
+    fn build_loop() -> (
+        RuntimeLoop,
+        crossbeam_channel::Sender<ControllerEvent>,
+        crossbeam_channel::Sender<ControllerOutput>,
+        crossbeam_channel::Sender<()>,
+    ) {
+        let (event_tx, event_rx) = crossbeam_channel::unbounded();
+        let (output_tx, output_rx) = crossbeam_channel::unbounded();
+        let (shutdown_tx, shutdown_rx) = crossbeam_channel::unbounded();
+        let runtime_loop = RuntimeLoop::new(event_rx, output_rx, shutdown_rx, Duration::from_secs(3600));
+        (runtime_loop, event_tx, output_tx, shutdown_tx)
+    }
+
+    #[test]
+    fn select_next_dispatches_controller_events_and_output() {
+        let (mut runtime_loop, event_tx, output_tx, _shutdown_tx) = build_loop();
+
+        event_tx.send(ControllerEvent::Toggle).expect("send event");
+        assert!(matches!(
+            runtime_loop.select_next(),
+            RuntimeLoopEvent::Controller(ControllerEvent::Toggle)
+        ));
+
+        output_tx
+            .send(ControllerOutput::StateChanged(ControllerState::Recording))
+            .expect("send output");
+        assert!(matches!(
+            runtime_loop.select_next(),
+            RuntimeLoopEvent::Output(ControllerOutput::StateChanged(ControllerState::Recording))
+        ));
+    }
+
+    #[test]
+    fn select_next_completes_pending_request_on_transcription_finished() {
+        let (mut runtime_loop, event_tx, _output_tx, _shutdown_tx) = build_loop();
+        let engine = Arc::new(FakeEngine::default());
+        runtime_loop.pending_mut().track(1, engine.clone());
+
+        event_tx
+            .send(ControllerEvent::TranscriptionFinished {
+                job_id: 1,
+                wav_path: PathBuf::from("/tmp/a.wav"),
+                result: Err(TranscriptionFailure::from(AppError::Transcription(
+                    "boom".to_owned(),
+                ))),
+                partial: false,
+            })
+            .expect("send finished event");
+
+        assert!(matches!(
+            runtime_loop.select_next(),
+            RuntimeLoopEvent::Controller(ControllerEvent::TranscriptionFinished { job_id: 1, .. })
+        ));
+        assert!(!runtime_loop.pending().is_in_flight(1));
+        assert!(
+            !engine.cancelled.load(Ordering::SeqCst),
+            "a normal finish must not cancel the engine"
+        );
+    }
+
+    #[test]
+    fn select_next_reports_shutdown_once_shutdown_channel_fires() {
+        let (mut runtime_loop, _event_tx, _output_tx, shutdown_tx) = build_loop();
+
+        shutdown_tx.send(()).expect("send shutdown");
+        assert!(matches!(
+            runtime_loop.select_next(),
+            RuntimeLoopEvent::Shutdown
+        ));
+    }
+
+    #[test]
+    fn select_next_reports_shutdown_once_event_channel_disconnects() {
+        let (mut runtime_loop, event_tx, _output_tx, _shutdown_tx) = build_loop();
+        drop(event_tx);
+
+        assert!(matches!(
+            runtime_loop.select_next(),
+            RuntimeLoopEvent::Shutdown
+        ));
+    }
+}
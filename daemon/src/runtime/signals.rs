@@ -0,0 +1,109 @@
+//! Bridges POSIX signals into the controller's `ControllerEvent` queue so the
+//! daemon can be driven by window-manager keybindings (`SIGUSR1`) and service
+//! managers (`SIGTERM`/`SIGHUP`) in addition to its IPC/UI front ends.
+
+use std::thread;
+
+use crossbeam_channel::Sender;
+use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM, SIGUSR1};
+use signal_hook::iterator::Signals;
+
+use crate::controller::events::{ControllerEvent, ShutdownMode};
+use crate::error::{AppError, AppResult};
+
+/// Spawns a dedicated thread that listens for `SIGUSR1`, `SIGTERM`, `SIGINT`,
+/// and `SIGHUP` via `signal_hook`'s self-pipe-backed iterator (async-signal-safe;
+/// the real work happens after the signal handler returns) and forwards each
+/// one onto `event_tx` as the matching `ControllerEvent`:
+///
+/// - `SIGUSR1` toggles recording, mirroring a hotkey press.
+/// - `SIGTERM`/`SIGINT` request a graceful shutdown that still drains any
+///   in-flight `Processing` job (`ShutdownMode::FlushPending`).
+/// - `SIGHUP` asks the controller to reload `config.transcription` from disk
+///   and re-check backend availability.
+///
+/// The thread exits when forwarding a signal fails because `event_tx` is
+/// disconnected (the controller has shut down); a repeated signal is
+/// forwarded again like the first.
+pub fn spawn_signal_bridge(event_tx: Sender<ControllerEvent>) -> AppResult<thread::JoinHandle<()>> {
+    let mut signals = Signals::new([SIGUSR1, SIGTERM, SIGINT, SIGHUP])
+        .map_err(|error| AppError::Controller(format!("failed to register signal handler: {error}")))?;
+
+    thread::Builder::new()
+        .name("quedo-signals".to_owned())
+        .spawn(move || {
+            for signal in signals.forever() {
+                let event = match signal {
+                    SIGUSR1 => ControllerEvent::Toggle,
+                    SIGTERM | SIGINT => ControllerEvent::Shutdown(ShutdownMode::FlushPending),
+                    SIGHUP => ControllerEvent::ReloadConfig,
+                    _ => continue,
+                };
+                if event_tx.send(event).is_err() {
+                    break;
+                }
+            }
+        })
+        .map_err(|error| AppError::Controller(format!("failed to spawn signal bridge: {error}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn sigusr1_is_forwarded_as_toggle() {
+        let (event_tx, event_rx) = crossbeam_channel::unbounded();
+        let _bridge = spawn_signal_bridge(event_tx).expect("spawn signal bridge");
+
+        // Give the bridge thread time to register its handlers before we
+        // raise, since signal_hook installs them asynchronously relative to
+        // the spawned thread starting.
+        std::thread::sleep(Duration::from_millis(50));
+        unsafe {
+            libc::raise(SIGUSR1);
+        }
+
+        let event = event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("toggle event");
+        assert!(matches!(event, ControllerEvent::Toggle));
+    }
+
+    #[test]
+    fn sighup_is_forwarded_as_reload_config() {
+        let (event_tx, event_rx) = crossbeam_channel::unbounded();
+        let _bridge = spawn_signal_bridge(event_tx).expect("spawn signal bridge");
+
+        std::thread::sleep(Duration::from_millis(50));
+        unsafe {
+            libc::raise(SIGHUP);
+        }
+
+        let event = event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("reload config event");
+        assert!(matches!(event, ControllerEvent::ReloadConfig));
+    }
+
+    #[test]
+    fn sigterm_is_forwarded_as_flush_pending_shutdown() {
+        let (event_tx, event_rx) = crossbeam_channel::unbounded();
+        let _bridge = spawn_signal_bridge(event_tx).expect("spawn signal bridge");
+
+        std::thread::sleep(Duration::from_millis(50));
+        unsafe {
+            libc::raise(SIGTERM);
+        }
+
+        let event = event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("shutdown event");
+        assert!(matches!(
+            event,
+            ControllerEvent::Shutdown(ShutdownMode::FlushPending)
+        ));
+    }
+}
@@ -1,24 +1,125 @@
-use crossbeam_channel::{Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
 
-use crate::controller::events::{ControllerEvent, ControllerOutput};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+
+use crate::config::BusyUpdatePolicy;
+use crate::controller::events::{ControllerEvent, ControllerOutput, ShutdownMode};
+use crate::error::{AppError, AppResult};
+
+/// How long `RuntimeTopology::shutdown` waits for the controller to
+/// acknowledge a drain request before giving up.
+const SHUTDOWN_ACK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A live-reconfiguration request carried by `RuntimeTopology::reconfigure`:
+/// the model to transcribe with, the recognition language, what a busy
+/// `Toggle`/`Start` should do (see `BusyUpdatePolicy`), and which capture
+/// device to record from.
+#[derive(Debug, Clone)]
+pub struct RuntimeConfig {
+    pub model_id: Option<String>,
+    pub language: Option<String>,
+    pub busy_update_policy: BusyUpdatePolicy,
+    pub device: Option<String>,
+}
+
+/// Lets a caller block until the runtime has finished spinning up (threads
+/// spawned, sockets bound) before sending its first event, mirroring
+/// watchexec's `start_lock`. Cheap to clone: every clone observes the same
+/// underlying flag.
+#[derive(Clone, Default)]
+struct StartLock(Arc<(Mutex<bool>, Condvar)>);
+
+impl StartLock {
+    fn mark_ready(&self) {
+        let (lock, condvar) = &*self.0;
+        *lock.lock().expect("start lock poisoned") = true;
+        condvar.notify_all();
+    }
+
+    fn wait_ready(&self) {
+        let (lock, condvar) = &*self.0;
+        let mut ready = lock.lock().expect("start lock poisoned");
+        while !*ready {
+            ready = condvar.wait(ready).expect("start lock poisoned");
+        }
+    }
+}
 
 pub struct RuntimeTopology {
     pub controller_event_tx: Sender<ControllerEvent>,
     pub controller_event_rx: Receiver<ControllerEvent>,
     pub controller_output_tx: Sender<ControllerOutput>,
     pub controller_output_rx: Receiver<ControllerOutput>,
+    /// Carries a live-reconfiguration request to whoever reads
+    /// `reconfigure_rx`. `run_app` does not hand `reconfigure_rx` to the
+    /// controller loop, so a pushed `RuntimeConfig` is not applied to a
+    /// running controller.
+    pub reconfigure_tx: Sender<RuntimeConfig>,
+    pub reconfigure_rx: Receiver<RuntimeConfig>,
+    start_lock: StartLock,
 }
 
 impl RuntimeTopology {
     pub fn new() -> Self {
         let (controller_event_tx, controller_event_rx) = crossbeam_channel::unbounded();
         let (controller_output_tx, controller_output_rx) = crossbeam_channel::unbounded();
+        let (reconfigure_tx, reconfigure_rx) = crossbeam_channel::unbounded();
 
         Self {
             controller_event_tx,
             controller_event_rx,
             controller_output_tx,
             controller_output_rx,
+            reconfigure_tx,
+            reconfigure_rx,
+            start_lock: StartLock::default(),
+        }
+    }
+
+    /// Marks the runtime fully spun up; every `await_start` call (including
+    /// ones already blocked) unblocks.
+    pub fn mark_started(&self) {
+        self.start_lock.mark_ready();
+    }
+
+    /// Blocks until `mark_started` has been called, so a caller doesn't race
+    /// sending the first event against the controller thread still starting.
+    pub fn await_start(&self) {
+        self.start_lock.wait_ready();
+    }
+
+    /// Pushes a `RuntimeConfig` onto `reconfigure_tx` for whoever reads
+    /// `reconfigure_rx`; see that field for what does and doesn't read it.
+    pub fn reconfigure(&self, config: RuntimeConfig) -> AppResult<()> {
+        self.reconfigure_tx.send(config).map_err(|_| {
+            AppError::Controller("controller reconfigure channel closed".to_owned())
+        })
+    }
+
+    /// Signals the controller to drain (flush any pending transcription) and
+    /// stop, then blocks until it acknowledges with `ControllerOutput::Stopped`.
+    pub fn shutdown(&self) -> AppResult<()> {
+        self.controller_event_tx
+            .send(ControllerEvent::Shutdown(ShutdownMode::FlushPending))
+            .map_err(|_| AppError::Controller("controller event channel closed".to_owned()))?;
+
+        loop {
+            match self.controller_output_rx.recv_timeout(SHUTDOWN_ACK_TIMEOUT) {
+                Ok(ControllerOutput::Stopped) => return Ok(()),
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(AppError::Controller(
+                        "timed out waiting for controller to acknowledge shutdown".to_owned(),
+                    ))
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(AppError::Controller(
+                        "controller output channel closed before shutdown acknowledgement"
+                            .to_owned(),
+                    ))
+                }
+            }
         }
     }
 }
@@ -31,9 +132,14 @@ impl Default for RuntimeTopology {
 
 #[cfg(test)]
 mod tests {
-    use super::RuntimeTopology;
+    use super::{RuntimeConfig, RuntimeTopology};
+    use crate::config::BusyUpdatePolicy;
     use crate::controller::events::{ControllerEvent, ControllerOutput};
     use crate::controller::state::ControllerState;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
 
     #[test]
     fn channels_round_trip_messages() {
@@ -56,4 +162,61 @@ mod tests {
             ControllerOutput::StateChanged(ControllerState::Idle)
         ));
     }
+
+    #[test]
+    fn reconfigure_delivers_the_pushed_config() {
+        let topology = RuntimeTopology::new();
+        let config = RuntimeConfig {
+            model_id: Some("base.en".to_owned()),
+            language: Some("en".to_owned()),
+            busy_update_policy: BusyUpdatePolicy::Restart,
+            device: Some("default".to_owned()),
+        };
+
+        topology.reconfigure(config).expect("reconfigure");
+        let applied = topology.reconfigure_rx.recv().expect("recv reconfigure");
+        assert_eq!(applied.model_id.as_deref(), Some("base.en"));
+        assert_eq!(applied.busy_update_policy, BusyUpdatePolicy::Restart);
+    }
+
+    #[test]
+    fn await_start_blocks_until_mark_started() {
+        let topology = Arc::new(RuntimeTopology::new());
+        let unblocked = Arc::new(AtomicBool::new(false));
+
+        let waiter_topology = topology.clone();
+        let waiter_unblocked = unblocked.clone();
+        let waiter = thread::spawn(move || {
+            waiter_topology.await_start();
+            waiter_unblocked.store(true, Ordering::SeqCst);
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        assert!(!unblocked.load(Ordering::SeqCst));
+
+        topology.mark_started();
+        waiter.join().expect("join waiter");
+        assert!(unblocked.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn shutdown_blocks_until_stopped_is_observed() {
+        let topology = Arc::new(RuntimeTopology::new());
+        let responder_topology = topology.clone();
+
+        let responder = thread::spawn(move || {
+            let event = responder_topology
+                .controller_event_rx
+                .recv()
+                .expect("recv shutdown event");
+            assert!(matches!(event, ControllerEvent::Shutdown(_)));
+            responder_topology
+                .controller_output_tx
+                .send(ControllerOutput::Stopped)
+                .expect("send stopped");
+        });
+
+        topology.shutdown().expect("shutdown");
+        responder.join().expect("join responder");
+    }
 }
@@ -0,0 +1,248 @@
+//! Lua post-processing hook for transcript text, following the embedded-Lua
+//! approach (`mlua`) other editors use to let users script behavior without
+//! recompiling. Entirely gated behind the `scripting` cargo feature, since
+//! `mlua` vendors and builds a Lua interpreter that most installs don't need.
+//!
+//! A script is a Lua chunk that reads the `transcript` and `metadata`
+//! globals and returns the rewritten transcript string, e.g.:
+//!
+//! ```lua
+//! return transcript:gsub("teh", "the")
+//! ```
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use franken_whisper::BackendKind;
+use mlua::Lua;
+
+use crate::config::ScriptingConfig;
+use crate::error::{AppError, AppResult};
+
+/// Metadata handed to the post-transcript script alongside the raw
+/// transcript text, as the `metadata` Lua table.
+pub struct TranscriptContext<'a> {
+    pub run_id: &'a str,
+    pub language: Option<&'a str>,
+    pub backend: BackendKind,
+    pub duration_ms: u64,
+}
+
+/// A `post_transcript_script`, held as source text rather than a compiled
+/// `mlua::Function` because a `Function` borrows its owning `Lua` and
+/// `run` spins up a fresh `Lua` per invocation (see `run`'s doc comment).
+pub struct TranscriptScript {
+    source: String,
+    name: String,
+}
+
+impl TranscriptScript {
+    /// Reads `path` and syntax-checks it by compiling it to a chunk without
+    /// running it, surfacing a bad script as `AppError::Config` the same
+    /// way any other invalid config is reported at startup.
+    pub fn load(path: &Path) -> AppResult<Self> {
+        let source = std::fs::read_to_string(path)?;
+        let name = path.display().to_string();
+
+        Lua::new()
+            .load(&source)
+            .set_name(&name)
+            .into_function()
+            .map_err(|error| {
+                AppError::Config(format!(
+                    "post_transcript_script `{name}` failed to compile: {error}"
+                ))
+            })?;
+
+        Ok(Self { source, name })
+    }
+
+    /// Runs the script against `transcript`, aborting with
+    /// `AppError::Scripting` if it raises an error or overruns `timeout` so
+    /// a runaway script can't hang output. A fresh `Lua` instance backs
+    /// every call, so a script can't accumulate state (e.g. a counter)
+    /// across transcripts.
+    pub fn run(
+        &self,
+        transcript: &str,
+        context: &TranscriptContext,
+        timeout: Duration,
+    ) -> AppResult<String> {
+        let lua = Lua::new();
+        let deadline = Instant::now() + timeout;
+        lua.set_interrupt(move |_| {
+            if Instant::now() >= deadline {
+                Err(mlua::Error::RuntimeError(
+                    "post_transcript_script timed out".to_owned(),
+                ))
+            } else {
+                Ok(mlua::VmState::Continue)
+            }
+        });
+
+        let metadata = lua.create_table().map_err(lua_error)?;
+        metadata.set("run_id", context.run_id).map_err(lua_error)?;
+        metadata.set("language", context.language).map_err(lua_error)?;
+        metadata
+            .set("backend", format!("{:?}", context.backend))
+            .map_err(lua_error)?;
+        metadata
+            .set("duration_ms", context.duration_ms)
+            .map_err(lua_error)?;
+
+        let globals = lua.globals();
+        globals.set("transcript", transcript).map_err(lua_error)?;
+        globals.set("metadata", metadata).map_err(lua_error)?;
+
+        lua.load(&self.source)
+            .set_name(&self.name)
+            .eval::<String>()
+            .map_err(lua_error)
+    }
+}
+
+fn lua_error(error: mlua::Error) -> AppError {
+    AppError::Scripting(error.to_string())
+}
+
+/// Loads and syntax-checks `config.post_transcript_script` once, the way
+/// `load_config` validates every other part of the config at startup; the
+/// loaded script itself is discarded here; callers re-load it per
+/// transcript via `TranscriptScript::load` (see `run`'s doc comment for why
+/// it isn't cached).
+pub fn validate_config(config: &ScriptingConfig) -> AppResult<()> {
+    if !config.enabled {
+        return Ok(());
+    }
+
+    let path = config.post_transcript_script.as_deref().ok_or_else(|| {
+        AppError::Config(
+            "scripting.enabled is true but scripting.post_transcript_script is not set".to_owned(),
+        )
+    })?;
+
+    if !path.exists() {
+        return Err(AppError::Config(format!(
+            "scripting.post_transcript_script `{}` does not exist",
+            path.display()
+        )));
+    }
+
+    TranscriptScript::load(path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_config, TranscriptContext, TranscriptScript};
+    use crate::config::ScriptingConfig;
+    use crate::error::AppError;
+    use franken_whisper::BackendKind;
+    use std::time::Duration;
+
+    fn write_script(contents: &str) -> tempfile::NamedTempFile {
+        let file = tempfile::NamedTempFile::new().expect("tempfile");
+        std::fs::write(file.path(), contents).expect("write script");
+        file
+    }
+
+    #[test]
+    fn load_rejects_syntax_errors() {
+        let file = write_script("return transcript:gsub(");
+        let error = TranscriptScript::load(file.path()).expect_err("must fail");
+        assert!(matches!(error, AppError::Config(message) if message.contains("failed to compile")));
+    }
+
+    #[test]
+    fn run_rewrites_transcript_using_metadata() {
+        let file = write_script(
+            r#"
+            if metadata.language == "en" then
+                return transcript:gsub("teh", "the")
+            end
+            return transcript
+            "#,
+        );
+        let script = TranscriptScript::load(file.path()).expect("load");
+        let context = TranscriptContext {
+            run_id: "run-1",
+            language: Some("en"),
+            backend: BackendKind::WhisperCpp,
+            duration_ms: 1_200,
+        };
+        let rewritten = script
+            .run("teh quick fox", &context, Duration::from_millis(500))
+            .expect("run");
+        assert_eq!(rewritten, "the quick fox");
+    }
+
+    #[test]
+    fn run_surfaces_runtime_errors() {
+        let file = write_script("error(\"boom\")");
+        let script = TranscriptScript::load(file.path()).expect("load");
+        let context = TranscriptContext {
+            run_id: "run-2",
+            language: None,
+            backend: BackendKind::Auto,
+            duration_ms: 0,
+        };
+        let error = script
+            .run("hello", &context, Duration::from_millis(500))
+            .expect_err("must fail");
+        assert!(matches!(error, AppError::Scripting(message) if message.contains("boom")));
+    }
+
+    #[test]
+    fn run_aborts_on_timeout() {
+        let file = write_script("while true do end");
+        let script = TranscriptScript::load(file.path()).expect("load");
+        let context = TranscriptContext {
+            run_id: "run-3",
+            language: None,
+            backend: BackendKind::Auto,
+            duration_ms: 0,
+        };
+        let error = script
+            .run("hello", &context, Duration::from_millis(10))
+            .expect_err("must time out");
+        assert!(matches!(error, AppError::Scripting(message) if message.contains("timed out")));
+    }
+
+    #[test]
+    fn validate_config_is_a_no_op_when_disabled() {
+        let config = ScriptingConfig::default();
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn validate_config_requires_a_path_when_enabled() {
+        let config = ScriptingConfig {
+            enabled: true,
+            ..ScriptingConfig::default()
+        };
+        let error = validate_config(&config).expect_err("must fail");
+        assert!(matches!(error, AppError::Config(message) if message.contains("post_transcript_script")));
+    }
+
+    #[test]
+    fn validate_config_rejects_a_missing_file() {
+        let config = ScriptingConfig {
+            enabled: true,
+            post_transcript_script: Some("/does/not/exist.lua".into()),
+            ..ScriptingConfig::default()
+        };
+        let error = validate_config(&config).expect_err("must fail");
+        assert!(matches!(error, AppError::Config(message) if message.contains("does not exist")));
+    }
+
+    #[test]
+    fn validate_config_accepts_a_well_formed_script() {
+        let file = write_script("return transcript");
+        let config = ScriptingConfig {
+            enabled: true,
+            post_transcript_script: Some(file.path().to_path_buf()),
+            ..ScriptingConfig::default()
+        };
+        assert!(validate_config(&config).is_ok());
+    }
+}
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+use crate::transcription::scheduler::{TranscriptResult, TranscriptSegment};
+
+/// A subtitle container format `render` can produce from a
+/// `TranscriptResult`'s `segments`; see `ControllerEvent::ExportCaptions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptionFormat {
+    Srt,
+    Vtt,
+}
+
+/// Renders `result`'s `segments` as `format`, one cue per segment in
+/// timestamp order. A segment with a `speaker_label` (only set when
+/// `TranscriptionConfig::diarize` is on) gets it folded into the cue text as
+/// a `[spk_0]` prefix, since neither SRT nor VTT has a dedicated speaker
+/// field plain players render.
+pub fn render(result: &TranscriptResult, format: CaptionFormat) -> String {
+    let mut body = String::new();
+    if format == CaptionFormat::Vtt {
+        body.push_str("WEBVTT\n\n");
+    }
+
+    for (index, segment) in result.segments.iter().enumerate() {
+        if index > 0 {
+            body.push('\n');
+        }
+        if format == CaptionFormat::Srt {
+            body.push_str(&(index + 1).to_string());
+            body.push('\n');
+        }
+        body.push_str(&format_timestamp(segment.start_ms, format));
+        body.push_str(" --> ");
+        body.push_str(&format_timestamp(segment.end_ms, format));
+        body.push('\n');
+        body.push_str(&cue_text(segment));
+        body.push('\n');
+    }
+
+    body
+}
+
+fn cue_text(segment: &TranscriptSegment) -> String {
+    match &segment.speaker_label {
+        Some(label) => format!("[{label}] {}", segment.text),
+        None => segment.text.clone(),
+    }
+}
+
+/// `HH:MM:SS,mmm` for `CaptionFormat::Srt`, `HH:MM:SS.mmm` for
+/// `CaptionFormat::Vtt` — the two formats agree on everything but the
+/// millisecond separator.
+fn format_timestamp(total_ms: u64, format: CaptionFormat) -> String {
+    let hours = total_ms / 3_600_000;
+    let minutes = (total_ms % 3_600_000) / 60_000;
+    let seconds = (total_ms % 60_000) / 1000;
+    let millis = total_ms % 1000;
+    let separator = match format {
+        CaptionFormat::Srt => ',',
+        CaptionFormat::Vtt => '.',
+    };
+    format!("{hours:02}:{minutes:02}:{seconds:02}{separator}{millis:03}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, CaptionFormat};
+    use crate::transcription::scheduler::{TranscriptResult, TranscriptSegment};
+    use franken_whisper::BackendKind;
+
+    fn segment(text: &str, start_ms: u64, end_ms: u64, speaker_label: Option<&str>) -> TranscriptSegment {
+        TranscriptSegment {
+            text: text.to_owned(),
+            start_ms,
+            end_ms,
+            words: Vec::new(),
+            speaker_label: speaker_label.map(str::to_owned),
+        }
+    }
+
+    fn sample_result(segments: Vec<TranscriptSegment>) -> TranscriptResult {
+        TranscriptResult {
+            run_id: "run-1".to_owned(),
+            backend: BackendKind::WhisperCpp,
+            transcript: segments.iter().map(|s| s.text.clone()).collect::<Vec<_>>().join(" "),
+            language: Some("en".to_owned()),
+            segments,
+            warnings: Vec::new(),
+            finished_at_rfc3339: "2026-02-25T00:00:02Z".to_owned(),
+            no_speech: false,
+            elapsed_ms: 250,
+        }
+    }
+
+    #[test]
+    fn srt_numbers_cues_sequentially_and_uses_comma_millis() {
+        let result = sample_result(vec![
+            segment("hello there", 0, 1_500, None),
+            segment("general kenobi", 1_500, 3_025, None),
+        ]);
+
+        let srt = render(&result, CaptionFormat::Srt);
+        assert_eq!(
+            srt,
+            "1\n00:00:00,000 --> 00:00:01,500\nhello there\n\n\
+             2\n00:00:01,500 --> 00:00:03,025\ngeneral kenobi\n"
+        );
+    }
+
+    #[test]
+    fn vtt_has_a_header_and_dot_millis_with_no_cue_numbers() {
+        let result = sample_result(vec![segment("hello there", 0, 1_500, None)]);
+
+        let vtt = render(&result, CaptionFormat::Vtt);
+        assert_eq!(vtt, "WEBVTT\n\n00:00:00.000 --> 00:00:01.500\nhello there\n");
+    }
+
+    #[test]
+    fn speaker_label_is_folded_into_the_cue_text() {
+        let result = sample_result(vec![segment("hi", 0, 500, Some("spk_1"))]);
+
+        let srt = render(&result, CaptionFormat::Srt);
+        assert!(srt.contains("[spk_1] hi"));
+    }
+
+    #[test]
+    fn an_hour_long_timestamp_rolls_over_correctly() {
+        let result = sample_result(vec![segment("late", 3_661_250, 3_662_000, None)]);
+
+        let srt = render(&result, CaptionFormat::Srt);
+        assert!(srt.contains("01:01:01,250 --> 01:01:02,000"));
+    }
+}
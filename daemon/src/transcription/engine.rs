@@ -1,10 +1,20 @@
-use franken_whisper::{FrankenWhisperEngine, RunReport, TranscribeRequest};
+use franken_whisper::{BackendKind, FrankenWhisperEngine, RunReport, TranscribeRequest};
+use std::any::Any;
 use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
 
 use crate::error::{AppError, AppResult};
 
 pub trait EngineAdapter {
     fn transcribe_request(&self, request: TranscribeRequest) -> AppResult<RunReport>;
+
+    /// Best-effort request to abort whatever `transcribe_request` call is
+    /// currently blocked on this engine, so `BusyUpdatePolicy::Restart`/
+    /// `Signal` can reclaim a busy worker instead of waiting out a stale
+    /// job. Implementations that have no way to preempt an in-flight call
+    /// (e.g. a blocking in-process FFI call) should return an error rather
+    /// than silently doing nothing.
+    fn cancel(&self) -> AppResult<()>;
 }
 
 pub struct FrankenEngine {
@@ -25,8 +35,42 @@ impl FrankenEngine {
         Ok(Self { inner })
     }
 
+    /// Calls into the FFI-backed `FrankenWhisperEngine`, catching a panic
+    /// that crosses the native boundary instead of letting it unwind through
+    /// the caller (a worker thread in `spawn_transcription_workers`) and take
+    /// the process down with it.
     pub fn transcribe(&self, request: TranscribeRequest) -> AppResult<RunReport> {
-        map_transcribe_result(self.inner.transcribe(request))
+        if !backend_compiled_in(request.backend) {
+            return Err(AppError::Transcription(format!(
+                "backend {:?} is not compiled into this build; {}",
+                request.backend,
+                backend_feature_name(request.backend)
+                    .map(|feature| format!("enable the `{feature}` Cargo feature"))
+                    .unwrap_or_else(|| "no feature enables it on this platform".to_owned())
+            )));
+        }
+
+        let inner = &self.inner;
+        match panic::catch_unwind(AssertUnwindSafe(|| inner.transcribe(request))) {
+            Ok(result) => map_transcribe_result(result),
+            Err(payload) => Err(AppError::Transcription(format!(
+                "engine transcribe failed: {}",
+                describe_panic_payload(&payload)
+            ))),
+        }
+    }
+}
+
+/// Extracts a human-readable message from a `catch_unwind` payload; panics
+/// raised via `panic!("...")` carry a `&str` or `String`, anything else is
+/// reported generically.
+pub(super) fn describe_panic_payload(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_owned()
     }
 }
 
@@ -34,13 +78,45 @@ impl EngineAdapter for FrankenEngine {
     fn transcribe_request(&self, request: TranscribeRequest) -> AppResult<RunReport> {
         self.transcribe(request)
     }
+
+    fn cancel(&self) -> AppResult<()> {
+        Err(AppError::Transcription(
+            "in-process engine cannot cancel a blocking transcribe call".to_owned(),
+        ))
+    }
 }
 
 fn map_init_error(error: &impl Display) -> AppError {
     AppError::Transcription(format!("engine init failed: {error}"))
 }
 
-fn map_transcribe_result<T, E>(result: Result<T, E>) -> AppResult<T>
+/// Whether `backend` was compiled into this binary, per the
+/// `backend-whisper-cpp` / `backend-insanely-fast` / `backend-diarization`
+/// Cargo features (mirroring the `--no-default-features --features
+/// <backend>` pattern rs-matter uses for its crypto backends). `Auto` is
+/// always available: it's resolved to a concrete backend at runtime rather
+/// than naming one up front.
+pub fn backend_compiled_in(backend: BackendKind) -> bool {
+    match backend {
+        BackendKind::Auto => true,
+        BackendKind::WhisperCpp => cfg!(feature = "backend-whisper-cpp"),
+        BackendKind::InsanelyFast => cfg!(feature = "backend-insanely-fast"),
+        BackendKind::WhisperDiarization => cfg!(feature = "backend-diarization"),
+    }
+}
+
+/// The Cargo feature that enables `backend`, for remediation messages.
+/// `None` for `Auto`, which isn't gated by any single feature.
+pub fn backend_feature_name(backend: BackendKind) -> Option<&'static str> {
+    match backend {
+        BackendKind::Auto => None,
+        BackendKind::WhisperCpp => Some("backend-whisper-cpp"),
+        BackendKind::InsanelyFast => Some("backend-insanely-fast"),
+        BackendKind::WhisperDiarization => Some("backend-diarization"),
+    }
+}
+
+pub(super) fn map_transcribe_result<T, E>(result: Result<T, E>) -> AppResult<T>
 where
     E: Display,
 {
@@ -49,8 +125,34 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{map_transcribe_result, FrankenEngine};
+    use super::{
+        backend_compiled_in, backend_feature_name, describe_panic_payload, map_transcribe_result,
+        FrankenEngine,
+    };
     use crate::error::AppError;
+    use franken_whisper::BackendKind;
+
+    #[test]
+    fn auto_backend_is_always_compiled_in() {
+        assert!(backend_compiled_in(BackendKind::Auto));
+        assert_eq!(backend_feature_name(BackendKind::Auto), None);
+    }
+
+    #[test]
+    fn every_concrete_backend_names_its_gating_feature() {
+        assert_eq!(
+            backend_feature_name(BackendKind::WhisperCpp),
+            Some("backend-whisper-cpp")
+        );
+        assert_eq!(
+            backend_feature_name(BackendKind::InsanelyFast),
+            Some("backend-insanely-fast")
+        );
+        assert_eq!(
+            backend_feature_name(BackendKind::WhisperDiarization),
+            Some("backend-diarization")
+        );
+    }
 
     #[test]
     fn init_error_mapping_uses_stable_prefix() {
@@ -74,4 +176,16 @@ mod tests {
             AppError::Transcription(message) if message == "engine transcribe failed: timeout"
         ));
     }
+
+    #[test]
+    fn panic_payload_extracts_str_and_string_messages() {
+        let payload: Box<dyn std::any::Any + Send> = Box::new("boom");
+        assert_eq!(describe_panic_payload(&*payload), "boom");
+
+        let payload: Box<dyn std::any::Any + Send> = Box::new("boom".to_owned());
+        assert_eq!(describe_panic_payload(&*payload), "boom");
+
+        let payload: Box<dyn std::any::Any + Send> = Box::new(42_u32);
+        assert_eq!(describe_panic_payload(&*payload), "unknown panic payload");
+    }
 }
@@ -0,0 +1,220 @@
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Write};
+use std::process::{Child, Command};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use franken_whisper::{RunReport, TranscribeRequest};
+use interprocess::local_socket::{LocalSocketListener, LocalSocketStream};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::error::{AppError, AppResult};
+use crate::transcription::engine::{map_transcribe_result, EngineAdapter, FrankenEngine};
+use crate::transcription::supervisor::SupervisedEngine;
+
+/// How long `spawn` waits for the worker process to bind its socket before
+/// giving up and reporting the out-of-process engine unavailable.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const CONNECT_RETRY_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Runs `FrankenWhisperEngine` in a separate process and talks to it over a
+/// local socket, so a segfault or OOM in the native whisper FFI takes down
+/// only the worker process rather than the whole daemon. Requests and
+/// replies are exchanged as length-prefixed JSON over the socket; see
+/// `write_framed`/`read_framed`.
+pub struct ProcessEngine {
+    child: Mutex<Child>,
+    stream: Mutex<LocalSocketStream>,
+}
+
+impl ProcessEngine {
+    /// Spawns a worker process (`<current exe> engine-worker --socket
+    /// <name>`) and connects to the socket it binds, retrying for up to
+    /// `CONNECT_TIMEOUT` while the worker starts up.
+    pub fn spawn() -> AppResult<Self> {
+        let (child, stream) = spawn_worker()?;
+
+        Ok(Self {
+            child: Mutex::new(child),
+            stream: Mutex::new(stream),
+        })
+    }
+}
+
+impl EngineAdapter for ProcessEngine {
+    fn transcribe_request(&self, request: TranscribeRequest) -> AppResult<RunReport> {
+        let mut stream = self
+            .stream
+            .lock()
+            .map_err(|_| AppError::Transcription("engine worker socket lock poisoned".to_owned()))?;
+
+        map_transcribe_result(write_framed(&mut *stream, &request))?;
+        let response: Result<RunReport, String> =
+            map_transcribe_result(read_framed(&mut *stream))?;
+        response.map_err(AppError::Transcription)
+    }
+
+    /// Kills the worker process, which unblocks whatever `transcribe_request`
+    /// call is waiting on it with a connection error, then replaces it with a
+    /// freshly spawned worker so the engine is immediately usable again.
+    fn cancel(&self) -> AppResult<()> {
+        let mut child = self
+            .child
+            .lock()
+            .map_err(|_| AppError::Transcription("engine worker child lock poisoned".to_owned()))?;
+        let _ = child.kill();
+        let _ = child.wait();
+
+        let (new_child, new_stream) = spawn_worker()?;
+        *child = new_child;
+        *self
+            .stream
+            .lock()
+            .map_err(|_| AppError::Transcription("engine worker socket lock poisoned".to_owned()))? =
+            new_stream;
+
+        Ok(())
+    }
+}
+
+/// Spawns a fresh `engine-worker` child process and connects to the socket
+/// it binds; shared by `ProcessEngine::spawn` and `ProcessEngine::cancel`,
+/// which both need to stand up a new worker from scratch.
+fn spawn_worker() -> AppResult<(Child, LocalSocketStream)> {
+    let socket_name = unique_socket_name()?;
+    let exe = std::env::current_exe().map_err(|error| {
+        AppError::Transcription(format!(
+            "failed to resolve current executable to spawn engine worker: {error}"
+        ))
+    })?;
+
+    let child = Command::new(exe)
+        .arg("engine-worker")
+        .arg("--socket")
+        .arg(&socket_name)
+        .spawn()
+        .map_err(|error| {
+            AppError::Transcription(format!("failed to spawn engine worker process: {error}"))
+        })?;
+
+    let stream = connect_with_retry(&socket_name)?;
+    Ok((child, stream))
+}
+
+impl Drop for ProcessEngine {
+    fn drop(&mut self) {
+        if let Ok(mut child) = self.child.lock() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// Builds the default transcription engine: an out-of-process `ProcessEngine`
+/// if the host supports spawning a worker and connecting to its socket,
+/// falling back to a panic-supervised in-process `SupervisedEngine`
+/// otherwise (e.g. sandboxes that disallow process spawning, or exotic
+/// platforms `interprocess` can't bind a local socket on).
+pub fn new_default_engine() -> AppResult<Box<dyn EngineAdapter + Send + Sync>> {
+    match ProcessEngine::spawn() {
+        Ok(engine) => Ok(Box::new(engine)),
+        Err(error) => {
+            tracing::warn!(
+                "out-of-process engine unavailable ({error}), falling back to in-process engine"
+            );
+            Ok(Box::new(SupervisedEngine::spawn()?))
+        }
+    }
+}
+
+/// Entry point for the `engine-worker` subcommand: binds `socket_name`,
+/// accepts a single connection from the parent daemon, and services
+/// length-prefixed `TranscribeRequest`/`RunReport` frames on it with an
+/// in-process `FrankenEngine` until the connection closes.
+pub fn run_engine_worker(socket_name: &str) -> AppResult<()> {
+    let listener = LocalSocketListener::bind(socket_name).map_err(|error| {
+        AppError::Transcription(format!(
+            "engine worker failed to bind socket {socket_name}: {error}"
+        ))
+    })?;
+    let mut stream = listener.accept().map_err(|error| {
+        AppError::Transcription(format!("engine worker failed to accept connection: {error}"))
+    })?;
+
+    let engine = FrankenEngine::new()?;
+
+    loop {
+        let request = match read_framed::<TranscribeRequest>(&mut stream) {
+            Ok(request) => request,
+            Err(_) => return Ok(()), // parent closed the connection; exit cleanly
+        };
+
+        let response: Result<RunReport, String> =
+            engine.transcribe(request).map_err(|error| error.to_string());
+        if write_framed(&mut stream, &response).is_err() {
+            return Ok(());
+        }
+    }
+}
+
+/// Derives a short, unique local-socket name from the current binary path
+/// plus the current time, so concurrent daemon instances never collide.
+/// Kept well under the ~100-byte `sun_path` limit Unix domain sockets impose.
+fn unique_socket_name() -> AppResult<String> {
+    let exe = std::env::current_exe().map_err(|error| {
+        AppError::Transcription(format!(
+            "failed to resolve current executable for engine socket name: {error}"
+        ))
+    })?;
+    let now_nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|error| {
+            AppError::Transcription(format!("system clock before unix epoch: {error}"))
+        })?
+        .as_nanos();
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    exe.hash(&mut hasher);
+    now_nanos.hash(&mut hasher);
+    let hash64 = hasher.finish();
+
+    Ok(format!("/tmp/wa.{}.{:x}.sock", std::process::id(), hash64))
+}
+
+fn connect_with_retry(socket_name: &str) -> AppResult<LocalSocketStream> {
+    let deadline = Instant::now() + CONNECT_TIMEOUT;
+    loop {
+        match LocalSocketStream::connect(socket_name) {
+            Ok(stream) => return Ok(stream),
+            Err(_) if Instant::now() < deadline => {
+                std::thread::sleep(CONNECT_RETRY_INTERVAL);
+            }
+            Err(error) => {
+                return Err(AppError::Transcription(format!(
+                    "timed out connecting to engine worker socket {socket_name}: {error}"
+                )))
+            }
+        }
+    }
+}
+
+fn write_framed<T: Serialize>(stream: &mut impl Write, value: &T) -> std::io::Result<()> {
+    let payload = serde_json::to_vec(value)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+    let len = u32::try_from(payload.len())
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+    stream.write_all(&len.to_le_bytes())?;
+    stream.write_all(&payload)?;
+    stream.flush()
+}
+
+fn read_framed<T: DeserializeOwned>(stream: &mut impl Read) -> std::io::Result<T> {
+    let mut len_bytes = [0_u8; 4];
+    stream.read_exact(&mut len_bytes)?;
+    let len = u32::from_le_bytes(len_bytes) as usize;
+
+    let mut payload = vec![0_u8; len];
+    stream.read_exact(&mut payload)?;
+    serde_json::from_slice(&payload)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+}
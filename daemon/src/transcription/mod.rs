@@ -1,6 +1,18 @@
+pub mod captions;
 pub mod engine;
+pub mod engine_process;
+pub mod network_streaming;
 pub mod request_builder;
+pub mod retry;
 pub mod scheduler;
+pub mod streaming;
+pub mod supervisor;
 
+pub use captions::CaptionFormat;
 pub use engine::FrankenEngine;
+pub use engine_process::{new_default_engine, run_engine_worker, ProcessEngine};
+pub use network_streaming::{NetworkStreamingClient, NetworkStreamingEngine};
+pub use retry::{ErrorSeverity, RetryPolicy, RetryingEngine, TranscriptionFailure};
 pub use scheduler::{run_transcription_job, TranscriptResult};
+pub use streaming::{run_streaming_transcription_job, AudioSnapshot, StreamingDelta};
+pub use supervisor::SupervisedEngine;
@@ -0,0 +1,306 @@
+use std::fmt;
+use std::sync::Arc;
+
+use franken_whisper::model::{
+    InputSource, ReplayEnvelope, RunEvent, RunReport, TranscribeRequest, TranscriptionResult,
+};
+use franken_whisper::BackendKind;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::clock::{Clocks, SystemClocks};
+use crate::error::{AppError, AppResult};
+use crate::transcription::engine::EngineAdapter;
+use crate::transcription::streaming::stabilize;
+
+/// How many PCM samples `NetworkStreamingEngine` uploads per
+/// `NetworkStreamingClient::send_chunk` call (200ms at 16 kHz).
+const CHUNK_FRAMES: usize = 3_200;
+
+/// How many trailing words of each chunk's cumulative transcript
+/// `NetworkStreamingEngine` holds back, reusing
+/// `transcription::streaming::stabilize`'s holdback model so a later chunk
+/// can still revise them before they're folded into the final transcript.
+const CHUNK_HOLDBACK_WORDS: usize = 2;
+
+/// One end of a connection to a remote streaming speech-to-text service.
+/// Sending PCM and receiving the service's current best transcript is kept
+/// behind this trait, rather than a concrete websocket client, so
+/// `NetworkStreamingEngine` is testable without a live network connection; a
+/// real implementation would hold the open websocket/credentials from
+/// `crate::config::NetworkStreamingConfig` and translate these calls into
+/// outbound audio frames and inbound item messages.
+pub trait NetworkStreamingClient: fmt::Debug + Send + Sync {
+    /// Sends one more chunk of 16-bit PCM samples and returns the service's
+    /// current best cumulative transcript, covering all samples sent so far
+    /// on this connection.
+    fn send_chunk(&self, pcm: &[i16]) -> AppResult<String>;
+
+    /// Signals end-of-audio and returns the final cumulative transcript.
+    fn finish(&self) -> AppResult<String>;
+}
+
+/// An `EngineAdapter` that transcribes by streaming PCM chunks of the
+/// capture WAV to a remote service via `NetworkStreamingClient`, instead of
+/// running a local model; used as a fallback when no local backend is
+/// available. franken_whisper's `BackendKind` has no variant of its own for
+/// this backend (its source isn't vendored into this tree to extend), so
+/// `TranscriptionResult::backend` is reported as `BackendKind::Auto` for
+/// these runs rather than a dedicated `NetworkStreaming` value.
+#[derive(Debug)]
+pub struct NetworkStreamingEngine {
+    client: Arc<dyn NetworkStreamingClient>,
+    clocks: Arc<dyn Clocks>,
+}
+
+impl NetworkStreamingEngine {
+    pub fn new(client: Arc<dyn NetworkStreamingClient>) -> Self {
+        Self {
+            client,
+            clocks: Arc::new(SystemClocks::new()),
+        }
+    }
+
+    /// Swaps in a `SimulatedClocks` (or other injected `Clocks`) so tests can
+    /// assert on `RunReport`'s minted timestamps deterministically instead of
+    /// just their relative ordering.
+    pub fn with_clocks(mut self, clocks: Arc<dyn Clocks>) -> Self {
+        self.clocks = clocks;
+        self
+    }
+}
+
+impl EngineAdapter for NetworkStreamingEngine {
+    fn transcribe_request(&self, request: TranscribeRequest) -> AppResult<RunReport> {
+        let path = match &request.input {
+            InputSource::File { path } => path.clone(),
+            other => {
+                return Err(AppError::Transcription(format!(
+                    "network streaming engine only supports file input, got {other:?}"
+                )))
+            }
+        };
+
+        let mut reader = hound::WavReader::open(&path).map_err(|error| {
+            AppError::Transcription(format!(
+                "failed to open capture for network streaming: {error}"
+            ))
+        })?;
+        let samples: Vec<i16> = reader.samples::<i16>().collect::<Result<_, _>>().map_err(
+            |error| AppError::Transcription(format!("failed to read capture samples: {error}")),
+        )?;
+
+        let mut partial_index = 0usize;
+        let mut stable_parts = Vec::new();
+        for chunk in samples.chunks(CHUNK_FRAMES) {
+            let cumulative = self.client.send_chunk(chunk)?;
+            let stable = stabilize(&cumulative, &mut partial_index, CHUNK_HOLDBACK_WORDS, false);
+            if !stable.is_empty() {
+                stable_parts.push(stable);
+            }
+        }
+
+        let cumulative = self.client.finish()?;
+        let stable = stabilize(&cumulative, &mut partial_index, CHUNK_HOLDBACK_WORDS, true);
+        if !stable.is_empty() {
+            stable_parts.push(stable);
+        }
+
+        let transcript = stable_parts.join(" ");
+        let started_at_rfc3339 = self.clocks.now_rfc3339();
+        let input_path = path.display().to_string();
+
+        Ok(RunReport {
+            run_id: Uuid::new_v4().to_string(),
+            trace_id: Uuid::new_v4().to_string(),
+            started_at_rfc3339,
+            finished_at_rfc3339: self.clocks.now_rfc3339(),
+            input_path: input_path.clone(),
+            normalized_wav_path: input_path,
+            request,
+            result: TranscriptionResult {
+                backend: BackendKind::Auto,
+                transcript,
+                language: None,
+                segments: vec![],
+                acceleration: None,
+                raw_output: json!({}),
+                artifact_paths: vec![],
+            },
+            events: vec![RunEvent {
+                seq: 1,
+                ts_rfc3339: self.clocks.now_rfc3339(),
+                stage: "network_streaming".to_owned(),
+                code: "done".to_owned(),
+                message: "network streaming transcription finished".to_owned(),
+                payload: json!({}),
+            }],
+            warnings: vec![],
+            evidence: vec![],
+            replay: ReplayEnvelope::default(),
+        })
+    }
+
+    /// Mirrors `FrankenEngine::cancel`: there's no open connection kept
+    /// around between calls to preempt, since `transcribe_request` owns the
+    /// whole upload from start to finish.
+    fn cancel(&self) -> AppResult<()> {
+        Err(AppError::Transcription(
+            "network streaming engine cannot cancel an in-flight upload".to_owned(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NetworkStreamingClient, NetworkStreamingEngine};
+    use crate::error::AppResult;
+    use crate::transcription::engine::EngineAdapter;
+    use franken_whisper::model::InputSource;
+    use franken_whisper::BackendKind;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct ScriptedClient {
+        sent_chunks: Mutex<Vec<Vec<i16>>>,
+        cumulative_transcripts: Mutex<Vec<&'static str>>,
+        final_transcript: &'static str,
+    }
+
+    impl NetworkStreamingClient for ScriptedClient {
+        fn send_chunk(&self, pcm: &[i16]) -> AppResult<String> {
+            self.sent_chunks.lock().expect("lock").push(pcm.to_vec());
+            let mut transcripts = self.cumulative_transcripts.lock().expect("lock");
+            Ok(if transcripts.is_empty() {
+                String::new()
+            } else {
+                transcripts.remove(0).to_owned()
+            })
+        }
+
+        fn finish(&self) -> AppResult<String> {
+            Ok(self.final_transcript.to_owned())
+        }
+    }
+
+    fn write_wav(path: &std::path::Path, sample_count: usize) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).expect("create wav");
+        for sample in std::iter::repeat(0_i16).take(sample_count) {
+            writer.write_sample(sample).expect("write sample");
+        }
+        writer.finalize().expect("finalize wav");
+    }
+
+    #[test]
+    fn streams_chunks_and_flushes_the_final_transcript() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let wav_path = temp.path().join("capture.wav");
+        write_wav(&wav_path, 3_200 * 2);
+
+        let client = std::sync::Arc::new(ScriptedClient {
+            sent_chunks: Mutex::new(Vec::new()),
+            cumulative_transcripts: Mutex::new(vec!["hello", "hello world"]),
+            final_transcript: "hello world this is fine",
+        });
+        let engine = NetworkStreamingEngine::new(client.clone());
+
+        let request = franken_whisper::model::TranscribeRequest {
+            input: InputSource::File {
+                path: wav_path.clone(),
+            },
+            backend: BackendKind::Auto,
+            model: None,
+            language: None,
+            translate: false,
+            diarize: false,
+            persist: false,
+            db_path: PathBuf::from("/tmp/history.sqlite3"),
+            timeout_ms: Some(1_000),
+            backend_params: Default::default(),
+        };
+
+        let report = engine.transcribe_request(request).expect("transcribe");
+
+        assert_eq!(report.result.transcript, "hello world this is fine");
+        assert_eq!(report.result.backend, BackendKind::Auto);
+        assert_eq!(client.sent_chunks.lock().expect("lock").len(), 2);
+    }
+
+    #[test]
+    fn fails_with_a_clear_error_when_the_capture_file_is_missing() {
+        let client = std::sync::Arc::new(ScriptedClient {
+            sent_chunks: Mutex::new(Vec::new()),
+            cumulative_transcripts: Mutex::new(Vec::new()),
+            final_transcript: "",
+        });
+        let engine = NetworkStreamingEngine::new(client);
+
+        let request = franken_whisper::model::TranscribeRequest {
+            input: InputSource::File {
+                path: PathBuf::from("/nonexistent/capture.wav"),
+            },
+            backend: BackendKind::Auto,
+            model: None,
+            language: None,
+            translate: false,
+            diarize: false,
+            persist: false,
+            db_path: PathBuf::from("/tmp/history.sqlite3"),
+            timeout_ms: Some(1_000),
+            backend_params: Default::default(),
+        };
+
+        let error = engine.transcribe_request(request).expect_err("must fail");
+        assert!(matches!(
+            error,
+            crate::error::AppError::Transcription(message) if message.contains("failed to open capture")
+        ));
+    }
+
+    #[test]
+    fn cancel_reports_unsupported() {
+        let client = std::sync::Arc::new(ScriptedClient::default());
+        let engine = NetworkStreamingEngine::new(client);
+        assert!(engine.cancel().is_err());
+    }
+
+    #[test]
+    fn injected_clock_drives_the_run_report_timestamps() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let wav_path = temp.path().join("capture.wav");
+        write_wav(&wav_path, 3_200);
+
+        let client = std::sync::Arc::new(ScriptedClient {
+            sent_chunks: Mutex::new(Vec::new()),
+            cumulative_transcripts: Mutex::new(Vec::new()),
+            final_transcript: "pinned",
+        });
+        let clocks = std::sync::Arc::new(crate::clock::SimulatedClocks::new());
+        clocks.pin_rfc3339("2026-03-01T12:00:00+00:00");
+        let engine = NetworkStreamingEngine::new(client).with_clocks(clocks);
+
+        let request = franken_whisper::model::TranscribeRequest {
+            input: InputSource::File { path: wav_path },
+            backend: BackendKind::Auto,
+            model: None,
+            language: None,
+            translate: false,
+            diarize: false,
+            persist: false,
+            db_path: PathBuf::from("/tmp/history.sqlite3"),
+            timeout_ms: Some(1_000),
+            backend_params: Default::default(),
+        };
+
+        let report = engine.transcribe_request(request).expect("transcribe");
+        assert_eq!(report.started_at_rfc3339, "2026-03-01T12:00:00+00:00");
+        assert_eq!(report.finished_at_rfc3339, "2026-03-01T12:00:00+00:00");
+    }
+}
@@ -9,6 +9,7 @@ pub fn build_request(
     wav_path: PathBuf,
     db_path: PathBuf,
     cfg: &TranscriptionConfig,
+    persist: bool,
 ) -> TranscribeRequest {
     TranscribeRequest {
         input: InputSource::File { path: wav_path },
@@ -17,12 +18,13 @@ pub fn build_request(
         language: cfg.language.clone(),
         translate: cfg.translate,
         diarize: cfg.diarize,
-        persist: true,
+        persist,
         db_path,
         timeout_ms: Some(cfg.timeout_ms()),
         backend_params: BackendParams {
             threads: cfg.threads,
             processors: cfg.processors,
+            vocabulary: cfg.vocabulary.clone(),
             ..BackendParams::default()
         },
     }
@@ -47,10 +49,28 @@ mod tests {
             timeout_seconds: 12,
             threads: Some(7),
             processors: Some(2),
+            worker_count: 2,
+            partial_interval_ms: None,
+            max_queued_jobs: 8,
+            busy_update_policy: crate::config::BusyUpdatePolicy::Queue,
+            slow_timeout_ms: 60_000,
+            slow_timeout_terminate_after: 3,
+            max_transcribe_retries: 2,
+            max_recoverable_job_retries: 1,
+            streaming_stability: crate::config::StreamingStability::Medium,
+            streaming_stability_window: None,
+            vocabulary: Some(vec!["quedo".to_owned(), "franken".to_owned()]),
+            vocabulary_filter: None,
+            network_streaming: None,
+            lateness_ms: 0,
+            vad_trim: false,
+            vad_margin_db: 6.0,
+            vad_pad_ms: 200,
+            vad_split_above_ms: Some(60_000),
         };
         let wav = PathBuf::from("/tmp/in.wav");
         let db = PathBuf::from("/tmp/history.sqlite3");
-        let request = build_request(wav.clone(), db.clone(), &cfg);
+        let request = build_request(wav.clone(), db.clone(), &cfg, true);
 
         match request.input {
             InputSource::File { path } => assert_eq!(path, wav),
@@ -66,6 +86,10 @@ mod tests {
         assert_eq!(request.timeout_ms, Some(12_000));
         assert_eq!(request.backend_params.threads, Some(7));
         assert_eq!(request.backend_params.processors, Some(2));
+        assert_eq!(
+            request.backend_params.vocabulary,
+            Some(vec!["quedo".to_owned(), "franken".to_owned()])
+        );
     }
 
     #[test]
@@ -75,6 +99,7 @@ mod tests {
             PathBuf::from("/tmp/input.wav"),
             PathBuf::from("/tmp/history.sqlite3"),
             &cfg,
+            true,
         );
         assert_eq!(request.model, None);
         assert_eq!(request.language, None);
@@ -83,4 +108,16 @@ mod tests {
         assert_eq!(request.backend_params.threads, None);
         assert_eq!(request.backend_params.processors, None);
     }
+
+    #[test]
+    fn build_request_does_not_persist_partial_decodes() {
+        let cfg = TranscriptionConfig::default();
+        let request = build_request(
+            PathBuf::from("/tmp/input.wav"),
+            PathBuf::from("/tmp/history.sqlite3"),
+            &cfg,
+            false,
+        );
+        assert!(!request.persist);
+    }
 }
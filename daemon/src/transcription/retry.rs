@@ -0,0 +1,380 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crossbeam_channel::RecvTimeoutError;
+use franken_whisper::{RunReport, TranscribeRequest};
+
+use crate::config::TranscriptionConfig;
+use crate::error::{AppError, AppResult};
+use crate::transcription::engine::EngineAdapter;
+
+/// Nextest-style slow-test policy applied to a single `transcribe_request`
+/// call: `slow_timeout` is the grace period before an attempt is considered
+/// stuck, and `terminate_after` is how many consecutive `slow_timeout`
+/// periods are tolerated before the attempt is cancelled and treated as a
+/// timeout. `max_retries` bounds how many times a transient failure (see
+/// `is_transient`) is retried before giving up.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub slow_timeout: Duration,
+    pub terminate_after: u32,
+    pub max_retries: u32,
+}
+
+impl RetryPolicy {
+    fn deadline(&self) -> Duration {
+        self.slow_timeout * self.terminate_after.max(1)
+    }
+}
+
+impl From<&TranscriptionConfig> for RetryPolicy {
+    fn from(config: &TranscriptionConfig) -> Self {
+        Self {
+            slow_timeout: Duration::from_millis(config.slow_timeout_ms),
+            terminate_after: config.slow_timeout_terminate_after,
+            max_retries: config.max_transcribe_retries,
+        }
+    }
+}
+
+/// Classifies whether a failed attempt is worth retrying. Cold model loads
+/// and momentary device contention are transient; anything else (bad input,
+/// misconfiguration) is not, and retrying it would just waste the deadline.
+pub fn is_transient(error: &AppError) -> bool {
+    let AppError::Transcription(message) = error else {
+        return false;
+    };
+    let message = message.to_ascii_lowercase();
+    message.contains("timeout") || message.contains("busy") || message.contains("cold")
+}
+
+/// How urgently a terminal (post-retry) transcription failure should be
+/// treated by the controller: `Recoverable` causes are the same transient
+/// causes `is_transient` already retries within a single attempt — they
+/// reached here because `RetryPolicy::max_retries` was exhausted, not
+/// because the cause stopped being transient, so the controller gets one
+/// more bounded shot at a fresh job rather than degrading outright.
+/// `Fatal` causes (a missing binary, an unsupported backend, a bad model
+/// path) would just fail the same way again, so they escalate straight to
+/// `ControllerState::Degraded`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorSeverity {
+    Recoverable,
+    Fatal,
+}
+
+/// A `transcribe_request` failure that has already exhausted
+/// `RetryingEngine`'s in-attempt retries, carrying enough structure for
+/// `ControllerOutput::Error` to distinguish a transient cause (worth one
+/// more job-level retry) from a fatal one (escalate to `Degraded`).
+#[derive(Debug, Clone)]
+pub struct TranscriptionFailure {
+    pub severity: ErrorSeverity,
+    pub message: String,
+}
+
+impl From<AppError> for TranscriptionFailure {
+    fn from(error: AppError) -> Self {
+        let severity = if is_transient(&error) {
+            ErrorSeverity::Recoverable
+        } else {
+            ErrorSeverity::Fatal
+        };
+        Self {
+            severity,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Wraps an inner `EngineAdapter`, running each `transcribe_request` call on
+/// a dedicated thread bounded by `RetryPolicy::deadline`. An attempt that
+/// exceeds the deadline is cancelled via `EngineAdapter::cancel` and treated
+/// as a timeout; an attempt that fails with a transient error (per
+/// `is_transient`) is retried up to `policy.max_retries` times. The number
+/// of attempts actually made is appended to a successful `RunReport`'s
+/// `warnings`, since `RunReport` itself belongs to `franken_whisper` and has
+/// no dedicated field for it.
+pub struct RetryingEngine {
+    inner: Arc<dyn EngineAdapter + Send + Sync>,
+    policy: RetryPolicy,
+}
+
+impl RetryingEngine {
+    pub fn new(inner: Arc<dyn EngineAdapter + Send + Sync>, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+
+    fn run_once(&self, request: TranscribeRequest) -> AppResult<RunReport> {
+        let (result_tx, result_rx) = crossbeam_channel::bounded(1);
+        let inner = self.inner.clone();
+
+        thread::Builder::new()
+            .name("quedo-transcribe-attempt".to_owned())
+            .spawn(move || {
+                let result = inner.transcribe_request(request);
+                let _ = result_tx.send(result);
+            })
+            .map_err(|error| {
+                AppError::Transcription(format!("failed to spawn transcribe attempt: {error}"))
+            })?;
+
+        match result_rx.recv_timeout(self.policy.deadline()) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => {
+                let _ = self.inner.cancel();
+                Err(AppError::Transcription(
+                    "engine transcribe failed: timeout".to_owned(),
+                ))
+            }
+            Err(RecvTimeoutError::Disconnected) => Err(AppError::Transcription(
+                "engine transcribe failed: attempt thread died before reporting".to_owned(),
+            )),
+        }
+    }
+}
+
+impl EngineAdapter for RetryingEngine {
+    fn transcribe_request(&self, request: TranscribeRequest) -> AppResult<RunReport> {
+        let mut attempts: u32 = 0;
+        loop {
+            attempts += 1;
+            match self.run_once(request.clone()) {
+                Ok(mut report) => {
+                    report.warnings.push(format!("attempts: {attempts}"));
+                    return Ok(report);
+                }
+                Err(error) => {
+                    if attempts > self.policy.max_retries || !is_transient(&error) {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+    }
+
+    fn cancel(&self) -> AppResult<()> {
+        self.inner.cancel()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_transient, RetryPolicy, RetryingEngine};
+    use crate::error::{AppError, AppResult};
+    use crate::transcription::engine::EngineAdapter;
+    use franken_whisper::{
+        model::{BackendParams, InputSource},
+        RunReport, TranscribeRequest,
+    };
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    struct ScriptedEngine {
+        attempts: AtomicU32,
+        outcomes: Mutex<VecDeque<AppResult<RunReport>>>,
+    }
+
+    impl ScriptedEngine {
+        fn new(outcomes: Vec<AppResult<RunReport>>) -> Self {
+            Self {
+                attempts: AtomicU32::new(0),
+                outcomes: Mutex::new(outcomes.into_iter().collect()),
+            }
+        }
+    }
+
+    impl EngineAdapter for ScriptedEngine {
+        fn transcribe_request(&self, _request: TranscribeRequest) -> AppResult<RunReport> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            self.outcomes
+                .lock()
+                .expect("lock outcomes")
+                .pop_front()
+                .expect("configured outcome")
+        }
+
+        fn cancel(&self) -> AppResult<()> {
+            Ok(())
+        }
+    }
+
+    fn sample_request() -> TranscribeRequest {
+        TranscribeRequest {
+            input: InputSource::File {
+                path: "/tmp/in.wav".into(),
+            },
+            backend: franken_whisper::BackendKind::Auto,
+            model: None,
+            language: None,
+            translate: false,
+            diarize: false,
+            persist: true,
+            db_path: "/tmp/history.sqlite3".into(),
+            timeout_ms: Some(1_000),
+            backend_params: BackendParams::default(),
+        }
+    }
+
+    fn sample_report() -> RunReport {
+        RunReport {
+            run_id: "run-1".to_owned(),
+            trace_id: "trace-1".to_owned(),
+            started_at_rfc3339: "2026-02-25T00:00:00Z".to_owned(),
+            finished_at_rfc3339: "2026-02-25T00:00:01Z".to_owned(),
+            input_path: "/tmp/in.wav".to_owned(),
+            normalized_wav_path: "/tmp/normalized.wav".to_owned(),
+            request: sample_request(),
+            result: franken_whisper::model::TranscriptionResult {
+                backend: franken_whisper::BackendKind::Auto,
+                transcript: "hello".to_owned(),
+                language: None,
+                segments: vec![],
+                acceleration: None,
+                raw_output: serde_json::json!({}),
+                artifact_paths: vec![],
+            },
+            events: vec![],
+            warnings: vec![],
+            evidence: vec![],
+            replay: Default::default(),
+        }
+    }
+
+    #[test]
+    fn is_transient_matches_timeout_busy_and_cold_messages() {
+        assert!(is_transient(&AppError::Transcription(
+            "engine transcribe failed: timeout".to_owned()
+        )));
+        assert!(is_transient(&AppError::Transcription(
+            "device busy, retry later".to_owned()
+        )));
+        assert!(is_transient(&AppError::Transcription(
+            "cold model load still in progress".to_owned()
+        )));
+        assert!(!is_transient(&AppError::Transcription(
+            "invalid model path".to_owned()
+        )));
+        assert!(!is_transient(&AppError::Config("bad config".to_owned())));
+    }
+
+    #[test]
+    fn retries_transient_failures_up_to_max_retries_then_succeeds() {
+        let engine = Arc::new(ScriptedEngine::new(vec![
+            Err(AppError::Transcription(
+                "engine transcribe failed: timeout".to_owned(),
+            )),
+            Ok(sample_report()),
+        ]));
+        let wrapper = RetryingEngine::new(
+            engine.clone(),
+            RetryPolicy {
+                slow_timeout: Duration::from_secs(5),
+                terminate_after: 1,
+                max_retries: 2,
+            },
+        );
+
+        let report = wrapper
+            .transcribe_request(sample_request())
+            .expect("eventually succeeds");
+        assert_eq!(engine.attempts.load(Ordering::SeqCst), 2);
+        assert_eq!(report.warnings, vec!["attempts: 2".to_owned()]);
+    }
+
+    #[test]
+    fn gives_up_after_max_retries_exhausted() {
+        let engine = Arc::new(ScriptedEngine::new(vec![
+            Err(AppError::Transcription(
+                "engine transcribe failed: timeout".to_owned(),
+            )),
+            Err(AppError::Transcription(
+                "engine transcribe failed: timeout".to_owned(),
+            )),
+        ]));
+        let wrapper = RetryingEngine::new(
+            engine.clone(),
+            RetryPolicy {
+                slow_timeout: Duration::from_secs(5),
+                terminate_after: 1,
+                max_retries: 1,
+            },
+        );
+
+        let error = wrapper
+            .transcribe_request(sample_request())
+            .expect_err("retries exhausted");
+        assert_eq!(engine.attempts.load(Ordering::SeqCst), 2);
+        assert!(matches!(
+            error,
+            AppError::Transcription(message) if message == "engine transcribe failed: timeout"
+        ));
+    }
+
+    #[test]
+    fn non_transient_failure_is_not_retried() {
+        let engine = Arc::new(ScriptedEngine::new(vec![Err(AppError::Transcription(
+            "invalid model path".to_owned(),
+        ))]));
+        let wrapper = RetryingEngine::new(
+            engine.clone(),
+            RetryPolicy {
+                slow_timeout: Duration::from_secs(5),
+                terminate_after: 1,
+                max_retries: 5,
+            },
+        );
+
+        let error = wrapper
+            .transcribe_request(sample_request())
+            .expect_err("not transient");
+        assert_eq!(engine.attempts.load(Ordering::SeqCst), 1);
+        assert!(matches!(
+            error,
+            AppError::Transcription(message) if message == "invalid model path"
+        ));
+    }
+
+    #[test]
+    fn stuck_attempt_is_cancelled_once_deadline_elapses() {
+        struct HangingEngine {
+            cancelled: Arc<std::sync::atomic::AtomicBool>,
+        }
+        impl EngineAdapter for HangingEngine {
+            fn transcribe_request(&self, _request: TranscribeRequest) -> AppResult<RunReport> {
+                std::thread::sleep(Duration::from_secs(10));
+                Ok(sample_report())
+            }
+            fn cancel(&self) -> AppResult<()> {
+                self.cancelled.store(true, Ordering::SeqCst);
+                Ok(())
+            }
+        }
+
+        let cancelled = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let engine = Arc::new(HangingEngine {
+            cancelled: cancelled.clone(),
+        });
+        let wrapper = RetryingEngine::new(
+            engine,
+            RetryPolicy {
+                slow_timeout: Duration::from_millis(20),
+                terminate_after: 1,
+                max_retries: 0,
+            },
+        );
+
+        let error = wrapper
+            .transcribe_request(sample_request())
+            .expect_err("deadline exceeded");
+        assert!(matches!(
+            error,
+            AppError::Transcription(message) if message == "engine transcribe failed: timeout"
+        ));
+        assert!(cancelled.load(Ordering::SeqCst));
+    }
+}
@@ -1,46 +1,390 @@
 use std::path::PathBuf;
+use std::time::Instant;
 
+use chrono::Utc;
 use franken_whisper::BackendKind;
 use serde::Serialize;
+use uuid::Uuid;
 
-use crate::config::TranscriptionConfig;
-use crate::error::AppResult;
+use crate::capture::analysis::validate_wav;
+use crate::config::{TranscriptionConfig, VocabularyFilter, VocabularyFilterMethod};
+use crate::error::{AppError, AppResult};
 use crate::transcription::engine::EngineAdapter;
 use crate::transcription::request_builder::build_request;
 
+/// A single word's timing within a `TranscriptSegment`, when the backend
+/// reports per-word offsets.
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptWord {
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// A timed span of the transcript, letting a consumer build a subtitle
+/// track or align the transcript against the source WAV instead of only
+/// having the flat `TranscriptResult::transcript` string to work with.
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptSegment {
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub words: Vec<TranscriptWord>,
+    /// Who spoke this segment (`spk_0`, `spk_1`, ...), set only when
+    /// `TranscriptionConfig::diarize` is true and the backend reported a
+    /// speaker index for it; see `run_transcription_job`.
+    pub speaker_label: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct TranscriptResult {
     pub run_id: String,
     pub backend: BackendKind,
     pub transcript: String,
     pub language: Option<String>,
+    pub segments: Vec<TranscriptSegment>,
     pub warnings: Vec<String>,
     pub finished_at_rfc3339: String,
+    /// Set when `vad_trim` found no speech anywhere in the capture, so the
+    /// backend was never invoked and `transcript`/`segments` are empty by
+    /// construction rather than because the backend happened to hear
+    /// nothing; see `no_speech_result` and
+    /// `NotificationCategory::NoSpeechDetected`.
+    pub no_speech: bool,
+    /// Wall-clock time `run_transcription_job` spent producing this result,
+    /// from just after `validate_wav` to the final (possibly
+    /// VAD-segment-concatenated) `TranscriptResult`; surfaced on
+    /// `ControllerOutput::TranscriptReady`'s `emit_events` JSON line so a
+    /// client can tell a slow run from a fast one without timestamping it
+    /// itself.
+    pub elapsed_ms: u64,
+}
+
+fn seconds_to_ms(seconds: f64) -> u64 {
+    (seconds.max(0.0) * 1000.0).round() as u64
+}
+
+/// Formats a backend speaker index as the `spk_N` label surfaced on
+/// `TranscriptSegment::speaker_label`.
+fn speaker_label(index: u32) -> String {
+    format!("spk_{index}")
+}
+
+/// Builds a `TranscriptSegment` from plain timing values rather than
+/// `franken_whisper::model::Segment` directly, so the millisecond rounding
+/// and struct shape are unit-testable without constructing that crate's
+/// type by hand; see `run_transcription_job` for the one call site that
+/// bridges the two.
+fn build_segment(
+    text: &str,
+    start_seconds: f64,
+    end_seconds: f64,
+    words: impl IntoIterator<Item = (String, f64, f64)>,
+    speaker_label: Option<String>,
+) -> TranscriptSegment {
+    TranscriptSegment {
+        text: text.to_owned(),
+        start_ms: seconds_to_ms(start_seconds),
+        end_ms: seconds_to_ms(end_seconds),
+        words: words
+            .into_iter()
+            .map(|(word_text, word_start, word_end)| TranscriptWord {
+                text: word_text,
+                start_ms: seconds_to_ms(word_start),
+                end_ms: seconds_to_ms(word_end),
+            })
+            .collect(),
+        speaker_label,
+    }
 }
 
 pub fn run_transcription_job(
-    engine: &impl EngineAdapter,
+    engine: &dyn EngineAdapter,
+    wav_path: PathBuf,
+    db_path: PathBuf,
+    config: &TranscriptionConfig,
+    persist: bool,
+) -> AppResult<TranscriptResult> {
+    validate_wav(&wav_path).map_err(|error| AppError::Capture(error.to_string()))?;
+    let started_at = Instant::now();
+
+    let mut no_speech_detected = false;
+    let segment_paths = if config.vad_trim {
+        let boundary_config = crate::capture::vad::SpeechBoundaryConfig {
+            margin_db: config.vad_margin_db,
+            pad_ms: config.vad_pad_ms,
+            split_above_ms: config.vad_split_above_ms,
+        };
+        match crate::capture::vad::trim_silence_and_segment(&wav_path, &boundary_config) {
+            Ok(report) => {
+                // franken_whisper's own RunReport.events (the `RunStore`
+                // stream "normalize.ok"/"backend.ok" come from) belongs to
+                // its engine process, which this step runs ahead of and has
+                // no way to append an equivalent "vad.ok" event to; this log
+                // line is the closest analog this crate can surface on its
+                // own.
+                tracing::info!(
+                    span_count = report.spans.len(),
+                    trimmed_duration_ms = report.trimmed_duration_ms,
+                    "VAD-trimmed capture {}",
+                    wav_path.display()
+                );
+                if report.spans.is_empty() {
+                    no_speech_detected = true;
+                }
+                report.segment_paths
+            }
+            Err(error) => {
+                tracing::warn!(
+                    "failed to VAD-trim capture {}: {error}; transcribing as recorded",
+                    wav_path.display()
+                );
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
+    if no_speech_detected {
+        // The capture passed `validate_wav` (it's a well-formed WAV with some
+        // duration) but `vad_trim` found not a single speech span in it, so
+        // there's nothing worth handing to the backend; short-circuit with an
+        // empty, unpersisted result instead of risking the backend
+        // hallucinating a transcript from pure silence/noise. The caller
+        // (`controller::deliver_transcript_result`) reports this distinctly
+        // via `NotificationCategory::NoSpeechDetected` rather than as a
+        // generic transcription failure.
+        tracing::info!("no speech detected in {}; skipping transcription", wav_path.display());
+        return Ok(no_speech_result(config, started_at.elapsed().as_millis() as u64));
+    }
+
+    let mut result = if segment_paths.is_empty() {
+        transcribe_one(engine, wav_path, db_path, config, persist)?
+    } else {
+        transcribe_segments_sequentially(engine, segment_paths, db_path, config, persist)?
+    };
+    result.elapsed_ms = started_at.elapsed().as_millis() as u64;
+    Ok(result)
+}
+
+/// Transcribes every path in `segment_paths` (in order) and concatenates
+/// their transcripts/segments into one `TranscriptResult`, shifting each
+/// segment's own `start_ms`/`end_ms`/word timings forward by the (already
+/// shifted) `end_ms` of the last timed segment before it so the combined
+/// timeline stays continuous; a chunk whose result has no timed segments
+/// does not advance that offset. Used when `run_transcription_job`'s VAD trimming split a very
+/// long capture into separate voiced chunks instead of one file; see
+/// `capture::vad::trim_silence_and_segment`.
+fn transcribe_segments_sequentially(
+    engine: &dyn EngineAdapter,
+    segment_paths: Vec<PathBuf>,
+    db_path: PathBuf,
+    config: &TranscriptionConfig,
+    persist: bool,
+) -> AppResult<TranscriptResult> {
+    let mut combined: Option<TranscriptResult> = None;
+    let mut offset_ms: u64 = 0;
+
+    for segment_path in segment_paths {
+        let mut segment_result =
+            transcribe_one(engine, segment_path, db_path.clone(), config, persist)?;
+
+        for segment in segment_result.segments.iter_mut() {
+            segment.start_ms += offset_ms;
+            segment.end_ms += offset_ms;
+            for word in segment.words.iter_mut() {
+                word.start_ms += offset_ms;
+                word.end_ms += offset_ms;
+            }
+        }
+        if let Some(last_segment) = segment_result.segments.last() {
+            offset_ms = last_segment.end_ms;
+        }
+
+        match &mut combined {
+            None => combined = Some(segment_result),
+            Some(result) => {
+                if !result.transcript.is_empty() && !segment_result.transcript.is_empty() {
+                    result.transcript.push(' ');
+                }
+                result.transcript.push_str(&segment_result.transcript);
+                result.segments.append(&mut segment_result.segments);
+                result.warnings.append(&mut segment_result.warnings);
+                result.finished_at_rfc3339 = segment_result.finished_at_rfc3339;
+            }
+        }
+    }
+
+    combined.ok_or_else(|| AppError::Transcription("VAD segmentation produced no segments".to_owned()))
+}
+
+/// Builds the empty `TranscriptResult` returned when `vad_trim` finds no
+/// speech anywhere in a capture; `run_id`/`finished_at_rfc3339` are minted
+/// here rather than coming from a backend `RunReport`, since the backend is
+/// never invoked for this result. Modeled on how
+/// `network_streaming::NetworkStreamingEngine` mints its own `RunReport`
+/// fields for a transcript that didn't come from a local franken_whisper run.
+fn no_speech_result(config: &TranscriptionConfig, elapsed_ms: u64) -> TranscriptResult {
+    TranscriptResult {
+        run_id: Uuid::new_v4().to_string(),
+        backend: config.backend,
+        transcript: String::new(),
+        language: None,
+        segments: Vec::new(),
+        warnings: Vec::new(),
+        finished_at_rfc3339: Utc::now().to_rfc3339(),
+        no_speech: true,
+        elapsed_ms,
+    }
+}
+
+fn transcribe_one(
+    engine: &dyn EngineAdapter,
     wav_path: PathBuf,
     db_path: PathBuf,
     config: &TranscriptionConfig,
+    persist: bool,
 ) -> AppResult<TranscriptResult> {
-    let request = build_request(wav_path, db_path, config);
+    let request = build_request(wav_path, db_path, config, persist);
     let report = engine.transcribe_request(request)?;
 
-    Ok(TranscriptResult {
+    let segments = report
+        .result
+        .segments
+        .iter()
+        .map(|segment| {
+            build_segment(
+                &segment.text,
+                segment.start,
+                segment.end,
+                segment
+                    .words
+                    .iter()
+                    .map(|word| (word.text.clone(), word.start, word.end)),
+                // `segment.speaker` is our best-effort guess at how
+                // `franken_whisper` surfaces a per-segment diarization index;
+                // only trusted when diarization was actually requested, so a
+                // backend's default single-speaker index doesn't masquerade
+                // as a real turn when `diarize` is off.
+                if config.diarize {
+                    segment.speaker.map(speaker_label)
+                } else {
+                    None
+                },
+            )
+        })
+        .collect();
+
+    let mut result = TranscriptResult {
         run_id: report.run_id,
         backend: report.result.backend,
         transcript: report.result.transcript,
         language: report.result.language,
+        segments,
         warnings: report.warnings,
         finished_at_rfc3339: report.finished_at_rfc3339,
-    })
+        no_speech: false,
+        // Overwritten by `run_transcription_job` with the whole job's
+        // wall-clock time once this (possibly per-segment) result returns;
+        // zero here is never observed by a caller outside this module.
+        elapsed_ms: 0,
+    };
+
+    apply_lateness_and_ordering(&mut result.segments, config.lateness_ms);
+
+    if let Some(filter) = &config.vocabulary_filter {
+        apply_vocabulary_filter(&mut result, filter);
+    }
+
+    Ok(result)
+}
+
+/// Shifts every segment/word's `start_ms`/`end_ms` forward by `lateness_ms`
+/// to compensate for engine processing delay, then clamps each segment's
+/// start to be `>=` the previous segment's end (and its end to be `>=` its
+/// own, now-clamped start) so emitted timing is monotonic and
+/// non-overlapping even if a revised partial result reported an earlier
+/// start than what was already emitted.
+fn apply_lateness_and_ordering(segments: &mut [TranscriptSegment], lateness_ms: u64) {
+    let mut previous_end_ms = 0u64;
+    for segment in segments.iter_mut() {
+        segment.start_ms += lateness_ms;
+        segment.end_ms += lateness_ms;
+        for word in segment.words.iter_mut() {
+            word.start_ms += lateness_ms;
+            word.end_ms += lateness_ms;
+        }
+
+        if segment.start_ms < previous_end_ms {
+            segment.start_ms = previous_end_ms;
+        }
+        if segment.end_ms < segment.start_ms {
+            segment.end_ms = segment.start_ms;
+        }
+        previous_end_ms = segment.end_ms;
+    }
+}
+
+fn vocabulary_filter_matches(word: &str, terms: &[String]) -> bool {
+    let normalized = word
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase();
+    terms.iter().any(|term| term.to_lowercase() == normalized)
+}
+
+fn vocabulary_filter_word(word: &str, filter: &VocabularyFilter) -> Option<String> {
+    if !vocabulary_filter_matches(word, &filter.terms) {
+        return Some(word.to_owned());
+    }
+    match filter.method {
+        VocabularyFilterMethod::Mask => Some("***".to_owned()),
+        VocabularyFilterMethod::Remove => None,
+        VocabularyFilterMethod::Tag => Some(format!("{word}[flagged]")),
+    }
+}
+
+/// Redacts `filter.terms` out of `result`'s transcript and segments,
+/// word-by-word, per `filter.method`. Applied unconditionally as a fallback
+/// after every `transcribe_request` call: there's no signal in `RunReport`
+/// for whether the backend already honored `backend_params.vocabulary`'s
+/// filtering natively, so this always runs to guarantee the redaction holds
+/// regardless of backend support.
+fn apply_vocabulary_filter(result: &mut TranscriptResult, filter: &VocabularyFilter) {
+    result.transcript = result
+        .transcript
+        .split_whitespace()
+        .filter_map(|word| vocabulary_filter_word(word, filter))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    for segment in &mut result.segments {
+        segment.text = segment
+            .text
+            .split_whitespace()
+            .filter_map(|word| vocabulary_filter_word(word, filter))
+            .collect::<Vec<_>>()
+            .join(" ");
+        segment
+            .words
+            .retain_mut(|word| match vocabulary_filter_word(&word.text, filter) {
+                Some(replacement) => {
+                    word.text = replacement;
+                    true
+                }
+                None => false,
+            });
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::run_transcription_job;
+    use super::{
+        apply_lateness_and_ordering, apply_vocabulary_filter, build_segment,
+        run_transcription_job, speaker_label, TranscriptResult,
+    };
     use crate::config::schema::TranscriptionConfig;
+    use crate::config::{VocabularyFilter, VocabularyFilterMethod};
     use crate::error::{AppError, AppResult};
     use crate::transcription::engine::EngineAdapter;
     use crate::transcription::request_builder::build_request;
@@ -52,6 +396,20 @@ mod tests {
     use std::path::PathBuf;
     use std::sync::Mutex;
 
+    fn write_wav(path: &std::path::Path) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).expect("create wav");
+        for sample in [0_i16; 1_600] {
+            writer.write_sample(sample).expect("write sample");
+        }
+        writer.finalize().expect("finalize wav");
+    }
+
     #[derive(Default)]
     struct FakeEngine {
         requests: Mutex<Vec<TranscribeRequest>>,
@@ -76,6 +434,10 @@ mod tests {
                 .take()
                 .expect("configured result")
         }
+
+        fn cancel(&self) -> AppResult<()> {
+            Ok(())
+        }
     }
 
     fn sample_report() -> RunReport {
@@ -125,13 +487,18 @@ mod tests {
 
     #[test]
     fn maps_run_report_to_transcript_result() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let wav_path = temp.path().join("in.wav");
+        write_wav(&wav_path);
+
         let engine = FakeEngine::with_result(Ok(sample_report()));
         let config = TranscriptionConfig::default();
         let output = run_transcription_job(
             &engine,
-            PathBuf::from("/tmp/in.wav"),
+            wav_path,
             PathBuf::from("/tmp/history.sqlite3"),
             &config,
+            true,
         )
         .expect("success");
 
@@ -139,6 +506,7 @@ mod tests {
         assert_eq!(output.backend, BackendKind::WhisperCpp);
         assert_eq!(output.transcript, "hello world");
         assert_eq!(output.language.as_deref(), Some("en"));
+        assert!(output.segments.is_empty());
         assert_eq!(output.warnings, vec!["minor".to_owned()]);
         assert_eq!(output.finished_at_rfc3339, "2026-02-25T00:00:02Z");
     }
@@ -155,17 +523,37 @@ mod tests {
             timeout_seconds: 12,
             threads: Some(7),
             processors: Some(2),
+            worker_count: 2,
+            partial_interval_ms: None,
+            max_queued_jobs: 8,
+            busy_update_policy: crate::config::BusyUpdatePolicy::Queue,
+            slow_timeout_ms: 60_000,
+            slow_timeout_terminate_after: 3,
+            max_transcribe_retries: 2,
+            max_recoverable_job_retries: 1,
+            streaming_stability: crate::config::StreamingStability::Medium,
+            streaming_stability_window: None,
+            vocabulary: None,
+            vocabulary_filter: None,
+            network_streaming: None,
+            lateness_ms: 0,
+            vad_trim: false,
+            vad_margin_db: 6.0,
+            vad_pad_ms: 200,
+            vad_split_above_ms: Some(60_000),
         };
-        let wav_path = PathBuf::from("/tmp/input.wav");
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let wav_path = temp.path().join("input.wav");
+        write_wav(&wav_path);
         let db_path = PathBuf::from("/tmp/history.sqlite3");
 
-        run_transcription_job(&engine, wav_path.clone(), db_path.clone(), &config)
+        run_transcription_job(&engine, wav_path.clone(), db_path.clone(), &config, true)
             .expect("transcription should succeed");
 
         let captured = engine.requests.lock().expect("lock captured requests");
         assert_eq!(captured.len(), 1, "exactly one request should be sent");
 
-        let expected = build_request(wav_path, db_path, &config);
+        let expected = build_request(wav_path, db_path, &config, true);
         let sent = captured.first().expect("request present");
         match (&sent.input, &expected.input) {
             (
@@ -197,15 +585,443 @@ mod tests {
 
     #[test]
     fn propagates_engine_failures() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let wav_path = temp.path().join("in.wav");
+        write_wav(&wav_path);
+
         let engine = FakeEngine::with_result(Err(AppError::Transcription("timeout".to_owned())));
         let config = TranscriptionConfig::default();
         let error = run_transcription_job(
             &engine,
-            PathBuf::from("/tmp/in.wav"),
+            wav_path,
             PathBuf::from("/tmp/history.sqlite3"),
             &config,
+            true,
         )
         .expect_err("must fail");
         assert!(matches!(error, AppError::Transcription(message) if message.contains("timeout")));
     }
+
+    #[test]
+    fn rejects_a_zero_length_capture_before_reaching_the_engine() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let wav_path = temp.path().join("empty.wav");
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        hound::WavWriter::create(&wav_path, spec)
+            .expect("create wav")
+            .finalize()
+            .expect("finalize wav");
+
+        let engine = FakeEngine::with_result(Ok(sample_report()));
+        let config = TranscriptionConfig::default();
+        let error = run_transcription_job(
+            &engine,
+            wav_path,
+            PathBuf::from("/tmp/history.sqlite3"),
+            &config,
+            true,
+        )
+        .expect_err("must fail validation");
+
+        assert!(matches!(error, AppError::Capture(message) if message.contains("zero audio frames")));
+        assert!(engine.requests.lock().expect("lock").is_empty());
+    }
+
+    #[test]
+    fn rejects_a_corrupt_capture_with_a_distinct_reason_from_an_empty_one() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let empty_path = temp.path().join("empty.wav");
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        hound::WavWriter::create(&empty_path, spec)
+            .expect("create wav")
+            .finalize()
+            .expect("finalize wav");
+        let corrupt_path = temp.path().join("corrupt.wav");
+        std::fs::write(&corrupt_path, [0xFFu8; 64]).expect("write garbage bytes");
+
+        let config = TranscriptionConfig::default();
+        let empty_error = run_transcription_job(
+            &FakeEngine::with_result(Ok(sample_report())),
+            empty_path,
+            PathBuf::from("/tmp/history.sqlite3"),
+            &config,
+            true,
+        )
+        .expect_err("empty capture must fail validation");
+        let corrupt_error = run_transcription_job(
+            &FakeEngine::with_result(Ok(sample_report())),
+            corrupt_path,
+            PathBuf::from("/tmp/history.sqlite3"),
+            &config,
+            true,
+        )
+        .expect_err("corrupt capture must fail validation");
+
+        assert_ne!(empty_error.to_string(), corrupt_error.to_string());
+    }
+
+    #[test]
+    fn build_segment_rounds_seconds_to_milliseconds() {
+        let segment = build_segment("hello world", 1.234, 2.0, std::iter::empty(), None);
+        assert_eq!(segment.text, "hello world");
+        assert_eq!(segment.start_ms, 1_234);
+        assert_eq!(segment.end_ms, 2_000);
+        assert!(segment.words.is_empty());
+        assert_eq!(segment.speaker_label, None);
+    }
+
+    #[test]
+    fn build_segment_maps_per_word_offsets() {
+        let words = vec![
+            ("hello".to_owned(), 1.0, 1.4),
+            ("world".to_owned(), 1.5, 2.0),
+        ];
+        let segment = build_segment("hello world", 1.0, 2.0, words, None);
+
+        assert_eq!(segment.words.len(), 2);
+        assert_eq!(segment.words[0].text, "hello");
+        assert_eq!(segment.words[0].start_ms, 1_000);
+        assert_eq!(segment.words[0].end_ms, 1_400);
+        assert_eq!(segment.words[1].text, "world");
+        assert_eq!(segment.words[1].start_ms, 1_500);
+        assert_eq!(segment.words[1].end_ms, 2_000);
+    }
+
+    #[test]
+    fn build_segment_carries_a_speaker_label_when_given_one() {
+        let segment = build_segment(
+            "hello world",
+            0.0,
+            1.0,
+            std::iter::empty(),
+            Some("spk_1".to_owned()),
+        );
+        assert_eq!(segment.speaker_label.as_deref(), Some("spk_1"));
+    }
+
+    #[test]
+    fn speaker_label_formats_the_backend_index() {
+        assert_eq!(speaker_label(0), "spk_0");
+        assert_eq!(speaker_label(2), "spk_2");
+    }
+
+    fn sample_transcript_result() -> TranscriptResult {
+        TranscriptResult {
+            run_id: "run-1".to_owned(),
+            backend: BackendKind::WhisperCpp,
+            transcript: "hello world this is fine".to_owned(),
+            language: None,
+            segments: vec![build_segment(
+                "hello world this is fine",
+                0.0,
+                2.0,
+                [
+                    ("hello".to_owned(), 0.0, 0.4),
+                    ("world".to_owned(), 0.5, 0.9),
+                    ("this".to_owned(), 1.0, 1.2),
+                    ("is".to_owned(), 1.3, 1.4),
+                    ("fine".to_owned(), 1.5, 2.0),
+                ],
+                None,
+            )],
+            warnings: vec![],
+            finished_at_rfc3339: "2026-02-25T00:00:02Z".to_owned(),
+            no_speech: false,
+            elapsed_ms: 842,
+        }
+    }
+
+    #[test]
+    fn apply_vocabulary_filter_masks_matched_words() {
+        let mut result = sample_transcript_result();
+        let filter = VocabularyFilter {
+            terms: vec!["world".to_owned()],
+            method: VocabularyFilterMethod::Mask,
+        };
+
+        apply_vocabulary_filter(&mut result, &filter);
+
+        assert_eq!(result.transcript, "hello *** this is fine");
+        assert_eq!(result.segments[0].text, "hello *** this is fine");
+        assert_eq!(result.segments[0].words[1].text, "***");
+        assert_eq!(result.segments[0].words.len(), 5);
+    }
+
+    #[test]
+    fn apply_vocabulary_filter_removes_matched_words_and_their_word_entries() {
+        let mut result = sample_transcript_result();
+        let filter = VocabularyFilter {
+            terms: vec!["world".to_owned()],
+            method: VocabularyFilterMethod::Remove,
+        };
+
+        apply_vocabulary_filter(&mut result, &filter);
+
+        assert_eq!(result.transcript, "hello this is fine");
+        assert_eq!(result.segments[0].text, "hello this is fine");
+        assert_eq!(result.segments[0].words.len(), 4);
+        assert!(result.segments[0]
+            .words
+            .iter()
+            .all(|word| word.text != "world"));
+    }
+
+    #[test]
+    fn apply_vocabulary_filter_tags_matched_words_in_place() {
+        let mut result = sample_transcript_result();
+        let filter = VocabularyFilter {
+            terms: vec!["world".to_owned()],
+            method: VocabularyFilterMethod::Tag,
+        };
+
+        apply_vocabulary_filter(&mut result, &filter);
+
+        assert_eq!(result.transcript, "hello world[flagged] this is fine");
+        assert_eq!(result.segments[0].words[1].text, "world[flagged]");
+        assert_eq!(result.segments[0].words.len(), 5);
+    }
+
+    #[test]
+    fn apply_vocabulary_filter_matches_case_insensitively_and_ignores_punctuation() {
+        let mut result = sample_transcript_result();
+        result.transcript = "Hello, World!".to_owned();
+        let filter = VocabularyFilter {
+            terms: vec!["world".to_owned()],
+            method: VocabularyFilterMethod::Mask,
+        };
+
+        apply_vocabulary_filter(&mut result, &filter);
+
+        assert_eq!(result.transcript, "Hello, ***");
+    }
+
+    #[test]
+    fn vocabulary_filter_runs_as_a_fallback_after_the_engine_call() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let wav_path = temp.path().join("in.wav");
+        write_wav(&wav_path);
+
+        let engine = FakeEngine::with_result(Ok(sample_report()));
+        let config = TranscriptionConfig {
+            vocabulary_filter: Some(VocabularyFilter {
+                terms: vec!["world".to_owned()],
+                method: VocabularyFilterMethod::Mask,
+            }),
+            ..TranscriptionConfig::default()
+        };
+
+        let output = run_transcription_job(
+            &engine,
+            wav_path,
+            PathBuf::from("/tmp/history.sqlite3"),
+            &config,
+            true,
+        )
+        .expect("success");
+
+        assert_eq!(output.transcript, "hello ***");
+    }
+
+    #[test]
+    fn apply_lateness_and_ordering_shifts_every_segment_and_word_forward() {
+        let mut segments = vec![
+            build_segment("hello", 0.0, 0.4, [("hello".to_owned(), 0.0, 0.4)], None),
+            build_segment("world", 0.5, 0.9, [("world".to_owned(), 0.5, 0.9)], None),
+        ];
+
+        apply_lateness_and_ordering(&mut segments, 200);
+
+        assert_eq!(segments[0].start_ms, 200);
+        assert_eq!(segments[0].end_ms, 600);
+        assert_eq!(segments[0].words[0].start_ms, 200);
+        assert_eq!(segments[0].words[0].end_ms, 600);
+        assert_eq!(segments[1].start_ms, 700);
+        assert_eq!(segments[1].end_ms, 1_100);
+    }
+
+    #[test]
+    fn apply_lateness_and_ordering_clamps_an_overlapping_segment_to_be_monotonic() {
+        let mut segments = vec![
+            build_segment("hello", 0.0, 1.0, std::iter::empty(), None),
+            // Starts before the previous segment ends, as a revised partial
+            // result might report.
+            build_segment("world", 0.5, 1.5, std::iter::empty(), None),
+        ];
+
+        apply_lateness_and_ordering(&mut segments, 0);
+
+        assert_eq!(segments[0].end_ms, 1_000);
+        assert_eq!(segments[1].start_ms, 1_000);
+        assert_eq!(segments[1].end_ms, 1_500);
+    }
+
+    #[test]
+    fn apply_lateness_and_ordering_clamps_end_when_start_is_pushed_past_it() {
+        let mut segments = vec![
+            build_segment("hello", 0.0, 1.0, std::iter::empty(), None),
+            // Entirely contained within the previous segment; clamping its
+            // start to 1.0s would otherwise put it after its own end.
+            build_segment("world", 0.2, 0.6, std::iter::empty(), None),
+        ];
+
+        apply_lateness_and_ordering(&mut segments, 0);
+
+        assert_eq!(segments[1].start_ms, 1_000);
+        assert_eq!(segments[1].end_ms, 1_000);
+    }
+
+    fn write_wav_samples(path: &std::path::Path, samples: &[i16]) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).expect("create wav");
+        for sample in samples {
+            writer.write_sample(*sample).expect("write sample");
+        }
+        writer.finalize().expect("finalize wav");
+    }
+
+    fn tone_i16(len: usize, freq_hz: f64, sample_rate: u32) -> Vec<i16> {
+        (0..len)
+            .map(|i| {
+                let t = i as f64 / f64::from(sample_rate);
+                ((2.0 * std::f64::consts::PI * freq_hz * t).sin() * f64::from(i16::MAX) * 0.8) as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn vad_trim_passes_the_trimmed_capture_through_to_the_backend() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let wav_path = temp.path().join("in.wav");
+        let mut samples = vec![0_i16; 16_000];
+        samples.extend(tone_i16(16_000, 1_000.0, 16_000));
+        samples.extend(vec![0_i16; 16_000]);
+        write_wav_samples(&wav_path, &samples);
+
+        let engine = FakeEngine::with_result(Ok(sample_report()));
+        let config = TranscriptionConfig {
+            vad_trim: true,
+            vad_split_above_ms: None,
+            ..TranscriptionConfig::default()
+        };
+        let output = run_transcription_job(
+            &engine,
+            wav_path,
+            PathBuf::from("/tmp/history.sqlite3"),
+            &config,
+            true,
+        )
+        .expect("success");
+
+        assert_eq!(output.transcript, "hello world");
+        assert_eq!(engine.requests.lock().expect("lock").len(), 1);
+    }
+
+    #[test]
+    fn vad_trim_short_circuits_with_an_empty_no_speech_result_instead_of_calling_the_backend() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let wav_path = temp.path().join("in.wav");
+        write_wav_samples(&wav_path, &[0_i16; 16_000]);
+
+        let engine = FakeEngine::with_result(Ok(sample_report()));
+        let config = TranscriptionConfig {
+            vad_trim: true,
+            ..TranscriptionConfig::default()
+        };
+        let output = run_transcription_job(
+            &engine,
+            wav_path,
+            PathBuf::from("/tmp/history.sqlite3"),
+            &config,
+            true,
+        )
+        .expect("success");
+
+        assert!(output.no_speech);
+        assert_eq!(output.transcript, "");
+        assert!(output.segments.is_empty());
+        assert!(
+            engine.requests.lock().expect("lock").is_empty(),
+            "the backend must never be invoked on a capture with no speech"
+        );
+    }
+
+    struct SequencedEngine {
+        requests: Mutex<Vec<TranscribeRequest>>,
+        outcomes: Mutex<std::collections::VecDeque<AppResult<RunReport>>>,
+    }
+
+    impl SequencedEngine {
+        fn new(outcomes: Vec<AppResult<RunReport>>) -> Self {
+            Self {
+                requests: Mutex::new(Vec::new()),
+                outcomes: Mutex::new(outcomes.into_iter().collect()),
+            }
+        }
+    }
+
+    impl EngineAdapter for SequencedEngine {
+        fn transcribe_request(&self, request: TranscribeRequest) -> AppResult<RunReport> {
+            self.requests.lock().expect("lock").push(request);
+            self.outcomes.lock().expect("lock").pop_front().expect("configured outcome")
+        }
+
+        fn cancel(&self) -> AppResult<()> {
+            Ok(())
+        }
+    }
+
+    fn report_with_transcript(transcript: &str) -> RunReport {
+        let mut report = sample_report();
+        report.result.transcript = transcript.to_owned();
+        report
+    }
+
+    #[test]
+    fn vad_trim_transcribes_split_segments_sequentially_and_concatenates_them() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        let wav_path = temp.path().join("in.wav");
+        // Leading silence lets the noise floor settle below the tone.
+        let mut samples = vec![0_i16; 8_000];
+        samples.extend(tone_i16(16_000, 1_000.0, 16_000));
+        samples.extend(vec![0_i16; 8_000]);
+        samples.extend(tone_i16(16_000, 1_000.0, 16_000));
+        write_wav_samples(&wav_path, &samples);
+
+        let engine = SequencedEngine::new(vec![
+            Ok(report_with_transcript("first")),
+            Ok(report_with_transcript("second")),
+        ]);
+        let config = TranscriptionConfig {
+            vad_trim: true,
+            vad_pad_ms: 50,
+            vad_split_above_ms: Some(500),
+            ..TranscriptionConfig::default()
+        };
+        let output = run_transcription_job(
+            &engine,
+            wav_path,
+            PathBuf::from("/tmp/history.sqlite3"),
+            &config,
+            true,
+        )
+        .expect("success");
+
+        assert_eq!(output.transcript, "first second");
+        assert_eq!(engine.requests.lock().expect("lock").len(), 2);
+    }
 }
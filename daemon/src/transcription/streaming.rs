@@ -0,0 +1,291 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+
+use crossbeam_channel::{unbounded, Receiver};
+
+use crate::config::{StreamingStability, TranscriptionConfig};
+use crate::error::AppResult;
+use crate::transcription::engine::EngineAdapter;
+use crate::transcription::scheduler::run_transcription_job;
+
+impl StreamingStability {
+    /// How many trailing words of a decode `stabilize` holds back as still
+    /// liable to change before reporting them as stable.
+    pub(crate) fn holdback_words(self) -> usize {
+        match self {
+            StreamingStability::Low => 3,
+            StreamingStability::Medium => 2,
+            StreamingStability::High => 1,
+        }
+    }
+}
+
+/// One still-growing or final decode of the same in-progress utterance, fed
+/// to `run_streaming_transcription_job` in order. `is_final` marks the last
+/// snapshot of the utterance, after which the stability cursor resets.
+pub struct AudioSnapshot {
+    pub wav_path: PathBuf,
+    pub is_final: bool,
+}
+
+/// One update out of `run_streaming_transcription_job`: the words newly
+/// promoted from "still may change" to stable since the previous update for
+/// this utterance, and whether this was the terminal flush.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamingDelta {
+    pub stable_text: String,
+    pub is_final: bool,
+}
+
+/// Splits `transcript` into whitespace-separated items and returns the ones
+/// newly promoted to stable since `*partial_index`, advancing it past them.
+/// A non-final decode holds back the trailing `holdback` items, since a
+/// later decode of the same (still-growing) audio may revise them; a final
+/// decode flushes everything remaining and resets `*partial_index` to 0 so
+/// the next utterance starts from a clean cursor.
+pub(crate) fn stabilize(
+    transcript: &str,
+    partial_index: &mut usize,
+    holdback: usize,
+    is_final: bool,
+) -> String {
+    let items: Vec<&str> = transcript.split_whitespace().collect();
+    let stable_end = if is_final {
+        items.len()
+    } else {
+        items.len().saturating_sub(holdback)
+    };
+
+    let newly_stable = if stable_end > *partial_index {
+        items[*partial_index..stable_end].join(" ")
+    } else {
+        String::new()
+    };
+
+    *partial_index = if is_final { 0 } else { stable_end.max(*partial_index) };
+    newly_stable
+}
+
+/// Feeds successive `AudioSnapshot`s of one in-progress recording to
+/// `engine` and emits only each decode's newly-stabilized words, instead of
+/// the whole transcript every time, so a UI can append text as it firms up
+/// rather than replacing the in-progress line on every update.
+///
+/// `franken_whisper` has no API to feed audio into a single decode
+/// incrementally, so each snapshot is a full, independent
+/// `EngineAdapter::transcribe_request` call via `run_transcription_job`
+/// (never persisted to history); stability is inferred by holding back each
+/// decode's trailing words per `config.streaming_stability` rather than
+/// from any real per-word confidence the engine reports.
+pub fn run_streaming_transcription_job(
+    engine: Arc<dyn EngineAdapter + Send + Sync>,
+    snapshots: Receiver<AudioSnapshot>,
+    db_path: PathBuf,
+    config: TranscriptionConfig,
+) -> Receiver<AppResult<StreamingDelta>> {
+    let (tx, rx) = unbounded();
+    let holdback = config.holdback_words();
+
+    thread::spawn(move || {
+        let mut partial_index = 0usize;
+        while let Ok(snapshot) = snapshots.recv() {
+            let is_final = snapshot.is_final;
+            let delta = run_transcription_job(
+                &engine,
+                snapshot.wav_path,
+                db_path.clone(),
+                &config,
+                false,
+            )
+            .map(|result| StreamingDelta {
+                stable_text: stabilize(&result.transcript, &mut partial_index, holdback, is_final),
+                is_final,
+            });
+
+            if tx.send(delta).is_err() {
+                break;
+            }
+        }
+    });
+
+    rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_streaming_transcription_job, stabilize, AudioSnapshot};
+    use crate::config::schema::TranscriptionConfig;
+    use crate::error::AppResult;
+    use crate::transcription::engine::EngineAdapter;
+    use franken_whisper::model::{
+        BackendKind, InputSource, ReplayEnvelope, RunEvent, RunReport, TranscribeRequest,
+        TranscriptionResult,
+    };
+    use serde_json::json;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn stabilize_holds_back_trailing_words_until_a_later_decode_confirms_them() {
+        let mut cursor = 0usize;
+
+        let first = stabilize("hello wor", &mut cursor, 2, false);
+        assert_eq!(first, "");
+        assert_eq!(cursor, 0);
+
+        let second = stabilize("hello world this", &mut cursor, 2, false);
+        assert_eq!(second, "hello");
+        assert_eq!(cursor, 1);
+
+        let third = stabilize("hello world this is", &mut cursor, 2, false);
+        assert_eq!(third, "world");
+        assert_eq!(cursor, 2);
+    }
+
+    #[test]
+    fn stabilize_flushes_everything_remaining_on_final_and_resets_the_cursor() {
+        let mut cursor = 2usize;
+
+        let flushed = stabilize("hello world this is fine", &mut cursor, 2, true);
+        assert_eq!(flushed, "this is fine");
+        assert_eq!(cursor, 0);
+    }
+
+    #[test]
+    fn stabilize_emits_nothing_new_when_no_additional_words_have_firmed_up() {
+        let mut cursor = 1usize;
+
+        let delta = stabilize("hello world", &mut cursor, 2, false);
+        assert_eq!(delta, "");
+        assert_eq!(cursor, 1);
+    }
+
+    #[derive(Default)]
+    struct ScriptedEngine {
+        transcripts: Mutex<Vec<&'static str>>,
+    }
+
+    impl EngineAdapter for ScriptedEngine {
+        fn transcribe_request(&self, _request: TranscribeRequest) -> AppResult<RunReport> {
+            let transcript = self
+                .transcripts
+                .lock()
+                .expect("lock")
+                .pop()
+                .expect("a scripted transcript for each call");
+            Ok(sample_report(transcript))
+        }
+
+        fn cancel(&self) -> AppResult<()> {
+            Ok(())
+        }
+    }
+
+    fn sample_report(transcript: &str) -> RunReport {
+        RunReport {
+            run_id: "run-1".to_owned(),
+            trace_id: "trace-1".to_owned(),
+            started_at_rfc3339: "2026-02-25T00:00:00Z".to_owned(),
+            finished_at_rfc3339: "2026-02-25T00:00:01Z".to_owned(),
+            input_path: "/tmp/in.wav".to_owned(),
+            normalized_wav_path: "/tmp/normalized.wav".to_owned(),
+            request: TranscribeRequest {
+                input: InputSource::File {
+                    path: PathBuf::from("/tmp/in.wav"),
+                },
+                backend: BackendKind::WhisperCpp,
+                model: None,
+                language: None,
+                translate: false,
+                diarize: false,
+                persist: false,
+                db_path: PathBuf::from("/tmp/history.sqlite3"),
+                timeout_ms: Some(1_000),
+                backend_params: Default::default(),
+            },
+            result: TranscriptionResult {
+                backend: BackendKind::WhisperCpp,
+                transcript: transcript.to_owned(),
+                language: None,
+                segments: vec![],
+                acceleration: None,
+                raw_output: json!({}),
+                artifact_paths: vec![],
+            },
+            events: vec![RunEvent {
+                seq: 1,
+                ts_rfc3339: "2026-02-25T00:00:01Z".to_owned(),
+                stage: "backend".to_owned(),
+                code: "done".to_owned(),
+                message: "ok".to_owned(),
+                payload: json!({}),
+            }],
+            warnings: vec![],
+            evidence: vec![],
+            replay: ReplayEnvelope::default(),
+        }
+    }
+
+    fn write_wav(path: &std::path::Path) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).expect("create wav");
+        for sample in [0_i16; 1_600] {
+            writer.write_sample(sample).expect("write sample");
+        }
+        writer.finalize().expect("finalize wav");
+    }
+
+    #[test]
+    fn streams_deltas_across_growing_snapshots_and_flushes_on_the_final_one() {
+        let temp = tempfile::TempDir::new().expect("tempdir");
+        // `ScriptedEngine` pops transcripts, so list them in reverse order.
+        let engine = Arc::new(ScriptedEngine {
+            transcripts: Mutex::new(vec![
+                "hello world this is fine",
+                "hello world this",
+                "hello wor",
+            ]),
+        });
+        let config = TranscriptionConfig {
+            streaming_stability: crate::config::StreamingStability::Medium,
+            ..TranscriptionConfig::default()
+        };
+
+        let (snapshot_tx, snapshot_rx) = crossbeam_channel::unbounded();
+        let deltas = run_streaming_transcription_job(
+            engine,
+            snapshot_rx,
+            temp.path().join("history.sqlite3"),
+            config,
+        );
+
+        for (index, is_final) in [(0, false), (1, false), (2, true)] {
+            let wav_path = temp.path().join(format!("snapshot-{index}.wav"));
+            write_wav(&wav_path);
+            snapshot_tx
+                .send(AudioSnapshot { wav_path, is_final })
+                .expect("send snapshot");
+        }
+        drop(snapshot_tx);
+
+        let first = deltas.recv().expect("first delta").expect("ok");
+        assert_eq!(first.stable_text, "");
+        assert!(!first.is_final);
+
+        let second = deltas.recv().expect("second delta").expect("ok");
+        assert_eq!(second.stable_text, "hello");
+        assert!(!second.is_final);
+
+        let third = deltas.recv().expect("final delta").expect("ok");
+        assert_eq!(third.stable_text, "world this is fine");
+        assert!(third.is_final);
+
+        assert!(deltas.recv().is_err(), "channel should close once snapshots are exhausted");
+    }
+}
@@ -0,0 +1,104 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+use std::thread;
+
+use crossbeam_channel::{bounded, Receiver, Sender};
+use franken_whisper::{RunReport, TranscribeRequest};
+
+use crate::error::{AppError, AppResult};
+use crate::transcription::engine::{describe_panic_payload, EngineAdapter, FrankenEngine};
+
+/// One `transcribe_request` call handed to the supervised worker thread,
+/// paired with a channel to deliver its result back.
+struct Job {
+    request: TranscribeRequest,
+    reply_tx: Sender<AppResult<RunReport>>,
+}
+
+/// Hosts a `FrankenEngine` on a dedicated worker thread and, if that thread
+/// ever panics, records the panic payload, rebuilds the engine via
+/// `FrankenEngine::new`, and keeps serving requests from the job channel
+/// instead of leaving transcription permanently wedged. `FrankenEngine`
+/// already catches FFI panics itself (see `FrankenEngine::transcribe`), so
+/// this is a second line of defense against anything that still manages to
+/// unwind past it.
+pub struct SupervisedEngine {
+    job_tx: Mutex<Sender<Job>>,
+}
+
+impl SupervisedEngine {
+    /// Builds the initial `FrankenEngine` on the calling thread (so a
+    /// construction failure is reported immediately) then hands it off to
+    /// the supervised worker thread.
+    pub fn spawn() -> AppResult<Self> {
+        let engine = FrankenEngine::new()?;
+        let (job_tx, job_rx) = bounded::<Job>(0);
+
+        thread::Builder::new()
+            .name("quedo-engine-supervisor".to_owned())
+            .spawn(move || run_supervised(engine, job_rx))
+            .map_err(|error| {
+                AppError::Transcription(format!(
+                    "failed to spawn engine supervisor thread: {error}"
+                ))
+            })?;
+
+        Ok(Self {
+            job_tx: Mutex::new(job_tx),
+        })
+    }
+}
+
+impl EngineAdapter for SupervisedEngine {
+    fn transcribe_request(&self, request: TranscribeRequest) -> AppResult<RunReport> {
+        let (reply_tx, reply_rx) = bounded(1);
+        {
+            let job_tx = self
+                .job_tx
+                .lock()
+                .map_err(|_| AppError::Transcription("engine supervisor job channel lock poisoned".to_owned()))?;
+            job_tx.send(Job { request, reply_tx }).map_err(|_| {
+                AppError::Transcription("engine supervisor worker thread is gone".to_owned())
+            })?;
+        }
+        reply_rx.recv().map_err(|_| {
+            AppError::Transcription(
+                "engine supervisor worker thread died before replying".to_owned(),
+            )
+        })?
+    }
+
+    fn cancel(&self) -> AppResult<()> {
+        Err(AppError::Transcription(
+            "supervised in-process engine cannot cancel a blocking transcribe call".to_owned(),
+        ))
+    }
+}
+
+/// Runs on the dedicated supervisor thread: serves jobs with `engine` until
+/// the job channel closes, rebuilding `engine` whenever a job panics instead
+/// of letting the thread die and take the channel with it.
+fn run_supervised(mut engine: FrankenEngine, job_rx: Receiver<Job>) {
+    while let Ok(job) = job_rx.recv() {
+        match panic::catch_unwind(AssertUnwindSafe(|| engine.transcribe(job.request))) {
+            Ok(result) => {
+                let _ = job.reply_tx.send(result);
+            }
+            Err(payload) => {
+                let message = describe_panic_payload(&payload);
+                tracing::error!("engine supervisor worker panicked, rebuilding engine: {message}");
+                let _ = job.reply_tx.send(Err(AppError::Transcription(format!(
+                    "engine transcribe failed: {message}"
+                ))));
+
+                match FrankenEngine::new() {
+                    Ok(fresh) => engine = fresh,
+                    Err(error) => {
+                        tracing::error!("failed to rebuild engine after panic: {error}");
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,41 +1,100 @@
 use crate::controller::events::ControllerEvent;
-use crate::error::AppResult;
+use crate::error::{AppError, AppResult};
 
-#[cfg(target_os = "macos")]
-mod macos_hotkey {
-    use global_hotkey::hotkey::{Code, HotKey, Modifiers};
-    use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager};
+/// Whether the current session can even attempt a global hotkey grab.
+/// `global-hotkey`'s Linux backend only speaks X11; under Wayland there is
+/// no portable way to register a system-wide shortcut (each compositor
+/// would need its own portal integration), so registration would otherwise
+/// fail confusingly deep inside the platform backend. Detected the same way
+/// most X11-vs-Wayland checks are: a Wayland session sets `WAYLAND_DISPLAY`.
+#[cfg(target_os = "linux")]
+fn wayland_blocks_global_hotkeys() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+#[cfg(target_os = "linux")]
+const WAYLAND_UNAVAILABLE_MESSAGE: &str =
+    "global hotkeys require X11; this session is running Wayland (WAYLAND_DISPLAY is set), \
+     where system-wide key grabs aren't available";
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+mod global_backend {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    use global_hotkey::hotkey::{Code, HotKey, Modifiers as GlobalModifiers};
+    use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
 
     use super::*;
+    use crate::config::{HotkeyAction, HotkeyBinding, HotkeyMode, Keysym, Modifier, ModifierSet};
+    use crate::controller::events::ShutdownMode;
 
     pub struct HotkeyController {
         manager: GlobalHotKeyManager,
-        hotkey: HotKey,
+        registered: Vec<(HotKey, HotkeyAction)>,
+        mode: HotkeyMode,
+        /// The last `HotKeyState` reported for each registered hotkey id, so
+        /// `drain_events` can drop a repeated `Pressed` the OS sends while a
+        /// chord is held (X11/macOS auto-repeat) instead of re-firing
+        /// `RecordStart`/`Toggle` for every repeat tick; only a genuine
+        /// press-then-release edge should ever produce a `ControllerEvent`.
+        last_state: RefCell<HashMap<u32, HotKeyState>>,
     }
 
     impl HotkeyController {
-        pub fn new(binding: &str) -> AppResult<Self> {
-            let (modifiers, code) = parse_binding(binding)?;
+        pub fn new(bindings: &[HotkeyBinding], mode: HotkeyMode) -> AppResult<Self> {
+            #[cfg(target_os = "linux")]
+            if wayland_blocks_global_hotkeys() {
+                return Err(AppError::Controller(WAYLAND_UNAVAILABLE_MESSAGE.to_owned()));
+            }
+
             let manager = GlobalHotKeyManager::new().map_err(|error| {
                 crate::error::AppError::Controller(format!(
                     "failed to initialize global hotkey manager: {error}"
                 ))
             })?;
-            let hotkey = HotKey::new(Some(modifiers), code);
-            manager.register(hotkey).map_err(|error| {
-                crate::error::AppError::Controller(format!(
-                    "failed to register global hotkey `{binding}`: {error}"
-                ))
-            })?;
 
-            Ok(Self { manager, hotkey })
+            let mut registered = Vec::with_capacity(bindings.len());
+            for binding in bindings {
+                let parsed = crate::config::parse_binding(&binding.binding)?;
+                let hotkey = HotKey::new(
+                    Some(to_global_modifiers(&parsed.modifiers)),
+                    to_global_code(parsed.key),
+                );
+                manager.register(hotkey).map_err(|error| {
+                    crate::error::AppError::Controller(format!(
+                        "failed to register global hotkey `{}`: {error}",
+                        binding.binding
+                    ))
+                })?;
+                registered.push((hotkey, binding.action));
+            }
+
+            Ok(Self {
+                manager,
+                registered,
+                mode,
+                last_state: RefCell::new(HashMap::new()),
+            })
         }
 
         pub fn drain_events(&self) -> Vec<ControllerEvent> {
             let mut events = Vec::new();
+            let mut last_state = self.last_state.borrow_mut();
             while let Ok(event) = GlobalHotKeyEvent::receiver().try_recv() {
-                if event.id == self.hotkey.id() {
-                    events.push(ControllerEvent::Toggle);
+                if last_state.insert(event.id, event.state) == Some(event.state) {
+                    // Auto-repeat re-sends the same edge while the chord is
+                    // held; only a state change is a real press/release.
+                    continue;
+                }
+                if let Some((_, action)) = self
+                    .registered
+                    .iter()
+                    .find(|(hotkey, _)| hotkey.id() == event.id)
+                {
+                    if let Some(controller_event) = event_for(*action, self.mode, event.state) {
+                        events.push(controller_event);
+                    }
                 }
             }
             events
@@ -44,82 +103,220 @@ mod macos_hotkey {
 
     impl Drop for HotkeyController {
         fn drop(&mut self) {
-            let _ = self.manager.unregister(self.hotkey);
+            for (hotkey, _) in &self.registered {
+                let _ = self.manager.unregister(*hotkey);
+            }
         }
     }
 
-    fn parse_binding(binding: &str) -> AppResult<(Modifiers, Code)> {
-        let tokens = binding
-            .split('+')
-            .map(|part| part.trim().to_ascii_lowercase())
-            .collect::<Vec<_>>();
-
-        if tokens.is_empty() {
-            return Err(crate::error::AppError::Config(
-                "hotkey binding cannot be empty".to_owned(),
-            ));
+    /// Maps a bound action plus the physical press/release edge to the
+    /// controller event it should emit. `toggle`'s chord behaves like its
+    /// name in `HotkeyMode::Toggle` (each press flips recording on/off and
+    /// releases are ignored), but in `HotkeyMode::PushToTalk` it instead
+    /// emits `Start` on press and `Stop` on release, so recording only runs
+    /// while the chord is physically held. Every other action only reacts
+    /// to the press edge, matching the non-held, one-shot semantics of
+    /// `start`/`stop`/`cancel`.
+    fn event_for(
+        action: HotkeyAction,
+        mode: HotkeyMode,
+        state: HotKeyState,
+    ) -> Option<ControllerEvent> {
+        match (action, mode, state) {
+            (HotkeyAction::Toggle, HotkeyMode::PushToTalk, HotKeyState::Pressed) => {
+                Some(ControllerEvent::Start)
+            }
+            (HotkeyAction::Toggle, HotkeyMode::PushToTalk, HotKeyState::Released) => {
+                Some(ControllerEvent::Stop)
+            }
+            (_, _, HotKeyState::Released) => None,
+            (HotkeyAction::Toggle, HotkeyMode::Toggle, HotKeyState::Pressed) => {
+                Some(ControllerEvent::Toggle)
+            }
+            (HotkeyAction::Start, _, HotKeyState::Pressed) => Some(ControllerEvent::Start),
+            (HotkeyAction::Stop, _, HotKeyState::Pressed) => Some(ControllerEvent::Stop),
+            (HotkeyAction::Cancel, _, HotKeyState::Pressed) => Some(ControllerEvent::Cancel),
+            (HotkeyAction::CopyPrevious, _, HotKeyState::Pressed) => {
+                Some(ControllerEvent::CopyPrevious)
+            }
+            (HotkeyAction::ReTranscribe, _, HotKeyState::Pressed) => {
+                Some(ControllerEvent::ReTranscribe)
+            }
+            (HotkeyAction::OpenHistory, _, HotKeyState::Pressed) => {
+                Some(ControllerEvent::QueryHistory { limit: 20 })
+            }
+            (HotkeyAction::Quit, _, HotKeyState::Pressed) => {
+                Some(ControllerEvent::Shutdown(ShutdownMode::FlushPending))
+            }
         }
+    }
 
-        let mut modifiers = Modifiers::empty();
-        let mut key = None;
-
-        for token in tokens {
-            match token.as_str() {
-                "ctrl" | "control" => modifiers |= Modifiers::CONTROL,
-                "shift" => modifiers |= Modifiers::SHIFT,
-                "alt" | "option" => modifiers |= Modifiers::ALT,
-                "cmd" | "command" | "super" => modifiers |= Modifiers::SUPER,
-                "space" => key = Some(Code::Space),
-                "a" => key = Some(Code::KeyA),
-                "b" => key = Some(Code::KeyB),
-                "c" => key = Some(Code::KeyC),
-                "d" => key = Some(Code::KeyD),
-                "e" => key = Some(Code::KeyE),
-                "f" => key = Some(Code::KeyF),
-                "g" => key = Some(Code::KeyG),
-                "h" => key = Some(Code::KeyH),
-                "i" => key = Some(Code::KeyI),
-                "j" => key = Some(Code::KeyJ),
-                "k" => key = Some(Code::KeyK),
-                "l" => key = Some(Code::KeyL),
-                "m" => key = Some(Code::KeyM),
-                "n" => key = Some(Code::KeyN),
-                "o" => key = Some(Code::KeyO),
-                "p" => key = Some(Code::KeyP),
-                "q" => key = Some(Code::KeyQ),
-                "r" => key = Some(Code::KeyR),
-                "s" => key = Some(Code::KeyS),
-                "t" => key = Some(Code::KeyT),
-                "u" => key = Some(Code::KeyU),
-                "v" => key = Some(Code::KeyV),
-                "w" => key = Some(Code::KeyW),
-                "x" => key = Some(Code::KeyX),
-                "y" => key = Some(Code::KeyY),
-                "z" => key = Some(Code::KeyZ),
-                _ => {
-                    return Err(crate::error::AppError::Config(format!(
-                        "unsupported hotkey token `{token}` in binding `{binding}`"
-                    )));
-                }
+    #[cfg(test)]
+    mod tests {
+        use super::{event_for, ShutdownMode};
+        use crate::config::{HotkeyAction, HotkeyMode};
+        use crate::controller::events::ControllerEvent;
+        use global_hotkey::HotKeyState;
+
+        #[test]
+        fn one_shot_actions_only_fire_on_the_press_edge() {
+            for action in [
+                HotkeyAction::CopyPrevious,
+                HotkeyAction::ReTranscribe,
+                HotkeyAction::OpenHistory,
+                HotkeyAction::Quit,
+            ] {
+                assert!(
+                    event_for(action, HotkeyMode::Toggle, HotKeyState::Released).is_none(),
+                    "{action:?} must not fire on release"
+                );
+                assert!(event_for(action, HotkeyMode::Toggle, HotKeyState::Pressed).is_some());
             }
         }
 
-        let key = key.ok_or_else(|| {
-            crate::error::AppError::Config(format!(
-                "hotkey binding `{binding}` must include a key token (for example `Space`)"
-            ))
-        })?;
+        #[test]
+        fn new_actions_map_to_their_controller_events() {
+            assert!(matches!(
+                event_for(HotkeyAction::CopyPrevious, HotkeyMode::Toggle, HotKeyState::Pressed),
+                Some(ControllerEvent::CopyPrevious)
+            ));
+            assert!(matches!(
+                event_for(HotkeyAction::ReTranscribe, HotkeyMode::Toggle, HotKeyState::Pressed),
+                Some(ControllerEvent::ReTranscribe)
+            ));
+            assert!(matches!(
+                event_for(HotkeyAction::OpenHistory, HotkeyMode::Toggle, HotKeyState::Pressed),
+                Some(ControllerEvent::QueryHistory { limit: 20 })
+            ));
+            assert!(matches!(
+                event_for(HotkeyAction::Quit, HotkeyMode::Toggle, HotKeyState::Pressed),
+                Some(ControllerEvent::Shutdown(ShutdownMode::FlushPending))
+            ));
+        }
+    }
+
+    fn to_global_modifiers(modifiers: &ModifierSet) -> GlobalModifiers {
+        let mut result = GlobalModifiers::empty();
+        if modifiers.contains(Modifier::Control) {
+            result |= GlobalModifiers::CONTROL;
+        }
+        if modifiers.contains(Modifier::Shift) {
+            result |= GlobalModifiers::SHIFT;
+        }
+        if modifiers.contains(Modifier::Alt) {
+            result |= GlobalModifiers::ALT;
+        }
+        if modifiers.contains(Modifier::Super) {
+            result |= GlobalModifiers::SUPER;
+        }
+        result
+    }
 
-        Ok((modifiers, key))
+    fn to_global_code(key: Keysym) -> Code {
+        match key {
+            Keysym::Space => Code::Space,
+            Keysym::Escape => Code::Escape,
+            Keysym::A => Code::KeyA,
+            Keysym::B => Code::KeyB,
+            Keysym::C => Code::KeyC,
+            Keysym::D => Code::KeyD,
+            Keysym::E => Code::KeyE,
+            Keysym::F => Code::KeyF,
+            Keysym::G => Code::KeyG,
+            Keysym::H => Code::KeyH,
+            Keysym::I => Code::KeyI,
+            Keysym::J => Code::KeyJ,
+            Keysym::K => Code::KeyK,
+            Keysym::L => Code::KeyL,
+            Keysym::M => Code::KeyM,
+            Keysym::N => Code::KeyN,
+            Keysym::O => Code::KeyO,
+            Keysym::P => Code::KeyP,
+            Keysym::Q => Code::KeyQ,
+            Keysym::R => Code::KeyR,
+            Keysym::S => Code::KeyS,
+            Keysym::T => Code::KeyT,
+            Keysym::U => Code::KeyU,
+            Keysym::V => Code::KeyV,
+            Keysym::W => Code::KeyW,
+            Keysym::X => Code::KeyX,
+            Keysym::Y => Code::KeyY,
+            Keysym::Z => Code::KeyZ,
+            Keysym::Digit0 => Code::Digit0,
+            Keysym::Digit1 => Code::Digit1,
+            Keysym::Digit2 => Code::Digit2,
+            Keysym::Digit3 => Code::Digit3,
+            Keysym::Digit4 => Code::Digit4,
+            Keysym::Digit5 => Code::Digit5,
+            Keysym::Digit6 => Code::Digit6,
+            Keysym::Digit7 => Code::Digit7,
+            Keysym::Digit8 => Code::Digit8,
+            Keysym::Digit9 => Code::Digit9,
+            Keysym::F1 => Code::F1,
+            Keysym::F2 => Code::F2,
+            Keysym::F3 => Code::F3,
+            Keysym::F4 => Code::F4,
+            Keysym::F5 => Code::F5,
+            Keysym::F6 => Code::F6,
+            Keysym::F7 => Code::F7,
+            Keysym::F8 => Code::F8,
+            Keysym::F9 => Code::F9,
+            Keysym::F10 => Code::F10,
+            Keysym::F11 => Code::F11,
+            Keysym::F12 => Code::F12,
+            Keysym::F13 => Code::F13,
+            Keysym::F14 => Code::F14,
+            Keysym::F15 => Code::F15,
+            Keysym::F16 => Code::F16,
+            Keysym::F17 => Code::F17,
+            Keysym::F18 => Code::F18,
+            Keysym::F19 => Code::F19,
+            Keysym::F20 => Code::F20,
+            Keysym::F21 => Code::F21,
+            Keysym::F22 => Code::F22,
+            Keysym::F23 => Code::F23,
+            Keysym::F24 => Code::F24,
+            Keysym::Up => Code::ArrowUp,
+            Keysym::Down => Code::ArrowDown,
+            Keysym::Left => Code::ArrowLeft,
+            Keysym::Right => Code::ArrowRight,
+            Keysym::Enter => Code::Enter,
+            Keysym::Tab => Code::Tab,
+            Keysym::Backspace => Code::Backspace,
+            Keysym::Delete => Code::Delete,
+            Keysym::Home => Code::Home,
+            Keysym::End => Code::End,
+            Keysym::PageUp => Code::PageUp,
+            Keysym::PageDown => Code::PageDown,
+            Keysym::Backquote => Code::Backquote,
+            Keysym::Minus => Code::Minus,
+            Keysym::Equal => Code::Equal,
+            Keysym::BracketLeft => Code::BracketLeft,
+            Keysym::BracketRight => Code::BracketRight,
+            Keysym::Semicolon => Code::Semicolon,
+            Keysym::Quote => Code::Quote,
+            Keysym::Comma => Code::Comma,
+            Keysym::Period => Code::Period,
+            Keysym::Slash => Code::Slash,
+        }
     }
 }
 
-#[cfg(not(target_os = "macos"))]
+/// Last-resort fallback for a platform `global-hotkey` doesn't support at
+/// all (everything besides macOS/Windows/X11-Linux). Silently drains
+/// nothing rather than failing to start, same as every platform used to
+/// behave before hotkey support was implemented; `doctor`'s
+/// `hotkey_registration` check still only sees the real per-platform
+/// outcome on the platforms that have one.
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
 pub struct HotkeyController;
 
-#[cfg(not(target_os = "macos"))]
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
 impl HotkeyController {
-    pub fn new(_binding: &str) -> AppResult<Self> {
+    pub fn new(
+        _bindings: &[crate::config::HotkeyBinding],
+        _mode: crate::config::HotkeyMode,
+    ) -> AppResult<Self> {
         Ok(Self)
     }
 
@@ -128,17 +325,45 @@ impl HotkeyController {
     }
 }
 
-#[cfg(target_os = "macos")]
-pub use macos_hotkey::HotkeyController;
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub use global_backend::HotkeyController;
 
 #[cfg(test)]
 mod tests {
     use super::HotkeyController;
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
     #[test]
-    fn non_macos_hotkey_is_noop() {
-        let controller = HotkeyController::new("Ctrl+Shift+Space").expect("new");
+    fn unsupported_platform_hotkey_is_noop() {
+        let bindings = vec![crate::config::HotkeyBinding {
+            action: crate::config::HotkeyAction::Toggle,
+            binding: "Ctrl+Shift+Space".to_owned(),
+        }];
+        let controller =
+            HotkeyController::new(&bindings, crate::config::HotkeyMode::Toggle).expect("new");
         assert!(controller.drain_events().is_empty());
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn wayland_session_is_rejected_with_a_clear_message() {
+        // Safe: this test is the only thing in the process reading or
+        // writing `WAYLAND_DISPLAY`, and runs single-threaded within this
+        // crate's test binary.
+        let previous = std::env::var_os("WAYLAND_DISPLAY");
+        std::env::set_var("WAYLAND_DISPLAY", "wayland-0");
+
+        let bindings = vec![crate::config::HotkeyBinding {
+            action: crate::config::HotkeyAction::Toggle,
+            binding: "Ctrl+Shift+Space".to_owned(),
+        }];
+        let error = HotkeyController::new(&bindings, crate::config::HotkeyMode::Toggle)
+            .expect_err("wayland must be rejected");
+        assert!(error.to_string().contains("Wayland"));
+
+        match previous {
+            Some(value) => std::env::set_var("WAYLAND_DISPLAY", value),
+            None => std::env::remove_var("WAYLAND_DISPLAY"),
+        }
+    }
 }
@@ -2,6 +2,7 @@ pub mod hotkey;
 pub mod notify;
 pub mod tray;
 
+use crate::config::{HotkeyBinding, HotkeyMode};
 use crate::controller::events::ControllerEvent;
 use crate::controller::state::ControllerState;
 use crate::error::AppResult;
@@ -12,10 +13,10 @@ pub struct UiFrontend {
 }
 
 impl UiFrontend {
-    pub fn new(binding: &str) -> AppResult<Self> {
+    pub fn new(bindings: &[HotkeyBinding], mode: HotkeyMode) -> AppResult<Self> {
         Ok(Self {
             tray: tray::TrayController::new()?,
-            hotkey: hotkey::HotkeyController::new(binding)?,
+            hotkey: hotkey::HotkeyController::new(bindings, mode)?,
         })
     }
 
@@ -35,12 +36,17 @@ pub use notify::Notifier;
 #[cfg(test)]
 mod tests {
     use super::UiFrontend;
+    use crate::config::{HotkeyAction, HotkeyBinding, HotkeyMode};
     use crate::controller::state::ControllerState;
 
     #[cfg(not(target_os = "macos"))]
     #[test]
     fn non_macos_frontend_behaves_as_noop() {
-        let ui = UiFrontend::new("Ctrl+Shift+Space").expect("ui");
+        let bindings = vec![HotkeyBinding {
+            action: HotkeyAction::Toggle,
+            binding: "Ctrl+Shift+Space".to_owned(),
+        }];
+        let ui = UiFrontend::new(&bindings, HotkeyMode::Toggle).expect("ui");
         assert!(ui.drain_events().is_empty());
         ui.set_state(&ControllerState::Idle).expect("set");
     }
@@ -1,8 +1,36 @@
-use notify_rust::Notification;
+use notify_rust::{Notification, Timeout, Urgency};
 use std::sync::Arc;
 
+use crate::controller::events::NotificationSeverity;
 use crate::error::AppResult;
 
+/// Maps a controller notification's severity to the desktop urgency it
+/// should be shown with. `None` means the notification is routine status
+/// chatter that should stay lightweight rather than interrupt the user with
+/// a real toast.
+fn urgency_for(severity: NotificationSeverity) -> Option<Urgency> {
+    match severity {
+        NotificationSeverity::Info => None,
+        NotificationSeverity::Success => Some(Urgency::Low),
+        NotificationSeverity::Warning => Some(Urgency::Normal),
+        NotificationSeverity::Error => Some(Urgency::Critical),
+    }
+}
+
+/// A structured description of one desktop toast, independent of the
+/// backend that ends up rendering it. `actions` is a list of
+/// `(id, label)` pairs, e.g. `("copy", "Copy again")`; `NotificationBackend`
+/// implementations that can't show actions (or whose toolkit doesn't
+/// support them) are free to ignore the field and show a plain banner.
+pub struct NotificationRequest<'a> {
+    pub summary: &'a str,
+    pub body: &'a str,
+    pub urgency: Urgency,
+    pub timeout: Timeout,
+    pub icon: Option<&'a str>,
+    pub actions: &'a [(String, String)],
+}
+
 pub struct Notifier {
     enabled: bool,
     backend: Arc<dyn NotificationBackend>,
@@ -25,20 +53,42 @@ impl Clone for Notifier {
     }
 }
 
+/// Shows one `NotificationRequest` and reports which action (if any) the
+/// user clicked. `Ok(None)` covers both "no actions were offered" and
+/// "the notification closed without a click".
 trait NotificationBackend: Send + Sync {
-    fn show(&self, summary: &str, body: &str) -> Result<(), String>;
+    fn show(&self, request: &NotificationRequest) -> Result<Option<String>, String>;
 }
 
 struct NotifyRustBackend;
 
 impl NotificationBackend for NotifyRustBackend {
-    fn show(&self, summary: &str, body: &str) -> Result<(), String> {
-        Notification::new()
-            .summary(summary)
-            .body(body)
-            .show()
-            .map(|_| ())
-            .map_err(|error| error.to_string())
+    fn show(&self, request: &NotificationRequest) -> Result<Option<String>, String> {
+        let mut notification = Notification::new();
+        notification
+            .summary(request.summary)
+            .body(request.body)
+            .urgency(request.urgency)
+            .timeout(request.timeout.clone());
+        if let Some(icon) = request.icon {
+            notification.icon(icon);
+        }
+        for (id, label) in request.actions {
+            notification.action(id, label);
+        }
+
+        let handle = notification.show().map_err(|error| error.to_string())?;
+        if request.actions.is_empty() {
+            return Ok(None);
+        }
+
+        let mut clicked = None;
+        handle.wait_for_action(|action| {
+            if action != "__closed" {
+                clicked = Some(action.to_owned());
+            }
+        });
+        Ok(clicked)
     }
 }
 
@@ -56,36 +106,170 @@ impl Notifier {
     }
 
     pub fn notify(&self, summary: &str, body: &str) -> AppResult<()> {
+        self.notify_with_severity(summary, body, NotificationSeverity::Error)
+    }
+
+    /// Shows a real desktop toast for `Success`/`Warning`/`Error` severities,
+    /// with urgency matching the severity; `Info` is routine status chatter
+    /// and stays lightweight (no toast).
+    pub fn notify_with_severity(
+        &self,
+        summary: &str,
+        body: &str,
+        severity: NotificationSeverity,
+    ) -> AppResult<()> {
         if !self.enabled {
             return Ok(());
         }
 
-        let _ = self.backend.show(summary, body);
+        if let Some(urgency) = urgency_for(severity) {
+            let request = NotificationRequest {
+                summary,
+                body,
+                urgency,
+                timeout: Timeout::Default,
+                icon: None,
+                actions: &[],
+            };
+            let _ = self.backend.show(&request);
+        }
         Ok(())
     }
+
+    /// Shows a notification offering `actions` (each an `(id, label)` pair)
+    /// and blocks until the backend reports which one was clicked, same as
+    /// `notify_with_severity` otherwise: disabled or `Info`-severity
+    /// notifications never reach the backend, and a backend error is
+    /// swallowed and reported as "nothing was clicked" rather than
+    /// propagated.
+    pub fn notify_with_actions(
+        &self,
+        summary: &str,
+        body: &str,
+        severity: NotificationSeverity,
+        timeout: Timeout,
+        actions: &[(String, String)],
+    ) -> AppResult<Option<String>> {
+        if !self.enabled {
+            return Ok(None);
+        }
+
+        let Some(urgency) = urgency_for(severity) else {
+            return Ok(None);
+        };
+
+        let request = NotificationRequest {
+            summary,
+            body,
+            urgency,
+            timeout,
+            icon: None,
+            actions,
+        };
+        Ok(self.backend.show(&request).unwrap_or(None))
+    }
+}
+
+/// The production `NotificationSink`: every `Success`/`Warning`/`Error`
+/// notification the controller sends is shown as a desktop toast via
+/// `Notifier` (`Info` ones get no toast), in addition to going
+/// out over the `ControllerOutput` channel as before. `event_tx` is only
+/// used by `notify_with_actions`, to feed the clicked action back into the
+/// controller loop as a fresh `ControllerEvent` once the user responds,
+/// since showing an actionable toast blocks the thread that's waiting on
+/// it and must not block the controller loop itself.
+pub struct DesktopNotificationSink {
+    notifier: Notifier,
+    event_tx: crossbeam_channel::Sender<crate::controller::events::ControllerEvent>,
+}
+
+impl DesktopNotificationSink {
+    pub fn new(
+        enabled: bool,
+        event_tx: crossbeam_channel::Sender<crate::controller::events::ControllerEvent>,
+    ) -> Self {
+        Self {
+            notifier: Notifier::new(enabled),
+            event_tx,
+        }
+    }
+}
+
+impl crate::controller::NotificationSink for DesktopNotificationSink {
+    fn notify(
+        &self,
+        severity: NotificationSeverity,
+        _category: crate::controller::events::NotificationCategory,
+        detail: &str,
+    ) {
+        let _ = self.notifier.notify_with_severity("Quedo", detail, severity);
+    }
+
+    fn notify_with_actions(
+        &self,
+        severity: NotificationSeverity,
+        _category: crate::controller::events::NotificationCategory,
+        detail: &str,
+        actions: &[(String, String)],
+    ) {
+        let notifier = self.notifier.clone();
+        let event_tx = self.event_tx.clone();
+        let detail = detail.to_owned();
+        let actions = actions.to_owned();
+        std::thread::spawn(move || {
+            if let Ok(Some(action_id)) =
+                notifier.notify_with_actions("Quedo", &detail, severity, Timeout::Default, &actions)
+            {
+                if let Some(event) = controller_event_for_action(&action_id) {
+                    let _ = event_tx.send(event);
+                }
+            }
+        });
+    }
+}
+
+/// Maps a clicked notification action id back onto the `ControllerEvent`
+/// it should produce. Only the "Transcribed" toast's "Copy again"/"Discard"
+/// buttons exist today; unrecognized ids (e.g. from a stale notification
+/// after a daemon restart) are ignored rather than guessed at.
+fn controller_event_for_action(
+    action_id: &str,
+) -> Option<crate::controller::events::ControllerEvent> {
+    use crate::controller::events::ControllerEvent;
+    match action_id {
+        "copy" => Some(ControllerEvent::CopyPrevious),
+        "discard" => Some(ControllerEvent::DiscardLastTranscript),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Notifier;
+    use super::{Notifier, NotificationRequest};
+    use crate::controller::events::NotificationSeverity;
+    use notify_rust::{Timeout, Urgency};
     use std::sync::{Arc, Mutex};
 
     #[derive(Default)]
     struct FakeNotificationBackend {
-        calls: Mutex<Vec<(String, String)>>,
+        calls: Mutex<Vec<(String, String, Urgency)>>,
         fail: bool,
+        /// The action id `show` reports as clicked, for tests exercising
+        /// `notify_with_actions`.
+        clicked: Option<String>,
     }
 
     impl super::NotificationBackend for FakeNotificationBackend {
-        fn show(&self, summary: &str, body: &str) -> Result<(), String> {
-            self.calls
-                .lock()
-                .expect("lock calls")
-                .push((summary.to_owned(), body.to_owned()));
+        fn show(&self, request: &NotificationRequest) -> Result<Option<String>, String> {
+            self.calls.lock().expect("lock calls").push((
+                request.summary.to_owned(),
+                request.body.to_owned(),
+                request.urgency,
+            ));
             if self.fail {
                 return Err("backend unavailable".to_owned());
             }
-            Ok(())
+            Ok(self.clicked.clone())
         }
     }
 
@@ -105,12 +289,102 @@ mod tests {
         let backend = Arc::new(FakeNotificationBackend {
             calls: Mutex::new(Vec::new()),
             fail: true,
+            clicked: None,
         });
         let notifier = Notifier::with_backend(true, backend.clone());
         notifier.notify("Title", "Body").expect("enabled notify");
         assert_eq!(
             backend.calls.lock().expect("lock calls").as_slice(),
-            [("Title".to_owned(), "Body".to_owned())]
+            [("Title".to_owned(), "Body".to_owned(), Urgency::Critical)]
+        );
+    }
+
+    #[test]
+    fn info_severity_stays_lightweight_and_skips_the_backend() {
+        let backend = Arc::new(FakeNotificationBackend::default());
+        let notifier = Notifier::with_backend(true, backend.clone());
+        notifier
+            .notify_with_severity("Title", "Recording started", NotificationSeverity::Info)
+            .expect("notify");
+        assert!(
+            backend.calls.lock().expect("lock calls").is_empty(),
+            "info notifications should not produce a desktop toast"
         );
     }
+
+    #[test]
+    fn warning_and_error_severities_reach_the_backend_with_matching_urgency() {
+        let backend = Arc::new(FakeNotificationBackend::default());
+        let notifier = Notifier::with_backend(true, backend.clone());
+        notifier
+            .notify_with_severity("Title", "stalled input", NotificationSeverity::Warning)
+            .expect("notify warning");
+        notifier
+            .notify_with_severity("Title", "job failed", NotificationSeverity::Error)
+            .expect("notify error");
+
+        let calls = backend.calls.lock().expect("lock calls");
+        assert_eq!(calls[0].2, Urgency::Normal);
+        assert_eq!(calls[1].2, Urgency::Critical);
+    }
+
+    #[test]
+    fn notify_with_actions_reports_the_clicked_action_id() {
+        let backend = Arc::new(FakeNotificationBackend {
+            calls: Mutex::new(Vec::new()),
+            fail: false,
+            clicked: Some("copy".to_owned()),
+        });
+        let notifier = Notifier::with_backend(true, backend);
+        let clicked = notifier
+            .notify_with_actions(
+                "Transcribed",
+                "hello world",
+                NotificationSeverity::Success,
+                Timeout::Default,
+                &[
+                    ("copy".to_owned(), "Copy again".to_owned()),
+                    ("discard".to_owned(), "Discard".to_owned()),
+                ],
+            )
+            .expect("notify with actions");
+        assert_eq!(clicked.as_deref(), Some("copy"));
+    }
+
+    #[test]
+    fn notify_with_actions_swallows_backend_errors_as_no_click() {
+        let backend = Arc::new(FakeNotificationBackend {
+            calls: Mutex::new(Vec::new()),
+            fail: true,
+            clicked: None,
+        });
+        let notifier = Notifier::with_backend(true, backend);
+        let clicked = notifier
+            .notify_with_actions(
+                "Transcribed",
+                "hello world",
+                NotificationSeverity::Success,
+                Timeout::Default,
+                &[("copy".to_owned(), "Copy again".to_owned())],
+            )
+            .expect("notify with actions");
+        assert!(clicked.is_none());
+    }
+
+    #[test]
+    fn disabled_notifier_skips_actions_backend() {
+        let backend = Arc::new(FakeNotificationBackend::default());
+        let notifier = Notifier::with_backend(false, backend.clone());
+        let clicked = notifier
+            .notify_with_actions(
+                "Transcribed",
+                "hello world",
+                NotificationSeverity::Success,
+                Timeout::Default,
+                &[("copy".to_owned(), "Copy again".to_owned())],
+            )
+            .expect("notify with actions");
+        assert!(clicked.is_none());
+        assert!(backend.calls.lock().expect("lock calls").is_empty());
+    }
 }
@@ -1,4 +1,4 @@
-use crate::controller::events::ControllerEvent;
+use crate::controller::events::{ControllerEvent, ShutdownMode};
 use crate::controller::state::ControllerState;
 use crate::error::AppResult;
 
@@ -76,7 +76,7 @@ mod macos_tray {
                 } else if event.id == self.doctor_id {
                     events.push(ControllerEvent::RunDoctor);
                 } else if event.id == self.quit_id {
-                    events.push(ControllerEvent::Shutdown);
+                    events.push(ControllerEvent::Shutdown(ShutdownMode::FlushPending));
                 }
             }
             events
@@ -86,7 +86,7 @@ mod macos_tray {
             let label = match state {
                 ControllerState::Idle => "Quedo: idle",
                 ControllerState::Recording => "Quedo: recording",
-                ControllerState::Processing => "Quedo: processing",
+                ControllerState::Processing { .. } => "Quedo: processing",
                 ControllerState::Degraded(_) => "Quedo: degraded",
                 ControllerState::Unavailable(_) => "Quedo: unavailable",
             };
@@ -135,8 +135,11 @@ mod tests {
         tray.set_state(&ControllerState::Idle).expect("set idle");
         tray.set_state(&ControllerState::Recording)
             .expect("set recording");
-        tray.set_state(&ControllerState::Processing)
-            .expect("set processing");
+        tray.set_state(&ControllerState::Processing {
+            in_flight: 1,
+            queued: 0,
+        })
+        .expect("set processing");
         tray.set_state(&ControllerState::Degraded("err".to_owned()))
             .expect("set degraded");
         tray.set_state(&ControllerState::Unavailable("err".to_owned()))
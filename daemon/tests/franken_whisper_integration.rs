@@ -7,8 +7,8 @@ use std::time::{Duration, Instant};
 use franken_whisper::BackendKind;
 use quedo_daemon::bootstrap::{bootstrap_env, AppPaths};
 use quedo_daemon::config::{AppConfig, OutputMode, TranscriptionConfig};
-use quedo_daemon::controller::events::{ControllerEvent, ControllerOutput};
-use quedo_daemon::controller::queue::SingleFlightQueue;
+use quedo_daemon::controller::events::{ControllerEvent, ControllerOutput, ShutdownMode};
+use quedo_daemon::controller::queue::JobQueue;
 use quedo_daemon::controller::state::ControllerState;
 use quedo_daemon::controller::{run_controller_loop, ControllerContext};
 use quedo_daemon::history::HistoryStore;
@@ -203,6 +203,8 @@ fn make_paths(root: &Path) -> AppPaths {
         config_file: root.join("config/config.toml"),
         history_db: root.join("data/history.sqlite3"),
         autostart_file: root.join("autostart/quedo-daemon.desktop"),
+        ipc_socket: root.join("cache/quedo.sock"),
+        system_config_file: root.join("system-config.toml"),
     }
 }
 
@@ -270,6 +272,59 @@ fn run_whisper_cli_to_text(
         .unwrap_or_else(|error| panic!("failed to read {}: {error}", txt_path.display()))
 }
 
+fn write_wav_i16(path: &Path, samples: &[i16], sample_rate: u32) {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(path, spec).expect("create wav");
+    for sample in samples {
+        writer.write_sample(*sample).expect("write sample");
+    }
+    writer.finalize().expect("finalize wav");
+}
+
+fn read_wav_i16(path: &Path) -> (Vec<i16>, u32) {
+    let mut reader = hound::WavReader::open(path).expect("open wav");
+    let sample_rate = reader.spec().sample_rate;
+    let samples = reader
+        .samples::<i16>()
+        .map(|sample| sample.expect("sample"))
+        .collect();
+    (samples, sample_rate)
+}
+
+/// A steady hiss-like broadband signal, the same construction
+/// `capture::denoise`'s own tests use to stand in for background noise
+/// without pulling in a `rand` dependency just for this fixture.
+fn synthetic_noise(len: usize, sample_rate: u32, amplitude: f64) -> Vec<i16> {
+    let freqs = [733.0, 1_901.0, 3_407.0, 4_999.0];
+    (0..len)
+        .map(|i| {
+            let t = i as f64 / sample_rate as f64;
+            let value: f64 = freqs
+                .iter()
+                .map(|f| (2.0 * std::f64::consts::PI * f * t).sin())
+                .sum::<f64>()
+                / freqs.len() as f64;
+            (value * amplitude * f64::from(i16::MAX)) as i16
+        })
+        .collect()
+}
+
+fn mix_in_noise(samples: &[i16], sample_rate: u32, amplitude: f64) -> Vec<i16> {
+    let noise = synthetic_noise(samples.len(), sample_rate, amplitude);
+    samples
+        .iter()
+        .zip(noise.iter())
+        .map(|(&sample, &noise)| {
+            (i32::from(sample) + i32::from(noise)).clamp(i32::from(i16::MIN), i32::from(i16::MAX)) as i16
+        })
+        .collect()
+}
+
 #[test]
 fn sqlite_history_roundtrip_with_real_sqlite() {
     let temp = tempfile::TempDir::new().expect("tempdir");
@@ -324,24 +379,27 @@ fn sqlite_history_roundtrip_with_real_sqlite() {
 }
 
 #[test]
-fn rapid_queue_operations_hold_single_flight_policy() {
-    let mut queue = SingleFlightQueue::new(1);
+fn rapid_queue_operations_hold_single_worker_ordering() {
+    let mut queue = JobQueue::new(1, 8);
 
     for index in 0..2_000 {
         let first = PathBuf::from(format!("/tmp/job-{index}.wav"));
-        queue.enqueue(first.clone()).expect("first enqueue");
+        queue.enqueue(first.clone());
 
-        let overflow = queue.enqueue(PathBuf::from(format!("/tmp/job-{index}-overflow.wav")));
+        let second = PathBuf::from(format!("/tmp/job-{index}-overflow.wav"));
+        queue.enqueue(second.clone());
+
+        let (first_job_id, first_path) = queue.start_next().expect("first job starts");
+        assert_eq!(first_path, first);
         assert!(
-            overflow.is_err(),
-            "queue accepted an overflow job at index {index}"
+            queue.start_next().is_none(),
+            "single in-flight worker must not start a second job at index {index}"
         );
 
-        assert_eq!(queue.start_next(), Some(first));
-        assert!(queue.start_next().is_none());
-
-        queue.mark_finished();
-        queue.mark_finished();
+        queue.mark_finished(first_job_id);
+        let (second_job_id, second_path) = queue.start_next().expect("second job starts");
+        assert_eq!(second_path, second);
+        queue.mark_finished(second_job_id);
     }
 }
 
@@ -539,6 +597,45 @@ fn wer_scoring_with_jiwer_is_within_reasonable_threshold() {
     assert!(wer <= 0.35, "WER too high: {wer}");
 }
 
+#[test]
+#[ignore = "requires local whisper-cli model + fixture"]
+fn denoise_recovers_wer_on_a_noise_augmented_fixture() {
+    if should_skip(&["whisper-cli"], true, true) {
+        return;
+    }
+
+    let fixture = resolve_fixture_wav().expect("fixture");
+    let model = resolve_model_path().expect("model");
+    let temp = tempfile::TempDir::new().expect("tempdir");
+    let reference =
+        "And so my fellow Americans ask not what your country can do for you ask what you can do for your country";
+
+    let baseline_prefix = temp.path().join("jfk_clean");
+    let baseline_transcript =
+        run_whisper_cli_to_text(&fixture, &model, &baseline_prefix, &path_with_local_bin());
+    let baseline_wer =
+        word_error_rate(&normalize_text(reference), &normalize_text(&baseline_transcript));
+
+    let (clean_samples, sample_rate) = read_wav_i16(&fixture);
+    let noisy_samples = mix_in_noise(&clean_samples, sample_rate, 0.08);
+    let noisy_path = temp.path().join("jfk_noisy.wav");
+    write_wav_i16(&noisy_path, &noisy_samples, sample_rate);
+
+    quedo_daemon::capture::denoise::denoise_wav(&noisy_path).expect("denoise noisy fixture");
+
+    let denoised_prefix = temp.path().join("jfk_denoised");
+    let denoised_transcript =
+        run_whisper_cli_to_text(&noisy_path, &model, &denoised_prefix, &path_with_local_bin());
+    let denoised_wer =
+        word_error_rate(&normalize_text(reference), &normalize_text(&denoised_transcript));
+
+    let tolerance = 0.15;
+    assert!(
+        denoised_wer <= baseline_wer + tolerance,
+        "denoised noisy-fixture WER {denoised_wer:.4} exceeded clean baseline {baseline_wer:.4} + tolerance {tolerance}"
+    );
+}
+
 #[test]
 #[ignore = "requires full local ffmpeg + whisper-cli + model + fixture"]
 fn full_pipeline_e2e_fixture_to_transcript_and_history() {
@@ -599,6 +696,7 @@ fn full_pipeline_e2e_fixture_to_transcript_and_history() {
     let context = ControllerContext {
         config: config.clone(),
         paths: paths.clone(),
+        clocks: std::sync::Arc::new(quedo_daemon::clock::SystemClocks::new()),
     };
     let (event_tx, output_rx, controller_join) = spawn_controller(context);
 
@@ -631,10 +729,10 @@ fn full_pipeline_e2e_fixture_to_transcript_and_history() {
         recv_until(&output_rx, Duration::from_secs(5), |output| {
             matches!(
                 output,
-                ControllerOutput::StateChanged(ControllerState::Processing)
+                ControllerOutput::StateChanged(ControllerState::Processing { .. })
             )
         }),
-        ControllerOutput::StateChanged(ControllerState::Processing)
+        ControllerOutput::StateChanged(ControllerState::Processing { .. })
     ));
 
     let transcript = match recv_until(&output_rx, Duration::from_secs(120), |output| {
@@ -671,7 +769,7 @@ fn full_pipeline_e2e_fixture_to_transcript_and_history() {
         "history database should not be empty"
     );
 
-    event_tx.send(ControllerEvent::Shutdown).expect("shutdown");
+    event_tx.send(ControllerEvent::Shutdown(ShutdownMode::Discard)).expect("shutdown");
     assert!(matches!(
         recv_until(&output_rx, Duration::from_secs(5), |output| {
             matches!(output, ControllerOutput::Stopped)
@@ -809,6 +907,7 @@ fn missing_ffmpeg_produces_graceful_transcription_error() {
     let context = ControllerContext {
         config,
         paths: paths.clone(),
+        clocks: std::sync::Arc::new(quedo_daemon::clock::SystemClocks::new()),
     };
     let (event_tx, output_rx, controller_join) = spawn_controller(context);
 
@@ -833,7 +932,7 @@ fn missing_ffmpeg_produces_graceful_transcription_error() {
     let _ = recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(
             output,
-            ControllerOutput::StateChanged(ControllerState::Processing)
+            ControllerOutput::StateChanged(ControllerState::Processing { .. })
         )
     });
 
@@ -855,7 +954,7 @@ fn missing_ffmpeg_produces_graceful_transcription_error() {
     let degraded_note = match recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(output, ControllerOutput::Notification(_))
     }) {
-        ControllerOutput::Notification(message) => message,
+        ControllerOutput::Notification(notification) => notification.detail,
         other => panic!("expected notification, got {other:?}"),
     };
     assert!(
@@ -864,7 +963,7 @@ fn missing_ffmpeg_produces_graceful_transcription_error() {
         "unexpected degraded notification: {degraded_note}"
     );
 
-    event_tx.send(ControllerEvent::Shutdown).expect("shutdown");
+    event_tx.send(ControllerEvent::Shutdown(ShutdownMode::Discard)).expect("shutdown");
     let _ = recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(output, ControllerOutput::Stopped)
     });
@@ -959,6 +1058,7 @@ fn corrupt_and_empty_wav_fail_gracefully() {
     let context = ControllerContext {
         config,
         paths: paths.clone(),
+        clocks: std::sync::Arc::new(quedo_daemon::clock::SystemClocks::new()),
     };
     let (event_tx, output_rx, controller_join) = spawn_controller(context);
 
@@ -985,7 +1085,7 @@ fn corrupt_and_empty_wav_fail_gracefully() {
         let _ = recv_until(&output_rx, Duration::from_secs(5), |output| {
             matches!(
                 output,
-                ControllerOutput::StateChanged(ControllerState::Processing)
+                ControllerOutput::StateChanged(ControllerState::Processing { .. })
             )
         });
         let degraded = match recv_until(&output_rx, Duration::from_secs(60), |output| {
@@ -1026,7 +1126,7 @@ fn corrupt_and_empty_wav_fail_gracefully() {
     let _ = recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(
             output,
-            ControllerOutput::StateChanged(ControllerState::Processing)
+            ControllerOutput::StateChanged(ControllerState::Processing { .. })
         )
     });
 
@@ -1048,7 +1148,7 @@ fn corrupt_and_empty_wav_fail_gracefully() {
         )
     });
 
-    event_tx.send(ControllerEvent::Shutdown).expect("shutdown");
+    event_tx.send(ControllerEvent::Shutdown(ShutdownMode::Discard)).expect("shutdown");
     let _ = recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(output, ControllerOutput::Stopped)
     });
@@ -9,7 +9,7 @@ use franken_whisper::BackendKind;
 use fsqlite_types::value::SqliteValue;
 use quedo_daemon::bootstrap::{bootstrap_env, AppPaths};
 use quedo_daemon::config::{AppConfig, OutputMode, TranscriptionConfig};
-use quedo_daemon::controller::events::{ControllerEvent, ControllerOutput};
+use quedo_daemon::controller::events::{ControllerEvent, ControllerOutput, ShutdownMode};
 use quedo_daemon::controller::state::ControllerState;
 use quedo_daemon::controller::{run_controller_loop, ControllerContext};
 use serde_json::Value;
@@ -148,6 +148,8 @@ fn make_paths(root: &Path) -> AppPaths {
         config_file: root.join("config/config.toml"),
         history_db: root.join("data/history.sqlite3"),
         autostart_file: root.join("autostart/quedo-daemon.desktop"),
+        ipc_socket: root.join("cache/quedo.sock"),
+        system_config_file: root.join("system-config.toml"),
     }
 }
 
@@ -332,6 +334,7 @@ fn metal_backend_requires_structured_evidence() {
     let context = ControllerContext {
         config: build_default_config(&model),
         paths: paths.clone(),
+        clocks: std::sync::Arc::new(quedo_daemon::clock::SystemClocks::new()),
     };
     let (event_tx, output_rx, controller_join) = spawn_controller(context);
 
@@ -352,7 +355,7 @@ fn metal_backend_requires_structured_evidence() {
     let _ = recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(
             output,
-            ControllerOutput::StateChanged(ControllerState::Processing)
+            ControllerOutput::StateChanged(ControllerState::Processing { .. })
         )
     });
 
@@ -391,7 +394,7 @@ fn metal_backend_requires_structured_evidence() {
         "structured rollout stage missing"
     );
 
-    event_tx.send(ControllerEvent::Shutdown).expect("shutdown");
+    event_tx.send(ControllerEvent::Shutdown(ShutdownMode::Discard)).expect("shutdown");
     let _ = recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(output, ControllerOutput::Stopped)
     });
@@ -451,6 +454,7 @@ fn capture_artifact_is_native_before_normalize() {
     let context = ControllerContext {
         config: build_default_config(&model),
         paths: paths.clone(),
+        clocks: std::sync::Arc::new(quedo_daemon::clock::SystemClocks::new()),
     };
     let (event_tx, output_rx, controller_join) = spawn_controller(context);
 
@@ -471,7 +475,7 @@ fn capture_artifact_is_native_before_normalize() {
     let _ = recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(
             output,
-            ControllerOutput::StateChanged(ControllerState::Processing)
+            ControllerOutput::StateChanged(ControllerState::Processing { .. })
         )
     });
 
@@ -578,7 +582,7 @@ fn capture_artifact_is_native_before_normalize() {
         "backend completion payload must be present"
     );
 
-    event_tx.send(ControllerEvent::Shutdown).expect("shutdown");
+    event_tx.send(ControllerEvent::Shutdown(ShutdownMode::Discard)).expect("shutdown");
     let _ = recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(output, ControllerOutput::Stopped)
     });
@@ -676,6 +680,7 @@ fn persisted_request_metadata_contains_contract_fields() {
     let context = ControllerContext {
         config: config.clone(),
         paths: paths.clone(),
+        clocks: std::sync::Arc::new(quedo_daemon::clock::SystemClocks::new()),
     };
     let (event_tx, output_rx, controller_join) = spawn_controller(context);
 
@@ -696,7 +701,7 @@ fn persisted_request_metadata_contains_contract_fields() {
     let _ = recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(
             output,
-            ControllerOutput::StateChanged(ControllerState::Processing)
+            ControllerOutput::StateChanged(ControllerState::Processing { .. })
         )
     });
 
@@ -751,7 +756,7 @@ fn persisted_request_metadata_contains_contract_fields() {
         Some(config.transcription.processors.expect("processors") as u64)
     );
 
-    event_tx.send(ControllerEvent::Shutdown).expect("shutdown");
+    event_tx.send(ControllerEvent::Shutdown(ShutdownMode::Discard)).expect("shutdown");
     let _ = recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(output, ControllerOutput::Stopped)
     });
@@ -785,6 +790,7 @@ fn missing_ffmpeg_disables_recording_in_unavailable_mode_release_gate() {
     let context = ControllerContext {
         config: build_default_config(&model),
         paths: paths.clone(),
+        clocks: std::sync::Arc::new(quedo_daemon::clock::SystemClocks::new()),
     };
     let (event_tx, output_rx, controller_join) = spawn_controller(context);
 
@@ -805,7 +811,7 @@ fn missing_ffmpeg_disables_recording_in_unavailable_mode_release_gate() {
     let blocked_note = match recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(output, ControllerOutput::Notification(_))
     }) {
-        ControllerOutput::Notification(message) => message,
+        ControllerOutput::Notification(notification) => notification.detail,
         other => panic!("expected notification, got {other:?}"),
     };
     assert!(
@@ -815,7 +821,7 @@ fn missing_ffmpeg_disables_recording_in_unavailable_mode_release_gate() {
         "unexpected unavailable notification: {blocked_note}"
     );
 
-    event_tx.send(ControllerEvent::Shutdown).expect("shutdown");
+    event_tx.send(ControllerEvent::Shutdown(ShutdownMode::Discard)).expect("shutdown");
     let _ = recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(output, ControllerOutput::Stopped)
     });
@@ -891,6 +897,7 @@ fn corrupt_and_empty_wav_fail_gracefully_release_gate() {
     let context = ControllerContext {
         config: build_default_config(&model),
         paths: paths.clone(),
+        clocks: std::sync::Arc::new(quedo_daemon::clock::SystemClocks::new()),
     };
     let (event_tx, output_rx, controller_join) = spawn_controller(context);
 
@@ -916,7 +923,7 @@ fn corrupt_and_empty_wav_fail_gracefully_release_gate() {
         });
         let degraded = match next_state {
             ControllerOutput::StateChanged(ControllerState::Degraded(reason)) => reason,
-            ControllerOutput::StateChanged(ControllerState::Processing) => {
+            ControllerOutput::StateChanged(ControllerState::Processing { .. }) => {
                 match recv_until(&output_rx, Duration::from_secs(60), |output| {
                     matches!(
                         output,
@@ -938,7 +945,7 @@ fn corrupt_and_empty_wav_fail_gracefully_release_gate() {
         "empty and corrupt WAV failures should produce distinct details"
     );
 
-    event_tx.send(ControllerEvent::Shutdown).expect("shutdown");
+    event_tx.send(ControllerEvent::Shutdown(ShutdownMode::Discard)).expect("shutdown");
     let _ = recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(output, ControllerOutput::Stopped)
     });
@@ -986,6 +993,7 @@ fn differential_reference_comparison_matches_whisper_cli() {
     let context = ControllerContext {
         config: build_default_config(&model),
         paths: paths.clone(),
+        clocks: std::sync::Arc::new(quedo_daemon::clock::SystemClocks::new()),
     };
     let (event_tx, output_rx, controller_join) = spawn_controller(context);
 
@@ -1006,7 +1014,7 @@ fn differential_reference_comparison_matches_whisper_cli() {
     let _ = recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(
             output,
-            ControllerOutput::StateChanged(ControllerState::Processing)
+            ControllerOutput::StateChanged(ControllerState::Processing { .. })
         )
     });
 
@@ -1032,7 +1040,7 @@ fn differential_reference_comparison_matches_whisper_cli() {
         "daemon WER drift too high vs direct whisper-cli: daemon={daemon_wer:.4}, direct={direct_wer:.4}"
     );
 
-    event_tx.send(ControllerEvent::Shutdown).expect("shutdown");
+    event_tx.send(ControllerEvent::Shutdown(ShutdownMode::Discard)).expect("shutdown");
     let _ = recv_until(&output_rx, Duration::from_secs(5), |output| {
         matches!(output, ControllerOutput::Stopped)
     });